@@ -0,0 +1,184 @@
+//! # Alarm Flags
+//!
+//! The DS3231 has two alarms. When the time matches an alarm, the chip sets
+//! that alarm's flag (A1F/A2F) in the status register and, if the alarm's
+//! interrupt is enabled, pulls the INT/SQW pin low until the flag is
+//! cleared. The INT pin handler therefore usually only has to find out which
+//! alarm fired and clear its flag.
+//!
+//! The driver methods need the `alarms` feature (on by default); [`Alarm`]
+//! itself is always available for the batch and flag types.
+
+#[cfg(all(feature = "alarms", feature = "async"))]
+use crate::interface::AsyncRegisterInterface;
+#[cfg(feature = "alarms")]
+use crate::interface::RegisterInterface;
+
+#[cfg(feature = "alarms")]
+use crate::Ds3231;
+#[cfg(all(feature = "alarms", feature = "async"))]
+use crate::ds3231::Ds3231Async;
+#[cfg(feature = "alarms")]
+use crate::error::Error;
+#[cfg(feature = "alarms")]
+use crate::flags::StatusFlags;
+use crate::registers::{A1F_BIT, A2F_BIT};
+#[cfg(feature = "alarms")]
+use crate::registers::{Register, StatusRegister};
+#[cfg(feature = "alarms")]
+use crate::trace::debug;
+
+/// One of the two DS3231 alarms
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Alarm {
+    /// Alarm 1 (seconds resolution)
+    One,
+    /// Alarm 2 (minutes resolution)
+    Two,
+}
+
+impl Alarm {
+    /// Flag bit of this alarm in the status register
+    pub(crate) const fn flag_bit(self) -> u8 {
+        match self {
+            Alarm::One => A1F_BIT,
+            Alarm::Two => A2F_BIT,
+        }
+    }
+}
+
+#[cfg(feature = "alarms")]
+#[maybe_async_cfg::maybe(
+    idents(
+        AsyncRegisterInterface(sync = "RegisterInterface", async = "AsyncRegisterInterface"),
+        Ds3231(sync = "Ds3231", async = "Ds3231Async")
+    ),
+    sync(keep_self),
+    async(feature = "async")
+)]
+impl<I2C, V> Ds3231<I2C, V>
+where
+    I2C: AsyncRegisterInterface,
+{
+    /// Returns `true` if `alarm` has fired since its flag was last cleared.
+    pub async fn alarm_fired(&mut self, alarm: Alarm) -> Result<bool, Error<I2C::Error>> {
+        let status = self.read_register(Register::Status).await?;
+        Ok(StatusRegister::from_bits(status).alarm_flag(alarm))
+    }
+
+    /// Clear the flag of `alarm`, releasing the INT pin if no other alarm
+    /// holds it.
+    pub async fn clear_alarm_flag(&mut self, alarm: Alarm) -> Result<(), Error<I2C::Error>> {
+        self.clear_status_flags(alarm.flag_bit()).await?;
+        debug!("clear_alarm_flag: {:?}", alarm);
+        Ok(())
+    }
+
+    /// Clear both alarm flags.
+    pub async fn clear_alarm_flags(&mut self) -> Result<(), Error<I2C::Error>> {
+        self.clear_status_flags(A1F_BIT | A2F_BIT).await?;
+        debug!("clear_alarm_flags");
+        Ok(())
+    }
+
+    /// Read the status register once and clear the flags of the alarms
+    /// that fired, returning the status as read.
+    ///
+    /// The write leaves the other flags alone, so an alarm firing between
+    /// the read and the write isn't lost. Nothing is written if neither
+    /// alarm fired.
+    pub async fn poll_alarms(&mut self) -> Result<StatusFlags, Error<I2C::Error>> {
+        let status = self.clear_status_flags(A1F_BIT | A2F_BIT).await?;
+        let fired = status & (A1F_BIT | A2F_BIT);
+        if fired != 0 {
+            debug!("poll_alarms: {:#04x}", fired);
+        }
+        Ok(StatusFlags::from_bits_retain(status))
+    }
+
+    /// Enable or disable pulling the INT pin low when `alarm` fires.
+    ///
+    /// The pin only signals alarms while the square wave output is disabled
+    /// (INTCN set); the flag is set either way.
+    pub async fn set_alarm_interrupt(
+        &mut self,
+        alarm: Alarm,
+        enabled: bool,
+    ) -> Result<(), Error<I2C::Error>> {
+        self.modify_control(|control| control.set_alarm_interrupt(alarm, enabled))
+            .await
+    }
+}
+
+#[cfg(all(test, feature = "alarms"))]
+mod tests {
+    use super::*;
+    use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
+
+    const DS3231_ADDR: u8 = 0x68;
+
+    #[test]
+    fn test_alarm_fired() {
+        let expectations = vec![
+            I2cTransaction::write_read(DS3231_ADDR, vec![Register::Status.addr()], vec![0x02]),
+            I2cTransaction::write_read(DS3231_ADDR, vec![Register::Status.addr()], vec![0x02]),
+        ];
+        let mut ds3231 = Ds3231::new(I2cMock::new(&expectations));
+
+        assert!(!ds3231.alarm_fired(Alarm::One).unwrap());
+        assert!(ds3231.alarm_fired(Alarm::Two).unwrap());
+
+        ds3231.release_i2c().done();
+    }
+
+    #[test]
+    fn test_clear_alarm_flags_preserves_other_bits() {
+        let expectations = vec![
+            I2cTransaction::write_read(DS3231_ADDR, vec![Register::Status.addr()], vec![0x8B]),
+            I2cTransaction::write(DS3231_ADDR, vec![Register::Status.addr(), 0x8A]),
+            I2cTransaction::write_read(DS3231_ADDR, vec![Register::Status.addr()], vec![0x8A]),
+            // A1F read as 0 and is written as 1, in case it was raised meanwhile
+            I2cTransaction::write(DS3231_ADDR, vec![Register::Status.addr(), 0x89]),
+        ];
+        let mut ds3231 = Ds3231::new(I2cMock::new(&expectations));
+
+        ds3231.clear_alarm_flag(Alarm::One).unwrap();
+        ds3231.clear_alarm_flags().unwrap();
+
+        ds3231.release_i2c().done();
+    }
+
+    #[test]
+    fn test_poll_alarms_clears_only_fired_flags() {
+        let expectations = vec![
+            I2cTransaction::write_read(DS3231_ADDR, vec![Register::Status.addr()], vec![0x89]),
+            I2cTransaction::write(DS3231_ADDR, vec![Register::Status.addr(), 0x8A]),
+            I2cTransaction::write_read(DS3231_ADDR, vec![Register::Status.addr()], vec![0x08]),
+        ];
+        let mut ds3231 = Ds3231::new(I2cMock::new(&expectations));
+
+        let status = ds3231.poll_alarms().unwrap();
+        assert!(status.alarm_fired(Alarm::One));
+        assert!(!status.alarm_fired(Alarm::Two));
+        assert!(!ds3231.poll_alarms().unwrap().alarm_fired(Alarm::One));
+
+        ds3231.release_i2c().done();
+    }
+
+    #[test]
+    fn test_set_alarm_interrupt() {
+        let expectations = vec![
+            I2cTransaction::write_read(DS3231_ADDR, vec![Register::Control.addr()], vec![0x1C]),
+            I2cTransaction::write(DS3231_ADDR, vec![Register::Control.addr(), 0x1E]),
+            I2cTransaction::write_read(DS3231_ADDR, vec![Register::Control.addr()], vec![0x1E]),
+            I2cTransaction::write(DS3231_ADDR, vec![Register::Control.addr(), 0x1C]),
+        ];
+        let mut ds3231 = Ds3231::new(I2cMock::new(&expectations));
+
+        ds3231.set_alarm_interrupt(Alarm::Two, true).unwrap();
+        ds3231.set_alarm_interrupt(Alarm::Two, false).unwrap();
+
+        ds3231.release_i2c().done();
+    }
+}
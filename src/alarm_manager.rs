@@ -0,0 +1,135 @@
+//! # Alarm Dispatch for Main Loops
+//!
+//! [`AlarmManager`] calls a handler registered per alarm whenever that alarm
+//! has fired, so a bare-metal superloop only has to call
+//! [`poll`](AlarmManager::poll) once per iteration, e.g. after the INT pin
+//! woke the MCU:
+//!
+//! ```no_run
+//! # use ds3231_rtc::Ds3231;
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! # let mut rtc = Ds3231::new(embedded_hal_mock::eh1::i2c::Mock::new(&[]));
+//! use ds3231_rtc::{alarm::Alarm, alarm_manager::AlarmManager};
+//! # let sleep_until_int_pin = || ();
+//!
+//! let mut samples = 0;
+//! let mut uploads = 0;
+//! let mut take_sample = || samples += 1;
+//! let mut upload = || uploads += 1;
+//! let mut alarms = AlarmManager::new()
+//!     .on(Alarm::One, &mut take_sample)
+//!     .on(Alarm::Two, &mut upload);
+//!
+//! loop {
+//!     alarms.poll(&mut rtc)?;
+//!     sleep_until_int_pin();
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Each poll reads the status register once and clears only the flags of
+//! the alarms that fired, with [`Ds3231::poll_alarms`], before running their
+//! handlers. Alarm 1 is handled before Alarm 2 when both fired.
+
+use crate::Ds3231;
+use crate::alarm::Alarm;
+use crate::error::Error;
+use crate::flags::StatusFlags;
+use crate::interface::RegisterInterface;
+
+/// Handler called when an alarm fires
+pub type AlarmHandler<'a> = &'a mut dyn FnMut();
+
+/// Handlers for the two alarms, dispatched by polling the status register
+#[derive(Default)]
+pub struct AlarmManager<'a> {
+    one: Option<AlarmHandler<'a>>,
+    two: Option<AlarmHandler<'a>>,
+}
+
+impl<'a> AlarmManager<'a> {
+    /// Manager without handlers.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Call `handler` whenever `alarm` fires, replacing an earlier one.
+    pub fn on(mut self, alarm: Alarm, handler: AlarmHandler<'a>) -> Self {
+        *self.handler_mut(alarm) = Some(handler);
+        self
+    }
+
+    /// Stop handling `alarm`. Its flag is still cleared when it fires.
+    pub fn remove(&mut self, alarm: Alarm) {
+        *self.handler_mut(alarm) = None;
+    }
+
+    /// Clear the flags of the alarms that fired and run their handlers.
+    ///
+    /// Returns the status register as read, so the caller can check which
+    /// alarms fired or whether the oscillator stopped.
+    pub fn poll<I2C, V>(
+        &mut self,
+        rtc: &mut Ds3231<I2C, V>,
+    ) -> Result<StatusFlags, Error<I2C::Error>>
+    where
+        I2C: RegisterInterface,
+    {
+        let status = rtc.poll_alarms()?;
+        for alarm in [Alarm::One, Alarm::Two] {
+            if !status.alarm_fired(alarm) {
+                continue;
+            }
+            if let Some(handler) = self.handler_mut(alarm) {
+                handler();
+            }
+        }
+        Ok(status)
+    }
+
+    fn handler_mut(&mut self, alarm: Alarm) -> &mut Option<AlarmHandler<'a>> {
+        match alarm {
+            Alarm::One => &mut self.one,
+            Alarm::Two => &mut self.two,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registers::Register;
+    use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
+
+    #[test]
+    fn test_poll_runs_handlers_of_fired_alarms() {
+        let expectations = [
+            I2cTransaction::write_read(0x68, vec![Register::Status.addr()], vec![0x0B]),
+            I2cTransaction::write(0x68, vec![Register::Status.addr(), 0x88]),
+            I2cTransaction::write_read(0x68, vec![Register::Status.addr()], vec![0x08]),
+            I2cTransaction::write_read(0x68, vec![Register::Status.addr()], vec![0x0A]),
+            I2cTransaction::write(0x68, vec![Register::Status.addr(), 0x89]),
+        ];
+        let mut ds3231 = Ds3231::new(I2cMock::new(&expectations));
+
+        let mut ones = 0;
+        let mut twos = 0;
+        {
+            let mut on_one = || ones += 1;
+            let mut on_two = || twos += 1;
+            let mut alarms = AlarmManager::new()
+                .on(Alarm::One, &mut on_one)
+                .on(Alarm::Two, &mut on_two);
+
+            alarms.poll(&mut ds3231).unwrap();
+            alarms.poll(&mut ds3231).unwrap();
+            alarms.remove(Alarm::Two);
+            let status = alarms.poll(&mut ds3231).unwrap();
+            assert!(status.alarm_fired(Alarm::Two));
+        }
+
+        assert_eq!((ones, twos), (1, 1));
+        ds3231.release_i2c().done();
+    }
+}
@@ -0,0 +1,138 @@
+//! # Async Driver
+//!
+//! An async variant of the driver (enabled with the `async` feature) built on
+//! [`embedded_hal_async::i2c::I2c`], so Embassy and other async executors are
+//! not blocked while the bus transfer is in progress.
+//!
+//! `rtc-hal` only defines blocking traits, so the async driver exposes the
+//! same operations as inherent `async fn`s with matching names. Both drivers
+//! are generated from the same source, so every inherent method of the
+//! blocking [`Ds3231`](crate::Ds3231) that touches the bus has an async
+//! counterpart here, and the two can't drift apart.
+//!
+//! ```no_run
+//! use ds3231_rtc::asynch::Ds3231;
+//! # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+//! # let i2c = embedded_hal_mock::eh1::i2c::Mock::new(&[]);
+//!
+//! let mut rtc = Ds3231::new(i2c);
+//! let now = rtc.get_datetime().await?;
+//! # Ok(())
+//! # }
+//! ```
+
+/// Async DS3231 Real-Time Clock driver
+pub use crate::ds3231::Ds3231Async as Ds3231;
+
+/// Async DS3232 Real-Time Clock driver with SRAM access
+pub use crate::ds3232::Ds3232Async as Ds3232;
+
+/// Pending configuration changes of the async driver
+pub use crate::batch::BatchAsync as Batch;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::Error;
+    use crate::registers::{EOSC_BIT, Register};
+    use core::pin::pin;
+    use core::task::{Context, Poll, Waker};
+    use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTrans};
+    use rtc_hal::datetime::DateTime;
+    use rtc_hal::square_wave::SquareWaveFreq;
+
+    /// The mock completes every transfer immediately, so a single poll suffices.
+    fn block_on<F: Future>(future: F) -> F::Output {
+        let mut future = pin!(future);
+        match future
+            .as_mut()
+            .poll(&mut Context::from_waker(Waker::noop()))
+        {
+            Poll::Ready(output) => output,
+            Poll::Pending => panic!("mock I2C future was not ready"),
+        }
+    }
+
+    #[test]
+    fn test_get_and_set_datetime() {
+        let data = vec![0x00, 0x30, 0x14, 0x05, 0x21, 0x08, 0x25];
+        let mut write = vec![Register::Seconds.addr()];
+        write.extend_from_slice(&data);
+        let expectations = [
+            I2cTrans::write_read(0x68, vec![Register::Seconds.addr()], data),
+            I2cTrans::write(0x68, write),
+        ];
+        let mut ds3231 = Ds3231::new(I2cMock::new(&expectations));
+
+        let dt = block_on(ds3231.get_datetime()).unwrap();
+        assert_eq!(dt, DateTime::new(2025, 8, 21, 14, 30, 0).unwrap());
+        block_on(ds3231.set_datetime(&dt)).unwrap();
+
+        ds3231.release_i2c().done();
+    }
+
+    #[test]
+    fn test_set_datetime_rejects_before_bus_access() {
+        let mut ds3231 = Ds3231::new(I2cMock::new(&[]));
+
+        let dt = DateTime::new(2250, 1, 1, 0, 0, 0).unwrap();
+        assert!(matches!(
+            block_on(ds3231.set_datetime(&dt)),
+            Err(Error::DateTime(_))
+        ));
+
+        ds3231.release_i2c().done();
+    }
+
+    #[test]
+    fn test_start_square_wave() {
+        let expectations = [
+            I2cTrans::write_read(0x68, vec![Register::Control.addr()], vec![0x1C]),
+            I2cTrans::write(0x68, vec![Register::Control.addr(), 0x08]),
+        ];
+        let mut ds3231 = Ds3231::new(I2cMock::new(&expectations));
+
+        block_on(ds3231.start_square_wave(SquareWaveFreq::Hz1024)).unwrap();
+        assert!(matches!(
+            block_on(ds3231.start_square_wave(SquareWaveFreq::Hz32768)),
+            Err(Error::UnsupportedSqwFrequency)
+        ));
+
+        ds3231.release_i2c().done();
+    }
+
+    #[test]
+    fn test_clock_and_status() {
+        let expectations = [
+            I2cTrans::write_read(0x68, vec![Register::Control.addr()], vec![0x00]),
+            I2cTrans::write(0x68, vec![Register::Control.addr(), EOSC_BIT]),
+            I2cTrans::write_read(0x68, vec![Register::Status.addr()], vec![0x88]),
+            I2cTrans::write_read(0x68, vec![Register::Status.addr()], vec![0x88]),
+            I2cTrans::write(0x68, vec![Register::Status.addr(), 0x0B]),
+        ];
+        let mut ds3231 = Ds3231::new(I2cMock::new(&expectations));
+
+        block_on(ds3231.halt_clock()).unwrap();
+        assert!(block_on(ds3231.oscillator_stopped()).unwrap());
+        block_on(ds3231.clear_oscillator_stop_flag()).unwrap();
+
+        ds3231.release_i2c().done();
+    }
+
+    #[test]
+    fn test_shared_helpers_are_async() {
+        let expectations = [
+            I2cTrans::write(0x68, vec![Register::Seconds.addr(), 0x45, 0x30, 0x14]),
+            I2cTrans::write_read(0x68, vec![Register::Hours.addr()], vec![0x14]),
+        ];
+        let mut ds3231 = Ds3231::new(I2cMock::new(&expectations));
+
+        block_on(ds3231.set_time(14, 30, 45)).unwrap();
+        assert_eq!(
+            block_on(ds3231.hour_mode()).unwrap(),
+            crate::hour_mode::HourMode::TwentyFourHour
+        );
+
+        ds3231.release_i2c().done();
+    }
+}
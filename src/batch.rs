@@ -0,0 +1,270 @@
+//! # Batched Configuration
+//!
+//! Each configuration method is a read-modify-write of its own, so setting
+//! up the INT/SQW pin and the alarms one call at a time costs a pair of
+//! transfers per call. [`batch`](crate::Ds3231::batch) collects such changes
+//! instead and [`commit`](Batch::commit) applies them with at most one
+//! read-modify-write of the control register and one of the status register:
+//!
+//! ```no_run
+//! # use ds3231_rtc::Ds3231;
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! # let mut rtc = Ds3231::new(embedded_hal_mock::eh1::i2c::Mock::new(&[]));
+//! use ds3231_rtc::alarm::Alarm;
+//!
+//! rtc.batch()
+//!     .disable_square_wave()
+//!     .alarm_interrupt(Alarm::One, true)
+//!     .alarm_interrupt(Alarm::Two, false)
+//!     .clear_alarm_flags()
+//!     .commit()?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! When two changes touch the same bit, the later one wins. Nothing is
+//! written until `commit`; an unsupported square wave frequency fails the
+//! commit before the bus is touched.
+
+use rtc_hal::square_wave::SquareWaveFreq;
+
+#[cfg(feature = "async")]
+use crate::interface::AsyncRegisterInterface;
+use crate::interface::RegisterInterface;
+
+use crate::Ds3231;
+#[cfg(feature = "alarms")]
+use crate::alarm::Alarm;
+#[cfg(feature = "async")]
+use crate::ds3231::Ds3231Async;
+use crate::error::Error;
+use crate::registers::{ControlRegister, StatusRegister};
+use crate::square_wave::rate_select;
+use crate::variant::AnyVariant;
+
+/// Control register fields to change, `None` where left alone
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+struct ControlChanges {
+    interrupt_control: Option<bool>,
+    rate_select: Option<u8>,
+    alarm1_interrupt: Option<bool>,
+    alarm2_interrupt: Option<bool>,
+}
+
+impl ControlChanges {
+    fn apply(self, control: &mut ControlRegister) {
+        if let Some(intcn) = self.interrupt_control {
+            control.set_interrupt_control(intcn);
+        }
+        if let Some(rate) = self.rate_select {
+            control.set_rate_select(rate);
+        }
+        if let Some(enabled) = self.alarm1_interrupt {
+            control.set_alarm1_interrupt(enabled);
+        }
+        if let Some(enabled) = self.alarm2_interrupt {
+            control.set_alarm2_interrupt(enabled);
+        }
+    }
+}
+
+/// Pending configuration changes, created by [`Ds3231::batch`]
+#[maybe_async_cfg::maybe(
+    idents(
+        Ds3231(sync = "Ds3231", async = "Ds3231Async"),
+        Batch(sync = "Batch", async = "BatchAsync")
+    ),
+    sync(keep_self),
+    async(feature = "async")
+)]
+#[must_use = "nothing is written until `commit` is called"]
+pub struct Batch<'a, I2C, V = AnyVariant> {
+    rtc: &'a mut Ds3231<I2C, V>,
+    control: ControlChanges,
+    frequency: Option<SquareWaveFreq>,
+    /// Status flags to clear, as set bits
+    status_clear: StatusRegister,
+}
+
+#[maybe_async_cfg::maybe(
+    idents(
+        AsyncRegisterInterface(sync = "RegisterInterface", async = "AsyncRegisterInterface"),
+        Ds3231(sync = "Ds3231", async = "Ds3231Async"),
+        Batch(sync = "Batch", async = "BatchAsync")
+    ),
+    sync(keep_self),
+    async(feature = "async")
+)]
+impl<I2C, V> Ds3231<I2C, V>
+where
+    I2C: AsyncRegisterInterface,
+{
+    /// Start collecting configuration changes to apply together.
+    ///
+    /// See the [module documentation](crate::batch).
+    pub fn batch(&mut self) -> Batch<'_, I2C, V> {
+        Batch {
+            rtc: self,
+            control: ControlChanges::default(),
+            frequency: None,
+            status_clear: StatusRegister::default(),
+        }
+    }
+}
+
+#[maybe_async_cfg::maybe(
+    idents(
+        AsyncRegisterInterface(sync = "RegisterInterface", async = "AsyncRegisterInterface"),
+        Batch(sync = "Batch", async = "BatchAsync")
+    ),
+    sync(keep_self),
+    async(feature = "async")
+)]
+impl<I2C, V> Batch<'_, I2C, V>
+where
+    I2C: AsyncRegisterInterface,
+{
+    /// Select the square wave frequency.
+    pub fn square_wave_frequency(mut self, freq: SquareWaveFreq) -> Self {
+        self.frequency = Some(freq);
+        self
+    }
+
+    /// Enable the square wave output (clear INTCN).
+    pub fn enable_square_wave(mut self) -> Self {
+        self.control.interrupt_control = Some(false);
+        self
+    }
+
+    /// Disable the square wave output so the pin signals alarms (set INTCN).
+    pub fn disable_square_wave(mut self) -> Self {
+        self.control.interrupt_control = Some(true);
+        self
+    }
+
+    #[cfg(feature = "alarms")]
+    /// Enable or disable the INT pin interrupt of `alarm`.
+    pub fn alarm_interrupt(mut self, alarm: Alarm, enabled: bool) -> Self {
+        match alarm {
+            Alarm::One => self.control.alarm1_interrupt = Some(enabled),
+            Alarm::Two => self.control.alarm2_interrupt = Some(enabled),
+        }
+        self
+    }
+
+    #[cfg(feature = "alarms")]
+    /// Clear the flag of `alarm`.
+    pub fn clear_alarm_flag(mut self, alarm: Alarm) -> Self {
+        self.status_clear.set_alarm_flag(alarm, true);
+        self
+    }
+
+    #[cfg(feature = "alarms")]
+    /// Clear both alarm flags.
+    pub fn clear_alarm_flags(mut self) -> Self {
+        self.status_clear.set_alarm1_flag(true);
+        self.status_clear.set_alarm2_flag(true);
+        self
+    }
+
+    /// Clear the Oscillator Stop Flag.
+    pub fn clear_oscillator_stop_flag(mut self) -> Self {
+        self.status_clear.set_oscillator_stop_flag(true);
+        self
+    }
+
+    /// Apply the collected changes.
+    ///
+    /// Registers without changes are not touched, and a register whose value
+    /// already matches is only read.
+    pub async fn commit(self) -> Result<(), Error<I2C::Error>> {
+        let mut control = self.control;
+        if let Some(freq) = self.frequency {
+            control.rate_select = Some(rate_select(self.rtc.variant.square_wave_bits(freq)?));
+        }
+
+        if control != ControlChanges::default() {
+            self.rtc
+                .modify_control(|value| control.apply(value))
+                .await?;
+        }
+
+        if self.status_clear != StatusRegister::default() {
+            self.rtc
+                .clear_status_flags(self.status_clear.bits())
+                .await?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(all(test, feature = "alarms"))]
+mod tests {
+    use super::*;
+    use crate::registers::Register;
+    use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
+
+    const DS3231_ADDR: u8 = 0x68;
+
+    #[test]
+    fn test_commit_uses_one_update_per_register() {
+        let expectations = [
+            I2cTransaction::write_read(DS3231_ADDR, vec![Register::Control.addr()], vec![0x1C]),
+            // 1.024 kHz, square wave on, alarm 1 interrupt enabled
+            I2cTransaction::write(DS3231_ADDR, vec![Register::Control.addr(), 0x09]),
+            I2cTransaction::write_read(DS3231_ADDR, vec![Register::Status.addr()], vec![0x8B]),
+            I2cTransaction::write(DS3231_ADDR, vec![Register::Status.addr(), 0x08]),
+        ];
+        let mut ds3231 = Ds3231::new(I2cMock::new(&expectations));
+
+        ds3231
+            .batch()
+            .disable_square_wave()
+            .square_wave_frequency(SquareWaveFreq::Hz1024)
+            .enable_square_wave()
+            .alarm_interrupt(Alarm::One, true)
+            .alarm_interrupt(Alarm::Two, false)
+            .clear_alarm_flags()
+            .clear_oscillator_stop_flag()
+            .commit()
+            .unwrap();
+
+        ds3231.release_i2c().done();
+    }
+
+    #[test]
+    fn test_commit_keeps_flags_it_does_not_clear() {
+        let expectations = [
+            I2cTransaction::write_read(DS3231_ADDR, vec![Register::Status.addr()], vec![0x01]),
+            // A2F and OSF are written as 1 in case either was raised meanwhile
+            I2cTransaction::write(DS3231_ADDR, vec![Register::Status.addr(), 0x82]),
+        ];
+        let mut ds3231 = Ds3231::new(I2cMock::new(&expectations));
+
+        ds3231
+            .batch()
+            .clear_alarm_flag(Alarm::One)
+            .commit()
+            .unwrap();
+
+        ds3231.release_i2c().done();
+    }
+
+    #[test]
+    fn test_empty_and_invalid_batches() {
+        let mut ds3231 = Ds3231::new(I2cMock::new(&[]));
+
+        ds3231.batch().commit().unwrap();
+        assert_eq!(
+            ds3231
+                .batch()
+                .clear_alarm_flags()
+                .square_wave_frequency(SquareWaveFreq::Hz32768)
+                .commit(),
+            Err(Error::UnsupportedSqwFrequency)
+        );
+
+        ds3231.release_i2c().done();
+    }
+}
@@ -0,0 +1,120 @@
+//! # BCD Conversions
+//!
+//! The DS3231 stores every time and date field as binary-coded decimal:
+//! one decimal digit per nibble, so 59 is stored as `0x59`. These are the
+//! routines the driver itself uses, for code that meets the same encoding
+//! elsewhere, e.g. multiplexed 7-segment displays or BCD-based protocols.
+//!
+//! [`to_bcd`], [`from_bcd`] and [`is_bcd`] are `const fn`s, unlike the ones
+//! in `rtc_hal::bcd`, so they also work in constants:
+//!
+//! ```
+//! use ds3231_rtc::bcd;
+//!
+//! const NOON: u8 = bcd::to_bcd(12);
+//! assert_eq!(NOON, 0x12);
+//! assert_eq!(bcd::from_bcd(0x59), 59);
+//! ```
+//!
+//! [`datetime_to_registers`] and [`registers_to_datetime`] convert between a
+//! [`DateTime`] and the seven timekeeping registers 0x00-0x06, with the
+//! same validation as the driver's `set_datetime` and `get_datetime`.
+
+use core::convert::Infallible;
+
+use rtc_hal::datetime::DateTime;
+
+use crate::datetime::{decode_datetime, encode_datetime};
+use crate::error::Error;
+use crate::hour_mode::HourMode;
+use crate::year_policy::YearPolicy;
+
+/// Encode `value` (0-99) as BCD.
+///
+/// The result is unspecified for values above 99.
+pub const fn to_bcd(value: u8) -> u8 {
+    ((value / 10) << 4) | (value % 10)
+}
+
+/// Decode a BCD byte.
+///
+/// The result is unspecified if a nibble is above 9, see [`is_bcd`].
+pub const fn from_bcd(bcd: u8) -> u8 {
+    (bcd >> 4) * 10 + (bcd & 0x0F)
+}
+
+/// Returns `true` if both nibbles of `value` are decimal digits.
+pub const fn is_bcd(value: u8) -> bool {
+    value & 0x0F <= 9 && value >> 4 <= 9
+}
+
+/// Encode `datetime` as the contents of the timekeeping registers 0x00-0x06.
+///
+/// The year must fall in the century starting at `base_century` or the one
+/// after it, which sets the century bit.
+pub fn datetime_to_registers(
+    datetime: &DateTime,
+    base_century: u8,
+    hour_mode: HourMode,
+) -> Result<[u8; 7], Error<Infallible>> {
+    let data = encode_datetime(
+        datetime,
+        base_century,
+        hour_mode,
+        YearPolicy::BaseCenturyWindow,
+    )?;
+
+    let mut registers = [0u8; 7];
+    registers.copy_from_slice(&data[1..]);
+    Ok(registers)
+}
+
+/// Decode the contents of the timekeeping registers 0x00-0x06.
+///
+/// Fails with [`Error::CorruptRegister`] if a register holds invalid BCD,
+/// and with [`Error::DateTime`] if the fields are not a valid date.
+pub fn registers_to_datetime(
+    registers: &[u8; 7],
+    base_century: u8,
+) -> Result<DateTime, Error<Infallible>> {
+    decode_datetime(registers, base_century)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registers::Register;
+    use rtc_hal::datetime::DateTimeError;
+
+    #[test]
+    fn test_round_trip_all_values() {
+        for value in 0..=99 {
+            let bcd = to_bcd(value);
+            assert!(is_bcd(bcd));
+            assert_eq!(from_bcd(bcd), value);
+            assert_eq!(bcd, rtc_hal::bcd::from_decimal(value));
+        }
+        assert!(!is_bcd(0x1A));
+        assert!(!is_bcd(0xA1));
+    }
+
+    #[test]
+    fn test_datetime_registers() {
+        let dt = DateTime::new(2125, 8, 21, 14, 30, 0).unwrap();
+        let registers = datetime_to_registers(&dt, 20, HourMode::TwelveHour).unwrap();
+        assert_eq!(registers, [0x00, 0x30, 0x62, 0x03, 0x21, 0x88, 0x25]);
+        assert_eq!(registers_to_datetime(&registers, 20), Ok(dt));
+
+        assert_eq!(
+            datetime_to_registers(&dt, 19, HourMode::TwentyFourHour),
+            Err(Error::DateTime(DateTimeError::InvalidYear))
+        );
+        assert_eq!(
+            registers_to_datetime(&[0x00, 0x3A, 0x14, 0x05, 0x21, 0x08, 0x25], 20),
+            Err(Error::CorruptRegister {
+                register: Register::Minutes,
+                value: 0x3A,
+            })
+        );
+    }
+}
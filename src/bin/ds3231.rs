@@ -0,0 +1,203 @@
+//! Command-line tool for provisioning and debugging DS3231 boards over a
+//! Linux i2c-dev bus, built with the `cli` feature:
+//!
+//! ```text
+//! $ ds3231 set now
+//! $ ds3231 --bus /dev/i2c-0 read
+//! 2025-08-21T14:30:00
+//! ```
+//!
+//! Times are UTC.
+
+use std::error::Error;
+use std::process::ExitCode;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use ds3231_rtc::calendar;
+use ds3231_rtc::display::DateTimeDisplay;
+use ds3231_rtc::{DateTime, Ds3231};
+use rtc_hal::square_wave::SquareWaveFreq;
+
+const USAGE: &str = "\
+usage: ds3231 [--bus PATH] <command>
+
+commands:
+  read          print the date and time
+  set now       set the clock from the host clock
+  set <TIME>    set the clock to TIME, e.g. 2025-08-21T14:30:00
+  dump          print all registers
+  sqw off       put INT/SQW in interrupt mode
+  sqw <HZ>      output a square wave of 1, 1024, 4096 or 8192 Hz
+
+options:
+  --bus PATH    i2c-dev bus of the chip [default: /dev/i2c-1]
+
+All times are UTC.";
+
+/// Bus used without `--bus`
+const DEFAULT_BUS: &str = "/dev/i2c-1";
+
+/// A parsed command line
+#[derive(Debug, PartialEq)]
+struct Args {
+    bus: String,
+    command: Command,
+}
+
+#[derive(Debug, PartialEq)]
+enum Command {
+    Read,
+    SetNow,
+    Set(DateTime),
+    Dump,
+    SquareWave(Option<SquareWaveFreq>),
+}
+
+fn parse_args(args: &[String]) -> Result<Args, String> {
+    let mut bus = DEFAULT_BUS.to_string();
+    let mut rest = args;
+    if let [flag, path, tail @ ..] = rest
+        && flag == "--bus"
+    {
+        bus = path.clone();
+        rest = tail;
+    }
+
+    let words: Vec<&str> = rest.iter().map(String::as_str).collect();
+    let command = match words.as_slice() {
+        ["read"] => Command::Read,
+        ["set", "now"] => Command::SetNow,
+        ["set", time] => Command::Set(parse_time(time)?),
+        ["dump"] => Command::Dump,
+        ["sqw", "off"] => Command::SquareWave(None),
+        ["sqw", hz] => {
+            let hz = hz.parse().map_err(|_| format!("invalid frequency: {hz}"))?;
+            Command::SquareWave(Some(SquareWaveFreq::from_hz(hz)))
+        }
+        _ => return Err(USAGE.to_string()),
+    };
+    Ok(Args { bus, command })
+}
+
+/// Parse `YYYY-MM-DDTHH:MM:SS`, also accepting a space for the `T` and a
+/// trailing `Z`.
+fn parse_time(s: &str) -> Result<DateTime, String> {
+    let invalid = || format!("invalid time: {s}, expected YYYY-MM-DDTHH:MM:SS");
+    let s = s.strip_suffix('Z').unwrap_or(s);
+    let (date, time) = s.split_once(['T', ' ']).ok_or_else(invalid)?;
+
+    let mut date = date.split('-');
+    let mut time = time.split(':');
+    let field = |part: Option<&str>, len: usize| -> Result<u16, String> {
+        part.filter(|p| p.len() == len && p.bytes().all(|b| b.is_ascii_digit()))
+            .and_then(|p| p.parse().ok())
+            .ok_or_else(invalid)
+    };
+    let year = field(date.next(), 4)?;
+    let month = field(date.next(), 2)?;
+    let day = field(date.next(), 2)?;
+    let hour = field(time.next(), 2)?;
+    let minute = field(time.next(), 2)?;
+    let second = field(time.next(), 2)?;
+    if date.next().is_some() || time.next().is_some() {
+        return Err(invalid());
+    }
+
+    DateTime::new(
+        year,
+        month as u8,
+        day as u8,
+        hour as u8,
+        minute as u8,
+        second as u8,
+    )
+    .map_err(|e| format!("invalid time: {s}: {e}"))
+}
+
+fn host_time() -> Result<DateTime, Box<dyn Error>> {
+    let since_epoch = SystemTime::now().duration_since(UNIX_EPOCH)?;
+    Ok(calendar::from_unix_timestamp(since_epoch.as_secs())?)
+}
+
+fn run(args: Args) -> Result<(), Box<dyn Error>> {
+    let mut rtc = Ds3231::new_linux(&args.bus).map_err(|e| format!("{}: {e}", args.bus))?;
+
+    match args.command {
+        Command::Read => println!("{}", DateTimeDisplay(rtc.get_datetime()?)),
+        Command::SetNow => {
+            let now = host_time()?;
+            rtc.set_datetime(&now)?;
+            println!("{}", DateTimeDisplay(now));
+        }
+        Command::Set(datetime) => rtc.set_datetime(&datetime)?,
+        Command::Dump => println!("{:#?}", rtc.dump_registers()?),
+        Command::SquareWave(None) => rtc.disable_square_wave()?,
+        Command::SquareWave(Some(freq)) => rtc.start_square_wave(freq)?,
+    }
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let args = match parse_args(&args) {
+        Ok(args) => args,
+        Err(message) => {
+            eprintln!("{message}");
+            return ExitCode::from(2);
+        }
+    };
+
+    match run(args) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(line: &str) -> Result<Args, String> {
+        let words: Vec<String> = line.split_whitespace().map(String::from).collect();
+        parse_args(&words)
+    }
+
+    #[test]
+    fn test_parse_commands() {
+        assert_eq!(
+            args("read"),
+            Ok(Args {
+                bus: DEFAULT_BUS.to_string(),
+                command: Command::Read,
+            })
+        );
+        assert_eq!(
+            args("--bus /dev/i2c-0 sqw 1024"),
+            Ok(Args {
+                bus: "/dev/i2c-0".to_string(),
+                command: Command::SquareWave(Some(SquareWaveFreq::Hz1024)),
+            })
+        );
+        assert_eq!(args("sqw off").unwrap().command, Command::SquareWave(None));
+        assert_eq!(args("set now").unwrap().command, Command::SetNow);
+        assert!(args("").is_err());
+        assert!(args("set").is_err());
+        assert!(args("sqw fast").is_err());
+    }
+
+    #[test]
+    fn test_parse_time() {
+        let expected = DateTime::new(2025, 8, 21, 14, 30, 0).unwrap();
+        assert_eq!(parse_time("2025-08-21T14:30:00"), Ok(expected));
+        assert_eq!(parse_time("2025-08-21 14:30:00"), Ok(expected));
+        assert_eq!(parse_time("2025-08-21T14:30:00Z"), Ok(expected));
+
+        assert!(parse_time("2025-08-21").is_err());
+        assert!(parse_time("2025-8-21T14:30:00").is_err());
+        assert!(parse_time("2025-08-21T14:30:00:00").is_err());
+        assert!(parse_time("2025-02-30T00:00:00").is_err());
+    }
+}
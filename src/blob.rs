@@ -0,0 +1,370 @@
+//! # CRC-Protected Blob Storage
+//!
+//! Small configuration blobs (calibration values, user settings) kept in the
+//! battery-backed SRAM of a DS3232 or DS3234, or any other [`RtcNvram`].
+//!
+//! A [`BlobStore`] divides a region of NVRAM into fixed-size slots. Each slot
+//! holds a 4-byte header followed by the data:
+//!
+//! | Byte | Contents                                   |
+//! |------|--------------------------------------------|
+//! | 0    | Version byte chosen by the application     |
+//! | 1    | Data length                                |
+//! | 2-3  | CRC-16/CCITT-FALSE over bytes 0-1 and data |
+//!
+//! A slot that was never written, lost its contents with the backup battery,
+//! or was only partly written when power failed fails the CRC check, so
+//! [`load_blob`](BlobStore::load_blob) never returns garbage. Bump the
+//! version when the blob layout changes; blobs stored by older firmware are
+//! then rejected with [`BlobError::VersionMismatch`].
+//!
+//! ```no_run
+//! # use ds3231_rtc::ds3232::Ds3232;
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! # let mut rtc = Ds3232::new(embedded_hal_mock::eh1::i2c::Mock::new(&[]));
+//! use ds3231_rtc::blob::BlobStore;
+//!
+//! // Four 32-byte slots (28 data bytes each) at the start of SRAM
+//! # let calibration = [0u8; 12];
+//! let store = BlobStore::new(0, 32, 4).with_version(2);
+//! store.store_blob(&mut rtc, 0, &calibration)?;
+//!
+//! let mut buf = [0u8; 28];
+//! let len = store.load_blob(&mut rtc, 0, &mut buf)?;
+//! # Ok(())
+//! # }
+//! ```
+
+use rtc_hal::nvram::RtcNvram;
+
+/// Size of the per-slot header in bytes
+pub const BLOB_HEADER_SIZE: usize = 4;
+
+/// Errors returned by [`BlobStore`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum BlobError<E> {
+    /// The underlying NVRAM access failed
+    Nvram(E),
+    /// The slot number is outside the store, or the store doesn't fit in
+    /// the device's NVRAM
+    InvalidSlot,
+    /// The data doesn't fit in a slot
+    TooLarge,
+    /// The caller's buffer is smaller than the stored blob
+    BufferTooSmall,
+    /// The slot holds no valid blob (CRC mismatch or impossible length)
+    Corrupt,
+    /// The blob is intact but was stored with a different version byte
+    VersionMismatch(u8),
+}
+
+impl<E> core::fmt::Display for BlobError<E>
+where
+    E: core::fmt::Display,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            BlobError::Nvram(e) => write!(f, "NVRAM access failed: {e}"),
+            BlobError::InvalidSlot => write!(f, "Invalid blob slot"),
+            BlobError::TooLarge => write!(f, "Blob too large for slot"),
+            BlobError::BufferTooSmall => write!(f, "Buffer too small for blob"),
+            BlobError::Corrupt => write!(f, "Blob slot is empty or corrupt"),
+            BlobError::VersionMismatch(v) => write!(f, "Blob stored with version {v}"),
+        }
+    }
+}
+
+impl<E> core::error::Error for BlobError<E> where E: core::fmt::Debug + core::fmt::Display {}
+
+/// CRC-16/CCITT-FALSE (polynomial 0x1021, initial value 0xFFFF)
+pub(crate) fn crc16(crc: u16, data: &[u8]) -> u16 {
+    data.iter().fold(crc, |crc, &byte| {
+        (0..8).fold(crc ^ ((byte as u16) << 8), |crc, _| {
+            if crc & 0x8000 != 0 {
+                (crc << 1) ^ 0x1021
+            } else {
+                crc << 1
+            }
+        })
+    })
+}
+
+/// Initial value for [`crc16`]
+pub(crate) const CRC16_INIT: u16 = 0xFFFF;
+
+/// Layout of fixed-size blob slots in a region of NVRAM
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct BlobStore {
+    offset: u8,
+    slot_size: u8,
+    slots: u8,
+    version: u8,
+}
+
+impl BlobStore {
+    /// Store with `slots` slots of `slot_size` bytes each (header included),
+    /// starting at NVRAM `offset`, using version byte 0.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `slot_size` can't hold the header plus at least one byte.
+    pub const fn new(offset: u8, slot_size: u8, slots: u8) -> Self {
+        assert!(
+            slot_size as usize > BLOB_HEADER_SIZE,
+            "slot too small for the blob header"
+        );
+        Self {
+            offset,
+            slot_size,
+            slots,
+            version: 0,
+        }
+    }
+
+    /// Set the version byte written with, and required of, every blob.
+    pub const fn with_version(mut self, version: u8) -> Self {
+        self.version = version;
+        self
+    }
+
+    /// Largest blob a slot can hold
+    pub const fn capacity(&self) -> usize {
+        // `new` guarantees room for the header; saturating keeps the
+        // bounds checks below provably in range
+        (self.slot_size as usize).saturating_sub(BLOB_HEADER_SIZE)
+    }
+
+    /// NVRAM bytes covered by the store
+    pub const fn len(&self) -> usize {
+        self.slot_size as usize * self.slots as usize
+    }
+
+    /// Returns `true` if the store has no slots.
+    pub const fn is_empty(&self) -> bool {
+        self.slots == 0
+    }
+
+    /// NVRAM offset of `slot`, checking that it lies within the device.
+    fn slot_offset<R: RtcNvram>(&self, rtc: &R, slot: u8) -> Result<u8, BlobError<R::Error>> {
+        let end = self.offset as usize + self.len();
+        if slot >= self.slots || end > rtc.nvram_size() as usize {
+            return Err(BlobError::InvalidSlot);
+        }
+        Ok(self.offset + slot * self.slot_size)
+    }
+
+    /// Write `data` to `slot`, replacing whatever it held.
+    ///
+    /// The header and data go out in a single NVRAM write.
+    pub fn store_blob<R: RtcNvram>(
+        &self,
+        rtc: &mut R,
+        slot: u8,
+        data: &[u8],
+    ) -> Result<(), BlobError<R::Error>> {
+        let offset = self.slot_offset(rtc, slot)?;
+        if data.len() > self.capacity() {
+            return Err(BlobError::TooLarge);
+        }
+
+        let mut frame = [0u8; u8::MAX as usize];
+        frame[0] = self.version;
+        frame[1] = data.len() as u8;
+        let crc = crc16(crc16(CRC16_INIT, &frame[..2]), data);
+        frame[2..4].copy_from_slice(&crc.to_be_bytes());
+        frame[BLOB_HEADER_SIZE..BLOB_HEADER_SIZE + data.len()].copy_from_slice(data);
+
+        rtc.write_nvram(offset, &frame[..BLOB_HEADER_SIZE + data.len()])
+            .map_err(BlobError::Nvram)
+    }
+
+    /// Read the blob in `slot` into `buffer`, returning its length.
+    pub fn load_blob<R: RtcNvram>(
+        &self,
+        rtc: &mut R,
+        slot: u8,
+        buffer: &mut [u8],
+    ) -> Result<usize, BlobError<R::Error>> {
+        let offset = self.slot_offset(rtc, slot)?;
+
+        let mut frame = [0u8; u8::MAX as usize];
+        rtc.read_nvram(offset, &mut frame[..self.slot_size as usize])
+            .map_err(BlobError::Nvram)?;
+
+        let len = frame[1] as usize;
+        if len > self.capacity() {
+            return Err(BlobError::Corrupt);
+        }
+        let data = &frame[BLOB_HEADER_SIZE..BLOB_HEADER_SIZE + len];
+        let crc = u16::from_be_bytes([frame[2], frame[3]]);
+        if crc16(crc16(CRC16_INIT, &frame[..2]), data) != crc {
+            return Err(BlobError::Corrupt);
+        }
+        if frame[0] != self.version {
+            return Err(BlobError::VersionMismatch(frame[0]));
+        }
+
+        let out = buffer.get_mut(..len).ok_or(BlobError::BufferTooSmall)?;
+        out.copy_from_slice(data);
+        Ok(len)
+    }
+
+    /// Invalidate `slot` so that loading it fails with [`BlobError::Corrupt`].
+    pub fn erase_blob<R: RtcNvram>(
+        &self,
+        rtc: &mut R,
+        slot: u8,
+    ) -> Result<(), BlobError<R::Error>> {
+        let offset = self.slot_offset(rtc, slot)?;
+        // Length 0xFF never fits in a slot, so the header alone marks it invalid
+        rtc.write_nvram(offset, &[0xFF; BLOB_HEADER_SIZE])
+            .map_err(BlobError::Nvram)
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::error::Error;
+    use rtc_hal::datetime::DateTime;
+    use rtc_hal::error::ErrorType;
+    use rtc_hal::rtc::Rtc;
+
+    /// In-memory NVRAM with a settable clock and oscillator stop flag, for
+    /// the SRAM helper tests
+    pub(crate) struct MemNvram {
+        pub(crate) ram: Vec<u8>,
+        pub(crate) now: DateTime,
+        pub(crate) oscillator_stopped: bool,
+    }
+
+    impl MemNvram {
+        pub(crate) fn new(size: usize) -> Self {
+            Self {
+                ram: vec![0xA5; size],
+                now: DateTime::new(2025, 8, 21, 14, 30, 0).unwrap(),
+                oscillator_stopped: false,
+            }
+        }
+    }
+
+    impl ErrorType for MemNvram {
+        type Error = Error<()>;
+    }
+
+    impl Rtc for MemNvram {
+        fn get_datetime(&mut self) -> Result<DateTime, Self::Error> {
+            Ok(self.now)
+        }
+
+        fn set_datetime(&mut self, datetime: &DateTime) -> Result<(), Self::Error> {
+            self.now = *datetime;
+            Ok(())
+        }
+    }
+
+    impl RtcNvram for MemNvram {
+        fn read_nvram(&mut self, offset: u8, buffer: &mut [u8]) -> Result<(), Self::Error> {
+            let start = offset as usize;
+            let ram = self
+                .ram
+                .get(start..start + buffer.len())
+                .ok_or(Error::NvramOutOfBounds)?;
+            buffer.copy_from_slice(ram);
+            Ok(())
+        }
+
+        fn write_nvram(&mut self, offset: u8, data: &[u8]) -> Result<(), Self::Error> {
+            let start = offset as usize;
+            let ram = self
+                .ram
+                .get_mut(start..start + data.len())
+                .ok_or(Error::NvramOutOfBounds)?;
+            ram.copy_from_slice(data);
+            Ok(())
+        }
+
+        fn nvram_size(&self) -> u16 {
+            self.ram.len() as u16
+        }
+    }
+
+    #[test]
+    fn test_crc16_check_value() {
+        assert_eq!(crc16(CRC16_INIT, b"123456789"), 0x29B1);
+    }
+
+    #[test]
+    fn test_store_and_load() {
+        let mut rtc = MemNvram::new(236);
+        let store = BlobStore::new(8, 16, 3).with_version(7);
+
+        store.store_blob(&mut rtc, 1, b"hello").unwrap();
+        assert_eq!(rtc.ram[24], 7);
+        assert_eq!(rtc.ram[25], 5);
+
+        let mut buf = [0u8; 12];
+        assert_eq!(store.load_blob(&mut rtc, 1, &mut buf), Ok(5));
+        assert_eq!(&buf[..5], b"hello");
+
+        // Neighbouring slot untouched and still invalid
+        assert_eq!(
+            store.load_blob(&mut rtc, 0, &mut buf),
+            Err(BlobError::Corrupt)
+        );
+    }
+
+    #[test]
+    fn test_corruption_and_version_detected() {
+        let mut rtc = MemNvram::new(236);
+        let store = BlobStore::new(0, 16, 2).with_version(1);
+        store.store_blob(&mut rtc, 0, b"abc").unwrap();
+
+        let mut buf = [0u8; 12];
+        assert_eq!(
+            store.with_version(2).load_blob(&mut rtc, 0, &mut buf),
+            Err(BlobError::VersionMismatch(1))
+        );
+        assert_eq!(
+            store.load_blob(&mut rtc, 0, &mut [0u8; 2]),
+            Err(BlobError::BufferTooSmall)
+        );
+
+        rtc.ram[5] ^= 0x01;
+        assert_eq!(
+            store.load_blob(&mut rtc, 0, &mut buf),
+            Err(BlobError::Corrupt)
+        );
+
+        store.store_blob(&mut rtc, 0, b"abc").unwrap();
+        store.erase_blob(&mut rtc, 0).unwrap();
+        assert_eq!(
+            store.load_blob(&mut rtc, 0, &mut buf),
+            Err(BlobError::Corrupt)
+        );
+    }
+
+    #[test]
+    fn test_slot_and_size_checks() {
+        let mut rtc = MemNvram::new(236);
+        let store = BlobStore::new(0, 8, 2);
+
+        assert_eq!(
+            store.store_blob(&mut rtc, 2, b"x"),
+            Err(BlobError::InvalidSlot)
+        );
+        assert_eq!(
+            store.store_blob(&mut rtc, 0, b"12345"),
+            Err(BlobError::TooLarge)
+        );
+
+        // Store running past the end of the device
+        let store = BlobStore::new(230, 8, 1);
+        assert_eq!(
+            store.store_blob(&mut rtc, 0, b"x"),
+            Err(BlobError::InvalidSlot)
+        );
+    }
+}
@@ -0,0 +1,125 @@
+//! # Boot Counter
+//!
+//! A counter kept in the battery-backed SRAM of a DS3232 or DS3234 (or any
+//! other [`RtcNvram`]) that firmware bumps once per startup. The count
+//! survives resets and main-supply loss for as long as the backup battery
+//! holds, which makes it handy for field diagnostics: a unit that reboots far
+//! more often than its siblings has a problem worth looking at.
+//!
+//! A [`BootCounter`] occupies [`BOOT_COUNTER_SIZE`] bytes: the count as a
+//! big-endian `u32` followed by a CRC-16 over it. A counter that was never
+//! written or lost its contents with the backup battery fails the check and
+//! starts again from zero.
+//!
+//! ```no_run
+//! # use ds3231_rtc::ds3232::Ds3232;
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! # let mut rtc = Ds3232::new(embedded_hal_mock::eh1::i2c::Mock::new(&[]));
+//! use ds3231_rtc::boot_counter::BootCounter;
+//!
+//! const BOOTS: BootCounter = BootCounter::new(0xF0);
+//!
+//! let boots = BOOTS.increment(&mut rtc)?; // 1 on the very first start
+//! # Ok(())
+//! # }
+//! ```
+
+use rtc_hal::nvram::RtcNvram;
+
+use crate::blob::{CRC16_INIT, crc16};
+
+/// NVRAM bytes used by a [`BootCounter`]
+pub const BOOT_COUNTER_SIZE: usize = 6;
+
+/// Location of a boot counter in NVRAM
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct BootCounter {
+    offset: u8,
+}
+
+impl BootCounter {
+    /// Boot counter stored at NVRAM `offset`.
+    pub const fn new(offset: u8) -> Self {
+        Self { offset }
+    }
+
+    /// NVRAM offset of the counter
+    pub const fn offset(&self) -> u8 {
+        self.offset
+    }
+
+    /// Current count, or 0 if the stored value is missing or corrupt.
+    pub fn get<R: RtcNvram>(&self, rtc: &mut R) -> Result<u32, R::Error> {
+        let mut raw = [0u8; BOOT_COUNTER_SIZE];
+        rtc.read_nvram(self.offset, &mut raw)?;
+
+        let count = u32::from_be_bytes([raw[0], raw[1], raw[2], raw[3]]);
+        if encode(count) != raw {
+            return Ok(0);
+        }
+        Ok(count)
+    }
+
+    /// Increment the count and return the new value.
+    ///
+    /// Call once per startup. The count saturates at `u32::MAX`.
+    pub fn increment<R: RtcNvram>(&self, rtc: &mut R) -> Result<u32, R::Error> {
+        let count = self.get(rtc)?.saturating_add(1);
+        self.set(rtc, count)?;
+        Ok(count)
+    }
+
+    /// Store `count`, e.g. to reset the counter to zero after servicing.
+    pub fn set<R: RtcNvram>(&self, rtc: &mut R, count: u32) -> Result<(), R::Error> {
+        rtc.write_nvram(self.offset, &encode(count))
+    }
+}
+
+/// Stored form of `count`: the value followed by its CRC.
+fn encode(count: u32) -> [u8; BOOT_COUNTER_SIZE] {
+    let value = count.to_be_bytes();
+    let crc = crc16(CRC16_INIT, &value).to_be_bytes();
+    [value[0], value[1], value[2], value[3], crc[0], crc[1]]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blob::tests::MemNvram;
+    use crate::error::Error;
+
+    #[test]
+    fn test_counts_from_fresh_sram() {
+        let mut rtc = MemNvram::new(236);
+        let boots = BootCounter::new(0x10);
+
+        assert_eq!(boots.get(&mut rtc), Ok(0));
+        assert_eq!(boots.increment(&mut rtc), Ok(1));
+        assert_eq!(boots.increment(&mut rtc), Ok(2));
+        assert_eq!(BootCounter::new(0x10).get(&mut rtc), Ok(2));
+    }
+
+    #[test]
+    fn test_corrupt_count_restarts() {
+        let mut rtc = MemNvram::new(236);
+        let boots = BootCounter::new(0);
+
+        boots.set(&mut rtc, 41).unwrap();
+        rtc.ram[3] ^= 0x01;
+        assert_eq!(boots.increment(&mut rtc), Ok(1));
+
+        boots.set(&mut rtc, u32::MAX).unwrap();
+        assert_eq!(boots.increment(&mut rtc), Ok(u32::MAX));
+    }
+
+    #[test]
+    fn test_out_of_range_offset() {
+        let mut rtc = MemNvram::new(236);
+
+        assert_eq!(
+            BootCounter::new(232).increment(&mut rtc),
+            Err(Error::NvramOutOfBounds)
+        );
+    }
+}
@@ -0,0 +1,210 @@
+//! # DateTime Builder
+//!
+//! [`DateTime::new`] takes six positional `u8`/`u16` arguments, which makes
+//! it easy to swap the month and day or the minute and second without the
+//! compiler noticing. [`DateTimeBuilder`] names every field instead, and
+//! takes the month as a [`Month`]:
+//!
+//! ```
+//! use ds3231_rtc::DateTime;
+//! use ds3231_rtc::builder::DateTimeBuilderExt;
+//! use ds3231_rtc::month::Month;
+//!
+//! let dt = DateTime::builder()
+//!     .year(2025)
+//!     .month(Month::March)
+//!     .day(30)
+//!     .hour(14)
+//!     .minute(5)
+//!     .build()
+//!     .unwrap();
+//! assert_eq!(dt, DateTime::new(2025, 3, 30, 14, 5, 0).unwrap());
+//! ```
+//!
+//! The year, month and day are required; the time fields default to zero.
+
+use rtc_hal::datetime::{DateTime, DateTimeError};
+
+use crate::month::Month;
+
+/// Errors returned by [`DateTimeBuilder::build`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum BuildError {
+    /// [`year`](DateTimeBuilder::year) was never called
+    MissingYear,
+    /// [`month`](DateTimeBuilder::month) was never called
+    MissingMonth,
+    /// [`day`](DateTimeBuilder::day) was never called
+    MissingDay,
+    /// A field was set to an out-of-range value
+    Invalid(DateTimeError),
+}
+
+impl core::fmt::Display for BuildError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            BuildError::MissingYear => write!(f, "Year not set"),
+            BuildError::MissingMonth => write!(f, "Month not set"),
+            BuildError::MissingDay => write!(f, "Day not set"),
+            BuildError::Invalid(e) => write!(f, "Invalid date/time values: {e}"),
+        }
+    }
+}
+
+impl core::error::Error for BuildError {}
+
+impl From<DateTimeError> for BuildError {
+    fn from(value: DateTimeError) -> Self {
+        BuildError::Invalid(value)
+    }
+}
+
+/// Fluent builder for [`DateTime`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct DateTimeBuilder {
+    year: Option<u16>,
+    month: Option<Month>,
+    day: Option<u8>,
+    hour: u8,
+    minute: u8,
+    second: u8,
+}
+
+impl DateTimeBuilder {
+    /// Create an empty builder.
+    pub const fn new() -> Self {
+        Self {
+            year: None,
+            month: None,
+            day: None,
+            hour: 0,
+            minute: 0,
+            second: 0,
+        }
+    }
+
+    /// Set the full year (e.g. 2025).
+    pub const fn year(mut self, year: u16) -> Self {
+        self.year = Some(year);
+        self
+    }
+
+    /// Set the month.
+    pub const fn month(mut self, month: Month) -> Self {
+        self.month = Some(month);
+        self
+    }
+
+    /// Set the day of month (1-31).
+    pub const fn day(mut self, day: u8) -> Self {
+        self.day = Some(day);
+        self
+    }
+
+    /// Set the hour (0-23). Defaults to 0.
+    pub const fn hour(mut self, hour: u8) -> Self {
+        self.hour = hour;
+        self
+    }
+
+    /// Set the minute (0-59). Defaults to 0.
+    pub const fn minute(mut self, minute: u8) -> Self {
+        self.minute = minute;
+        self
+    }
+
+    /// Set the second (0-59). Defaults to 0.
+    pub const fn second(mut self, second: u8) -> Self {
+        self.second = second;
+        self
+    }
+
+    /// Validate the fields and build the datetime.
+    ///
+    /// Missing required fields are reported before range errors.
+    pub fn build(self) -> Result<DateTime, BuildError> {
+        let year = self.year.ok_or(BuildError::MissingYear)?;
+        let month = self.month.ok_or(BuildError::MissingMonth)?;
+        let day = self.day.ok_or(BuildError::MissingDay)?;
+        Ok(DateTime::new(
+            year,
+            month.to_number(),
+            day,
+            self.hour,
+            self.minute,
+            self.second,
+        )?)
+    }
+}
+
+/// Extension trait adding [`DateTime::builder`](DateTimeBuilderExt::builder).
+pub trait DateTimeBuilderExt {
+    /// Start building a datetime field by field.
+    fn builder() -> DateTimeBuilder;
+}
+
+impl DateTimeBuilderExt for DateTime {
+    fn builder() -> DateTimeBuilder {
+        DateTimeBuilder::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builder_full() {
+        let dt = DateTime::builder()
+            .year(2024)
+            .month(Month::February)
+            .day(29)
+            .hour(23)
+            .minute(59)
+            .second(58)
+            .build()
+            .unwrap();
+        assert_eq!(dt, DateTime::new(2024, 2, 29, 23, 59, 58).unwrap());
+    }
+
+    #[test]
+    fn test_builder_missing_fields() {
+        assert_eq!(
+            DateTimeBuilder::new().month(Month::January).day(1).build(),
+            Err(BuildError::MissingYear)
+        );
+        assert_eq!(
+            DateTimeBuilder::new().year(2025).day(1).build(),
+            Err(BuildError::MissingMonth)
+        );
+        assert_eq!(
+            DateTimeBuilder::new()
+                .year(2025)
+                .month(Month::January)
+                .build(),
+            Err(BuildError::MissingDay)
+        );
+    }
+
+    #[test]
+    fn test_builder_invalid_fields() {
+        let base = DateTime::builder()
+            .year(2025)
+            .month(Month::February)
+            .day(28);
+        assert_eq!(
+            base.day(29).build(),
+            Err(BuildError::Invalid(DateTimeError::InvalidDay))
+        );
+        assert_eq!(
+            base.month(Month::April).day(31).build(),
+            Err(BuildError::Invalid(DateTimeError::InvalidDay))
+        );
+        assert_eq!(
+            base.minute(60).build(),
+            Err(BuildError::Invalid(DateTimeError::InvalidMinute))
+        );
+    }
+}
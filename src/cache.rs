@@ -0,0 +1,141 @@
+//! # Control Register Cache
+//!
+//! Every change to the control register (square wave, oscillator enable) is
+//! a read-modify-write. Applications that toggle the square wave often can
+//! let the driver remember the register instead, so only the first change
+//! reads it and later ones are a single write:
+//!
+//! ```no_run
+//! # use ds3231_rtc::Ds3231;
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! # let mut rtc = Ds3231::new(embedded_hal_mock::eh1::i2c::Mock::new(&[]));
+//! rtc.set_control_cache(true);
+//! rtc.enable_square_wave()?; // read + write
+//! rtc.disable_square_wave()?; // write only
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! The cache assumes the driver is the only one changing the register. With
+//! several masters on the bus, or after anything else may have changed it
+//! (e.g. a power-on reset restoring the defaults), call
+//! [`invalidate_cache`](crate::Ds3231::invalidate_cache) so the next change
+//! reads it again.
+//!
+//! The status register is not cached: the chip sets the alarm and oscillator
+//! stop flags itself, and writing back a stale copy would clear a flag that
+//! was raised in the meantime.
+
+#[cfg(feature = "async")]
+use crate::interface::AsyncRegisterInterface;
+use crate::interface::RegisterInterface;
+
+use crate::Ds3231;
+#[cfg(feature = "async")]
+use crate::ds3231::Ds3231Async;
+
+/// Last known control register value, if caching is enabled
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub(crate) struct ControlCache {
+    enabled: bool,
+    value: Option<u8>,
+}
+
+impl ControlCache {
+    /// Cached value, `None` if disabled or not yet known.
+    pub(crate) fn get(&self) -> Option<u8> {
+        self.value.filter(|_| self.enabled)
+    }
+
+    /// Remember `value` as the register content, if enabled.
+    pub(crate) fn store(&mut self, value: u8) {
+        if self.enabled {
+            self.value = Some(value);
+        }
+    }
+
+    /// Forget the cached value.
+    pub(crate) fn invalidate(&mut self) {
+        self.value = None;
+    }
+}
+
+#[maybe_async_cfg::maybe(
+    idents(
+        AsyncRegisterInterface(sync = "RegisterInterface", async = "AsyncRegisterInterface"),
+        Ds3231(sync = "Ds3231", async = "Ds3231Async")
+    ),
+    sync(keep_self),
+    async(feature = "async")
+)]
+impl<I2C, V> Ds3231<I2C, V>
+where
+    I2C: AsyncRegisterInterface,
+{
+    /// Enable or disable caching of the control register.
+    ///
+    /// Disabled by default. See the [module documentation](crate::cache).
+    pub fn set_control_cache(&mut self, enabled: bool) {
+        self.control_cache = ControlCache {
+            enabled,
+            value: None,
+        };
+    }
+
+    /// Forget the cached control register value, so the next change reads
+    /// the register from the chip again.
+    pub fn invalidate_cache(&mut self) {
+        self.control_cache.invalidate();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registers::{INTCN_BIT, Register};
+    use embedded_hal::i2c::ErrorKind;
+    use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
+
+    const DS3231_ADDR: u8 = 0x68;
+
+    #[test]
+    fn test_cached_control_skips_reads() {
+        let expectations = [
+            I2cTransaction::write_read(DS3231_ADDR, vec![Register::Control.addr()], vec![0x1C]),
+            I2cTransaction::write(DS3231_ADDR, vec![Register::Control.addr(), 0x18]),
+            I2cTransaction::write(DS3231_ADDR, vec![Register::Control.addr(), 0x1C]),
+            // After invalidation the register is read again
+            I2cTransaction::write_read(DS3231_ADDR, vec![Register::Control.addr()], vec![0x1C]),
+        ];
+        let mut ds3231 = Ds3231::new(I2cMock::new(&expectations));
+        ds3231.set_control_cache(true);
+
+        ds3231.enable_square_wave().unwrap();
+        ds3231.disable_square_wave().unwrap();
+        // Already disabled: no transfer at all
+        ds3231.disable_square_wave().unwrap();
+        ds3231.invalidate_cache();
+        ds3231.disable_square_wave().unwrap();
+
+        ds3231.release_i2c().done();
+    }
+
+    #[test]
+    fn test_failed_write_invalidates_cache() {
+        let expectations = [
+            I2cTransaction::write_read(DS3231_ADDR, vec![Register::Control.addr()], vec![0x1C]),
+            I2cTransaction::write(DS3231_ADDR, vec![Register::Control.addr(), 0x18])
+                .with_error(ErrorKind::Other),
+            I2cTransaction::write_read(DS3231_ADDR, vec![Register::Control.addr()], vec![0x1C]),
+            I2cTransaction::write(DS3231_ADDR, vec![Register::Control.addr(), 0x18]),
+        ];
+        let mut ds3231 = Ds3231::new(I2cMock::new(&expectations));
+        ds3231.set_control_cache(true);
+
+        assert!(ds3231.enable_square_wave().is_err());
+        ds3231.enable_square_wave().unwrap();
+        assert_eq!(ds3231.control_cache.get(), Some(0x1C & !INTCN_BIT));
+
+        ds3231.release_i2c().done();
+    }
+}
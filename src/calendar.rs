@@ -0,0 +1,399 @@
+//! # Calendar Utilities
+//!
+//! Calendar math used by the driver, exposed for alarm and scheduling code
+//! that needs the same calculations:
+//!
+//! - leap years and month lengths ([`is_leap_year`], [`days_in_month`])
+//! - ordinal dates and ISO 8601 week numbers ([`day_of_year`], [`iso_week`])
+//! - day counts and Unix timestamps ([`days_since_epoch`],
+//!   [`to_unix_timestamp`], [`from_unix_timestamp`])
+//! - datetime arithmetic with full rollover ([`add_seconds`])
+//! - Julian days for astronomy-adjacent code ([`julian_day_number`],
+//!   [`julian_date`], [`modified_julian_day`], [`modified_julian_date`] and
+//!   their inverses)
+//!
+//! Day counts are based on the days-from-civil algorithm and are exact for
+//! the proleptic Gregorian calendar. All functions are pure and allocation free.
+
+use rtc_hal::datetime::{DateTime, DateTimeError};
+
+pub use rtc_hal::datetime::{days_in_month, is_leap_year};
+
+/// Seconds in one day
+pub const SECONDS_PER_DAY: u64 = 86_400;
+
+/// Cumulative days before the first of each month in a non-leap year
+const DAYS_BEFORE_MONTH: [u16; 12] = [0, 31, 59, 90, 120, 151, 181, 212, 243, 273, 304, 334];
+
+/// Ordinal day of the year (1 = January 1st, up to 366).
+pub fn day_of_year(datetime: &DateTime) -> u16 {
+    let month = datetime.month();
+    let mut day = DAYS_BEFORE_MONTH[(month as usize + 11) % 12] + datetime.day_of_month() as u16;
+    if month > 2 && is_leap_year(datetime.year()) {
+        day += 1;
+    }
+    day
+}
+
+/// Number of ISO 8601 weeks (52 or 53) in the given ISO week-numbering year.
+pub fn iso_weeks_in_year(year: u16) -> u8 {
+    // A year has 53 weeks if January 1st is a Thursday, or a Wednesday in a leap year
+    let jan1 = iso_weekday(days_since_epoch(year, 1, 1));
+    if jan1 == 4 || (jan1 == 3 && is_leap_year(year)) {
+        53
+    } else {
+        52
+    }
+}
+
+/// ISO 8601 week date as `(iso_year, week)`, with weeks numbered 1-53.
+///
+/// Weeks start on Monday and week 1 is the week containing the year's first
+/// Thursday, so the first days of January can belong to the previous ISO year
+/// and the last days of December to the next one.
+pub fn iso_week(datetime: &DateTime) -> (u16, u8) {
+    let year = datetime.year();
+    let weekday = iso_weekday(days_since_epoch(
+        year,
+        datetime.month(),
+        datetime.day_of_month(),
+    )) as i32;
+    let week = (day_of_year(datetime) as i32 - weekday + 10) / 7;
+
+    if week < 1 {
+        (year - 1, iso_weeks_in_year(year - 1))
+    } else if week > iso_weeks_in_year(year) as i32 {
+        (year + 1, 1)
+    } else {
+        (year, week as u8)
+    }
+}
+
+/// ISO weekday (1=Monday, 7=Sunday) for a number of days since 1970-01-01.
+fn iso_weekday(days: i64) -> u8 {
+    // 1970-01-01 was a Thursday
+    ((days + 3).rem_euclid(7) + 1) as u8
+}
+
+/// Number of days since 1970-01-01 for the given civil date.
+///
+/// Negative for dates before the epoch.
+pub fn days_since_epoch(year: u16, month: u8, day_of_month: u8) -> i64 {
+    let y = year as i64 - if month <= 2 { 1 } else { 0 };
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let m = month as i64;
+    let doy = (153 * (m + if m > 2 { -3 } else { 9 }) + 2) / 5 + day_of_month as i64 - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+/// Civil date `(year, month, day_of_month)` for a number of days since 1970-01-01.
+pub fn civil_from_days(days: i64) -> (i64, u8, u8) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u8;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u8;
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+/// Day of week (1=Sunday, 7=Saturday) for the given civil date.
+///
+/// 1970-01-01 was a Thursday, so the weekday follows directly from the
+/// number of days since the epoch.
+pub(crate) fn weekday_number(year: u16, month: u8, day_of_month: u8) -> u8 {
+    ((days_since_epoch(year, month, day_of_month) + 4).rem_euclid(7) + 1) as u8
+}
+
+/// Seconds since the Unix epoch for the given datetime.
+pub fn to_unix_timestamp(datetime: &DateTime) -> u64 {
+    let days = days_since_epoch(datetime.year(), datetime.month(), datetime.day_of_month()) as u64;
+
+    days * SECONDS_PER_DAY
+        + datetime.hour() as u64 * 3600
+        + datetime.minute() as u64 * 60
+        + datetime.second() as u64
+}
+
+/// Datetime for the given number of seconds since the Unix epoch.
+///
+/// Returns `DateTimeError::InvalidYear` if the result does not fit in a `u16` year.
+pub fn from_unix_timestamp(timestamp: u64) -> Result<DateTime, DateTimeError> {
+    let days = (timestamp / SECONDS_PER_DAY) as i64;
+    let secs = timestamp % SECONDS_PER_DAY;
+
+    let (year, month, day) = civil_from_days(days);
+    let year = u16::try_from(year).map_err(|_| DateTimeError::InvalidYear)?;
+
+    DateTime::new(
+        year,
+        month,
+        day,
+        (secs / 3600) as u8,
+        ((secs % 3600) / 60) as u8,
+        (secs % 60) as u8,
+    )
+}
+
+/// Add a signed number of seconds to a datetime, rolling over minutes, hours,
+/// days, months and years as needed.
+///
+/// Returns `DateTimeError::InvalidYear` if the result falls before 1970 or
+/// after the largest representable year.
+pub fn add_seconds(datetime: &DateTime, seconds: i64) -> Result<DateTime, DateTimeError> {
+    let timestamp = (to_unix_timestamp(datetime) as i64)
+        .checked_add(seconds)
+        .ok_or(DateTimeError::InvalidYear)?;
+    let timestamp = u64::try_from(timestamp).map_err(|_| DateTimeError::InvalidYear)?;
+    from_unix_timestamp(timestamp)
+}
+
+/// Julian Day Number of 1970-01-01
+const UNIX_EPOCH_JDN: i64 = 2_440_588;
+
+/// Modified Julian Day of 1970-01-01
+const UNIX_EPOCH_MJD: i64 = 40_587;
+
+/// Julian Date of 1970-01-01T00:00:00
+const UNIX_EPOCH_JD: f64 = 2_440_587.5;
+
+/// Julian Day Number of the given date.
+///
+/// The JDN is the integer Julian day that starts at noon of the given civil
+/// date, so it ignores the time of day. Use [`julian_date`] for a fractional value.
+pub fn julian_day_number(datetime: &DateTime) -> u32 {
+    (days_since_epoch(datetime.year(), datetime.month(), datetime.day_of_month()) + UNIX_EPOCH_JDN)
+        as u32
+}
+
+/// Julian Date (fractional Julian days) of the given datetime.
+///
+/// A Julian Date starts at noon, so midnight has a fractional part of 0.5.
+pub fn julian_date(datetime: &DateTime) -> f64 {
+    UNIX_EPOCH_JD + to_unix_timestamp(datetime) as f64 / SECONDS_PER_DAY as f64
+}
+
+/// Modified Julian Day (MJD) of the given date, which starts at midnight.
+pub fn modified_julian_day(datetime: &DateTime) -> u32 {
+    (days_since_epoch(datetime.year(), datetime.month(), datetime.day_of_month()) + UNIX_EPOCH_MJD)
+        as u32
+}
+
+/// Modified Julian Date (fractional MJD, `JD - 2400000.5`) of the given datetime.
+pub fn modified_julian_date(datetime: &DateTime) -> f64 {
+    UNIX_EPOCH_MJD as f64 + to_unix_timestamp(datetime) as f64 / SECONDS_PER_DAY as f64
+}
+
+/// Datetime at noon of the given Julian Day Number.
+///
+/// Returns `DateTimeError::InvalidYear` for days before 1970-01-01.
+pub fn from_julian_day_number(jdn: u32) -> Result<DateTime, DateTimeError> {
+    let days = jdn as i64 - UNIX_EPOCH_JDN;
+    let timestamp = days_to_timestamp(days)? + SECONDS_PER_DAY / 2;
+    from_unix_timestamp(timestamp)
+}
+
+/// Datetime at midnight of the given Modified Julian Day.
+///
+/// Returns `DateTimeError::InvalidYear` for days before 1970-01-01.
+pub fn from_modified_julian_day(mjd: u32) -> Result<DateTime, DateTimeError> {
+    from_unix_timestamp(days_to_timestamp(mjd as i64 - UNIX_EPOCH_MJD)?)
+}
+
+/// Datetime for the given Julian Date, rounded to the nearest second.
+///
+/// Returns `DateTimeError::InvalidYear` for dates before 1970-01-01.
+pub fn from_julian_date(jd: f64) -> Result<DateTime, DateTimeError> {
+    from_fractional_days(jd - UNIX_EPOCH_JD)
+}
+
+/// Datetime for the given Modified Julian Date, rounded to the nearest second.
+///
+/// Returns `DateTimeError::InvalidYear` for dates before 1970-01-01.
+pub fn from_modified_julian_date(mjd: f64) -> Result<DateTime, DateTimeError> {
+    from_fractional_days(mjd - UNIX_EPOCH_MJD as f64)
+}
+
+fn days_to_timestamp(days: i64) -> Result<u64, DateTimeError> {
+    u64::try_from(days)
+        .map(|days| days * SECONDS_PER_DAY)
+        .map_err(|_| DateTimeError::InvalidYear)
+}
+
+fn from_fractional_days(days_since_epoch: f64) -> Result<DateTime, DateTimeError> {
+    let seconds = days_since_epoch * SECONDS_PER_DAY as f64;
+    if seconds.is_nan() || seconds < 0.0 {
+        return Err(DateTimeError::InvalidYear);
+    }
+    from_unix_timestamp((seconds + 0.5) as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_epoch_is_zero() {
+        let dt = DateTime::new(1970, 1, 1, 0, 0, 0).unwrap();
+        assert_eq!(to_unix_timestamp(&dt), 0);
+        assert_eq!(from_unix_timestamp(0).unwrap(), dt);
+    }
+
+    #[test]
+    fn test_known_timestamps() {
+        let dt = DateTime::new(2025, 8, 21, 14, 30, 0).unwrap();
+        assert_eq!(to_unix_timestamp(&dt), 1_755_786_600);
+
+        let dt = DateTime::new(2000, 2, 29, 0, 0, 0).unwrap();
+        assert_eq!(to_unix_timestamp(&dt), 951_782_400);
+
+        let dt = DateTime::new(2100, 3, 1, 0, 0, 0).unwrap();
+        assert_eq!(to_unix_timestamp(&dt), 4_107_542_400);
+    }
+
+    #[test]
+    fn test_unix_timestamp_roundtrip() {
+        let cases = [
+            DateTime::new(1999, 12, 31, 23, 59, 59).unwrap(),
+            DateTime::new(2024, 2, 29, 12, 34, 56).unwrap(),
+            DateTime::new(2099, 12, 31, 23, 59, 59).unwrap(),
+            DateTime::new(2199, 1, 1, 0, 0, 1).unwrap(),
+        ];
+
+        for dt in cases {
+            assert_eq!(from_unix_timestamp(to_unix_timestamp(&dt)).unwrap(), dt);
+        }
+    }
+
+    #[test]
+    fn test_weekday_number() {
+        // 1970-01-01 was a Thursday
+        assert_eq!(weekday_number(1970, 1, 1), 5);
+        // 2000-01-01 was a Saturday
+        assert_eq!(weekday_number(2000, 1, 1), 7);
+        // 2025-08-24 was a Sunday
+        assert_eq!(weekday_number(2025, 8, 24), 1);
+        // 2150-01-01 is a Thursday
+        assert_eq!(weekday_number(2150, 1, 1), 5);
+    }
+
+    #[test]
+    fn test_day_of_year() {
+        let doy = |y, m, d| day_of_year(&DateTime::new(y, m, d, 0, 0, 0).unwrap());
+        assert_eq!(doy(2025, 1, 1), 1);
+        assert_eq!(doy(2025, 3, 1), 60);
+        assert_eq!(doy(2024, 3, 1), 61);
+        assert_eq!(doy(2025, 12, 31), 365);
+        assert_eq!(doy(2024, 12, 31), 366);
+    }
+
+    #[test]
+    fn test_iso_week() {
+        let week = |y, m, d| iso_week(&DateTime::new(y, m, d, 0, 0, 0).unwrap());
+        // 2025-01-01 is a Wednesday in week 1
+        assert_eq!(week(2025, 1, 1), (2025, 1));
+        // 2024-12-30 (Monday) already belongs to 2025-W01
+        assert_eq!(week(2024, 12, 30), (2025, 1));
+        // 2021-01-03 (Sunday) belongs to 2020-W53
+        assert_eq!(week(2021, 1, 3), (2020, 53));
+        // 2026-12-31 (Thursday) is in 2026-W53
+        assert_eq!(week(2026, 12, 31), (2026, 53));
+        assert_eq!(week(2025, 8, 21), (2025, 34));
+    }
+
+    #[test]
+    fn test_iso_weeks_in_year() {
+        assert_eq!(iso_weeks_in_year(2020), 53);
+        assert_eq!(iso_weeks_in_year(2024), 52);
+        assert_eq!(iso_weeks_in_year(2026), 53);
+    }
+
+    #[test]
+    fn test_leap_year_and_month_length() {
+        assert!(is_leap_year(2000));
+        assert!(!is_leap_year(2100));
+        assert_eq!(days_in_month(2100, 2), 28);
+        assert_eq!(days_in_month(2024, 2), 29);
+    }
+
+    #[test]
+    fn test_julian_day_number() {
+        let dt = DateTime::new(2000, 1, 1, 12, 0, 0).unwrap();
+        assert_eq!(julian_day_number(&dt), 2_451_545);
+        assert_eq!(julian_date(&dt), 2_451_545.0);
+
+        let dt = DateTime::new(1970, 1, 1, 0, 0, 0).unwrap();
+        assert_eq!(julian_date(&dt), 2_440_587.5);
+    }
+
+    #[test]
+    fn test_modified_julian_day() {
+        let dt = DateTime::new(2025, 8, 21, 6, 0, 0).unwrap();
+        assert_eq!(modified_julian_day(&dt), 60_908);
+        assert_eq!(modified_julian_date(&dt), 60_908.25);
+    }
+
+    #[test]
+    fn test_julian_roundtrip() {
+        let dt = DateTime::new(2025, 8, 21, 14, 30, 45).unwrap();
+        assert_eq!(from_julian_date(julian_date(&dt)).unwrap(), dt);
+        assert_eq!(
+            from_modified_julian_date(modified_julian_date(&dt)).unwrap(),
+            dt
+        );
+
+        assert_eq!(
+            from_julian_day_number(2_451_545).unwrap(),
+            DateTime::new(2000, 1, 1, 12, 0, 0).unwrap()
+        );
+        assert_eq!(
+            from_modified_julian_day(60_908).unwrap(),
+            DateTime::new(2025, 8, 21, 0, 0, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_julian_before_epoch() {
+        assert_eq!(
+            from_julian_day_number(2_440_000),
+            Err(DateTimeError::InvalidYear)
+        );
+        assert_eq!(
+            from_modified_julian_date(0.0),
+            Err(DateTimeError::InvalidYear)
+        );
+        assert_eq!(from_julian_date(f64::NAN), Err(DateTimeError::InvalidYear));
+    }
+
+    #[test]
+    fn test_add_seconds() {
+        let dt = DateTime::new(2024, 12, 31, 23, 59, 59).unwrap();
+        assert_eq!(
+            add_seconds(&dt, 1).unwrap(),
+            DateTime::new(2025, 1, 1, 0, 0, 0).unwrap()
+        );
+        assert_eq!(add_seconds(&dt, 0).unwrap(), dt);
+
+        let dt = DateTime::new(2024, 3, 1, 0, 30, 0).unwrap();
+        assert_eq!(
+            add_seconds(&dt, -3600).unwrap(),
+            DateTime::new(2024, 2, 29, 23, 30, 0).unwrap()
+        );
+
+        let dt = DateTime::new(1970, 1, 1, 0, 0, 0).unwrap();
+        assert_eq!(add_seconds(&dt, -1), Err(DateTimeError::InvalidYear));
+    }
+
+    #[test]
+    fn test_days_roundtrip() {
+        for days in (0..80_000).step_by(7) {
+            let (y, m, d) = civil_from_days(days);
+            assert_eq!(days_since_epoch(y as u16, m, d), days);
+        }
+    }
+}
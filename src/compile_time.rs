@@ -0,0 +1,155 @@
+//! # Firmware Build Time
+//!
+//! A brand-new board (or one whose backup battery died) reports whatever the
+//! DS3231 powered up with, typically 2000-01-01. A common trick is to
+//! initialise the RTC to the moment the firmware was built, which is at least
+//! close to the truth on a freshly flashed device.
+//!
+//! Unlike C's `__DATE__`/`__TIME__`, Rust has no built-in build timestamp, so
+//! the application's build script provides one as a Unix timestamp (UTC) in
+//! the `DS3231_BUILD_TIMESTAMP` environment variable:
+//!
+//! ```ignore
+//! // build.rs
+//! fn main() {
+//!     let now = std::time::SystemTime::now()
+//!         .duration_since(std::time::UNIX_EPOCH)
+//!         .unwrap()
+//!         .as_secs();
+//!     println!("cargo:rustc-env=DS3231_BUILD_TIMESTAMP={now}");
+//!     // Rebuild every time so the timestamp stays current
+//!     println!("cargo:rerun-if-changed=.");
+//! }
+//! ```
+//!
+//! [`compile_time!`](crate::compile_time!) reads it at compile time, and
+//! [`Ds3231::set_compile_time`] programs it only when the Oscillator Stop
+//! Flag shows that the current time is not valid:
+//!
+//! ```ignore
+//! if rtc.set_compile_time(ds3231_rtc::compile_time!())? {
+//!     // The clock had lost power and now holds the build time
+//! }
+//! ```
+
+use crate::{Ds3231, calendar, error::Error, interface::RegisterInterface};
+
+/// Parse a decimal Unix timestamp during constant evaluation.
+///
+/// Used by [`compile_time!`](crate::compile_time!); an empty or non-numeric
+/// string is a compile error there.
+#[doc(hidden)]
+pub const fn parse_timestamp(s: &str) -> u64 {
+    let bytes = s.as_bytes();
+    assert!(!bytes.is_empty(), "DS3231_BUILD_TIMESTAMP is empty");
+
+    let mut value: u64 = 0;
+    let mut i = 0;
+    while i < bytes.len() {
+        let b = bytes[i];
+        assert!(
+            b.is_ascii_digit(),
+            "DS3231_BUILD_TIMESTAMP must be a decimal Unix timestamp"
+        );
+        value = value * 10 + (b - b'0') as u64;
+        i += 1;
+    }
+    value
+}
+
+/// The firmware build time as a Unix timestamp, taken from the
+/// `DS3231_BUILD_TIMESTAMP` environment variable at compile time.
+///
+/// See the [module documentation](crate::compile_time) for the build
+/// script that sets it.
+#[macro_export]
+macro_rules! compile_time {
+    () => {{
+        const TIMESTAMP: u64 =
+            $crate::compile_time::parse_timestamp(env!("DS3231_BUILD_TIMESTAMP"));
+        TIMESTAMP
+    }};
+}
+
+impl<I2C, V> Ds3231<I2C, V>
+where
+    I2C: RegisterInterface,
+{
+    /// Set the RTC to the given build timestamp if the Oscillator Stop Flag
+    /// indicates the current time is invalid, then clear the flag.
+    ///
+    /// Returns `true` if the clock was set, or `false` if it was left alone
+    /// because it kept running.
+    pub fn set_compile_time(&mut self, timestamp: u64) -> Result<bool, Error<I2C::Error>> {
+        if !self.oscillator_stopped()? {
+            return Ok(false);
+        }
+
+        let datetime = calendar::from_unix_timestamp(timestamp).map_err(Error::DateTime)?;
+        self.set_datetime(&datetime)?;
+        self.clear_oscillator_stop_flag()?;
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registers::Register;
+    use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTrans};
+
+    #[test]
+    fn test_parse_timestamp() {
+        const TS: u64 = parse_timestamp("1755786600");
+        assert_eq!(TS, 1_755_786_600);
+        assert_eq!(parse_timestamp("0"), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "decimal Unix timestamp")]
+    fn test_parse_timestamp_rejects_garbage() {
+        parse_timestamp(core::hint::black_box("2025-08-21"));
+    }
+
+    #[test]
+    fn test_set_compile_time_when_oscillator_stopped() {
+        let expectations = [
+            I2cTrans::write_read(0x68, vec![Register::Status.addr()], vec![0x80]),
+            // 2025-08-21 14:30:00 UTC (Thursday)
+            I2cTrans::write(
+                0x68,
+                vec![
+                    Register::Seconds.addr(),
+                    0x00,
+                    0x30,
+                    0x14,
+                    0x05,
+                    0x21,
+                    0x08,
+                    0x25,
+                ],
+            ),
+            I2cTrans::write_read(0x68, vec![Register::Status.addr()], vec![0x80]),
+            I2cTrans::write(0x68, vec![Register::Status.addr(), 0x03]),
+        ];
+        let mut ds3231 = Ds3231::new(I2cMock::new(&expectations));
+
+        assert!(ds3231.set_compile_time(1_755_786_600).unwrap());
+
+        ds3231.release_i2c().done();
+    }
+
+    #[test]
+    fn test_set_compile_time_keeps_running_clock() {
+        let expectations = [I2cTrans::write_read(
+            0x68,
+            vec![Register::Status.addr()],
+            vec![0x00],
+        )];
+        let mut ds3231 = Ds3231::new(I2cMock::new(&expectations));
+
+        assert!(!ds3231.set_compile_time(1_755_786_600).unwrap());
+
+        ds3231.release_i2c().done();
+    }
+}
@@ -0,0 +1,190 @@
+//! # Compile-Time DateTime
+//!
+//! [`DateTime`] from `rtc-hal` can only be built at runtime. Firmware that
+//! wants a fallback datetime baked into flash (e.g. to restore after the
+//! oscillator has stopped) can use [`ConstDateTime`] instead: it is built by a
+//! `const fn` that checks every field during constant evaluation, so an
+//! invalid literal is a compile error rather than a runtime `Err`.
+//!
+//! ```
+//! use ds3231_rtc::{DateTime, const_datetime, const_datetime::ConstDateTime};
+//!
+//! const FALLBACK: ConstDateTime = ConstDateTime::new(2025, 1, 1, 0, 0, 0);
+//!
+//! // Or inline, still evaluated at compile time
+//! let fallback = const_datetime!(2025, 1, 1, 0, 0, 0);
+//! assert_eq!(fallback, FALLBACK);
+//!
+//! let dt: DateTime = FALLBACK.into();
+//! assert_eq!(dt, DateTime::new(2025, 1, 1, 0, 0, 0).unwrap());
+//! ```
+//!
+//! ```compile_fail
+//! use ds3231_rtc::const_datetime::ConstDateTime;
+//!
+//! // February 30th does not exist
+//! const BAD: ConstDateTime = ConstDateTime::new(2025, 2, 30, 0, 0, 0);
+//! ```
+
+use rtc_hal::datetime::DateTime;
+
+/// A datetime validated during constant evaluation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ConstDateTime {
+    year: u16,
+    month: u8,
+    day_of_month: u8,
+    hour: u8,
+    minute: u8,
+    second: u8,
+}
+
+const fn is_leap_year(year: u16) -> bool {
+    (year.is_multiple_of(4) && !year.is_multiple_of(100)) || year.is_multiple_of(400)
+}
+
+const fn days_in_month(year: u16, month: u8) -> u8 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+impl ConstDateTime {
+    /// Create a datetime, checking the same ranges as [`DateTime::new`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if any field is out of range. In a `const` item (or via
+    /// [`const_datetime!`](crate::const_datetime!)) this becomes a compile
+    /// error, so no check remains at runtime.
+    pub const fn new(
+        year: u16,
+        month: u8,
+        day_of_month: u8,
+        hour: u8,
+        minute: u8,
+        second: u8,
+    ) -> Self {
+        assert!(year >= 1970, "year must be 1970 or later");
+        assert!(month >= 1 && month <= 12, "month must be 1-12");
+        assert!(
+            day_of_month >= 1 && day_of_month <= days_in_month(year, month),
+            "day of month out of range"
+        );
+        assert!(hour < 24, "hour must be 0-23");
+        assert!(minute < 60, "minute must be 0-59");
+        assert!(second < 60, "second must be 0-59");
+
+        Self {
+            year,
+            month,
+            day_of_month,
+            hour,
+            minute,
+            second,
+        }
+    }
+
+    /// Full year
+    pub const fn year(&self) -> u16 {
+        self.year
+    }
+
+    /// Month (1-12)
+    pub const fn month(&self) -> u8 {
+        self.month
+    }
+
+    /// Day of month (1-31)
+    pub const fn day_of_month(&self) -> u8 {
+        self.day_of_month
+    }
+
+    /// Hour (0-23)
+    pub const fn hour(&self) -> u8 {
+        self.hour
+    }
+
+    /// Minute (0-59)
+    pub const fn minute(&self) -> u8 {
+        self.minute
+    }
+
+    /// Second (0-59)
+    pub const fn second(&self) -> u8 {
+        self.second
+    }
+
+    /// Convert to a [`DateTime`].
+    ///
+    /// `rtc-hal` has no unchecked constructor, so this repeats the (cheap)
+    /// range checks, but it can never fail.
+    pub fn to_datetime(&self) -> DateTime {
+        match DateTime::new(
+            self.year,
+            self.month,
+            self.day_of_month,
+            self.hour,
+            self.minute,
+            self.second,
+        ) {
+            Ok(datetime) => datetime,
+            // Every field was range checked in `new`
+            Err(_) => unreachable!(),
+        }
+    }
+}
+
+impl From<ConstDateTime> for DateTime {
+    fn from(value: ConstDateTime) -> Self {
+        value.to_datetime()
+    }
+}
+
+/// Build a [`ConstDateTime`](crate::const_datetime::ConstDateTime) that is
+/// validated at compile time, even when used in a non-`const` context.
+///
+/// Arguments are `year, month, day, hour, minute, second`.
+#[macro_export]
+macro_rules! const_datetime {
+    ($year:expr, $month:expr, $day:expr, $hour:expr, $minute:expr, $second:expr $(,)?) => {{
+        const DATETIME: $crate::const_datetime::ConstDateTime =
+            $crate::const_datetime::ConstDateTime::new(
+                $year, $month, $day, $hour, $minute, $second,
+            );
+        DATETIME
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_const_datetime_matches_datetime() {
+        const LEAP: ConstDateTime = ConstDateTime::new(2024, 2, 29, 23, 59, 59);
+        assert_eq!(
+            DateTime::from(LEAP),
+            DateTime::new(2024, 2, 29, 23, 59, 59).unwrap()
+        );
+        assert_eq!(LEAP.year(), 2024);
+        assert_eq!(LEAP.day_of_month(), 29);
+    }
+
+    #[test]
+    fn test_const_datetime_macro() {
+        let dt = const_datetime!(2000, 1, 1, 0, 0, 0);
+        assert_eq!(dt, ConstDateTime::new(2000, 1, 1, 0, 0, 0));
+    }
+
+    #[test]
+    #[should_panic(expected = "day of month out of range")]
+    fn test_const_datetime_rejects_invalid_day_at_runtime() {
+        let day = core::hint::black_box(29);
+        let _ = ConstDateTime::new(2025, 2, day, 0, 0, 0);
+    }
+}
@@ -27,14 +27,25 @@
 
 pub use rtc_hal::control::RtcPowerControl;
 
-use crate::{
-    Ds3231,
-    registers::{EOSC_BIT, Register},
-};
-
-impl<I2C> RtcPowerControl for Ds3231<I2C>
+#[cfg(feature = "async")]
+use crate::interface::AsyncRegisterInterface;
+use crate::interface::RegisterInterface;
+
+#[cfg(feature = "async")]
+use crate::ds3231::Ds3231Async;
+use crate::{Ds3231, error::Error, trace::debug};
+
+#[maybe_async_cfg::maybe(
+    idents(
+        AsyncRegisterInterface(sync = "RegisterInterface", async = "AsyncRegisterInterface"),
+        Ds3231(sync = "Ds3231", async = "Ds3231Async")
+    ),
+    sync(keep_self),
+    async(feature = "async")
+)]
+impl<I2C, V> Ds3231<I2C, V>
 where
-    I2C: embedded_hal::i2c::I2c,
+    I2C: AsyncRegisterInterface,
 {
     /// Start or resume the RTC oscillator so that timekeeping can continue.
     ///
@@ -42,8 +53,11 @@ where
     /// The operation is idempotent - calling it when already running has no effect.
     ///
     /// **Note**: When powered by VCC, the oscillator runs regardless of this setting.
-    fn start_clock(&mut self) -> Result<(), Self::Error> {
-        self.clear_register_bits(Register::Control, EOSC_BIT)
+    pub async fn start_clock(&mut self) -> Result<(), Error<I2C::Error>> {
+        self.modify_control(|control| control.set_oscillator_disabled(false))
+            .await?;
+        debug!("start_clock");
+        Ok(())
     }
 
     /// Halt the RTC oscillator to conserve power during battery backup operation.
@@ -53,8 +67,25 @@ where
     /// **Important**: This only takes effect when the DS3231 switches to battery
     /// backup power (VBAT). When powered by VCC, the oscillator continues running
     /// regardless of this setting.
+    pub async fn halt_clock(&mut self) -> Result<(), Error<I2C::Error>> {
+        self.modify_control(|control| control.set_oscillator_disabled(true))
+            .await?;
+        debug!("halt_clock");
+        Ok(())
+    }
+}
+
+/// Blocking `rtc-hal` implementation, delegating to the inherent methods.
+impl<I2C, V> RtcPowerControl for Ds3231<I2C, V>
+where
+    I2C: RegisterInterface,
+{
+    fn start_clock(&mut self) -> Result<(), Self::Error> {
+        Ds3231::start_clock(self)
+    }
+
     fn halt_clock(&mut self) -> Result<(), Self::Error> {
-        self.set_register_bits(Register::Control, EOSC_BIT)
+        Ds3231::halt_clock(self)
     }
 }
 
@@ -329,4 +360,19 @@ mod tests {
 
         i2c_mock.done();
     }
+
+    #[test]
+    fn test_power_control_trait_delegates() {
+        let expectations = vec![
+            I2cTransaction::write_read(DS3231_ADDR, vec![Register::Control.addr()], vec![0x00]),
+            I2cTransaction::write(DS3231_ADDR, vec![Register::Control.addr(), EOSC_BIT]),
+        ];
+
+        let mut i2c_mock = I2cMock::new(&expectations);
+        let mut ds3231 = Ds3231::new(&mut i2c_mock);
+
+        RtcPowerControl::halt_clock(&mut ds3231).unwrap();
+
+        i2c_mock.done();
+    }
 }
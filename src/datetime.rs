@@ -2,67 +2,263 @@
 //!
 //! This module provides an implementation of the [`Rtc`] trait for the
 //! DS3231 real-time clock (RTC).
+//!
+//! The DS3231 keeps a separate day-of-week register that it increments at
+//! midnight but never derives from the date. The driver always computes the
+//! weekday from the date when setting the time (see [`weekday_for`]), so a
+//! caller can never store a weekday that disagrees with the calendar.
+
+use core::time::Duration;
+
+#[cfg(feature = "async")]
+use crate::interface::AsyncRegisterInterface;
+use crate::interface::RegisterInterface;
+
+use rtc_hal::{
+    datetime::{DateTime, DateTimeError, Weekday},
+    rtc::Rtc,
+};
+
+#[cfg(feature = "async")]
+use crate::ds3231::Ds3231Async;
+use crate::{
+    Ds3231, bcd, calendar,
+    error::Error,
+    hour_mode::{self, HourMode},
+    month::Month,
+    registers::{CENTURY_BIT, Register},
+    trace::debug,
+    year_policy::YearPolicy,
+};
+
+/// Weekdays in register order (index 0 = Sunday = register value 1)
+const WEEKDAYS: [Weekday; 7] = [
+    Weekday::Sunday,
+    Weekday::Monday,
+    Weekday::Tuesday,
+    Weekday::Wednesday,
+    Weekday::Thursday,
+    Weekday::Friday,
+    Weekday::Saturday,
+];
+
+/// Arithmetic and numbering helpers for [`Weekday`].
+///
+/// [`Weekday`] is defined by `rtc-hal` and numbers days 1=Sunday to
+/// 7=Saturday, which is also the convention this driver uses for the DS3231
+/// day register. ISO 8601 numbers days 1=Monday to 7=Sunday instead; the
+/// `iso` conversions make that distinction explicit at the call site.
+pub trait WeekdayExt: Sized {
+    /// Following day, wrapping from Saturday to Sunday
+    fn next(self) -> Self;
 
-use rtc_hal::{bcd, datetime::DateTimeError, rtc::Rtc};
+    /// Preceding day, wrapping from Sunday to Saturday
+    fn prev(self) -> Self;
 
-use crate::{Ds3231, registers::Register};
+    /// ISO 8601 day number (1=Monday, 7=Sunday)
+    fn to_iso_number(self) -> u8;
 
-impl<I2C> Rtc for Ds3231<I2C>
+    /// Create a weekday from its ISO 8601 number (1=Monday, 7=Sunday)
+    fn from_iso_number(n: u8) -> Result<Self, DateTimeError>;
+
+    /// Number of days from `self` forward to `other` (0-6)
+    fn days_until(self, other: Self) -> u8;
+}
+
+impl WeekdayExt for Weekday {
+    fn next(self) -> Self {
+        WEEKDAYS[(self.to_number() % 7) as usize]
+    }
+
+    fn prev(self) -> Self {
+        WEEKDAYS[((self.to_number() + 5) % 7) as usize]
+    }
+
+    fn to_iso_number(self) -> u8 {
+        (self.to_number() + 5) % 7 + 1
+    }
+
+    fn from_iso_number(n: u8) -> Result<Self, DateTimeError> {
+        if n == 0 || n > 7 {
+            return Err(DateTimeError::InvalidWeekday);
+        }
+        Ok(WEEKDAYS[(n % 7) as usize])
+    }
+
+    fn days_until(self, other: Self) -> u8 {
+        (other.to_number() + 7 - self.to_number()) % 7
+    }
+}
+
+/// Compute the day of the week for the given date.
+///
+/// Unlike [`DateTime::calculate_weekday`], this cannot fail for a valid
+/// [`DateTime`].
+pub fn weekday_for(datetime: &DateTime) -> Weekday {
+    let n = calendar::weekday_number(datetime.year(), datetime.month(), datetime.day_of_month());
+    WEEKDAYS[((n + 6) % 7) as usize]
+}
+
+/// Returns `true` if `value` is BCD with a tens digit of at most `max_tens`.
+const fn is_bcd(value: u8, max_tens: u8) -> bool {
+    value & 0x0F <= 9 && value >> 4 <= max_tens
+}
+
+/// Reject timekeeping registers (0x00-0x06) holding something the chip
+/// can't have counted to, such as a non-BCD nibble or a set unused bit.
+///
+/// Values that are valid BCD but not a valid date, e.g. February 30th, are
+/// left to [`DateTime::new`]. In 12-hour mode the hour must be 1-12, as
+/// other hours would decode to a different time of day.
+pub(crate) fn check_time_registers<E>(data: &[u8; 7]) -> Result<(), Error<E>>
 where
-    I2C: embedded_hal::i2c::I2c,
+    E: core::fmt::Debug,
 {
-    /// Read the current date and time from the DS3231.
-    fn get_datetime(&mut self) -> Result<rtc_hal::datetime::DateTime, Self::Error> {
-        // Since DS3231 allows Subsequent registers can be accessed sequentially until a STOP condition is executed
-        // Read all 7 registers in one burst operation
-        let mut data = [0; 7];
-        self.read_register_bytes(Register::Seconds, &mut data)?;
-
-        // Convert from BCD format and extract fields
-        let second = bcd::to_decimal(data[0]);
-        let minute = bcd::to_decimal(data[1]);
-
-        // Handle both 12-hour and 24-hour modes for hours
-        let raw_hour = data[2];
-        let hour = if (raw_hour & 0b0100_0000) != 0 {
-            // 12-hour mode
-            // Extract the Hour part (4-0 bits)
-            let hr = bcd::to_decimal(raw_hour & 0b0001_1111);
-            // Extract the AM/PM (5th bit). if it is set, then it is PM
-            let pm = (raw_hour & 0b0010_0000) != 0;
-
-            // Convert it to 24 hour format:
-            match (hr, pm) {
-                (12, false) => 0,    // 12 AM = 00:xx
-                (12, true) => 12,    // 12 PM = 12:xx
-                (h, false) => h,     // 1-11 AM
-                (h, true) => h + 12, // 1-11 PM
-            }
-        } else {
-            // 24-hour mode
-            // Extrac the hour value from 5-0 bits
-            bcd::to_decimal(raw_hour & 0b0011_1111)
-        };
+    let hours_valid = match HourMode::from_register(data[2]) {
+        HourMode::TwelveHour => {
+            let digits = data[2] & 0b0001_1111;
+            is_bcd(digits, 1) && matches!(bcd::from_bcd(digits), 1..=12)
+        }
+        HourMode::TwentyFourHour => is_bcd(data[2] & 0b0011_1111, 2),
+    };
+    let fields = [
+        (Register::Seconds, is_bcd(data[0], 5)),
+        (Register::Minutes, is_bcd(data[1], 5)),
+        (Register::Hours, hours_valid),
+        (Register::Day, true),
+        (Register::Date, is_bcd(data[4], 3)),
+        (Register::Month, is_bcd(data[5] & !CENTURY_BIT, 1)),
+        (Register::Year, is_bcd(data[6], 9)),
+    ];
 
-        // let weekday = Weekday::from_number(bcd::to_decimal(data[3]))
-        //     .map_err(crate::error::Error::DateTime)?;
-
-        let day_of_month = bcd::to_decimal(data[4]);
-        // Extract century bit
-        // If it is set, then it is next century
-        // Let's say base century is 20, then next century will be 21
-        let is_century_bit_set = (data[5] & 0b1000_0000) != 0;
-        let mut century = self.base_century;
-        if is_century_bit_set {
-            century += 1;
+    for ((register, valid), &value) in fields.into_iter().zip(data) {
+        if !valid || value & !register.writable_mask() != 0 {
+            return Err(Error::CorruptRegister { register, value });
         }
+    }
+    Ok(())
+}
+
+/// Decode the seven timekeeping registers (0x00-0x06) into a datetime.
+///
+/// Fails with [`Error::CorruptRegister`] if a register holds invalid BCD.
+pub(crate) fn decode_datetime<E>(data: &[u8; 7], base_century: u8) -> Result<DateTime, Error<E>>
+where
+    E: core::fmt::Debug,
+{
+    check_time_registers(data)?;
+
+    // Convert from BCD format and extract fields
+    let second = bcd::from_bcd(data[0]);
+    let minute = bcd::from_bcd(data[1]);
+
+    // Handle both 12-hour and 24-hour modes for hours
+    let hour = hour_mode::decode_hour(data[2]);
+
+    let day_of_month = bcd::from_bcd(data[4]);
+    // Extract century bit
+    // If it is set, then it is next century
+    // Let's say base century is 20, then next century will be 21
+    let is_century_bit_set = (data[5] & CENTURY_BIT) != 0;
+    let mut century = base_century as u16;
+    if is_century_bit_set {
+        century += 1;
+    }
+
+    let month = bcd::from_bcd(data[5] & !CENTURY_BIT);
+
+    let year = (century * 100) + bcd::from_bcd(data[6]) as u16;
+
+    DateTime::new(year, month, day_of_month, hour, minute, second).map_err(Error::DateTime)
+}
+
+/// Encode the seconds, minutes and hours registers (0x00-0x02).
+pub(crate) fn encode_time(datetime: &DateTime, hour_mode: HourMode) -> [u8; 3] {
+    [
+        bcd::to_bcd(datetime.second()),
+        bcd::to_bcd(datetime.minute()),
+        // Hours in the configured hour mode
+        hour_mode::encode_hour(datetime.hour(), hour_mode),
+    ]
+}
+
+/// Encode the day, date, month/century and year registers (0x03-0x06).
+///
+/// The year must already have been checked against the year policy.
+pub(crate) fn encode_date(datetime: &DateTime, base_century: u8) -> [u8; 4] {
+    let century_base = base_century as u16 * 100;
+    let is_next_century = datetime.year() >= (century_base + 100);
+    let year_2digit = if is_next_century {
+        (datetime.year() - century_base - 100) as u8
+    } else {
+        (datetime.year() - century_base) as u8
+    };
+
+    // Month register with century bit
+    let mut month_reg = bcd::to_bcd(datetime.month());
+    if is_next_century {
+        month_reg |= CENTURY_BIT;
+    }
+
+    [
+        // Day of week register - 1=Sunday, 7=Saturday
+        bcd::to_bcd(weekday_for(datetime).to_number()),
+        bcd::to_bcd(datetime.day_of_month()),
+        month_reg,
+        bcd::to_bcd(year_2digit),
+    ]
+}
+
+/// Validate `datetime`, apply the year policy and build the burst write for
+/// all seven timekeeping registers, starting with the register address.
+pub(crate) fn encode_datetime(
+    datetime: &DateTime,
+    base_century: u8,
+    hour_mode: HourMode,
+    year_policy: YearPolicy,
+) -> Result<[u8; 8], DateTimeError> {
+    // DateTime enforces this on construction, but the chip accepts any
+    // BCD it is given, so check again before anything reaches the bus.
+    datetime.validate()?;
 
-        let month = bcd::to_decimal(data[5] & 0b0111_1111);
+    // Validate (or clamp) the year against the current and next century
+    let datetime = &year_policy.apply(datetime, base_century)?;
 
-        let year = (century as u16 * 100) + bcd::to_decimal(data[6]) as u16;
+    let mut data = [0u8; 8];
+    data[0] = Register::Seconds.addr();
+    data[1..4].copy_from_slice(&encode_time(datetime, hour_mode));
+    data[4..8].copy_from_slice(&encode_date(datetime, base_century));
+    Ok(data)
+}
 
-        rtc_hal::datetime::DateTime::new(year, month, day_of_month, hour, minute, second)
-            .map_err(crate::error::Error::DateTime)
+#[maybe_async_cfg::maybe(
+    idents(
+        AsyncRegisterInterface(sync = "RegisterInterface", async = "AsyncRegisterInterface"),
+        Ds3231(sync = "Ds3231", async = "Ds3231Async")
+    ),
+    sync(keep_self),
+    async(feature = "async")
+)]
+impl<I2C, V> Ds3231<I2C, V>
+where
+    I2C: AsyncRegisterInterface,
+{
+    /// Read the current date and time from the DS3231.
+    ///
+    /// The registers are read as selected with
+    /// [`set_read_strategy`](Self::set_read_strategy).
+    pub async fn get_datetime(&mut self) -> Result<DateTime, crate::error::Error<I2C::Error>> {
+        // Since DS3231 allows Subsequent registers can be accessed sequentially until a STOP condition is executed
+        // Read all 7 registers in one burst operation (see `ReadStrategy`)
+        let data = self.read_time_registers().await?;
+
+        let datetime = decode_datetime(&data, self.base_century)?;
+        self.plausibility.check(&datetime)?;
+        debug!(
+            "get_datetime: {}",
+            crate::display::DateTimeDisplay(datetime)
+        );
+        Ok(datetime)
     }
 
     /// Set the current date and time in the DS3231.
@@ -81,16 +277,29 @@ where
     /// - Allowed years: 2100-2199 (stored as 00-99, century bit = 1)
     /// - Rejected years: 1900-1999, 2200+
     ///
+    /// Out-of-range years are rejected by default; see [`Ds3231::set_year_policy`]
+    /// to restrict the range further or clamp instead.
+    ///
     /// # Century Bit Handling
     ///
     /// The method automatically sets the DS3231's century bit based on which
     /// century range the year falls into, avoiding the ambiguity issues with
     /// this hardware feature.
     ///
+    /// # Single Burst Write
+    ///
+    /// All seven timekeeping registers, including the century bit in the
+    /// month register, go out in one write starting at register `0x00`.
+    /// Writing the seconds register resets the chip's countdown chain, and
+    /// the datasheet requires the remaining registers to follow within one
+    /// second; as one transfer they can't be torn by a rollover between
+    /// them, and nothing else on the bus can interleave.
+    ///
     /// # Time Format
     ///
-    /// The DS3231 is configured to use 24-hour time format. The weekday is
-    /// calculated from the date and stored in the day register (1=Sunday, 7=Saturday).
+    /// Hours are written in the format selected with [`Ds3231::set_hour_mode`]
+    /// (24-hour by default). The weekday is always computed from the date with
+    /// [`weekday_for`] and stored in the day register (1=Sunday, 7=Saturday).
     ///
     /// # Arguments
     ///
@@ -101,71 +310,273 @@ where
     /// Returns `Err(Error::DateTime(DateTimeError::InvalidYear))` if the year
     /// is outside the supported range.
     ///
+    /// The full calendar is validated before writing, so impossible dates are
+    /// rejected rather than stored: day 0, Feb 30, Apr 31 or Feb 29 in a
+    /// non-leap year yield `DateTimeError::InvalidDay`, and out-of-range
+    /// months, hours, minutes and seconds yield the matching
+    /// `DateTimeError` variant.
+    ///
     /// # Examples
     ///
-    /// ```
+    /// ```no_run
+    /// # use ds3231_rtc::{DateTime, Ds3231, error::Error};
+    /// # fn main() -> Result<(), Error<embedded_hal::i2c::ErrorKind>> {
+    /// # let mut rtc = Ds3231::new(embedded_hal_mock::eh1::i2c::Mock::new(&[]));
     /// // With base_century = 20, you can set dates from 2000-2199
     /// let datetime = DateTime::new(2023, 12, 25, 15, 30, 0)?;
     /// rtc.set_datetime(&datetime)?;
     ///
     /// // To set dates in a different century, update base_century first
-    /// let rtc = rtc.with_base_century(21)?; // Now supports 2100-2299
+    /// let mut rtc = rtc.with_base_century(21)?; // Now supports 2100-2299
     /// let datetime = DateTime::new(2150, 1, 1, 0, 0, 0)?;
     /// rtc.set_datetime(&datetime)?;
+    /// # Ok(())
+    /// # }
     /// ```
-    fn set_datetime(&mut self, datetime: &rtc_hal::datetime::DateTime) -> Result<(), Self::Error> {
-        let century_base = self.base_century as u16 * 100;
+    pub async fn set_datetime(
+        &mut self,
+        datetime: &DateTime,
+    ) -> Result<(), crate::error::Error<I2C::Error>> {
+        let data = encode_datetime(
+            datetime,
+            self.base_century,
+            self.hour_mode,
+            self.year_policy,
+        )
+        .map_err(crate::error::Error::DateTime)?;
+
+        // Write all 7 registers in one burst operation; see "Single Burst Write"
+        self.write_raw_bytes(&data).await?;
+        debug!(
+            "set_datetime: {}",
+            crate::display::DateTimeDisplay(*datetime)
+        );
+
+        Ok(())
+    }
+
+    /// Set the date and time, then read it back and check that the clock
+    /// holds it.
+    ///
+    /// The read may see the clock one second further, anything else fails
+    /// with [`Error::DateTimeMismatch`](crate::error::Error::DateTimeMismatch),
+    /// e.g. when the oscillator is disabled and the write didn't take, or a
+    /// board is fitted with the wrong part. Costs one extra burst read over
+    /// [`set_datetime`](Self::set_datetime), which makes it a fit for
+    /// provisioning.
+    pub async fn set_and_verify_datetime(
+        &mut self,
+        datetime: &DateTime,
+    ) -> Result<(), crate::error::Error<I2C::Error>> {
+        self.set_datetime(datetime).await?;
+        let stored = self.get_datetime().await?;
 
-        // Validate year is within the current or next century
-        if datetime.year() < century_base || datetime.year() > (century_base + 199) {
-            return Err(crate::error::Error::DateTime(DateTimeError::InvalidYear));
+        let seconds = calendar::to_unix_timestamp(&stored) as i64
+            - calendar::to_unix_timestamp(datetime) as i64;
+        if !(0..=1).contains(&seconds) {
+            return Err(crate::error::Error::DateTimeMismatch { seconds });
         }
+        Ok(())
+    }
+}
 
-        let is_next_century = datetime.year() >= (century_base + 100);
-        let year_2digit = if is_next_century {
-            (datetime.year() - century_base - 100) as u8
-        } else {
-            (datetime.year() - century_base) as u8
-        };
+/// Blocking `rtc-hal` implementation, delegating to the inherent methods.
+impl<I2C, V> Rtc for Ds3231<I2C, V>
+where
+    I2C: RegisterInterface,
+{
+    fn get_datetime(&mut self) -> Result<DateTime, Self::Error> {
+        Ds3231::get_datetime(self)
+    }
 
-        // Prepare data array for burst write (7 registers)
-        let mut data = [0u8; 8];
-        data[0] = Register::Seconds.addr();
+    fn set_datetime(&mut self, datetime: &DateTime) -> Result<(), Self::Error> {
+        Ds3231::set_datetime(self, datetime)
+    }
+}
+
+#[maybe_async_cfg::maybe(
+    idents(
+        AsyncRegisterInterface(sync = "RegisterInterface", async = "AsyncRegisterInterface"),
+        Ds3231(sync = "Ds3231", async = "Ds3231Async")
+    ),
+    sync(keep_self),
+    async(feature = "async")
+)]
+impl<I2C, V> Ds3231<I2C, V>
+where
+    I2C: AsyncRegisterInterface,
+{
+    /// Read the current time as seconds since the Unix epoch (1970-01-01T00:00:00).
+    ///
+    /// The DS3231 has no notion of time zones, so this assumes the chip stores UTC.
+    pub async fn get_unix_timestamp(&mut self) -> Result<u64, crate::error::Error<I2C::Error>> {
+        let datetime = self.get_datetime().await?;
+        Ok(calendar::to_unix_timestamp(&datetime))
+    }
+
+    /// Read the day-of-week register.
+    ///
+    /// The driver keeps this register consistent with the date whenever it sets
+    /// the time, but firmware that wrote it directly may have used a different
+    /// numbering, in which case `DateTimeError::InvalidWeekday` is returned for
+    /// values outside 1-7.
+    pub async fn get_weekday(&mut self) -> Result<Weekday, crate::error::Error<I2C::Error>> {
+        let raw = self.read_register(Register::Day).await?;
+        Weekday::from_number(raw & 0b0000_0111).map_err(crate::error::Error::DateTime)
+    }
 
-        // Seconds register (0x00)
-        data[1] = bcd::from_decimal(datetime.second());
+    /// Set the time from seconds since the Unix epoch (1970-01-01T00:00:00).
+    ///
+    /// The same year range restrictions as [`Rtc::set_datetime`] apply.
+    pub async fn set_unix_timestamp(
+        &mut self,
+        timestamp: u64,
+    ) -> Result<(), crate::error::Error<I2C::Error>> {
+        let datetime =
+            calendar::from_unix_timestamp(timestamp).map_err(crate::error::Error::DateTime)?;
+        self.set_datetime(&datetime).await
+    }
 
-        // Minutes register (0x01)
-        data[2] = bcd::from_decimal(datetime.minute());
+    /// Time elapsed since `earlier`, read from the RTC in a single burst.
+    ///
+    /// If the clock now reads earlier than `earlier` (because it was set
+    /// backwards in between), this returns [`Duration::ZERO`] rather than an
+    /// error. Use [`seconds_since`](Self::seconds_since) to detect that case.
+    pub async fn elapsed_since(
+        &mut self,
+        earlier: &DateTime,
+    ) -> Result<Duration, crate::error::Error<I2C::Error>> {
+        let seconds = self.seconds_since(earlier).await?;
+        Ok(Duration::from_secs(seconds.max(0) as u64))
+    }
 
-        // Hours register (0x02) - set to 24-hour mode
-        // Clear bit 6 (12/24 hour mode bit) to enable 24-hour mode
-        data[3] = bcd::from_decimal(datetime.hour()) & 0b0011_1111;
+    /// Signed number of seconds from `earlier` to the current RTC time.
+    ///
+    /// Negative if the clock now reads earlier than `earlier`.
+    pub async fn seconds_since(
+        &mut self,
+        earlier: &DateTime,
+    ) -> Result<i64, crate::error::Error<I2C::Error>> {
+        let now = self.get_unix_timestamp().await? as i64;
+        Ok(now - calendar::to_unix_timestamp(earlier) as i64)
+    }
 
-        let weekday = datetime
-            .calculate_weekday()
+    /// Set only the time of day, leaving the date untouched.
+    ///
+    /// Writes the seconds, minutes and hours registers in one burst, without
+    /// reading the current date first. Hours use the configured hour mode.
+    ///
+    /// Returns the matching `DateTimeError` if any field is out of range.
+    pub async fn set_time(
+        &mut self,
+        hour: u8,
+        minute: u8,
+        second: u8,
+    ) -> Result<(), crate::error::Error<I2C::Error>> {
+        // Any valid date will do; only the time fields are encoded
+        let datetime = DateTime::new(2000, 1, 1, hour, minute, second)
             .map_err(crate::error::Error::DateTime)?;
 
-        // Day of week register (0x03) - 1=Sunday, 7=Saturday
-        data[4] = bcd::from_decimal(weekday.to_number());
+        let mut data = [0u8; 4];
+        data[0] = Register::Seconds.addr();
+        data[1..].copy_from_slice(&encode_time(&datetime, self.hour_mode));
+        self.write_raw_bytes(&data).await
+    }
 
-        // Day of month register (0x04)
-        data[5] = bcd::from_decimal(datetime.day_of_month());
+    /// Set only the date, leaving the time of day untouched.
+    ///
+    /// Writes the day, date, month and year registers in one burst, without
+    /// reading the current time first. The weekday is computed from the date,
+    /// and the year is subject to the configured [`YearPolicy`](crate::year_policy::YearPolicy).
+    ///
+    /// Unlike [`set_datetime`](Self::set_datetime) this leaves the countdown
+    /// chain running, so a write just as the clock passes midnight can land
+    /// before or after the chip's own date increment.
+    ///
+    /// Returns the matching `DateTimeError` if the date is invalid or the
+    /// year can't be stored.
+    pub async fn set_date(
+        &mut self,
+        year: u16,
+        month: Month,
+        day_of_month: u8,
+    ) -> Result<(), crate::error::Error<I2C::Error>> {
+        let datetime = DateTime::new(year, month.to_number(), day_of_month, 0, 0, 0)
+            .map_err(crate::error::Error::DateTime)?;
+        let datetime = self
+            .year_policy
+            .apply(&datetime, self.base_century)
+            .map_err(crate::error::Error::DateTime)?;
 
-        // Month register(0x05) with century bit
-        let mut month_reg = bcd::from_decimal(datetime.month());
-        if is_next_century {
-            month_reg |= 0b1000_0000; // Set century bit
-        }
-        data[6] = month_reg;
+        let mut data = [0u8; 5];
+        data[0] = Register::Day.addr();
+        data[1..].copy_from_slice(&encode_date(&datetime, self.base_century));
+        self.write_raw_bytes(&data).await
+    }
 
-        data[7] = bcd::from_decimal(year_2digit);
+    /// Move the clock forward (positive) or backward (negative) by a number
+    /// of seconds, rolling over minutes, hours, days, months and years as
+    /// needed.
+    ///
+    /// Useful for trim buttons and small drift corrections. The current time
+    /// is read and the adjusted time written back in consecutive bus
+    /// transactions; writing the seconds register restarts the chip's
+    /// sub-second countdown.
+    ///
+    /// Returns the usual year range errors if the adjusted time can't be stored.
+    pub async fn adjust_by_seconds(
+        &mut self,
+        seconds: i32,
+    ) -> Result<(), crate::error::Error<I2C::Error>> {
+        let datetime = self.get_datetime().await?;
+        let adjusted = calendar::add_seconds(&datetime, seconds as i64)
+            .map_err(crate::error::Error::DateTime)?;
+        self.set_datetime(&adjusted).await
+    }
 
-        // Write all 7 registers in one burst operation
-        self.write_raw_bytes(&data)?;
+    /// Shift the clock forward or backward by whole hours, e.g. for a manual
+    /// daylight saving time toggle.
+    ///
+    /// The current time is read, shifted with full date rollover and written
+    /// back in consecutive bus transactions. Writing the seconds register
+    /// restarts the chip's sub-second countdown, so up to one second of phase
+    /// may be lost per shift.
+    ///
+    /// The net shift applied so far is tracked in the driver and can be read
+    /// with [`clock_shift`](Self::clock_shift) to avoid applying the same
+    /// adjustment twice. The counter lives in RAM only; restore it with
+    /// [`set_clock_shift`](Self::set_clock_shift) after a reset if needed.
+    ///
+    /// Returns `DateTimeError::InvalidHour` if the net shift would overflow an
+    /// `i8`, or the usual year range errors if the shifted time can't be stored.
+    pub async fn shift_clock(&mut self, hours: i8) -> Result<(), crate::error::Error<I2C::Error>> {
+        let total = self
+            .clock_shift_hours
+            .checked_add(hours)
+            .ok_or(crate::error::Error::DateTime(DateTimeError::InvalidHour))?;
 
+        self.adjust_by_seconds(hours as i32 * 3600).await?;
+
+        self.clock_shift_hours = total;
         Ok(())
     }
+
+    /// Net number of hours applied with [`shift_clock`](Self::shift_clock)
+    /// since the driver was created (or since [`set_clock_shift`](Self::set_clock_shift)).
+    pub fn clock_shift(&self) -> i8 {
+        self.clock_shift_hours
+    }
+
+    /// Returns `true` if the clock is currently shifted away from its base time.
+    pub fn is_clock_shifted(&self) -> bool {
+        self.clock_shift_hours != 0
+    }
+
+    /// Overwrite the tracked net shift without touching the clock, e.g. to
+    /// restore a value persisted across a reset.
+    pub fn set_clock_shift(&mut self, hours: i8) {
+        self.clock_shift_hours = hours;
+    }
 }
 
 #[cfg(test)]
@@ -253,6 +664,291 @@ mod tests {
         ds3231.release_i2c().done();
     }
 
+    #[test]
+    fn test_set_datetime_is_single_burst_across_century() {
+        // Last second of the base century: every register, century bit
+        // included, must go out in the same transfer
+        let datetime = DateTime::new(2099, 12, 31, 23, 59, 59).unwrap();
+        let expectations = [I2cTrans::write(
+            0x68,
+            vec![
+                Register::Seconds.addr(),
+                0x59, // sec
+                0x59, // min
+                0x23, // hour
+                0x05, // weekday (Thursday 2099-12-31)
+                0x31, // day
+                0x12, // month, century bit clear
+                0x99, // year (99)
+            ],
+        )];
+
+        let mut ds3231 = new_ds3231(I2cMock::new(&expectations));
+
+        ds3231.set_datetime(&datetime).unwrap();
+
+        ds3231.release_i2c().done();
+    }
+
+    #[test]
+    fn test_set_and_verify_datetime() {
+        let datetime = DateTime::new(2025, 8, 27, 15, 30, 45).unwrap();
+        let write = I2cTrans::write(
+            0x68,
+            vec![
+                Register::Seconds.addr(),
+                0x45,
+                0x30,
+                0x15,
+                0x04,
+                0x27,
+                0x08,
+                0x25,
+            ],
+        );
+        let read = |seconds| {
+            I2cTrans::write_read(
+                0x68,
+                vec![Register::Seconds.addr()],
+                vec![seconds, 0x30, 0x15, 0x04, 0x27, 0x08, 0x25],
+            )
+        };
+        let expectations = [
+            write.clone(),
+            read(0x46),
+            write.clone(),
+            read(0x45),
+            // Stopped oscillator: the registers were never loaded
+            write,
+            I2cTrans::write_read(
+                0x68,
+                vec![Register::Seconds.addr()],
+                vec![0x00, 0x00, 0x00, 0x01, 0x01, 0x01, 0x00],
+            ),
+        ];
+        let mut ds3231 = new_ds3231(I2cMock::new(&expectations));
+
+        ds3231.set_and_verify_datetime(&datetime).unwrap();
+        ds3231.set_and_verify_datetime(&datetime).unwrap();
+        assert_eq!(
+            ds3231.set_and_verify_datetime(&datetime),
+            Err(crate::error::Error::DateTimeMismatch {
+                seconds: -809_623_845
+            })
+        );
+
+        ds3231.release_i2c().done();
+    }
+
+    #[test]
+    fn test_set_datetime_12h_mode() {
+        let datetime = DateTime::new(2025, 8, 27, 15, 30, 45).unwrap();
+        let expectations = [I2cTrans::write(
+            0x68,
+            vec![
+                Register::Seconds.addr(),
+                0x45,        // sec
+                0x30,        // min
+                0b0110_0011, // hour: 12h mode, PM, 3
+                0x04,        // weekday
+                0x27,        // day
+                0x08,        // month
+                0x25,        // year
+            ],
+        )];
+
+        let mut ds3231 = new_ds3231(I2cMock::new(&expectations));
+        ds3231.set_hour_mode(crate::hour_mode::HourMode::TwelveHour);
+
+        ds3231.set_datetime(&datetime).unwrap();
+
+        ds3231.release_i2c().done();
+    }
+
+    #[test]
+    fn test_get_datetime_century_bit_with_custom_base() {
+        // 2250-06-15 12:00:00 with base century 21 and century bit set
+        let data = [0x00, 0x00, 0x12, 0x01, 0x15, 0x86, 0x50];
+        let expectations = [I2cTrans::write_read(
+            0x68,
+            vec![Register::Seconds.addr()],
+            data.to_vec(),
+        )];
+        let mut ds3231 = new_ds3231(I2cMock::new(&expectations))
+            .with_base_century(21)
+            .unwrap();
+
+        let dt = ds3231.get_datetime().unwrap();
+        assert_eq!(dt.year(), 2250);
+        assert_eq!(dt.month(), 6);
+
+        ds3231.release_i2c().done();
+    }
+
+    #[test]
+    fn test_get_datetime_century_bit_max_base_century() {
+        // Century bit set with the largest base century must not overflow
+        let data = [0x00, 0x00, 0x00, 0x01, 0x01, 0x81, 0x00];
+        let expectations = [I2cTrans::write_read(
+            0x68,
+            vec![Register::Seconds.addr()],
+            data.to_vec(),
+        )];
+        let mut ds3231 = new_ds3231(I2cMock::new(&expectations))
+            .with_base_century(255)
+            .unwrap();
+
+        assert_eq!(ds3231.get_datetime().unwrap().year(), 25600);
+
+        ds3231.release_i2c().done();
+    }
+
+    #[test]
+    fn test_set_datetime_clamp_policy() {
+        let datetime = DateTime::new(2300, 1, 1, 0, 0, 0).unwrap();
+        // Clamped to 2199-12-31 23:59:59
+        let expectations = [I2cTrans::write(
+            0x68,
+            vec![
+                Register::Seconds.addr(),
+                0x59, // sec
+                0x59, // min
+                0x23, // hour
+                0x03, // weekday (Tuesday)
+                0x31, // day
+                0x92, // month with century bit
+                0x99, // year (99)
+            ],
+        )];
+
+        let mut ds3231 = new_ds3231(I2cMock::new(&expectations));
+        ds3231.set_year_policy(crate::year_policy::YearPolicy::Clamp);
+
+        ds3231.set_datetime(&datetime).unwrap();
+
+        ds3231.release_i2c().done();
+    }
+
+    #[test]
+    fn test_set_datetime_strict_policy() {
+        let datetime = DateTime::new(2150, 1, 1, 0, 0, 0).unwrap();
+        let mut ds3231 = new_ds3231(I2cMock::new(&[]));
+        ds3231.set_year_policy(crate::year_policy::YearPolicy::Strict2000to2099);
+
+        let result = ds3231.set_datetime(&datetime);
+        assert!(matches!(
+            result,
+            Err(crate::error::Error::DateTime(DateTimeError::InvalidYear))
+        ));
+
+        ds3231.release_i2c().done();
+    }
+
+    #[test]
+    fn test_impossible_dates_are_rejected() {
+        for (year, month, day) in [(2025, 2, 30), (2025, 4, 31), (2025, 2, 29), (2100, 2, 29)] {
+            assert_eq!(
+                DateTime::new(year, month, day, 0, 0, 0),
+                Err(DateTimeError::InvalidDay),
+                "{year}-{month}-{day}"
+            );
+        }
+
+        let mut datetime = DateTime::new(2025, 1, 31, 0, 0, 0).unwrap();
+        assert_eq!(datetime.set_month(2), Err(DateTimeError::InvalidDay));
+        assert_eq!(datetime.month(), 1);
+    }
+
+    #[test]
+    fn test_get_datetime_rejects_corrupt_bcd() {
+        let cases = [
+            (
+                [0x3A, 0x30, 0x14, 0x05, 0x21, 0x08, 0x25],
+                Register::Seconds,
+                0x3A,
+            ),
+            (
+                [0x00, 0x30, 0x14, 0x05, 0x21, 0x08, 0xF1],
+                Register::Year,
+                0xF1,
+            ),
+            (
+                [0x00, 0x30, 0x14, 0x0D, 0x21, 0x08, 0x25],
+                Register::Day,
+                0x0D,
+            ),
+            // 12-hour mode, hour nibble above 9
+            (
+                [0x00, 0x30, 0x4B, 0x05, 0x21, 0x08, 0x25],
+                Register::Hours,
+                0x4B,
+            ),
+            // 12-hour mode, hours 0 and 19
+            (
+                [0x00, 0x30, 0x40, 0x05, 0x21, 0x08, 0x25],
+                Register::Hours,
+                0x40,
+            ),
+            (
+                [0x00, 0x30, 0x59, 0x05, 0x21, 0x08, 0x25],
+                Register::Hours,
+                0x59,
+            ),
+        ];
+        for (data, register, value) in cases {
+            let expectations = [I2cTrans::write_read(
+                0x68,
+                vec![Register::Seconds.addr()],
+                data.to_vec(),
+            )];
+            let mut ds3231 = new_ds3231(I2cMock::new(&expectations));
+
+            assert_eq!(
+                ds3231.get_datetime(),
+                Err(crate::error::Error::CorruptRegister { register, value })
+            );
+
+            ds3231.release_i2c().done();
+        }
+
+        // Valid BCD, but not a date
+        let expectations = [I2cTrans::write_read(
+            0x68,
+            vec![Register::Seconds.addr()],
+            vec![0x00, 0x30, 0x14, 0x05, 0x30, 0x02, 0x25],
+        )];
+        let mut ds3231 = new_ds3231(I2cMock::new(&expectations));
+        assert_eq!(
+            ds3231.get_datetime(),
+            Err(crate::error::Error::DateTime(DateTimeError::InvalidDay))
+        );
+        ds3231.release_i2c().done();
+    }
+
+    #[test]
+    fn test_set_datetime_leap_day() {
+        let datetime = DateTime::new(2024, 2, 29, 8, 0, 0).unwrap();
+        let expectations = [I2cTrans::write(
+            0x68,
+            vec![
+                Register::Seconds.addr(),
+                0x00, // sec
+                0x00, // min
+                0x08, // hour
+                0x05, // weekday (Thursday)
+                0x29, // day
+                0x02, // month
+                0x24, // year
+            ],
+        )];
+
+        let mut ds3231 = new_ds3231(I2cMock::new(&expectations));
+
+        ds3231.set_datetime(&datetime).unwrap();
+
+        ds3231.release_i2c().done();
+    }
+
     #[test]
     fn test_set_datetime_invalid_year() {
         let datetime = DateTime::new(1980, 1, 1, 0, 0, 0).unwrap();
@@ -298,7 +994,7 @@ mod tests {
         let data = [
             0x50,        // seconds = 50
             0x45,        // minutes = 45
-            0b0110_1011, // hour register: 12h mode, hr=11, PM
+            0b0111_0001, // hour register: 12h mode, PM, hr=BCD 11
             0x01,        // weekday = Sunday
             0x31,        // day of month
             0x12,        // month = December
@@ -370,4 +1066,321 @@ mod tests {
 
         ds3231.release_i2c().done();
     }
+
+    #[test]
+    fn test_get_unix_timestamp() {
+        // 2025-08-21 14:30:00
+        let data = [0x00, 0x30, 0x14, 0x05, 0x21, 0x08, 0x25];
+        let expectations = [I2cTrans::write_read(
+            0x68,
+            vec![Register::Seconds.addr()],
+            data.to_vec(),
+        )];
+        let mut ds3231 = new_ds3231(I2cMock::new(&expectations));
+
+        assert_eq!(ds3231.get_unix_timestamp().unwrap(), 1_755_786_600);
+
+        ds3231.release_i2c().done();
+    }
+
+    #[test]
+    fn test_set_unix_timestamp() {
+        let expectations = [I2cTrans::write(
+            0x68,
+            vec![
+                Register::Seconds.addr(),
+                0x00, // sec
+                0x30, // min
+                0x14, // hour
+                0x05, // weekday (Thursday)
+                0x21, // day
+                0x08, // month
+                0x25, // year
+            ],
+        )];
+        let mut ds3231 = new_ds3231(I2cMock::new(&expectations));
+
+        ds3231.set_unix_timestamp(1_755_786_600).unwrap();
+
+        ds3231.release_i2c().done();
+    }
+
+    #[test]
+    fn test_weekday_for() {
+        let dt = DateTime::new(2025, 8, 27, 0, 0, 0).unwrap();
+        assert_eq!(weekday_for(&dt), Weekday::Wednesday);
+
+        let dt = DateTime::new(2024, 2, 29, 0, 0, 0).unwrap();
+        assert_eq!(weekday_for(&dt), Weekday::Thursday);
+    }
+
+    #[test]
+    fn test_weekday_for_known_dates() {
+        let cases = [
+            ((2000, 3, 1), Weekday::Wednesday),
+            ((2010, 3, 7), Weekday::Sunday),
+            ((2099, 12, 31), Weekday::Thursday),
+            ((2100, 2, 28), Weekday::Sunday),
+            ((2199, 12, 31), Weekday::Tuesday),
+        ];
+
+        for ((year, month, day), expected) in cases {
+            let dt = DateTime::new(year, month, day, 0, 0, 0).unwrap();
+            assert_eq!(weekday_for(&dt), expected, "{year}-{month}-{day}");
+        }
+    }
+
+    #[test]
+    fn test_set_datetime_computes_weekday() {
+        // 2000-03-01 is a Wednesday
+        let datetime = DateTime::new(2000, 3, 1, 0, 0, 0).unwrap();
+        let expectations = [I2cTrans::write(
+            0x68,
+            vec![
+                Register::Seconds.addr(),
+                0x00, // sec
+                0x00, // min
+                0x00, // hour
+                0x04, // weekday (Wednesday)
+                0x01, // day
+                0x03, // month
+                0x00, // year
+            ],
+        )];
+
+        let mut ds3231 = new_ds3231(I2cMock::new(&expectations));
+
+        ds3231.set_datetime(&datetime).unwrap();
+
+        ds3231.release_i2c().done();
+    }
+
+    #[test]
+    fn test_weekday_next_prev() {
+        assert_eq!(Weekday::Saturday.next(), Weekday::Sunday);
+        assert_eq!(Weekday::Sunday.prev(), Weekday::Saturday);
+        assert_eq!(Weekday::Monday.next(), Weekday::Tuesday);
+
+        for day in WEEKDAYS {
+            assert_eq!(day.next().prev(), day);
+        }
+    }
+
+    #[test]
+    fn test_weekday_iso_numbering() {
+        assert_eq!(Weekday::Monday.to_iso_number(), 1);
+        assert_eq!(Weekday::Sunday.to_iso_number(), 7);
+        assert_eq!(Weekday::from_iso_number(1), Ok(Weekday::Monday));
+        assert_eq!(Weekday::from_iso_number(7), Ok(Weekday::Sunday));
+        assert_eq!(
+            Weekday::from_iso_number(0),
+            Err(DateTimeError::InvalidWeekday)
+        );
+
+        for day in WEEKDAYS {
+            assert_eq!(Weekday::from_iso_number(day.to_iso_number()), Ok(day));
+        }
+    }
+
+    #[test]
+    fn test_weekday_days_until() {
+        assert_eq!(Weekday::Monday.days_until(Weekday::Monday), 0);
+        assert_eq!(Weekday::Friday.days_until(Weekday::Monday), 3);
+        assert_eq!(Weekday::Sunday.days_until(Weekday::Saturday), 6);
+    }
+
+    #[test]
+    fn test_get_weekday() {
+        let expectations = [
+            I2cTrans::write_read(0x68, vec![Register::Day.addr()], vec![0x04]),
+            I2cTrans::write_read(0x68, vec![Register::Day.addr()], vec![0x00]),
+        ];
+        let mut ds3231 = new_ds3231(I2cMock::new(&expectations));
+
+        assert_eq!(ds3231.get_weekday().unwrap(), Weekday::Wednesday);
+        assert!(matches!(
+            ds3231.get_weekday(),
+            Err(crate::error::Error::DateTime(DateTimeError::InvalidWeekday))
+        ));
+
+        ds3231.release_i2c().done();
+    }
+
+    #[test]
+    fn test_shift_clock_rolls_over_date() {
+        // 2024-12-31 23:30:00 shifted +1h becomes 2025-01-01 00:30:00 (Wednesday),
+        // then shifted back -1h
+        let expectations = [
+            I2cTrans::write_read(
+                0x68,
+                vec![Register::Seconds.addr()],
+                vec![0x00, 0x30, 0x23, 0x03, 0x31, 0x12, 0x24],
+            ),
+            I2cTrans::write(
+                0x68,
+                vec![
+                    Register::Seconds.addr(),
+                    0x00,
+                    0x30,
+                    0x00,
+                    0x04,
+                    0x01,
+                    0x01,
+                    0x25,
+                ],
+            ),
+            I2cTrans::write_read(
+                0x68,
+                vec![Register::Seconds.addr()],
+                vec![0x00, 0x30, 0x00, 0x04, 0x01, 0x01, 0x25],
+            ),
+            I2cTrans::write(
+                0x68,
+                vec![
+                    Register::Seconds.addr(),
+                    0x00,
+                    0x30,
+                    0x23,
+                    0x03,
+                    0x31,
+                    0x12,
+                    0x24,
+                ],
+            ),
+        ];
+        let mut ds3231 = new_ds3231(I2cMock::new(&expectations));
+        assert!(!ds3231.is_clock_shifted());
+
+        ds3231.shift_clock(1).unwrap();
+        assert_eq!(ds3231.clock_shift(), 1);
+        assert!(ds3231.is_clock_shifted());
+
+        ds3231.shift_clock(-1).unwrap();
+        assert_eq!(ds3231.clock_shift(), 0);
+
+        ds3231.release_i2c().done();
+    }
+
+    #[test]
+    fn test_shift_clock_overflow_leaves_clock_untouched() {
+        let mut ds3231 = new_ds3231(I2cMock::new(&[]));
+        ds3231.set_clock_shift(i8::MAX);
+
+        assert!(matches!(
+            ds3231.shift_clock(1),
+            Err(crate::error::Error::DateTime(DateTimeError::InvalidHour))
+        ));
+        assert_eq!(ds3231.clock_shift(), i8::MAX);
+
+        ds3231.release_i2c().done();
+    }
+
+    #[test]
+    fn test_set_time_writes_time_registers_only() {
+        let expectations = [
+            I2cTrans::write(0x68, vec![Register::Seconds.addr(), 0x45, 0x30, 0x14]),
+            // 12-hour mode: 14:30 is 2 PM
+            I2cTrans::write(0x68, vec![Register::Seconds.addr(), 0x00, 0x30, 0x62]),
+        ];
+        let mut ds3231 = new_ds3231(I2cMock::new(&expectations));
+
+        ds3231.set_time(14, 30, 45).unwrap();
+        ds3231.set_hour_mode(crate::hour_mode::HourMode::TwelveHour);
+        ds3231.set_time(14, 30, 0).unwrap();
+        assert!(matches!(
+            ds3231.set_time(24, 0, 0),
+            Err(crate::error::Error::DateTime(DateTimeError::InvalidHour))
+        ));
+
+        ds3231.release_i2c().done();
+    }
+
+    #[test]
+    fn test_set_date_writes_date_registers_only() {
+        let expectations = [
+            // 2025-08-21 is a Thursday
+            I2cTrans::write(0x68, vec![Register::Day.addr(), 0x05, 0x21, 0x08, 0x25]),
+            // 2150-01-01 needs the century bit (Thursday)
+            I2cTrans::write(0x68, vec![Register::Day.addr(), 0x05, 0x01, 0x81, 0x50]),
+        ];
+        let mut ds3231 = new_ds3231(I2cMock::new(&expectations));
+
+        ds3231.set_date(2025, Month::August, 21).unwrap();
+        ds3231.set_date(2150, Month::January, 1).unwrap();
+        assert!(matches!(
+            ds3231.set_date(2025, Month::February, 29),
+            Err(crate::error::Error::DateTime(DateTimeError::InvalidDay))
+        ));
+        assert!(matches!(
+            ds3231.set_date(2200, Month::January, 1),
+            Err(crate::error::Error::DateTime(DateTimeError::InvalidYear))
+        ));
+
+        ds3231.release_i2c().done();
+    }
+
+    #[test]
+    fn test_adjust_by_seconds() {
+        let expectations = [
+            // 2024-02-28 23:59:50 + 15s = 2024-02-29 00:00:05 (Thursday)
+            I2cTrans::write_read(
+                0x68,
+                vec![Register::Seconds.addr()],
+                vec![0x50, 0x59, 0x23, 0x04, 0x28, 0x02, 0x24],
+            ),
+            I2cTrans::write(
+                0x68,
+                vec![
+                    Register::Seconds.addr(),
+                    0x05,
+                    0x00,
+                    0x00,
+                    0x05,
+                    0x29,
+                    0x02,
+                    0x24,
+                ],
+            ),
+            // 2000-01-01 00:00:10 - 30s would leave the supported range
+            I2cTrans::write_read(
+                0x68,
+                vec![Register::Seconds.addr()],
+                vec![0x10, 0x00, 0x00, 0x07, 0x01, 0x01, 0x00],
+            ),
+        ];
+        let mut ds3231 = new_ds3231(I2cMock::new(&expectations));
+
+        ds3231.adjust_by_seconds(15).unwrap();
+        assert!(matches!(
+            ds3231.adjust_by_seconds(-30),
+            Err(crate::error::Error::DateTime(DateTimeError::InvalidYear))
+        ));
+
+        ds3231.release_i2c().done();
+    }
+
+    #[test]
+    fn test_elapsed_since() {
+        let data = vec![0x00, 0x30, 0x14, 0x05, 0x21, 0x08, 0x25];
+        let expectations = [
+            I2cTrans::write_read(0x68, vec![Register::Seconds.addr()], data.clone()),
+            I2cTrans::write_read(0x68, vec![Register::Seconds.addr()], data.clone()),
+            I2cTrans::write_read(0x68, vec![Register::Seconds.addr()], data),
+        ];
+        let mut ds3231 = new_ds3231(I2cMock::new(&expectations));
+
+        // RTC reads 2025-08-21 14:30:00
+        let earlier = DateTime::new(2025, 8, 20, 14, 29, 30).unwrap();
+        assert_eq!(
+            ds3231.elapsed_since(&earlier).unwrap(),
+            Duration::from_secs(86_430)
+        );
+
+        // Clock was set backwards: saturates to zero, but the signed form shows it
+        let later = DateTime::new(2025, 8, 21, 14, 31, 0).unwrap();
+        assert_eq!(ds3231.elapsed_since(&later).unwrap(), Duration::ZERO);
+        assert_eq!(ds3231.seconds_since(&later).unwrap(), -60);
+
+        ds3231.release_i2c().done();
+    }
 }
@@ -0,0 +1,282 @@
+//! # Delays from the Square Wave
+//!
+//! [`RtcDelay`] implements [`DelayNs`] by polling a GPIO connected to the
+//! SQW output and counting its falling edges, for boards without a spare
+//! hardware timer. The RTC's oscillator is temperature compensated, so the
+//! delays are more accurate than a calibrated busy loop, at the cost of
+//! keeping the CPU busy while polling:
+//!
+//! ```no_run
+//! # use ds3231_rtc::Ds3231;
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! # let mut rtc = Ds3231::new(embedded_hal_mock::eh1::i2c::Mock::new(&[]));
+//! use embedded_hal::delay::DelayNs;
+//! use rtc_hal::square_wave::SquareWaveFreq;
+//! # use core::convert::Infallible;
+//! # struct SqwPin;
+//! # impl embedded_hal::digital::ErrorType for SqwPin { type Error = Infallible; }
+//! # impl embedded_hal::digital::InputPin for SqwPin {
+//! #     fn is_high(&mut self) -> Result<bool, Infallible> { Ok(true) }
+//! #     fn is_low(&mut self) -> Result<bool, Infallible> { Ok(false) }
+//! # }
+//! # let sqw_pin = SqwPin;
+//!
+//! let mut delay = rtc.sqw_delay(sqw_pin, SquareWaveFreq::Hz8192)?;
+//! delay.delay_ms(250);
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! A delay first waits for a falling edge and then counts one more for each
+//! started period, so it never ends early; it may run up to two periods
+//! longer than asked (about 0.25 ms at 8192 Hz, 2 s at 1 Hz). The SQW pin
+//! is open drain and needs a pull-up.
+//!
+//! Without edges the delay never ends: a stopped oscillator, a square wave
+//! switched off or to alarm interrupts, or a missing pull-up hang the
+//! caller. [`with_poll_limit`](RtcDelay::with_poll_limit) bounds the pin
+//! reads spent waiting for each edge, after which the delay returns early.
+
+use core::convert::Infallible;
+
+use embedded_hal::delay::DelayNs;
+use embedded_hal::digital::InputPin;
+use rtc_hal::square_wave::SquareWaveFreq;
+
+use crate::Ds3231;
+use crate::error::Error;
+use crate::interface::RegisterInterface;
+use crate::square_wave::freq_to_bits;
+
+/// Delay counting falling edges of the SQW output on a GPIO
+///
+/// By default each edge is waited for without a bound, so the delay spins
+/// forever once the square wave stops; see
+/// [`with_poll_limit`](Self::with_poll_limit).
+#[derive(Debug)]
+pub struct RtcDelay<P> {
+    pin: P,
+    hz: u32,
+    poll_limit: Option<u32>,
+}
+
+impl<P: InputPin<Error = Infallible>> RtcDelay<P> {
+    /// Count edges on `pin` of a square wave already running at `freq`.
+    ///
+    /// Fails with [`Error::UnsupportedSqwFrequency`] for frequencies the SQW
+    /// pin can't output.
+    pub fn new(pin: P, freq: SquareWaveFreq) -> Result<Self, Error<Infallible>> {
+        freq_to_bits::<Infallible>(freq)?;
+        Ok(Self {
+            pin,
+            hz: freq.to_hz(),
+            poll_limit: None,
+        })
+    }
+
+    /// Give up waiting for an edge after `polls` reads of the pin, ending
+    /// the delay early instead of hanging when the square wave stops.
+    ///
+    /// Pick the limit well above the reads one period takes on the target,
+    /// so that a running square wave never hits it.
+    pub fn with_poll_limit(mut self, polls: u32) -> Self {
+        self.poll_limit = Some(polls);
+        self
+    }
+
+    /// Returns the pin, consuming the delay.
+    pub fn release(self) -> P {
+        self.pin
+    }
+
+    /// Wait at least `ns` nanoseconds.
+    fn wait_ns(&mut self, ns: u64) {
+        if ns == 0 {
+            return;
+        }
+        // The first edge only aligns the count to a period boundary
+        for _ in 0..=self.periods(ns) {
+            if !self.wait_for_falling_edge() {
+                return;
+            }
+        }
+    }
+
+    /// Periods started within `ns` nanoseconds.
+    ///
+    /// `delay_ms(u32::MAX)` at 8192 Hz overflows a `u64` product, so this
+    /// multiplies in `u128`; the quotient always fits back into a `u64`.
+    fn periods(&self, ns: u64) -> u64 {
+        (u128::from(ns) * u128::from(self.hz)).div_ceil(1_000_000_000) as u64
+    }
+
+    /// Returns `false` if the poll limit ran out before an edge.
+    fn wait_for_falling_edge(&mut self) -> bool {
+        let Ok(mut high) = self.pin.is_high();
+        let mut polls = 0u32;
+        loop {
+            let Ok(now_high) = self.pin.is_high();
+            if high && !now_high {
+                return true;
+            }
+            high = now_high;
+            polls = polls.saturating_add(1);
+            if self.poll_limit.is_some_and(|limit| polls >= limit) {
+                return false;
+            }
+        }
+    }
+}
+
+impl<P: InputPin<Error = Infallible>> DelayNs for RtcDelay<P> {
+    fn delay_ns(&mut self, ns: u32) {
+        self.wait_ns(ns as u64);
+    }
+
+    fn delay_us(&mut self, us: u32) {
+        self.wait_ns(us as u64 * 1_000);
+    }
+
+    fn delay_ms(&mut self, ms: u32) {
+        self.wait_ns(ms as u64 * 1_000_000);
+    }
+}
+
+impl<I2C, V> Ds3231<I2C, V>
+where
+    I2C: RegisterInterface,
+{
+    /// Start the square wave at `freq` and return a delay counting its
+    /// edges on `pin`.
+    pub fn sqw_delay<P: InputPin<Error = Infallible>>(
+        &mut self,
+        pin: P,
+        freq: SquareWaveFreq,
+    ) -> Result<RtcDelay<P>, Error<I2C::Error>> {
+        self.start_square_wave(freq)?;
+        Ok(RtcDelay {
+            pin,
+            hz: freq.to_hz(),
+            poll_limit: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registers::Register;
+    use embedded_hal::digital::ErrorType;
+    use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
+
+    /// Pin toggling on every read, counting its falling edges
+    #[derive(Default)]
+    struct TogglingPin {
+        high: bool,
+        falling_edges: u32,
+    }
+
+    impl ErrorType for TogglingPin {
+        type Error = Infallible;
+    }
+
+    impl InputPin for TogglingPin {
+        fn is_high(&mut self) -> Result<bool, Infallible> {
+            self.high = !self.high;
+            if !self.high {
+                self.falling_edges += 1;
+            }
+            Ok(self.high)
+        }
+
+        fn is_low(&mut self) -> Result<bool, Infallible> {
+            self.is_high().map(|high| !high)
+        }
+    }
+
+    #[test]
+    fn test_delay_counts_started_periods() {
+        let mut delay = RtcDelay::new(TogglingPin::default(), SquareWaveFreq::Hz1024).unwrap();
+
+        delay.delay_ns(0);
+        assert_eq!(delay.pin.falling_edges, 0);
+
+        // 1 ms is 1.024 periods: two periods plus the aligning edge
+        delay.delay_ms(1);
+        assert_eq!(delay.pin.falling_edges, 3);
+
+        delay.delay_us(250_000);
+        assert_eq!(delay.release().falling_edges, 3 + 256 + 1);
+    }
+
+    #[test]
+    fn test_longest_delay_period_count() {
+        let delay = RtcDelay::new(TogglingPin::default(), SquareWaveFreq::Hz8192).unwrap();
+        // 4294967.295 s at 8192 Hz
+        assert_eq!(delay.periods(u32::MAX as u64 * 1_000_000), 35_184_372_081);
+
+        let delay = RtcDelay::new(TogglingPin::default(), SquareWaveFreq::Hz1).unwrap();
+        assert_eq!(delay.periods(u32::MAX as u64 * 1_000_000), 4_294_968);
+    }
+
+    /// Pin of a square wave that stopped, counting its reads
+    #[derive(Default)]
+    struct StuckPin {
+        reads: u32,
+    }
+
+    impl ErrorType for StuckPin {
+        type Error = Infallible;
+    }
+
+    impl InputPin for StuckPin {
+        fn is_high(&mut self) -> Result<bool, Infallible> {
+            self.reads += 1;
+            Ok(true)
+        }
+
+        fn is_low(&mut self) -> Result<bool, Infallible> {
+            self.is_high().map(|high| !high)
+        }
+    }
+
+    #[test]
+    fn test_poll_limit_ends_delay_without_edges() {
+        let mut delay = RtcDelay::new(StuckPin::default(), SquareWaveFreq::Hz1024)
+            .unwrap()
+            .with_poll_limit(100);
+
+        // Gives up on the first edge instead of waiting for all of them
+        delay.delay_ms(10);
+        assert_eq!(delay.release().reads, 101);
+
+        let mut delay = RtcDelay::new(TogglingPin::default(), SquareWaveFreq::Hz1024)
+            .unwrap()
+            .with_poll_limit(2);
+        delay.delay_ms(1);
+        assert_eq!(delay.release().falling_edges, 3);
+    }
+
+    #[test]
+    fn test_sqw_delay_starts_square_wave() {
+        let expectations = [
+            I2cTransaction::write_read(0x68, vec![Register::Control.addr()], vec![0x1C]),
+            // 1 Hz, INTCN cleared
+            I2cTransaction::write(0x68, vec![Register::Control.addr(), 0x00]),
+        ];
+        let mut ds3231 = Ds3231::new(I2cMock::new(&expectations));
+
+        let mut delay = ds3231
+            .sqw_delay(TogglingPin::default(), SquareWaveFreq::Hz1)
+            .unwrap();
+        delay.delay_ms(2000);
+        assert_eq!(delay.release().falling_edges, 3);
+
+        assert_eq!(
+            RtcDelay::new(TogglingPin::default(), SquareWaveFreq::Hz32768).err(),
+            Some(Error::UnsupportedSqwFrequency)
+        );
+
+        ds3231.release_i2c().done();
+    }
+}
@@ -0,0 +1,148 @@
+//! # DateTime Display
+//!
+//! The [`DateTime`] type comes from `rtc-hal` and implements neither
+//! `core::fmt::Display` nor `defmt::Format`, which forces users to fall back
+//! to `Debug` or `defmt::Debug2Format` when logging timestamps.
+//!
+//! [`DateTimeDisplay`] is a thin wrapper that formats a datetime as ISO 8601
+//! (`YYYY-MM-DDTHH:MM:SS`). With the `defmt` feature enabled it also implements
+//! `defmt::Format`, sending the six fields as raw integers with an interned
+//! format string instead of a rendered `Debug` string, which keeps RTT
+//! bandwidth and flash usage low.
+//!
+//! ```no_run
+//! # use ds3231_rtc::Ds3231;
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! # let mut rtc = Ds3231::new(embedded_hal_mock::eh1::i2c::Mock::new(&[]));
+//! use ds3231_rtc::display::DateTimeDisplay;
+//!
+//! let now = rtc.get_datetime()?;
+//! println!("now: {}", DateTimeDisplay(now));
+//! // or, with the `defmt` feature: defmt::info!("now: {}", DateTimeDisplay(now));
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! With the `ufmt` feature it implements `uDisplay` and `uDebug` as well,
+//! for projects that avoid `core::fmt` to save code size.
+
+use rtc_hal::datetime::DateTime;
+
+/// Wrapper that formats a [`DateTime`] as ISO 8601 (`YYYY-MM-DDTHH:MM:SS`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DateTimeDisplay(pub DateTime);
+
+impl From<DateTime> for DateTimeDisplay {
+    fn from(datetime: DateTime) -> Self {
+        DateTimeDisplay(datetime)
+    }
+}
+
+impl core::fmt::Display for DateTimeDisplay {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let dt = &self.0;
+        write!(
+            f,
+            "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}",
+            dt.year(),
+            dt.month(),
+            dt.day_of_month(),
+            dt.hour(),
+            dt.minute(),
+            dt.second()
+        )
+    }
+}
+
+#[cfg(feature = "defmt")]
+impl defmt::Format for DateTimeDisplay {
+    fn format(&self, f: defmt::Formatter) {
+        let dt = &self.0;
+        defmt::write!(
+            f,
+            "{=u16}-{=u8:02}-{=u8:02}T{=u8:02}:{=u8:02}:{=u8:02}",
+            dt.year(),
+            dt.month(),
+            dt.day_of_month(),
+            dt.hour(),
+            dt.minute(),
+            dt.second()
+        )
+    }
+}
+
+/// Write `value` with at least two digits.
+#[cfg(feature = "ufmt")]
+pub(crate) fn uwrite_two_digits<W>(
+    f: &mut ufmt::Formatter<'_, W>,
+    value: u8,
+) -> Result<(), W::Error>
+where
+    W: ufmt::uWrite + ?Sized,
+{
+    if value < 10 {
+        f.write_char('0')?;
+    }
+    ufmt::uDisplay::fmt(&value, f)
+}
+
+#[cfg(feature = "ufmt")]
+impl ufmt::uDisplay for DateTimeDisplay {
+    fn fmt<W>(&self, f: &mut ufmt::Formatter<'_, W>) -> Result<(), W::Error>
+    where
+        W: ufmt::uWrite + ?Sized,
+    {
+        let dt = &self.0;
+        ufmt::uwrite!(f, "{}-", dt.year())?;
+        uwrite_two_digits(f, dt.month())?;
+        f.write_char('-')?;
+        uwrite_two_digits(f, dt.day_of_month())?;
+        f.write_char('T')?;
+        uwrite_two_digits(f, dt.hour())?;
+        f.write_char(':')?;
+        uwrite_two_digits(f, dt.minute())?;
+        f.write_char(':')?;
+        uwrite_two_digits(f, dt.second())
+    }
+}
+
+/// Same as `uDisplay`; `DateTime` itself can't implement `uDebug`.
+#[cfg(feature = "ufmt")]
+impl ufmt::uDebug for DateTimeDisplay {
+    fn fmt<W>(&self, f: &mut ufmt::Formatter<'_, W>) -> Result<(), W::Error>
+    where
+        W: ufmt::uWrite + ?Sized,
+    {
+        ufmt::uDisplay::fmt(self, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display_iso8601() {
+        let dt = DateTime::new(2025, 8, 21, 14, 30, 5).unwrap();
+        assert_eq!(format!("{}", DateTimeDisplay(dt)), "2025-08-21T14:30:05");
+    }
+
+    #[test]
+    fn test_display_pads_fields() {
+        let dt = DateTime::new(2001, 1, 2, 3, 4, 5).unwrap();
+        assert_eq!(
+            format!("{}", DateTimeDisplay::from(dt)),
+            "2001-01-02T03:04:05"
+        );
+    }
+
+    #[cfg(feature = "ufmt")]
+    #[test]
+    fn test_ufmt_matches_display() {
+        let dt = DateTimeDisplay(DateTime::new(2025, 3, 7, 9, 5, 0).unwrap());
+        let mut s = String::new();
+        ufmt::uwrite!(s, "{} {:?}", dt, dt).unwrap();
+        assert_eq!(s, format!("{dt} {dt}"));
+        assert_eq!(s, "2025-03-07T09:05:00 2025-03-07T09:05:00");
+    }
+}
@@ -1,10 +1,34 @@
 //! DS3231 Real-Time Clock Driver
-
-use embedded_hal::i2c::I2c;
-
-use crate::{error::Error, registers::Register};
-
-/// DS3231 I2C device address (fixed)
+//!
+//! The driver core is written once as `async` code; `maybe-async-cfg`
+//! generates the blocking [`Ds3231`] from it, plus the async
+//! [`asynch::Ds3231`](crate::asynch::Ds3231) when the `async` feature is
+//! enabled. Other modules extend both the same way.
+
+#[cfg(feature = "async")]
+use crate::interface::AsyncRegisterInterface;
+use crate::interface::RegisterInterface;
+
+use core::marker::PhantomData;
+
+use embedded_hal::i2c::{ErrorKind, NoAcknowledgeSource};
+
+use crate::{
+    cache::ControlCache,
+    error::{Error, Operation},
+    flags::ControlFlags,
+    hour_mode::HourMode,
+    plausibility::Plausibility,
+    read_strategy::ReadStrategy,
+    registers::{A1F_BIT, A2F_BIT, ControlRegister, EN32KHZ_BIT, OSF_BIT, Register},
+    stats::Stats,
+    trace::{debug, trace},
+    variant::{AnyVariant, Variant},
+    year_policy::YearPolicy,
+};
+
+/// DS3231 I2C device address, used unless another is given to
+/// [`Ds3231::new_with_address`]
 pub const I2C_ADDR: u8 = 0x68;
 
 /// Default base century for year calculations (2000-2099).
@@ -12,19 +36,104 @@ pub const I2C_ADDR: u8 = 0x68;
 /// years 00-99 represent the 21st century by default.
 pub const DEFAULT_BASE_CENTURY: u8 = 20;
 
+/// Largest burst moved by the chunked transfer helpers, bounding the stack
+/// buffer they need
+pub(crate) const MAX_BURST: usize = 32;
+
 /// DS3231 Real-Time Clock driver
-pub struct Ds3231<I2C> {
+///
+/// `V` optionally fixes the chip variant at compile time; see
+/// [`variant`](crate::variant). The default, [`AnyVariant`], selects it at
+/// runtime.
+#[maybe_async_cfg::maybe(
+    idents(Ds3231(sync = "Ds3231", async = "Ds3231Async")),
+    sync(keep_self),
+    async(feature = "async")
+)]
+pub struct Ds3231<I2C, V = AnyVariant> {
     i2c: I2C,
+    address: u8,
     pub(crate) base_century: u8,
+    pub(crate) hour_mode: HourMode,
+    pub(crate) year_policy: YearPolicy,
+    pub(crate) clock_shift_hours: i8,
+    pub(crate) read_strategy: ReadStrategy,
+    pub(crate) control_cache: ControlCache,
+    pub(crate) verify_writes: bool,
+    pub(crate) plausibility: Plausibility,
+    pub(crate) stats: Stats,
+    pub(crate) variant: Variant,
+    _variant: PhantomData<V>,
 }
 
-impl<I2C: embedded_hal::i2c::I2c> rtc_hal::error::ErrorType for Ds3231<I2C> {
+#[maybe_async_cfg::maybe(
+    idents(
+        AsyncRegisterInterface(sync = "RegisterInterface", async = "AsyncRegisterInterface"),
+        Ds3231(sync = "Ds3231", async = "Ds3231Async")
+    ),
+    sync(keep_self),
+    async(feature = "async")
+)]
+impl<I2C: AsyncRegisterInterface, V> rtc_hal::error::ErrorType for Ds3231<I2C, V> {
     type Error = crate::error::Error<I2C::Error>;
 }
 
+/// Shows the configuration and the cached control register, without the
+/// bus, so a `{:?}` from a panic handler works with any bus type.
+///
+/// `control_cache` is `None` unless the [control cache](crate::cache) is
+/// enabled and holds a value; the status register is never cached.
+#[maybe_async_cfg::maybe(
+    idents(Ds3231(sync = "Ds3231", async = "Ds3231Async")),
+    sync(keep_self),
+    async(feature = "async")
+)]
+impl<I2C, V> core::fmt::Debug for Ds3231<I2C, V> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Ds3231")
+            .field("address", &format_args!("{:#04x}", self.address))
+            .field("variant", &self.variant)
+            .field("base_century", &self.base_century)
+            .field("hour_mode", &self.hour_mode)
+            .field(
+                "control_cache",
+                &self.control_cache.get().map(ControlFlags::from_bits_retain),
+            )
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(feature = "defmt")]
+#[maybe_async_cfg::maybe(
+    idents(Ds3231(sync = "Ds3231", async = "Ds3231Async")),
+    sync(keep_self),
+    async(feature = "async")
+)]
+impl<I2C, V> defmt::Format for Ds3231<I2C, V> {
+    fn format(&self, f: defmt::Formatter) {
+        defmt::write!(
+            f,
+            "Ds3231 {{ address: {=u8:#04x}, variant: {}, base_century: {=u8}, hour_mode: {}, control_cache: {}, .. }}",
+            self.address,
+            self.variant,
+            self.base_century,
+            self.hour_mode,
+            self.control_cache.get().map(ControlFlags::from_bits_retain)
+        );
+    }
+}
+
+#[maybe_async_cfg::maybe(
+    idents(
+        AsyncRegisterInterface(sync = "RegisterInterface", async = "AsyncRegisterInterface"),
+        Ds3231(sync = "Ds3231", async = "Ds3231Async")
+    ),
+    sync(keep_self),
+    async(feature = "async")
+)]
 impl<I2C, E> Ds3231<I2C>
 where
-    I2C: I2c<Error = E>,
+    I2C: AsyncRegisterInterface<Error = E>,
     E: core::fmt::Debug,
 {
     /// Create a new DS3231 driver instance
@@ -32,19 +141,117 @@ where
     /// # Parameters
     /// * `i2c` - I2C peripheral that implements the embedded-hal I2c trait
     ///
+    /// The bus can be passed by value, handing it to the driver until
+    /// [`release`](Self::release), or as `&mut i2c`, borrowing it for the
+    /// driver's lifetime. `embedded-hal` implements `I2c` for mutable
+    /// references, so both kinds of driver support every method and trait.
+    ///
     /// # Returns
     /// New DS3231 driver instance
     pub fn new(i2c: I2C) -> Self {
+        Self::from_parts(i2c, Variant::default())
+    }
+
+    /// Create a driver for a device at a non-standard bus address.
+    ///
+    /// A genuine DS3231 always answers at 0x68, which [`new`](Self::new)
+    /// uses; this is for clones and address translators.
+    pub fn new_with_address(i2c: I2C, address: u8) -> Self {
+        let mut rtc = Self::new(i2c);
+        rtc.address = address;
+        rtc
+    }
+
+    /// Create a driver after checking that a device answers at 0x68.
+    ///
+    /// Fails with [`Error::DeviceNotFound`] if the probe (a one-byte read of
+    /// the seconds register) isn't acknowledged, so a missing or unpowered
+    /// chip shows up during bring-up rather than on first use; other bus
+    /// errors are passed through. The bus is dropped with the driver on
+    /// failure; pass `&mut i2c` to keep it.
+    pub async fn new_checked(i2c: I2C) -> Result<Self, Error<E>>
+    where
+        E: embedded_hal::i2c::Error,
+    {
+        let mut rtc = Self::new(i2c);
+        rtc.probe().await?;
+        Ok(rtc)
+    }
+}
+
+#[maybe_async_cfg::maybe(
+    idents(
+        AsyncRegisterInterface(sync = "RegisterInterface", async = "AsyncRegisterInterface"),
+        Ds3231(sync = "Ds3231", async = "Ds3231Async")
+    ),
+    sync(keep_self),
+    async(feature = "async")
+)]
+impl<I2C, E, V> Ds3231<I2C, V>
+where
+    I2C: AsyncRegisterInterface<Error = E>,
+    E: core::fmt::Debug,
+{
+    /// Driver with default settings for the given runtime variant.
+    pub(crate) fn from_parts(i2c: I2C, variant: Variant) -> Self {
         Self {
             i2c,
+            address: I2C_ADDR,
             base_century: DEFAULT_BASE_CENTURY,
+            hour_mode: HourMode::TwentyFourHour,
+            year_policy: YearPolicy::BaseCenturyWindow,
+            clock_shift_hours: 0,
+            read_strategy: ReadStrategy::Burst,
+            control_cache: ControlCache::default(),
+            verify_writes: false,
+            plausibility: Plausibility::default(),
+            stats: Stats::default(),
+            variant,
+            _variant: PhantomData,
         }
     }
 
+    /// Sets the base century for year calculations, consuming and returning the driver.
+    ///
+    /// Builder-style variant of [`set_base_century`](Self::set_base_century),
+    /// intended to be chained onto [`new`](Self::new).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ds3231_rtc::{Ds3231, error::Error};
+    /// # fn main() -> Result<(), Error<embedded_hal::i2c::ErrorKind>> {
+    /// # let i2c = embedded_hal_mock::eh1::i2c::Mock::new(&[]);
+    /// // Years 00-99 will be interpreted as 2000-2099 (2100-2199 with the century bit set)
+    /// let rtc = Ds3231::new(i2c).with_base_century(20)?;
+    /// # let i2c = rtc.release_i2c();
+    ///
+    /// // Years 00-99 will be interpreted as 2100-2199 (2200-2299 with the century bit set)
+    /// let rtc = Ds3231::new(i2c).with_base_century(21)?;
+    /// # rtc.release_i2c().done();
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_base_century(mut self, base_century: u8) -> Result<Self, Error<E>> {
+        self.set_base_century(base_century)?;
+        Ok(self)
+    }
+
     /// Sets the base century for year calculations.
     ///
-    /// The DS3231 stores years as 00-99 in BCD format. This base century
-    /// determines how those 2-digit years are interpreted as full 4-digit years.
+    /// The DS3231 stores years as 00-99 in BCD format plus a century bit in the
+    /// month register. The base century determines how those values are
+    /// interpreted as full 4-digit years:
+    ///
+    /// - century bit clear: `base_century * 100 + year`
+    /// - century bit set: `(base_century + 1) * 100 + year`
+    ///
+    /// The chip toggles the century bit itself when the year rolls over from
+    /// 99 to 00, so a clock set in the base century keeps counting correctly
+    /// into the next one. The window is 200 years wide; a rollover past the
+    /// end of the next century clears the century bit again and the year is
+    /// read back as the start of the base century. Move the base century
+    /// forward before that point if the device is expected to run that long.
     ///
     /// # Arguments
     ///
@@ -53,24 +260,25 @@ where
     /// # Returns
     ///
     /// Returns `Err(Error::InvalidBaseCentury)` if base_century is less than 19.
-    ///
-    /// # Examples
-    ///
-    /// ```
-    /// // Years 00-99 will be interpreted as 2000-2099
-    /// let rtc = Ds3231::new(i2c).with_base_century(20)?;
-    ///
-    /// // Years 00-99 will be interpreted as 2100-2199
-    /// let rtc = Ds3231::new(i2c).with_base_century(21)?;
-    /// ```
     pub fn set_base_century(&mut self, base_century: u8) -> Result<(), Error<E>> {
         if base_century < 19 {
             return Err(Error::InvalidBaseCentury);
         }
         self.base_century = base_century;
+        self.plausibility.reset();
         Ok(())
     }
 
+    /// Returns the configured base century.
+    pub fn base_century(&self) -> u8 {
+        self.base_century
+    }
+
+    /// Returns the bus address the driver talks to.
+    pub fn address(&self) -> u8 {
+        self.address
+    }
+
     /// Returns the underlying I2C bus instance, consuming the driver.
     ///
     /// This allows the user to reuse the I2C bus for other purposes
@@ -83,115 +291,337 @@ where
         self.i2c
     }
 
-    /// Write a single byte to a DS3231 register
-    pub(crate) fn write_register(&mut self, register: Register, value: u8) -> Result<(), Error<E>> {
-        self.i2c.write(I2C_ADDR, &[register.addr(), value])?;
+    /// Returns the underlying bus, consuming the driver.
+    ///
+    /// Same as [`release_i2c`](Self::release_i2c), under the name used by
+    /// most `embedded-hal` drivers. The chip itself is left as it is, so it
+    /// keeps time while the bus is used elsewhere.
+    pub fn release(self) -> I2C {
+        self.i2c
+    }
 
-        Ok(())
+    /// Returns the transport, e.g. to configure a wrapper such as
+    /// [`Retry`](crate::retry::Retry) while the driver owns it.
+    ///
+    /// Registers written through it directly aren't seen by the
+    /// [control cache](crate::cache); invalidate it afterwards.
+    pub fn interface_mut(&mut self) -> &mut I2C {
+        &mut self.i2c
+    }
+
+    /// Write a single byte to a DS3231 register
+    pub(crate) async fn write_register(
+        &mut self,
+        register: Register,
+        value: u8,
+    ) -> Result<(), Error<E>> {
+        self.write_raw_bytes(&[register.addr(), value]).await
     }
 
     /// Read a single byte from a DS3231 register
-    pub(crate) fn read_register(&mut self, register: Register) -> Result<u8, Error<E>> {
+    pub(crate) async fn read_register(&mut self, register: Register) -> Result<u8, Error<E>> {
         let mut data = [0u8; 1];
-        self.i2c
-            .write_read(I2C_ADDR, &[register.addr()], &mut data)?;
+        self.read_bytes_at_address(register.addr(), &mut data)
+            .await?;
 
         Ok(data[0])
     }
 
+    /// Check that the device acknowledges a read at its address.
+    ///
+    /// Fails with [`Error::DeviceNotFound`] if the address isn't
+    /// acknowledged; other bus errors are returned as [`Error::Transfer`].
+    pub async fn probe(&mut self) -> Result<(), Error<E>>
+    where
+        E: embedded_hal::i2c::Error,
+    {
+        match self.read_register(Register::Seconds).await {
+            Ok(_) => Ok(()),
+            Err(Error::Transfer { source, .. })
+                if source.kind() == ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address) =>
+            {
+                Err(Error::DeviceNotFound { source })
+            }
+            Err(e) => Err(e),
+        }
+    }
+
     /// Read multiple bytes from DS3231 starting at a register
-    pub(crate) fn read_register_bytes(
+    pub(crate) async fn read_register_bytes(
         &mut self,
         register: Register,
         buffer: &mut [u8],
     ) -> Result<(), Error<E>> {
-        self.i2c.write_read(I2C_ADDR, &[register.addr()], buffer)?;
+        self.read_bytes_at_address(register.addr(), buffer).await
+    }
+
+    /// Read multiple bytes from DS3231 starting at a raw address
+    pub(crate) async fn read_bytes_at_address(
+        &mut self,
+        register_addr: u8,
+        buffer: &mut [u8],
+    ) -> Result<(), Error<E>> {
+        self.stats.count_read();
+        self.i2c
+            .read_registers(self.address, register_addr, buffer)
+            .await
+            .inspect_err(|_| self.stats.count_bus_error())
+            .map_err(Error::transfer(Operation::Read, register_addr))?;
+        trace!("read {:#04x}: {:02x?}", register_addr, buffer);
 
         Ok(())
     }
 
-    // Read multiple bytes from DS3231 starting at a raw address
-    // pub(crate) fn read_bytes_at_address(
-    //     &mut self,
-    //     register_addr: u8,
-    //     buffer: &mut [u8],
-    // ) -> Result<(), Error<E>> {
-    //     self.i2c.write_read(I2C_ADDR, &[register_addr], buffer)?;
+    /// Write raw bytes directly to DS3231 (register address must be first byte)
+    ///
+    /// The bytes are read back if [write verification](crate::verify) is
+    /// enabled.
+    pub(crate) async fn write_raw_bytes(&mut self, data: &[u8]) -> Result<(), Error<E>> {
+        self.stats.count_write();
+        self.i2c
+            .write_registers(self.address, data)
+            .await
+            .inspect_err(|_| self.stats.count_bus_error())
+            .map_err(Error::transfer(
+                Operation::Write,
+                data.first().copied().unwrap_or_default(),
+            ))?;
+
+        if let Some((&register, values)) = data.split_first() {
+            trace!("write {:#04x}: {:02x?}", register, values);
+            if register <= Register::Year.addr() {
+                self.plausibility.reset();
+            }
+            self.verify_written(register, values).await?;
+        }
 
-    //     Ok(())
-    // }
+        Ok(())
+    }
 
-    /// Write raw bytes directly to DS3231 via I2C (register address must be first byte)
-    pub(crate) fn write_raw_bytes(&mut self, data: &[u8]) -> Result<(), Error<E>> {
-        self.i2c.write(I2C_ADDR, data)?;
+    /// Read into `buffer` in bursts of at most `chunk_size` bytes.
+    ///
+    /// With `advance`, each burst starts at the register following the
+    /// previous one (the chip's auto-increment); otherwise every burst reads
+    /// `register` again, for data ports that increment internally.
+    pub(crate) async fn read_chunked(
+        &mut self,
+        register: u8,
+        buffer: &mut [u8],
+        chunk_size: usize,
+        advance: bool,
+    ) -> Result<(), Error<E>> {
+        let mut register = register;
+        for chunk in buffer.chunks_mut(chunk_size.clamp(1, MAX_BURST)) {
+            self.read_bytes_at_address(register, chunk).await?;
+            if advance {
+                register = register.wrapping_add(chunk.len() as u8);
+            }
+        }
 
         Ok(())
     }
 
-    /// Read-modify-write operation for setting bits
-    ///
-    /// Performs a read-modify-write operation to set the bits specified by the mask
-    /// while preserving all other bits in the register. Only performs a write if
-    /// the register value would actually change, optimizing I2C bus usage.
+    /// Write `data` in bursts of at most `chunk_size` bytes.
     ///
-    /// # Parameters
-    /// - `register`: The DS3231 register to modify
-    /// - `mask`: Bit mask where `1` bits will be set, `0` bits will be ignored
-    ///
-    /// # Example
-    /// ```ignore
-    /// // Set bits 2 and 4 in the control register
-    /// self.set_register_bits(Register::Control, 0b0001_0100)?;
-    /// ```
+    /// `advance` works as for [`read_chunked`](Self::read_chunked).
+    pub(crate) async fn write_chunked(
+        &mut self,
+        register: u8,
+        data: &[u8],
+        chunk_size: usize,
+        advance: bool,
+    ) -> Result<(), Error<E>> {
+        let mut register = register;
+        let mut frame = [0u8; MAX_BURST + 1];
+        for chunk in data.chunks(chunk_size.clamp(1, MAX_BURST)) {
+            frame[0] = register;
+            frame[1..=chunk.len()].copy_from_slice(chunk);
+            if advance {
+                self.write_raw_bytes(&frame[..=chunk.len()]).await?;
+                register = register.wrapping_add(chunk.len() as u8);
+            } else {
+                // A data port can't be read back to verify what was written
+                self.stats.count_write();
+                self.i2c
+                    .write_registers(self.address, &frame[..=chunk.len()])
+                    .await
+                    .inspect_err(|_| self.stats.count_bus_error())
+                    .map_err(Error::transfer(Operation::Write, register))?;
+                trace!("write {:#04x}: {:02x?}", register, chunk);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Replace the value of `register` with `f(value)`, writing only if it
+    /// changed.
     ///
-    /// # I2C Operations
-    /// - 1 read + 1 write (if change needed)
-    /// - 1 read only (if no change needed)
-    pub(crate) fn set_register_bits(
+    /// Delegates to
+    /// [`RegisterInterface::modify_register`](crate::interface::RegisterInterface::modify_register),
+    /// so transports that can lock the bus make the update atomic. With the
+    /// [control cache](crate::cache) enabled, a cached control register is
+    /// only written. A changed value is read back if
+    /// [write verification](crate::verify) is enabled.
+    pub(crate) async fn modify_register(
         &mut self,
         register: Register,
-        mask: u8,
+        f: impl FnOnce(u8) -> u8,
     ) -> Result<(), Error<E>> {
-        let current = self.read_register(register)?;
-        let new_value = current | mask;
+        let cached = match register {
+            Register::Control => self.control_cache.get(),
+            _ => None,
+        };
+
+        if let Some(current) = cached {
+            let new_value = f(current);
+            if new_value != current {
+                // Forget the value first so a failed write leaves no stale copy
+                self.control_cache.invalidate();
+                self.write_register(register, new_value).await?;
+                self.control_cache.store(new_value);
+            }
+            return Ok(());
+        }
+
+        let (mut current, mut new_value) = (0, 0);
+        self.stats.count_read();
+        let result = self
+            .i2c
+            .modify_register(self.address, register.addr(), |value| {
+                current = value;
+                new_value = f(value);
+                new_value
+            })
+            .await;
+        // Only a successful read yields a changed value to write
+        if new_value != current {
+            self.stats.count_write();
+        }
+        result
+            .inspect_err(|_| self.stats.count_bus_error())
+            .map_err(Error::transfer(Operation::Modify, register.addr()))?;
+        trace!(
+            "modify {:#04x}: {:#04x} -> {:#04x}",
+            register.addr(),
+            current,
+            new_value
+        );
         if new_value != current {
-            self.write_register(register, new_value)
-        } else {
-            Ok(())
+            if register.addr() <= Register::Year.addr() {
+                self.plausibility.reset();
+            }
+            self.verify_written(register.addr(), &[new_value]).await?;
         }
+        if register == Register::Control {
+            self.control_cache.store(new_value);
+        }
+
+        Ok(())
     }
 
-    /// Read-modify-write operation for clearing bits
+    /// Replace the value of `register` with `f(value)`, writing only if it
+    /// changed.
     ///
-    /// Performs a read-modify-write operation to clear the bits specified by the mask
-    /// while preserving all other bits in the register. Only performs a write if
-    /// the register value would actually change, optimizing I2C bus usage.
+    /// For bits the driver has no method for. The update takes the same path
+    /// as the driver's own: it goes through the transport's
+    /// [`modify_register`](crate::interface::RegisterInterface::modify_register),
+    /// keeps the [control cache](crate::cache) coherent and is read back if
+    /// [write verification](crate::verify) is enabled.
     ///
-    /// # Parameters
-    /// - `register`: The DS3231 register to modify
-    /// - `mask`: Bit mask where `1` bits will be cleared, `0` bits will be ignored
+    /// The status flags clear when written as 0, so for
+    /// [`Register::Status`] only a flag that was set and that `f` cleared is
+    /// written as 0; the others are written as 1, which keeps a flag raised
+    /// between the read and the write. Prefer the flag methods such as
+    /// [`clear_alarm_flag`](Self::clear_alarm_flag) for the flags themselves.
     ///
-    /// # Example
-    /// ```ignore
-    /// // Clear the Clock Halt bit (bit 7) in seconds register
-    /// self.clear_register_bits(Register::Seconds, 0b1000_0000)?;
+    /// ```no_run
+    /// # use ds3231_rtc::{Ds3231, error::Error, registers::Register};
+    /// # fn main() -> Result<(), Error<embedded_hal::i2c::ErrorKind>> {
+    /// # let mut rtc = Ds3231::new(embedded_hal_mock::eh1::i2c::Mock::new(&[]));
+    /// // Keep the square wave running on battery (BBSQW, control bit 6)
+    /// rtc.update_register(Register::Control, |control| control | 0x40)?;
+    /// # Ok(())
+    /// # }
     /// ```
-    ///
-    /// # I2C Operations
-    /// - 1 read + 1 write (if change needed)
-    /// - 1 read only (if no change needed)
-    pub(crate) fn clear_register_bits(
+    pub async fn update_register(
         &mut self,
         register: Register,
-        mask: u8,
+        f: impl FnOnce(u8) -> u8,
     ) -> Result<(), Error<E>> {
-        let current = self.read_register(register)?;
-        let new_value = current & !mask;
-        if new_value != current {
-            self.write_register(register, new_value)
-        } else {
-            Ok(())
+        if register != Register::Status {
+            return self.modify_register(register, f).await;
+        }
+        let flags = A1F_BIT | A2F_BIT | OSF_BIT;
+        self.modify_register(register, |status| {
+            let new_value = f(status);
+            if new_value == status {
+                return status;
+            }
+            let cleared = status & flags & !new_value;
+            new_value | (flags & !cleared)
+        })
+        .await
+    }
+
+    /// Restore the datasheet power-on configuration.
+    ///
+    /// Writes the [power-on value](Register::power_on_default) of the control
+    /// register (oscillator on, INT/SQW as interrupt output, alarm interrupts
+    /// off), re-enables the 32 kHz output and clears the aging offset. The
+    /// time, the alarms and the status flags are left untouched; registers
+    /// already at their default are only read.
+    pub async fn reset_to_defaults(&mut self) -> Result<(), Error<E>> {
+        for register in [Register::Control, Register::AgingOffset] {
+            if let Some(default) = register.power_on_default() {
+                self.modify_register(register, |_| default).await?;
+            }
+        }
+        let status = self.read_register(Register::Status).await?;
+        if status & EN32KHZ_BIT == 0 {
+            // Writing 1 leaves the flags alone, even one raised meanwhile
+            self.write_register(
+                Register::Status,
+                status | EN32KHZ_BIT | A1F_BIT | A2F_BIT | OSF_BIT,
+            )
+            .await?;
+        }
+        debug!("reset_to_defaults");
+        Ok(())
+    }
+
+    /// Update the control register through its typed value, writing only
+    /// if it changed.
+    pub(crate) async fn modify_control(
+        &mut self,
+        f: impl FnOnce(&mut ControlRegister),
+    ) -> Result<(), Error<E>> {
+        self.modify_register(Register::Control, |value| {
+            let mut control = ControlRegister::from_bits(value);
+            f(&mut control);
+            control.bits()
+        })
+        .await
+    }
+
+    /// Clear those of the status flags in `flags` (A1F, A2F, OSF) that are
+    /// set, returning the status register as read.
+    ///
+    /// A flag is cleared by writing 0 and left alone by writing 1, so the
+    /// write has 1 in every other flag rather than the value just read: a
+    /// flag the chip raises between the read and the write isn't lost.
+    /// Nothing is written if none of `flags` is set.
+    pub(crate) async fn clear_status_flags(&mut self, flags: u8) -> Result<u8, Error<E>> {
+        let status = self.read_register(Register::Status).await?;
+        let set = status & flags;
+        if set != 0 {
+            self.write_register(
+                Register::Status,
+                (status | A1F_BIT | A2F_BIT | OSF_BIT) & !set,
+            )
+            .await?;
         }
+        Ok(status)
     }
 }
 
@@ -200,6 +630,7 @@ mod tests {
     use super::*;
     use crate::error::Error;
     use crate::registers::Register;
+    use embedded_hal::i2c::{ErrorKind, NoAcknowledgeSource};
     use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
 
     const DS3231_ADDR: u8 = 0x68;
@@ -210,6 +641,7 @@ mod tests {
         let ds3231 = Ds3231::new(i2c_mock);
 
         assert_eq!(ds3231.base_century, DEFAULT_BASE_CENTURY);
+        assert_eq!(ds3231.address(), I2C_ADDR);
 
         let mut i2c_mock = ds3231.release_i2c();
         i2c_mock.done();
@@ -254,6 +686,25 @@ mod tests {
         i2c_mock.done();
     }
 
+    #[test]
+    fn test_with_base_century() {
+        let i2c_mock = I2cMock::new(&[]);
+        let ds3231 = Ds3231::new(i2c_mock).with_base_century(21).unwrap();
+        assert_eq!(ds3231.base_century(), 21);
+
+        let mut i2c_mock = ds3231.release_i2c();
+        i2c_mock.done();
+    }
+
+    #[test]
+    fn test_with_base_century_invalid() {
+        let mut i2c_mock = I2cMock::new(&[]);
+        let result = Ds3231::new(&mut i2c_mock).with_base_century(18);
+        assert!(matches!(result, Err(Error::InvalidBaseCentury)));
+
+        i2c_mock.done();
+    }
+
     #[test]
     fn test_write_register() {
         let expectations = vec![I2cTransaction::write(
@@ -397,7 +848,7 @@ mod tests {
     }
 
     #[test]
-    fn test_set_register_bits_change_needed() {
+    fn test_modify_register_set_bits_change_needed() {
         let expectations = vec![
             I2cTransaction::write_read(
                 DS3231_ADDR,
@@ -410,7 +861,7 @@ mod tests {
         let i2c_mock = I2cMock::new(&expectations);
         let mut ds3231 = Ds3231::new(i2c_mock);
 
-        let result = ds3231.set_register_bits(Register::Control, 0b0001_0000);
+        let result = ds3231.modify_register(Register::Control, |value| value | 0b0001_0000);
         assert!(result.is_ok());
 
         let mut i2c_mock = ds3231.release_i2c();
@@ -418,7 +869,7 @@ mod tests {
     }
 
     #[test]
-    fn test_set_register_bits_no_change_needed() {
+    fn test_modify_register_set_bits_no_change_needed() {
         let expectations = vec![I2cTransaction::write_read(
             DS3231_ADDR,
             vec![Register::Control.addr()],
@@ -428,7 +879,7 @@ mod tests {
         let i2c_mock = I2cMock::new(&expectations);
         let mut ds3231 = Ds3231::new(i2c_mock);
 
-        let result = ds3231.set_register_bits(Register::Control, 0b0001_0000);
+        let result = ds3231.modify_register(Register::Control, |value| value | 0b0001_0000);
         assert!(result.is_ok());
 
         let mut i2c_mock = ds3231.release_i2c();
@@ -436,7 +887,7 @@ mod tests {
     }
 
     #[test]
-    fn test_set_register_bits_multiple_bits() {
+    fn test_modify_register_set_bits_multiple_bits() {
         let expectations = vec![
             I2cTransaction::write_read(
                 DS3231_ADDR,
@@ -449,7 +900,7 @@ mod tests {
         let i2c_mock = I2cMock::new(&expectations);
         let mut ds3231 = Ds3231::new(i2c_mock);
 
-        let result = ds3231.set_register_bits(Register::Control, 0b1010_0101);
+        let result = ds3231.modify_register(Register::Control, |value| value | 0b1010_0101);
         assert!(result.is_ok());
 
         let mut i2c_mock = ds3231.release_i2c();
@@ -457,7 +908,7 @@ mod tests {
     }
 
     #[test]
-    fn test_set_register_bits_read_error() {
+    fn test_modify_register_set_bits_read_error() {
         let expectations = vec![
             I2cTransaction::write_read(DS3231_ADDR, vec![Register::Control.addr()], vec![0x00])
                 .with_error(embedded_hal::i2c::ErrorKind::Other),
@@ -466,7 +917,7 @@ mod tests {
         let i2c_mock = I2cMock::new(&expectations);
         let mut ds3231 = Ds3231::new(i2c_mock);
 
-        let result = ds3231.set_register_bits(Register::Control, 0b0001_0000);
+        let result = ds3231.modify_register(Register::Control, |value| value | 0b0001_0000);
         assert!(result.is_err());
 
         let mut i2c_mock = ds3231.release_i2c();
@@ -474,7 +925,7 @@ mod tests {
     }
 
     #[test]
-    fn test_set_register_bits_write_error() {
+    fn test_modify_register_set_bits_write_error() {
         let expectations = vec![
             I2cTransaction::write_read(
                 DS3231_ADDR,
@@ -488,7 +939,7 @@ mod tests {
         let i2c_mock = I2cMock::new(&expectations);
         let mut ds3231 = Ds3231::new(i2c_mock);
 
-        let result = ds3231.set_register_bits(Register::Control, 0b0001_0000);
+        let result = ds3231.modify_register(Register::Control, |value| value | 0b0001_0000);
         assert!(result.is_err());
 
         let mut i2c_mock = ds3231.release_i2c();
@@ -496,7 +947,7 @@ mod tests {
     }
 
     #[test]
-    fn test_clear_register_bits_change_needed() {
+    fn test_modify_register_clear_bits_change_needed() {
         let expectations = vec![
             I2cTransaction::write_read(
                 DS3231_ADDR,
@@ -509,7 +960,7 @@ mod tests {
         let i2c_mock = I2cMock::new(&expectations);
         let mut ds3231 = Ds3231::new(i2c_mock);
 
-        let result = ds3231.clear_register_bits(Register::Control, 0b0001_0000);
+        let result = ds3231.modify_register(Register::Control, |value| value & !0b0001_0000);
         assert!(result.is_ok());
 
         let mut i2c_mock = ds3231.release_i2c();
@@ -517,7 +968,7 @@ mod tests {
     }
 
     #[test]
-    fn test_clear_register_bits_no_change_needed() {
+    fn test_modify_register_clear_bits_no_change_needed() {
         let expectations = vec![I2cTransaction::write_read(
             DS3231_ADDR,
             vec![Register::Control.addr()],
@@ -527,7 +978,7 @@ mod tests {
         let i2c_mock = I2cMock::new(&expectations);
         let mut ds3231 = Ds3231::new(i2c_mock);
 
-        let result = ds3231.clear_register_bits(Register::Control, 0b0001_0000);
+        let result = ds3231.modify_register(Register::Control, |value| value & !0b0001_0000);
         assert!(result.is_ok());
 
         let mut i2c_mock = ds3231.release_i2c();
@@ -535,7 +986,7 @@ mod tests {
     }
 
     #[test]
-    fn test_clear_register_bits_multiple_bits() {
+    fn test_modify_register_clear_bits_multiple_bits() {
         let expectations = vec![
             I2cTransaction::write_read(
                 DS3231_ADDR,
@@ -548,7 +999,7 @@ mod tests {
         let i2c_mock = I2cMock::new(&expectations);
         let mut ds3231 = Ds3231::new(i2c_mock);
 
-        let result = ds3231.clear_register_bits(Register::Control, 0b1010_0101);
+        let result = ds3231.modify_register(Register::Control, |value| value & !0b1010_0101);
         assert!(result.is_ok());
 
         let mut i2c_mock = ds3231.release_i2c();
@@ -556,7 +1007,7 @@ mod tests {
     }
 
     #[test]
-    fn test_clear_register_bits_read_error() {
+    fn test_modify_register_clear_bits_read_error() {
         let expectations = vec![
             I2cTransaction::write_read(DS3231_ADDR, vec![Register::Control.addr()], vec![0x00])
                 .with_error(embedded_hal::i2c::ErrorKind::Other),
@@ -565,7 +1016,7 @@ mod tests {
         let i2c_mock = I2cMock::new(&expectations);
         let mut ds3231 = Ds3231::new(i2c_mock);
 
-        let result = ds3231.clear_register_bits(Register::Control, 0b0001_0000);
+        let result = ds3231.modify_register(Register::Control, |value| value & !0b0001_0000);
         assert!(result.is_err());
 
         let mut i2c_mock = ds3231.release_i2c();
@@ -573,7 +1024,7 @@ mod tests {
     }
 
     #[test]
-    fn test_clear_register_bits_write_error() {
+    fn test_modify_register_clear_bits_write_error() {
         let expectations = vec![
             I2cTransaction::write_read(
                 DS3231_ADDR,
@@ -587,13 +1038,54 @@ mod tests {
         let i2c_mock = I2cMock::new(&expectations);
         let mut ds3231 = Ds3231::new(i2c_mock);
 
-        let result = ds3231.clear_register_bits(Register::Control, 0b0001_0000);
+        let result = ds3231.modify_register(Register::Control, |value| value & !0b0001_0000);
         assert!(result.is_err());
 
         let mut i2c_mock = ds3231.release_i2c();
         i2c_mock.done();
     }
 
+    #[test]
+    fn test_owned_and_borrowed_bus() {
+        use rtc_hal::{control::RtcPowerControl, rtc::Rtc, square_wave::SquareWave};
+
+        fn start<R: Rtc + RtcPowerControl + SquareWave>(rtc: &mut R) -> Result<(), R::Error> {
+            rtc.start_clock()?;
+            rtc.disable_square_wave()
+        }
+
+        // Both settings already in place: one register read each, no writes
+        let read_control =
+            I2cTransaction::write_read(DS3231_ADDR, vec![Register::Control.addr()], vec![0x04]);
+        let expectations = vec![read_control; 4];
+
+        let mut owned = Ds3231::new(I2cMock::new(&expectations));
+        start(&mut owned).unwrap();
+        let mut i2c = owned.release();
+
+        {
+            let mut borrowed = Ds3231::new(&mut i2c);
+            start(&mut borrowed).unwrap();
+        }
+
+        i2c.done();
+    }
+
+    #[test]
+    fn test_new_with_address() {
+        let expectations = vec![
+            I2cTransaction::write_read(0x57, vec![Register::Status.addr()], vec![0x00]),
+            I2cTransaction::write(0x57, vec![Register::Control.addr(), 0x1C]),
+        ];
+        let mut ds3231 = Ds3231::new_with_address(I2cMock::new(&expectations), 0x57);
+        assert_eq!(ds3231.address(), 0x57);
+
+        ds3231.read_register(Register::Status).unwrap();
+        ds3231.write_register(Register::Control, 0x1C).unwrap();
+
+        ds3231.release().done();
+    }
+
     #[test]
     fn test_constants() {
         assert_eq!(I2C_ADDR, 0x68);
@@ -601,7 +1093,7 @@ mod tests {
     }
 
     #[test]
-    fn test_set_register_bits_preserves_other_bits() {
+    fn test_modify_register_set_bits_preserves_other_bits() {
         let expectations = vec![
             I2cTransaction::write_read(
                 DS3231_ADDR,
@@ -614,7 +1106,7 @@ mod tests {
         let i2c_mock = I2cMock::new(&expectations);
         let mut ds3231 = Ds3231::new(i2c_mock);
 
-        let result = ds3231.set_register_bits(Register::Control, 0b0001_0000);
+        let result = ds3231.modify_register(Register::Control, |value| value | 0b0001_0000);
         assert!(result.is_ok());
 
         let mut i2c_mock = ds3231.release_i2c();
@@ -622,7 +1114,7 @@ mod tests {
     }
 
     #[test]
-    fn test_clear_register_bits_preserves_other_bits() {
+    fn test_modify_register_clear_bits_preserves_other_bits() {
         let expectations = vec![
             I2cTransaction::write_read(
                 DS3231_ADDR,
@@ -635,10 +1127,137 @@ mod tests {
         let i2c_mock = I2cMock::new(&expectations);
         let mut ds3231 = Ds3231::new(i2c_mock);
 
-        let result = ds3231.clear_register_bits(Register::Control, 0b0001_0000);
+        let result = ds3231.modify_register(Register::Control, |value| value & !0b0001_0000);
         assert!(result.is_ok());
 
         let mut i2c_mock = ds3231.release_i2c();
         i2c_mock.done();
     }
+
+    #[test]
+    fn test_update_register() {
+        let expectations = [
+            I2cTransaction::write_read(DS3231_ADDR, vec![Register::Control.addr()], vec![0x1C]),
+            I2cTransaction::write(DS3231_ADDR, vec![Register::Control.addr(), 0x5C]),
+            I2cTransaction::write_read(DS3231_ADDR, vec![Register::Control.addr()], vec![0x5C]),
+        ];
+        let mut ds3231 = Ds3231::new(I2cMock::new(&expectations));
+
+        ds3231
+            .update_register(Register::Control, |control| control | 0x40)
+            .unwrap();
+        // Unchanged value: read only
+        ds3231
+            .update_register(Register::Control, |control| control | 0x40)
+            .unwrap();
+
+        ds3231.release_i2c().done();
+    }
+
+    #[test]
+    fn test_update_status_keeps_flags_raised_meanwhile() {
+        let expectations = [
+            // Setting EN32KHZ writes the clear flags as 1, so an alarm
+            // firing before the write keeps its flag
+            I2cTransaction::write_read(DS3231_ADDR, vec![Register::Status.addr()], vec![0x00]),
+            I2cTransaction::write(DS3231_ADDR, vec![Register::Status.addr(), 0x8B]),
+            // Clearing OSF clears only OSF
+            I2cTransaction::write_read(DS3231_ADDR, vec![Register::Status.addr()], vec![0x89]),
+            I2cTransaction::write(DS3231_ADDR, vec![Register::Status.addr(), 0x0B]),
+            // Clearing a flag that isn't set doesn't clear it if raised
+            I2cTransaction::write_read(DS3231_ADDR, vec![Register::Status.addr()], vec![0x08]),
+            I2cTransaction::write(DS3231_ADDR, vec![Register::Status.addr(), 0x83]),
+        ];
+        let mut ds3231 = Ds3231::new(I2cMock::new(&expectations));
+
+        ds3231
+            .update_register(Register::Status, |status| status | EN32KHZ_BIT)
+            .unwrap();
+        ds3231
+            .update_register(Register::Status, |status| status & !OSF_BIT)
+            .unwrap();
+        ds3231
+            .update_register(Register::Status, |status| status & !(A1F_BIT | EN32KHZ_BIT))
+            .unwrap();
+
+        ds3231.release_i2c().done();
+    }
+
+    #[test]
+    fn test_new_checked() {
+        let expectations = [I2cTransaction::write_read(
+            DS3231_ADDR,
+            vec![Register::Seconds.addr()],
+            vec![0x00],
+        )];
+        let ds3231 = Ds3231::new_checked(I2cMock::new(&expectations)).unwrap();
+        ds3231.release_i2c().done();
+
+        let expectations =
+            [
+                I2cTransaction::write_read(DS3231_ADDR, vec![Register::Seconds.addr()], vec![0x00])
+                    .with_error(ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address)),
+            ];
+        let mut i2c = I2cMock::new(&expectations);
+        assert!(matches!(
+            Ds3231::new_checked(&mut i2c),
+            Err(Error::DeviceNotFound {
+                source: ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address)
+            })
+        ));
+        i2c.done();
+
+        // Anything but a missing acknowledge is a bus fault, not a
+        // missing device
+        let expectations =
+            [
+                I2cTransaction::write_read(DS3231_ADDR, vec![Register::Seconds.addr()], vec![0x00])
+                    .with_error(ErrorKind::ArbitrationLoss),
+            ];
+        let mut i2c = I2cMock::new(&expectations);
+        assert!(matches!(
+            Ds3231::new_checked(&mut i2c),
+            Err(Error::Transfer {
+                source: ErrorKind::ArbitrationLoss,
+                ..
+            })
+        ));
+        i2c.done();
+    }
+
+    #[test]
+    fn test_reset_to_defaults() {
+        let expectations = [
+            I2cTransaction::write_read(DS3231_ADDR, vec![Register::Control.addr()], vec![0x07]),
+            I2cTransaction::write(DS3231_ADDR, vec![Register::Control.addr(), 0x1C]),
+            I2cTransaction::write_read(DS3231_ADDR, vec![Register::AgingOffset.addr()], vec![0x00]),
+            I2cTransaction::write_read(DS3231_ADDR, vec![Register::Status.addr()], vec![0x81]),
+            I2cTransaction::write(DS3231_ADDR, vec![Register::Status.addr(), 0x8B]),
+        ];
+        let mut ds3231 = Ds3231::new(I2cMock::new(&expectations));
+
+        ds3231.reset_to_defaults().unwrap();
+
+        ds3231.release_i2c().done();
+    }
+
+    #[test]
+    fn test_debug_shows_config_and_cache() {
+        let expectations = [
+            I2cTransaction::write_read(DS3231_ADDR, vec![Register::Control.addr()], vec![0x1C]),
+            I2cTransaction::write(DS3231_ADDR, vec![Register::Control.addr(), 0x18]),
+        ];
+        let mut ds3231 = Ds3231::new(I2cMock::new(&expectations));
+        assert_eq!(
+            format!("{ds3231:?}"),
+            "Ds3231 { address: 0x68, variant: Ds3231, base_century: 20, \
+             hour_mode: TwentyFourHour, control_cache: None, .. }"
+        );
+
+        ds3231.set_control_cache(true);
+        ds3231.enable_square_wave().unwrap();
+        assert!(format!("{ds3231:?}").contains("control_cache: Some(ControlFlags(RS2 | RS1))"));
+
+        ds3231.release_i2c().done();
+    }
 }
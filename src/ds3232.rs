@@ -0,0 +1,434 @@
+//! # DS3232 Support
+//!
+//! The DS3232 is a DS3231 with 236 bytes of battery-backed SRAM at register
+//! addresses 0x14-0xFF. Its timekeeping, control and status registers are
+//! identical, so [`Ds3232`] wraps a [`Ds3231`] driver and dereferences to it
+//! for everything except the SRAM.
+//!
+//! The SRAM is also available through `rtc-hal`'s [`RtcNvram`] trait, so
+//! code written for other RTCs with NVRAM (e.g. a DS1307) works unchanged.
+//!
+//! SRAM is addressed by offset from its start (0-235). Transfers that would
+//! run past the end are rejected with [`Error::NvramOutOfBounds`] before
+//! anything is sent on the bus, so they can never wrap around into the
+//! timekeeping registers.
+//!
+//! Many I2C peripherals limit how many bytes one transaction can carry, so
+//! longer transfers are split into bursts of
+//! [`sram_chunk_size`](Ds3232::sram_chunk_size) bytes (16 by default, at
+//! most 32). A bus error part way through leaves the earlier bursts written.
+//!
+//! The same SRAM methods also exist on `Ds3231<I2C, Ds3232Variant>`, for
+//! code that selects the variant through the driver's type parameter (see
+//! [`variant`](crate::variant)); they always use the default burst size.
+//!
+//! ```no_run
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! # let i2c = embedded_hal_mock::eh1::i2c::Mock::new(&[]);
+//! use ds3231_rtc::ds3232::Ds3232;
+//!
+//! let mut rtc = Ds3232::new(i2c);
+//! rtc.write_sram(0, &[0xCA, 0xFE])?;
+//! let now = rtc.get_datetime()?; // shared with the DS3231 driver
+//! # Ok(())
+//! # }
+//! ```
+
+use core::ops::{Deref, DerefMut};
+
+use rtc_hal::datetime::DateTime;
+use rtc_hal::error::ErrorType;
+use rtc_hal::nvram::RtcNvram;
+use rtc_hal::rtc::Rtc;
+
+#[cfg(feature = "async")]
+use crate::ds3231::Ds3231Async;
+#[cfg(feature = "async")]
+use crate::interface::AsyncRegisterInterface;
+use crate::{
+    Ds3231,
+    error::Error,
+    interface::RegisterInterface,
+    variant::{Ds3232Variant, Variant},
+};
+
+/// First SRAM register address
+pub const SRAM_START: u8 = 0x14;
+
+/// SRAM size in bytes (registers 0x14-0xFF)
+pub const SRAM_SIZE: usize = 236;
+
+/// Default number of SRAM bytes moved per bus transaction
+pub const DEFAULT_SRAM_CHUNK_SIZE: usize = 16;
+
+/// Largest supported number of SRAM bytes per bus transaction
+pub const MAX_SRAM_CHUNK_SIZE: usize = crate::ds3231::MAX_BURST;
+
+/// Variant set by the DS3232 driver, named here because the `maybe` blocks
+/// rename every `Ds3232` identifier
+const VARIANT: Variant = Variant::Ds3232;
+
+/// DS3232 Real-Time Clock driver with SRAM access
+#[maybe_async_cfg::maybe(
+    idents(
+        Ds3231(sync = "Ds3231", async = "Ds3231Async"),
+        Ds3232(sync = "Ds3232", async = "Ds3232Async")
+    ),
+    sync(keep_self),
+    async(feature = "async")
+)]
+pub struct Ds3232<I2C> {
+    rtc: Ds3231<I2C>,
+    chunk_size: usize,
+}
+
+#[maybe_async_cfg::maybe(
+    idents(
+        AsyncRegisterInterface(sync = "RegisterInterface", async = "AsyncRegisterInterface"),
+        Ds3231(sync = "Ds3231", async = "Ds3231Async"),
+        Ds3232(sync = "Ds3232", async = "Ds3232Async")
+    ),
+    sync(keep_self),
+    async(feature = "async")
+)]
+impl<I2C> Ds3232<I2C>
+where
+    I2C: AsyncRegisterInterface,
+{
+    /// Create a new DS3232 driver instance
+    pub fn new(i2c: I2C) -> Self {
+        Self::from_ds3231(Ds3231::new(i2c))
+    }
+
+    /// Wrap an already configured DS3231 driver.
+    ///
+    /// The driver's variant becomes [`Variant::Ds3232`].
+    pub fn from_ds3231(mut rtc: Ds3231<I2C>) -> Self {
+        rtc.variant = VARIANT;
+        Self {
+            rtc,
+            chunk_size: DEFAULT_SRAM_CHUNK_SIZE,
+        }
+    }
+
+    /// Number of SRAM bytes moved per bus transaction.
+    pub fn sram_chunk_size(&self) -> usize {
+        self.chunk_size
+    }
+
+    /// Set the number of SRAM bytes moved per bus transaction, clamped to
+    /// 1-[`MAX_SRAM_CHUNK_SIZE`]. Match it to the HAL's transfer limit,
+    /// leaving one byte for the register address on writes.
+    pub fn set_sram_chunk_size(&mut self, chunk_size: usize) {
+        self.chunk_size = chunk_size.clamp(1, MAX_SRAM_CHUNK_SIZE);
+    }
+
+    /// Returns the underlying DS3231 driver, consuming the wrapper.
+    pub fn into_inner(self) -> Ds3231<I2C> {
+        self.rtc
+    }
+
+    /// Returns the underlying I2C bus, consuming the driver.
+    pub fn release(self) -> I2C {
+        self.rtc.release()
+    }
+
+    /// Read `buffer.len()` bytes of SRAM starting at `offset`.
+    ///
+    /// Returns `Err(Error::NvramOutOfBounds)` if `offset` is past the end of
+    /// the SRAM or the range would extend beyond it. An empty `buffer` at a
+    /// valid offset does not touch the bus.
+    pub async fn read_sram(
+        &mut self,
+        offset: u8,
+        buffer: &mut [u8],
+    ) -> Result<(), Error<I2C::Error>> {
+        let register = sram_register(offset, buffer.len())?;
+        self.rtc
+            .read_chunked(register, buffer, self.chunk_size, true)
+            .await
+    }
+
+    /// Write `data` to SRAM starting at `offset`.
+    ///
+    /// Returns `Err(Error::NvramOutOfBounds)` if `offset` is past the end of
+    /// the SRAM or the range would extend beyond it.
+    pub async fn write_sram(&mut self, offset: u8, data: &[u8]) -> Result<(), Error<I2C::Error>> {
+        let register = sram_register(offset, data.len())?;
+        self.rtc
+            .write_chunked(register, data, self.chunk_size, true)
+            .await
+    }
+}
+
+#[maybe_async_cfg::maybe(
+    idents(
+        AsyncRegisterInterface(sync = "RegisterInterface", async = "AsyncRegisterInterface"),
+        Ds3231(sync = "Ds3231", async = "Ds3231Async"),
+        Ds3232(sync = "Ds3232", async = "Ds3232Async")
+    ),
+    sync(keep_self),
+    async(feature = "async")
+)]
+impl<I2C> Ds3231<I2C>
+where
+    I2C: AsyncRegisterInterface,
+{
+    /// Switch to the [`Ds3232`] driver for SRAM access if the configured
+    /// [`Variant`] has SRAM, otherwise hand the driver back unchanged.
+    pub fn into_ds3232(self) -> Result<Ds3232<I2C>, Self> {
+        if self.variant.sram_size() == 0 {
+            return Err(self);
+        }
+        Ok(Ds3232::from_ds3231(self))
+    }
+}
+
+#[maybe_async_cfg::maybe(
+    idents(
+        AsyncRegisterInterface(sync = "RegisterInterface", async = "AsyncRegisterInterface"),
+        Ds3231(sync = "Ds3231", async = "Ds3231Async")
+    ),
+    sync(keep_self),
+    async(feature = "async")
+)]
+impl<I2C> Ds3231<I2C, Ds3232Variant>
+where
+    I2C: AsyncRegisterInterface,
+{
+    /// Read `buffer.len()` bytes of SRAM starting at `offset`, in bursts of
+    /// [`DEFAULT_SRAM_CHUNK_SIZE`] bytes.
+    ///
+    /// Same checks as [`Ds3232::read_sram`]; use [`Ds3232`] where the burst
+    /// size must be configurable.
+    pub async fn read_sram(
+        &mut self,
+        offset: u8,
+        buffer: &mut [u8],
+    ) -> Result<(), Error<I2C::Error>> {
+        let register = sram_register(offset, buffer.len())?;
+        self.read_chunked(register, buffer, DEFAULT_SRAM_CHUNK_SIZE, true)
+            .await
+    }
+
+    /// Write `data` to SRAM starting at `offset`, in bursts of
+    /// [`DEFAULT_SRAM_CHUNK_SIZE`] bytes.
+    ///
+    /// Same checks as [`Ds3232::write_sram`].
+    pub async fn write_sram(&mut self, offset: u8, data: &[u8]) -> Result<(), Error<I2C::Error>> {
+        let register = sram_register(offset, data.len())?;
+        self.write_chunked(register, data, DEFAULT_SRAM_CHUNK_SIZE, true)
+            .await
+    }
+}
+
+/// Register address of SRAM `offset`, checking that `len` bytes fit.
+fn sram_register<E: core::fmt::Debug>(offset: u8, len: usize) -> Result<u8, Error<E>> {
+    if offset as usize >= SRAM_SIZE || offset as usize + len > SRAM_SIZE {
+        return Err(Error::NvramOutOfBounds);
+    }
+    Ok(SRAM_START + offset)
+}
+
+#[maybe_async_cfg::maybe(
+    idents(
+        Ds3231(sync = "Ds3231", async = "Ds3231Async"),
+        Ds3232(sync = "Ds3232", async = "Ds3232Async")
+    ),
+    sync(keep_self),
+    async(feature = "async")
+)]
+impl<I2C> Deref for Ds3232<I2C> {
+    type Target = Ds3231<I2C>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.rtc
+    }
+}
+
+#[maybe_async_cfg::maybe(
+    idents(Ds3232(sync = "Ds3232", async = "Ds3232Async")),
+    sync(keep_self),
+    async(feature = "async")
+)]
+impl<I2C> DerefMut for Ds3232<I2C> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.rtc
+    }
+}
+
+impl<I2C: RegisterInterface> ErrorType for Ds3232<I2C> {
+    type Error = Error<I2C::Error>;
+}
+
+impl<I2C: RegisterInterface> Rtc for Ds3232<I2C> {
+    fn get_datetime(&mut self) -> Result<DateTime, Self::Error> {
+        self.rtc.get_datetime()
+    }
+
+    fn set_datetime(&mut self, datetime: &DateTime) -> Result<(), Self::Error> {
+        self.rtc.set_datetime(datetime)
+    }
+}
+
+impl<I2C: RegisterInterface> RtcNvram for Ds3232<I2C> {
+    fn read_nvram(&mut self, offset: u8, buffer: &mut [u8]) -> Result<(), Self::Error> {
+        self.read_sram(offset, buffer)
+    }
+
+    fn write_nvram(&mut self, offset: u8, data: &[u8]) -> Result<(), Self::Error> {
+        self.write_sram(offset, data)
+    }
+
+    fn nvram_size(&self) -> u16 {
+        SRAM_SIZE as u16
+    }
+}
+
+impl<I2C: RegisterInterface> RtcNvram for Ds3231<I2C, Ds3232Variant> {
+    fn read_nvram(&mut self, offset: u8, buffer: &mut [u8]) -> Result<(), Self::Error> {
+        self.read_sram(offset, buffer)
+    }
+
+    fn write_nvram(&mut self, offset: u8, data: &[u8]) -> Result<(), Self::Error> {
+        self.write_sram(offset, data)
+    }
+
+    fn nvram_size(&self) -> u16 {
+        SRAM_SIZE as u16
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTrans};
+
+    #[test]
+    fn test_sram_read_write() {
+        let expectations = [
+            I2cTrans::write(0x68, vec![0x14, 0xCA, 0xFE]),
+            I2cTrans::write_read(0x68, vec![0xFE], vec![0x42, 0x43]),
+        ];
+        let mut ds3232 = Ds3232::new(I2cMock::new(&expectations));
+
+        ds3232.write_sram(0, &[0xCA, 0xFE]).unwrap();
+        let mut buf = [0u8; 2];
+        ds3232.read_sram(234, &mut buf).unwrap();
+        assert_eq!(buf, [0x42, 0x43]);
+
+        ds3232.release().done();
+    }
+
+    #[test]
+    fn test_sram_bounds_checked_before_bus_access() {
+        let mut ds3232 = Ds3232::new(I2cMock::new(&[]));
+
+        let mut buf = [0u8; 2];
+        assert_eq!(
+            ds3232.read_sram(235, &mut buf),
+            Err(Error::NvramOutOfBounds)
+        );
+        assert_eq!(
+            ds3232.write_sram(0, &[0u8; SRAM_SIZE + 1]),
+            Err(Error::NvramOutOfBounds)
+        );
+        assert_eq!(
+            ds3232.read_sram(u8::MAX, &mut []),
+            Err(Error::NvramOutOfBounds)
+        );
+
+        ds3232.into_inner().release_i2c().done();
+    }
+
+    #[test]
+    fn test_into_ds3232_requires_sram() {
+        let ds3231 = Ds3231::new_with_variant(I2cMock::new(&[]), Variant::Ds3231M);
+        let ds3231 = ds3231.into_ds3232().err().unwrap();
+
+        let ds3232 = Ds3231::new_with_variant(ds3231.release_i2c(), Variant::Ds3232)
+            .into_ds3232()
+            .ok()
+            .unwrap();
+        assert_eq!(ds3232.variant(), Variant::Ds3232);
+
+        ds3232.into_inner().release_i2c().done();
+    }
+
+    #[test]
+    fn test_typed_ds3232_sram() {
+        let expectations = [
+            I2cTrans::write(0x68, vec![0xFF, 0x5A]),
+            I2cTrans::write_read(0x68, vec![0x14], vec![0x42]),
+        ];
+        let mut rtc: Ds3231<_, Ds3232Variant> = Ds3231::new_typed(I2cMock::new(&expectations));
+        assert_eq!(rtc.variant(), Variant::Ds3232);
+
+        RtcNvram::write_nvram(&mut rtc, 235, &[0x5A]).unwrap();
+        let mut buf = [0u8];
+        rtc.read_sram(0, &mut buf).unwrap();
+        assert_eq!(buf, [0x42]);
+        assert_eq!(rtc.write_sram(235, &[0, 0]), Err(Error::NvramOutOfBounds));
+
+        rtc.release_i2c().done();
+    }
+
+    #[test]
+    fn test_timekeeping_is_shared() {
+        let expectations = [I2cTrans::write_read(
+            0x68,
+            vec![0x00],
+            vec![0x00, 0x30, 0x14, 0x05, 0x21, 0x08, 0x25],
+        )];
+        let mut ds3232 = Ds3232::new(I2cMock::new(&expectations));
+
+        assert_eq!(
+            Rtc::get_datetime(&mut ds3232).unwrap(),
+            DateTime::new(2025, 8, 21, 14, 30, 0).unwrap()
+        );
+
+        ds3232.into_inner().release_i2c().done();
+    }
+
+    #[test]
+    fn test_rtc_nvram_trait() {
+        fn stash<R: RtcNvram>(rtc: &mut R) -> Result<(), R::Error> {
+            let last = rtc.nvram_size() as u8 - 1;
+            rtc.write_nvram(last, &[0x5A])
+        }
+
+        let expectations = [I2cTrans::write(0x68, vec![0xFF, 0x5A])];
+        let mut ds3232 = Ds3232::new(I2cMock::new(&expectations));
+
+        stash(&mut ds3232).unwrap();
+        assert_eq!(
+            RtcNvram::read_nvram(&mut ds3232, 236, &mut [0u8]),
+            Err(Error::NvramOutOfBounds)
+        );
+
+        ds3232.into_inner().release_i2c().done();
+    }
+
+    #[test]
+    fn test_sram_transfers_are_chunked() {
+        let data: Vec<u8> = (0..40).collect();
+        let expectations = [
+            I2cTrans::write(0x68, [&[0x14][..], &data[..16]].concat()),
+            I2cTrans::write(0x68, [&[0x24][..], &data[16..32]].concat()),
+            I2cTrans::write(0x68, [&[0x34][..], &data[32..]].concat()),
+            I2cTrans::write_read(0x68, vec![0x14], data[..32].to_vec()),
+            I2cTrans::write_read(0x68, vec![0x34], data[32..].to_vec()),
+        ];
+        let mut ds3232 = Ds3232::new(I2cMock::new(&expectations));
+
+        ds3232.write_sram(0, &data).unwrap();
+
+        ds3232.set_sram_chunk_size(100);
+        assert_eq!(ds3232.sram_chunk_size(), MAX_SRAM_CHUNK_SIZE);
+        let mut buf = [0u8; 40];
+        ds3232.read_sram(0, &mut buf).unwrap();
+        assert_eq!(&buf[..], &data[..]);
+
+        ds3232.into_inner().release_i2c().done();
+    }
+}
@@ -0,0 +1,301 @@
+//! # DS3234 SPI Support
+//!
+//! The DS3234 is the SPI member of the family: its timekeeping, control and
+//! status registers have the same addresses and layout as the DS3231's.
+//! [`SpiInterface`] carries the driver's register transfers over an
+//! [`SpiDevice`], so every [`Ds3231`] method works unchanged on a DS3234.
+//!
+//! ```no_run
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! use ds3231_rtc::ds3234::Ds3234;
+//! # let spi = embedded_hal_mock::eh1::spi::Mock::new(&[]);
+//!
+//! // `spi` is an SpiDevice configured for SPI mode 1 or 3, up to 4 MHz
+//! let mut rtc = Ds3234::new_spi(spi);
+//! let now = rtc.get_datetime()?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! On the wire each transfer is a single chip-select frame starting with the
+//! register address: bit 7 clear for a read, set for a write, followed by the
+//! data bytes. The address auto-increments just as it does over I2C.
+//!
+//! The DS3234's 256 bytes of battery-backed SRAM sit behind an address/data
+//! register pair rather than in the register map; [`read_sram`](Ds3231::read_sram)
+//! and [`write_sram`](Ds3231::write_sram) (and `rtc-hal`'s [`RtcNvram`])
+//! handle that, splitting long transfers into bursts of at most 32 bytes.
+
+use embedded_hal::spi::{Operation, SpiDevice};
+#[cfg(feature = "async")]
+use embedded_hal_async::spi::SpiDevice as AsyncSpiDevice;
+
+use rtc_hal::nvram::RtcNvram;
+
+#[cfg(feature = "async")]
+use crate::ds3231::Ds3231Async;
+#[cfg(feature = "async")]
+use crate::interface::AsyncRegisterInterface;
+use crate::{Ds3231, ds3231::MAX_BURST, error::Error, interface::RegisterInterface};
+
+/// Address bit 7 selects a write on the DS3234
+const WRITE_BIT: u8 = 1 << 7;
+
+/// SRAM address register; holds the SRAM offset for the next data access
+const SRAM_ADDRESS: u8 = 0x18;
+
+/// SRAM data port; each access moves one byte and advances the SRAM address
+const SRAM_DATA: u8 = 0x19;
+
+/// DS3234 SRAM size in bytes
+pub const SRAM_SIZE: usize = 256;
+
+/// DS3234 driver: the [`Ds3231`] driver on an SPI bus
+pub type Ds3234<SPI> = Ds3231<SpiInterface<SPI>>;
+
+/// Register transport over an [`SpiDevice`]
+///
+/// Also implements the async register interface for `embedded-hal-async`
+/// SPI devices, for use with [`asynch::Ds3231`](crate::asynch::Ds3231).
+#[derive(Debug)]
+pub struct SpiInterface<SPI> {
+    spi: SPI,
+}
+
+impl<SPI> SpiInterface<SPI> {
+    /// Wrap an SPI device.
+    pub fn new(spi: SPI) -> Self {
+        Self { spi }
+    }
+
+    /// Returns the wrapped SPI device, consuming the adapter.
+    pub fn into_inner(self) -> SPI {
+        self.spi
+    }
+}
+
+#[maybe_async_cfg::maybe(
+    idents(
+        AsyncSpiDevice(sync = "SpiDevice", async = "AsyncSpiDevice"),
+        AsyncRegisterInterface(sync = "RegisterInterface", async = "AsyncRegisterInterface")
+    ),
+    sync(keep_self),
+    async(feature = "async", keep_self)
+)]
+impl<SPI: AsyncSpiDevice> AsyncRegisterInterface for SpiInterface<SPI> {
+    type Error = SPI::Error;
+
+    /// Read registers in one chip-select frame; the bus address is ignored.
+    async fn read_registers(
+        &mut self,
+        _address: u8,
+        register: u8,
+        buffer: &mut [u8],
+    ) -> Result<(), Self::Error> {
+        self.spi
+            .transaction(&mut [
+                Operation::Write(&[register & !WRITE_BIT]),
+                Operation::Read(buffer),
+            ])
+            .await
+    }
+
+    /// Write registers in one chip-select frame; the bus address is ignored.
+    ///
+    /// An empty `data` writes nothing.
+    async fn write_registers(&mut self, _address: u8, data: &[u8]) -> Result<(), Self::Error> {
+        let Some((&register, values)) = data.split_first() else {
+            return Ok(());
+        };
+        self.spi
+            .transaction(&mut [
+                Operation::Write(&[register | WRITE_BIT]),
+                Operation::Write(values),
+            ])
+            .await
+    }
+}
+
+#[maybe_async_cfg::maybe(
+    idents(
+        AsyncSpiDevice(sync = "SpiDevice", async = "AsyncSpiDevice"),
+        Ds3231(sync = "Ds3231", async = "Ds3231Async")
+    ),
+    sync(keep_self),
+    async(feature = "async")
+)]
+impl<SPI: AsyncSpiDevice> Ds3231<SpiInterface<SPI>> {
+    /// Create a DS3234 driver on an SPI device.
+    ///
+    /// Shorthand for `Ds3231::new(SpiInterface::new(spi))`.
+    pub fn new_spi(spi: SPI) -> Self {
+        Ds3231::new(SpiInterface::new(spi))
+    }
+
+    /// Returns the underlying SPI device, consuming the driver.
+    ///
+    /// Shorthand for `release().into_inner()`.
+    pub fn release_spi(self) -> SPI {
+        self.release().into_inner()
+    }
+
+    /// Read `buffer.len()` bytes of SRAM starting at `offset`.
+    ///
+    /// Returns `Err(Error::NvramOutOfBounds)` if the range would extend past
+    /// the end of the SRAM. An empty `buffer` does not touch the bus.
+    pub async fn read_sram(
+        &mut self,
+        offset: u8,
+        buffer: &mut [u8],
+    ) -> Result<(), Error<SPI::Error>> {
+        check_sram_range(offset, buffer.len())?;
+        if buffer.is_empty() {
+            return Ok(());
+        }
+        self.write_register_at(SRAM_ADDRESS, offset).await?;
+        self.read_chunked(SRAM_DATA, buffer, MAX_BURST, false).await
+    }
+
+    /// Write `data` to SRAM starting at `offset`.
+    ///
+    /// Returns `Err(Error::NvramOutOfBounds)` if the range would extend past
+    /// the end of the SRAM.
+    pub async fn write_sram(&mut self, offset: u8, data: &[u8]) -> Result<(), Error<SPI::Error>> {
+        check_sram_range(offset, data.len())?;
+        if data.is_empty() {
+            return Ok(());
+        }
+        self.write_register_at(SRAM_ADDRESS, offset).await?;
+        self.write_chunked(SRAM_DATA, data, MAX_BURST, false).await
+    }
+
+    /// Write one register given by its raw address.
+    async fn write_register_at(
+        &mut self,
+        register: u8,
+        value: u8,
+    ) -> Result<(), Error<SPI::Error>> {
+        self.write_raw_bytes(&[register, value]).await
+    }
+}
+
+/// Check that `len` bytes starting at `offset` fit in the SRAM.
+fn check_sram_range<E: core::fmt::Debug>(offset: u8, len: usize) -> Result<(), Error<E>> {
+    if offset as usize + len > SRAM_SIZE {
+        return Err(Error::NvramOutOfBounds);
+    }
+    Ok(())
+}
+
+impl<SPI: SpiDevice> RtcNvram for Ds3231<SpiInterface<SPI>> {
+    fn read_nvram(&mut self, offset: u8, buffer: &mut [u8]) -> Result<(), Self::Error> {
+        self.read_sram(offset, buffer)
+    }
+
+    fn write_nvram(&mut self, offset: u8, data: &[u8]) -> Result<(), Self::Error> {
+        self.write_sram(offset, data)
+    }
+
+    fn nvram_size(&self) -> u16 {
+        SRAM_SIZE as u16
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registers::{EOSC_BIT, Register};
+    use embedded_hal_mock::eh1::spi::{Mock as SpiMock, Transaction as SpiTrans};
+    use rtc_hal::datetime::DateTime;
+
+    fn read(register: u8, data: Vec<u8>) -> [SpiTrans<u8>; 4] {
+        [
+            SpiTrans::transaction_start(),
+            SpiTrans::write_vec(vec![register]),
+            SpiTrans::read_vec(data),
+            SpiTrans::transaction_end(),
+        ]
+    }
+
+    fn write(register: u8, data: Vec<u8>) -> [SpiTrans<u8>; 4] {
+        [
+            SpiTrans::transaction_start(),
+            SpiTrans::write_vec(vec![register]),
+            SpiTrans::write_vec(data),
+            SpiTrans::transaction_end(),
+        ]
+    }
+
+    #[test]
+    fn test_datetime_over_spi() {
+        let data = vec![0x00, 0x30, 0x14, 0x05, 0x21, 0x08, 0x25];
+        let mut expectations = Vec::new();
+        expectations.extend(read(0x00, data.clone()));
+        expectations.extend(write(0x80, data));
+        let mut ds3234 = Ds3234::new_spi(SpiMock::new(&expectations));
+
+        let dt = ds3234.get_datetime().unwrap();
+        assert_eq!(dt, DateTime::new(2025, 8, 21, 14, 30, 0).unwrap());
+        ds3234.set_datetime(&dt).unwrap();
+
+        ds3234.release_spi().done();
+    }
+
+    #[test]
+    fn test_register_update_over_spi() {
+        let mut expectations = Vec::new();
+        expectations.extend(read(Register::Control.addr(), vec![0x1C]));
+        expectations.extend(write(0x8E, vec![0x1C | EOSC_BIT]));
+        let mut ds3234 = Ds3234::new_spi(SpiMock::new(&expectations));
+
+        ds3234.halt_clock().unwrap();
+
+        ds3234.release_i2c().into_inner().done();
+    }
+
+    #[test]
+    fn test_empty_write_is_skipped() {
+        let mut spi = SpiInterface::new(SpiMock::new(&[]));
+
+        RegisterInterface::write_registers(&mut spi, 0x68, &[]).unwrap();
+
+        spi.into_inner().done();
+    }
+
+    #[test]
+    fn test_sram_uses_data_port() {
+        let data: Vec<u8> = (0..40).collect();
+        let mut expectations = Vec::new();
+        expectations.extend(write(0x98, vec![0x10]));
+        expectations.extend(write(0x99, data[..32].to_vec()));
+        expectations.extend(write(0x99, data[32..].to_vec()));
+        expectations.extend(write(0x98, vec![0x10]));
+        expectations.extend(read(0x19, data[..32].to_vec()));
+        expectations.extend(read(0x19, data[32..].to_vec()));
+        let mut ds3234 = Ds3234::new_spi(SpiMock::new(&expectations));
+
+        ds3234.write_sram(0x10, &data).unwrap();
+        let mut buf = [0u8; 40];
+        ds3234.read_sram(0x10, &mut buf).unwrap();
+        assert_eq!(&buf[..], &data[..]);
+
+        ds3234.release_i2c().into_inner().done();
+    }
+
+    #[test]
+    fn test_sram_bounds() {
+        let mut expectations = Vec::new();
+        expectations.extend(write(0x98, vec![0xFF]));
+        expectations.extend(write(0x99, vec![0xAA]));
+        let mut ds3234 = Ds3234::new_spi(SpiMock::new(&expectations));
+
+        assert_eq!(
+            ds3234.write_sram(0xF0, &[0u8; 17]),
+            Err(Error::NvramOutOfBounds)
+        );
+        ds3234.read_sram(0xFF, &mut []).unwrap();
+        RtcNvram::write_nvram(&mut ds3234, 0xFF, &[0xAA]).unwrap();
+
+        ds3234.release_i2c().into_inner().done();
+    }
+}
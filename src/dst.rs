@@ -0,0 +1,298 @@
+//! # Daylight Saving Time Rules
+//!
+//! A small DST engine (enabled with the `dst` feature) for products that must
+//! show correct local time without a network connection. A [`DstRule`]
+//! describes when daylight saving time starts and ends each year, e.g.
+//! "last Sunday of March at 01:00 UTC, +1h", and can be attached to a
+//! [`ZonedRtc`](crate::zoned::ZonedRtc) with
+//! [`set_dst_rule`](crate::zoned::ZonedRtc::set_dst_rule).
+//!
+//! Transition times follow either wall-clock semantics (as in POSIX `TZ`
+//! strings and US rules: the start time is in standard time, the end time in
+//! daylight time) or are fixed in UTC (as in the European Union).
+//!
+//! ```no_run
+//! # use ds3231_rtc::Ds3231;
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! # let i2c = embedded_hal_mock::eh1::i2c::Mock::new(&[]);
+//! use ds3231_rtc::{dst::DstRule, zoned::ZonedRtc};
+//! use rtc_hal::rtc::Rtc;
+//!
+//! // Central European Time: UTC+01:00, EU rules
+//! let mut rtc = ZonedRtc::new(Ds3231::new(i2c), 60);
+//! rtc.set_dst_rule(Some(DstRule::EU));
+//! let local = rtc.get_datetime()?; // CET or CEST as appropriate
+//! # Ok(())
+//! # }
+//! ```
+
+use rtc_hal::datetime::{DateTime, Weekday};
+
+use crate::calendar::{self, SECONDS_PER_DAY};
+use crate::month::Month;
+
+/// Which occurrence of a weekday within a month
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Occurrence {
+    /// First occurrence in the month
+    First,
+    /// Second occurrence in the month
+    Second,
+    /// Third occurrence in the month
+    Third,
+    /// Fourth occurrence in the month
+    Fourth,
+    /// Last occurrence in the month (fourth or fifth)
+    Last,
+}
+
+/// How transition times are interpreted
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum TransitionBasis {
+    /// Start time is local standard time, end time is local daylight time
+    LocalTime,
+    /// Both times are UTC
+    Utc,
+}
+
+/// A yearly transition point such as "last Sunday of March at 02:00"
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Transition {
+    /// Month of the transition
+    pub month: Month,
+    /// Which occurrence of `weekday` in `month`
+    pub occurrence: Occurrence,
+    /// Day of the week of the transition
+    pub weekday: Weekday,
+    /// Hour of the transition (0-23)
+    pub hour: u8,
+    /// Minute of the transition (0-59)
+    pub minute: u8,
+}
+
+impl Transition {
+    /// Day of month on which this transition falls in `year`.
+    pub fn day_in(&self, year: u16) -> u8 {
+        let month = self.month.to_number();
+        let first_weekday = calendar::weekday_number(year, month, 1);
+        let first_match = 1 + (self.weekday.to_number() + 7 - first_weekday) % 7;
+
+        let n = match self.occurrence {
+            Occurrence::First => 0,
+            Occurrence::Second => 1,
+            Occurrence::Third => 2,
+            Occurrence::Fourth => 3,
+            Occurrence::Last => 4,
+        };
+        let days = self.month.days(year);
+        let mut day = first_match + 7 * n;
+        while day > days {
+            day -= 7;
+        }
+        day
+    }
+
+    /// Seconds since the Unix epoch of this transition in `year`, interpreting
+    /// the hour and minute as an offset from midnight with no time zone.
+    fn naive_timestamp(&self, year: u16) -> i64 {
+        let days = calendar::days_since_epoch(year, self.month.to_number(), self.day_in(year));
+        days * SECONDS_PER_DAY as i64 + self.hour as i64 * 3600 + self.minute as i64 * 60
+    }
+}
+
+/// Weekdays (rtc-hal's `Weekday` lacks `defmt::Format`) are sent as their
+/// number, 1 = Sunday.
+#[cfg(feature = "defmt")]
+impl defmt::Format for Transition {
+    fn format(&self, f: defmt::Formatter) {
+        defmt::write!(
+            f,
+            "Transition {{ month: {}, occurrence: {}, weekday: {=u8}, time: {=u8:02}:{=u8:02} }}",
+            self.month,
+            self.occurrence,
+            self.weekday.to_number(),
+            self.hour,
+            self.minute
+        )
+    }
+}
+
+/// A daylight saving time rule
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct DstRule {
+    /// When daylight saving time starts
+    pub start: Transition,
+    /// When daylight saving time ends
+    pub end: Transition,
+    /// Amount added to standard time while DST is active, in minutes
+    pub save_minutes: i16,
+    /// How `start` and `end` times are interpreted
+    pub basis: TransitionBasis,
+}
+
+impl DstRule {
+    /// European Union: last Sunday of March to last Sunday of October, 01:00 UTC, +1h
+    pub const EU: DstRule = DstRule {
+        start: Transition {
+            month: Month::March,
+            occurrence: Occurrence::Last,
+            weekday: Weekday::Sunday,
+            hour: 1,
+            minute: 0,
+        },
+        end: Transition {
+            month: Month::October,
+            occurrence: Occurrence::Last,
+            weekday: Weekday::Sunday,
+            hour: 1,
+            minute: 0,
+        },
+        save_minutes: 60,
+        basis: TransitionBasis::Utc,
+    };
+
+    /// United States and Canada: second Sunday of March to first Sunday of
+    /// November, 02:00 local time, +1h
+    pub const US: DstRule = DstRule {
+        start: Transition {
+            month: Month::March,
+            occurrence: Occurrence::Second,
+            weekday: Weekday::Sunday,
+            hour: 2,
+            minute: 0,
+        },
+        end: Transition {
+            month: Month::November,
+            occurrence: Occurrence::First,
+            weekday: Weekday::Sunday,
+            hour: 2,
+            minute: 0,
+        },
+        save_minutes: 60,
+        basis: TransitionBasis::LocalTime,
+    };
+
+    /// Returns `true` if daylight saving time is in effect at the given UTC time
+    /// for a zone with the given standard offset.
+    ///
+    /// Rules whose start falls later in the year than their end (southern
+    /// hemisphere) are handled as DST spanning the new year.
+    pub fn is_dst(&self, utc: &DateTime, std_offset_minutes: i16) -> bool {
+        let now = calendar::to_unix_timestamp(utc) as i64;
+        let year = utc.year();
+
+        let (start, end) = match self.basis {
+            TransitionBasis::Utc => (
+                self.start.naive_timestamp(year),
+                self.end.naive_timestamp(year),
+            ),
+            TransitionBasis::LocalTime => {
+                let std_offset = std_offset_minutes as i64 * 60;
+                let dst_offset = std_offset + self.save_minutes as i64 * 60;
+                (
+                    self.start.naive_timestamp(year) - std_offset,
+                    self.end.naive_timestamp(year) - dst_offset,
+                )
+            }
+        };
+
+        if start <= end {
+            now >= start && now < end
+        } else {
+            now >= start || now < end
+        }
+    }
+
+    /// Total offset from UTC in minutes at the given UTC time
+    /// (`std_offset_minutes`, plus `save_minutes` while DST is active).
+    ///
+    /// Widened to `i32` so the sum can't overflow.
+    pub fn offset_at(&self, utc: &DateTime, std_offset_minutes: i16) -> i32 {
+        if self.is_dst(utc, std_offset_minutes) {
+            i32::from(std_offset_minutes) + i32::from(self.save_minutes)
+        } else {
+            i32::from(std_offset_minutes)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn utc(y: u16, mo: u8, d: u8, h: u8, mi: u8) -> DateTime {
+        DateTime::new(y, mo, d, h, mi, 0).unwrap()
+    }
+
+    #[test]
+    fn test_transition_day() {
+        // 2025: last Sunday of March is the 30th, second Sunday of March the 9th
+        assert_eq!(DstRule::EU.start.day_in(2025), 30);
+        assert_eq!(DstRule::EU.end.day_in(2025), 26);
+        assert_eq!(DstRule::US.start.day_in(2025), 9);
+        assert_eq!(DstRule::US.end.day_in(2025), 2);
+        // 2026: last Sunday of March is the 29th
+        assert_eq!(DstRule::EU.start.day_in(2026), 29);
+    }
+
+    #[test]
+    fn test_eu_rule() {
+        let rule = DstRule::EU;
+        assert!(!rule.is_dst(&utc(2025, 3, 30, 0, 59), 60));
+        assert!(rule.is_dst(&utc(2025, 3, 30, 1, 0), 60));
+        assert!(rule.is_dst(&utc(2025, 10, 26, 0, 59), 60));
+        assert!(!rule.is_dst(&utc(2025, 10, 26, 1, 0), 60));
+        assert!(!rule.is_dst(&utc(2025, 12, 1, 12, 0), 60));
+        assert_eq!(rule.offset_at(&utc(2025, 7, 1, 12, 0), 60), 120);
+
+        // Out-of-range offsets don't overflow
+        let rule = DstRule {
+            save_minutes: i16::MAX,
+            ..DstRule::EU
+        };
+        assert_eq!(
+            rule.offset_at(&utc(2025, 7, 1, 12, 0), i16::MAX),
+            2 * i32::from(i16::MAX)
+        );
+    }
+
+    #[test]
+    fn test_us_rule_local_basis() {
+        let rule = DstRule::US;
+        // US Eastern (UTC-05:00): starts 2025-03-09 02:00 EST = 07:00 UTC
+        assert!(!rule.is_dst(&utc(2025, 3, 9, 6, 59), -300));
+        assert!(rule.is_dst(&utc(2025, 3, 9, 7, 0), -300));
+        // Ends 2025-11-02 02:00 EDT = 06:00 UTC
+        assert!(rule.is_dst(&utc(2025, 11, 2, 5, 59), -300));
+        assert!(!rule.is_dst(&utc(2025, 11, 2, 6, 0), -300));
+    }
+
+    #[test]
+    fn test_southern_hemisphere_rule() {
+        // Australia/Sydney style: first Sunday of October to first Sunday of April
+        let rule = DstRule {
+            start: Transition {
+                month: Month::October,
+                occurrence: Occurrence::First,
+                weekday: Weekday::Sunday,
+                hour: 2,
+                minute: 0,
+            },
+            end: Transition {
+                month: Month::April,
+                occurrence: Occurrence::First,
+                weekday: Weekday::Sunday,
+                hour: 3,
+                minute: 0,
+            },
+            save_minutes: 60,
+            basis: TransitionBasis::LocalTime,
+        };
+        assert!(rule.is_dst(&utc(2025, 1, 15, 0, 0), 600));
+        assert!(!rule.is_dst(&utc(2025, 6, 15, 0, 0), 600));
+        assert!(rule.is_dst(&utc(2025, 12, 15, 0, 0), 600));
+    }
+}
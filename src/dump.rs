@@ -0,0 +1,459 @@
+//! # Register Dump
+//!
+//! [`dump_registers`](crate::Ds3231::dump_registers) reads the whole
+//! register map (0x00-0x12) in one burst into a [`RegisterSnapshot`]. Its
+//! `Debug` (and, with the `defmt` feature, `defmt::Format`) output names
+//! every register and decodes the control and status bits and the
+//! temperature, so a single log line over RTT or a serial console shows the
+//! complete device state:
+//!
+//! ```no_run
+//! # use ds3231_rtc::Ds3231;
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! # let mut rtc = Ds3231::new(embedded_hal_mock::eh1::i2c::Mock::new(&[]));
+//! let snapshot = rtc.dump_registers()?;
+//! println!("{:?}", snapshot);
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`RegisterSnapshot::diff`] compares two snapshots, e.g. taken before and
+//! after a firmware update, and lists the registers and bits that changed:
+//!
+//! ```no_run
+//! # use ds3231_rtc::Ds3231;
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! # let mut rtc = Ds3231::new(embedded_hal_mock::eh1::i2c::Mock::new(&[]));
+//! let before = rtc.dump_registers()?;
+//! // ...
+//! let after = rtc.dump_registers()?;
+//! for change in before.diff(&after) {
+//!     println!("{}: {:#04x} -> {:#04x}", change.name(), change.old_value(), change.new_value());
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! The per-register metadata on [`Register`] (writable bits, power-on
+//! value) tells changes the chip made by itself apart from configuration
+//! changes, see [`RegisterChange::hardware_bits`].
+
+use core::fmt;
+
+#[cfg(feature = "async")]
+use crate::interface::AsyncRegisterInterface;
+use crate::interface::RegisterInterface;
+
+use crate::Ds3231;
+#[cfg(feature = "async")]
+use crate::ds3231::Ds3231Async;
+use crate::error::Error;
+use crate::hour_mode::HourMode;
+use crate::registers::{
+    CENTURY_BIT, ControlRegister, HourRegister, Register, StatusRegister, decode_quarters,
+};
+
+/// Number of registers in the DS3231 register map
+pub const REGISTER_COUNT: usize = Register::ALL.len();
+
+/// Register names in address order
+pub(crate) const REGISTER_NAMES: [&str; REGISTER_COUNT] = [
+    "seconds",
+    "minutes",
+    "hours",
+    "day",
+    "date",
+    "month_century",
+    "year",
+    "alarm1_seconds",
+    "alarm1_minutes",
+    "alarm1_hours",
+    "alarm1_day_date",
+    "alarm2_minutes",
+    "alarm2_hours",
+    "alarm2_day_date",
+    "control",
+    "status",
+    "aging_offset",
+    "temperature_msb",
+    "temperature_lsb",
+];
+
+/// Contents of all DS3231 registers at one point in time
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct RegisterSnapshot {
+    registers: [u8; REGISTER_COUNT],
+}
+
+impl RegisterSnapshot {
+    /// Snapshot from raw register contents, index = register address.
+    pub const fn from_bytes(registers: [u8; REGISTER_COUNT]) -> Self {
+        Self { registers }
+    }
+
+    /// Raw register contents, index = register address.
+    pub const fn as_bytes(&self) -> &[u8; REGISTER_COUNT] {
+        &self.registers
+    }
+
+    /// Value of the register at `address`, `None` past the register map.
+    pub fn get(&self, address: u8) -> Option<u8> {
+        self.registers.get(address as usize).copied()
+    }
+
+    /// Value of `register`.
+    pub const fn register(&self, register: Register) -> u8 {
+        self.registers[register.addr() as usize]
+    }
+
+    /// Temperature in quarter degrees Celsius.
+    pub fn temperature_quarters(&self) -> i16 {
+        decode_quarters(
+            self.register(Register::TempMsb),
+            self.register(Register::TempLsb),
+        )
+    }
+
+    /// Registers whose value differs in `other`, in address order.
+    pub fn diff<'a>(&'a self, other: &'a RegisterSnapshot) -> Diff<'a> {
+        Diff {
+            old: self,
+            new: other,
+            address: 0,
+        }
+    }
+}
+
+/// One register that differs between two snapshots
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+pub struct RegisterChange {
+    register: Register,
+    old: u8,
+    new: u8,
+}
+
+impl RegisterChange {
+    /// Register name as shown in the snapshot's `Debug` output.
+    pub fn name(&self) -> &'static str {
+        REGISTER_NAMES[self.register as usize]
+    }
+
+    /// The register that changed.
+    pub const fn register(&self) -> Register {
+        self.register
+    }
+
+    /// Register address.
+    pub const fn address(&self) -> u8 {
+        self.register.addr()
+    }
+
+    /// Value in the first snapshot.
+    pub const fn old_value(&self) -> u8 {
+        self.old
+    }
+
+    /// Value in the second snapshot.
+    pub const fn new_value(&self) -> u8 {
+        self.new
+    }
+
+    /// Changed bits software can't write, i.e. changes made by the chip
+    /// itself such as BSY or a new temperature reading.
+    pub const fn hardware_bits(&self) -> u8 {
+        self.changed_bits() & !self.register().writable_mask()
+    }
+
+    /// Mask of the bits that changed.
+    pub const fn changed_bits(&self) -> u8 {
+        self.old ^ self.new
+    }
+
+    /// Mask of the bits that went from 0 to 1.
+    pub const fn set_bits(&self) -> u8 {
+        !self.old & self.new
+    }
+
+    /// Mask of the bits that went from 1 to 0.
+    pub const fn cleared_bits(&self) -> u8 {
+        self.old & !self.new
+    }
+}
+
+/// Iterator over the changes between two snapshots, returned by
+/// [`RegisterSnapshot::diff`]
+#[derive(Debug, Clone)]
+pub struct Diff<'a> {
+    old: &'a RegisterSnapshot,
+    new: &'a RegisterSnapshot,
+    address: usize,
+}
+
+impl Iterator for Diff<'_> {
+    type Item = RegisterChange;
+
+    fn next(&mut self) -> Option<RegisterChange> {
+        while let Some(&register) = Register::ALL.get(self.address) {
+            self.address += 1;
+            let (old, new) = (self.old.register(register), self.new.register(register));
+            if old != new {
+                return Some(RegisterChange { register, old, new });
+            }
+        }
+        None
+    }
+}
+
+/// Bit shown as `0` or `1`
+const fn bit(value: bool) -> u8 {
+    value as u8
+}
+
+/// Quarter degrees formatted as degrees with two decimals
+struct Celsius(i16);
+
+impl fmt::Display for Celsius {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sign = if self.0 < 0 { "-" } else { "" };
+        let quarters = self.0.unsigned_abs();
+        write!(f, "{sign}{}.{:02} °C", quarters / 4, quarters % 4 * 25)
+    }
+}
+
+impl fmt::Debug for RegisterSnapshot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let control = ControlRegister::from_bits(self.register(Register::Control));
+        let status = StatusRegister::from_bits(self.register(Register::Status));
+        let hours = HourRegister::from_bits(self.register(Register::Hours));
+
+        let mut s = f.debug_struct("RegisterSnapshot");
+        for (name, value) in REGISTER_NAMES.iter().zip(self.registers.iter()) {
+            s.field(name, &format_args!("{value:#04x}"));
+        }
+        s.field(
+            "clock",
+            &format_args!(
+                "{}, century={}",
+                match hours.hour_mode() {
+                    HourMode::TwelveHour => "12h",
+                    HourMode::TwentyFourHour => "24h",
+                },
+                bit(self.register(Register::Month) & CENTURY_BIT != 0)
+            ),
+        )
+        .field(
+            "control_bits",
+            &format_args!(
+                "EOSC={} BBSQW={} CONV={} RS={} INTCN={} A2IE={} A1IE={}",
+                bit(control.oscillator_disabled()),
+                bit(control.battery_backed_square_wave()),
+                bit(control.convert_temperature()),
+                control.rate_select(),
+                bit(control.interrupt_control()),
+                bit(control.alarm2_interrupt()),
+                bit(control.alarm1_interrupt())
+            ),
+        )
+        .field(
+            "status_bits",
+            &format_args!(
+                "OSF={} EN32kHz={} BSY={} A2F={} A1F={}",
+                bit(status.oscillator_stop_flag()),
+                bit(status.enable_32khz()),
+                bit(status.busy()),
+                bit(status.alarm2_flag()),
+                bit(status.alarm1_flag())
+            ),
+        )
+        .field(
+            "temperature",
+            &format_args!("{}", Celsius(self.temperature_quarters())),
+        )
+        .finish()
+    }
+}
+
+#[cfg(feature = "defmt")]
+impl defmt::Format for RegisterSnapshot {
+    fn format(&self, f: defmt::Formatter) {
+        let control = ControlRegister::from_bits(self.register(Register::Control));
+        let status = StatusRegister::from_bits(self.register(Register::Status));
+
+        defmt::write!(f, "RegisterSnapshot {{");
+        for (name, value) in REGISTER_NAMES.iter().zip(self.registers.iter()) {
+            defmt::write!(f, " {=str}: {=u8:#04x},", name, value);
+        }
+        defmt::write!(
+            f,
+            " control_bits: EOSC={=u8} BBSQW={=u8} CONV={=u8} RS={=u8} INTCN={=u8} A2IE={=u8} A1IE={=u8},",
+            bit(control.oscillator_disabled()),
+            bit(control.battery_backed_square_wave()),
+            bit(control.convert_temperature()),
+            control.rate_select(),
+            bit(control.interrupt_control()),
+            bit(control.alarm2_interrupt()),
+            bit(control.alarm1_interrupt())
+        );
+        defmt::write!(
+            f,
+            " status_bits: OSF={=u8} EN32kHz={=u8} BSY={=u8} A2F={=u8} A1F={=u8},",
+            bit(status.oscillator_stop_flag()),
+            bit(status.enable_32khz()),
+            bit(status.busy()),
+            bit(status.alarm2_flag()),
+            bit(status.alarm1_flag())
+        );
+        defmt::write!(
+            f,
+            " temperature_quarters: {=i16} }}",
+            self.temperature_quarters()
+        );
+    }
+}
+
+#[cfg(feature = "ufmt")]
+impl ufmt::uDebug for RegisterSnapshot {
+    fn fmt<W>(&self, f: &mut ufmt::Formatter<'_, W>) -> Result<(), W::Error>
+    where
+        W: ufmt::uWrite + ?Sized,
+    {
+        let control = ControlRegister::from_bits(self.register(Register::Control));
+        let status = StatusRegister::from_bits(self.register(Register::Status));
+
+        f.write_str("RegisterSnapshot {")?;
+        for (name, value) in REGISTER_NAMES.iter().zip(self.registers.iter()) {
+            ufmt::uwrite!(f, " {}: {:#04x},", *name, *value)?;
+        }
+        ufmt::uwrite!(
+            f,
+            " control_bits: EOSC={} BBSQW={} CONV={} RS={} INTCN={} A2IE={} A1IE={},",
+            bit(control.oscillator_disabled()),
+            bit(control.battery_backed_square_wave()),
+            bit(control.convert_temperature()),
+            control.rate_select(),
+            bit(control.interrupt_control()),
+            bit(control.alarm2_interrupt()),
+            bit(control.alarm1_interrupt())
+        )?;
+        ufmt::uwrite!(
+            f,
+            " status_bits: OSF={} EN32kHz={} BSY={} A2F={} A1F={},",
+            bit(status.oscillator_stop_flag()),
+            bit(status.enable_32khz()),
+            bit(status.busy()),
+            bit(status.alarm2_flag()),
+            bit(status.alarm1_flag())
+        )?;
+        ufmt::uwrite!(
+            f,
+            " temperature_quarters: {} }}",
+            self.temperature_quarters()
+        )
+    }
+}
+
+#[maybe_async_cfg::maybe(
+    idents(
+        AsyncRegisterInterface(sync = "RegisterInterface", async = "AsyncRegisterInterface"),
+        Ds3231(sync = "Ds3231", async = "Ds3231Async")
+    ),
+    sync(keep_self),
+    async(feature = "async")
+)]
+impl<I2C, V> Ds3231<I2C, V>
+where
+    I2C: AsyncRegisterInterface,
+{
+    /// Read all registers in one burst.
+    ///
+    /// See the [module documentation](crate::dump).
+    pub async fn dump_registers(&mut self) -> Result<RegisterSnapshot, Error<I2C::Error>> {
+        let mut registers = [0u8; REGISTER_COUNT];
+        self.read_register_bytes(Register::Seconds, &mut registers)
+            .await?;
+        Ok(RegisterSnapshot::from_bytes(registers))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registers::{A1F_BIT, INTCN_BIT, OSF_BIT};
+    use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
+
+    const REGISTERS: [u8; REGISTER_COUNT] = [
+        0x00, 0x30, 0x14, 0x05, 0x21, 0x88, 0x25, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x1C,
+        0x89, 0x00, 0xF9, 0x40,
+    ];
+
+    #[test]
+    fn test_dump_registers() {
+        let expectations = [I2cTransaction::write_read(
+            0x68,
+            vec![Register::Seconds.addr()],
+            REGISTERS.to_vec(),
+        )];
+        let mut ds3231 = Ds3231::new(I2cMock::new(&expectations));
+
+        let snapshot = ds3231.dump_registers().unwrap();
+        assert_eq!(snapshot.as_bytes(), &REGISTERS);
+        assert_eq!(snapshot.register(Register::Control), 0x1C);
+        assert_eq!(snapshot.get(0x12), Some(0x40));
+        assert_eq!(snapshot.get(0x13), None);
+
+        ds3231.release_i2c().done();
+    }
+
+    #[test]
+    fn test_debug_names_and_decodes_registers() {
+        let debug = format!("{:?}", RegisterSnapshot::from_bytes(REGISTERS));
+
+        assert!(debug.starts_with("RegisterSnapshot { seconds: 0x00, minutes: 0x30,"));
+        assert!(debug.contains("control: 0x1c"));
+        assert!(debug.contains("clock: 24h, century=1"));
+        assert!(debug.contains("control_bits: EOSC=0 BBSQW=0 CONV=0 RS=3 INTCN=1 A2IE=0 A1IE=0"));
+        assert!(debug.contains("status_bits: OSF=1 EN32kHz=1 BSY=0 A2F=0 A1F=1"));
+        assert!(debug.contains("temperature: -6.75 °C"));
+    }
+
+    #[test]
+    fn test_diff_lists_changed_registers() {
+        let before = RegisterSnapshot::from_bytes(REGISTERS);
+        let mut after = REGISTERS;
+        after[0x0E] = 0x18; // INTCN cleared
+        after[0x0F] = 0x08; // OSF and A1F cleared
+        after[0x11] = 0xFA; // new temperature reading
+        let after = RegisterSnapshot::from_bytes(after);
+
+        assert_eq!(before.diff(&before).next(), None);
+
+        let changes: Vec<_> = before.diff(&after).collect();
+        assert_eq!(changes.len(), 3);
+        assert_eq!(changes[0].name(), "control");
+        assert_eq!(changes[0].changed_bits(), INTCN_BIT);
+        assert_eq!(changes[0].cleared_bits(), INTCN_BIT);
+        assert_eq!(changes[1].address(), Register::Status.addr());
+        assert_eq!(
+            (changes[1].old_value(), changes[1].new_value()),
+            (0x89, 0x08)
+        );
+        assert_eq!(changes[1].cleared_bits(), OSF_BIT | A1F_BIT);
+        assert_eq!(changes[1].set_bits(), 0);
+        assert_eq!(changes[1].hardware_bits(), 0);
+        assert_eq!(changes[2].register(), Register::TempMsb);
+        assert_eq!(changes[2].hardware_bits(), 0x03);
+    }
+
+    #[cfg(feature = "ufmt")]
+    #[test]
+    fn test_ufmt_debug() {
+        let mut s = String::new();
+        ufmt::uwrite!(s, "{:?}", RegisterSnapshot::from_bytes(REGISTERS)).unwrap();
+
+        assert!(s.starts_with("RegisterSnapshot { seconds: 0x00, minutes: 0x30,"));
+        assert!(s.contains(" status_bits: OSF=1 EN32kHz=1 BSY=0 A2F=0 A1F=1,"));
+        assert!(s.ends_with(" temperature_quarters: -27 }"));
+    }
+}
@@ -0,0 +1,189 @@
+//! # embedded-hal 0.2 Compatibility
+//!
+//! Many HALs still only implement the `embedded-hal` 0.2 blocking I2C traits.
+//! With the `eh0` feature, [`Eh0I2c`] adapts such a bus to the
+//! `embedded-hal` 1.0 [`I2c`] trait the driver is written against, so the
+//! full driver works unchanged on top of it.
+//!
+//! ```no_run
+//! use ds3231_rtc::Ds3231;
+//! # use ds3231_rtc::{eh0::Eh0Error, error::Error};
+//! # use embedded_hal_mock::eh0::MockError;
+//! # fn main() -> Result<(), Error<Eh0Error<MockError>>> {
+//! # let i2c = embedded_hal_mock::eh0::i2c::Mock::new(&[]);
+//!
+//! // `i2c` implements embedded_hal 0.2 `Write`, `Read` and `WriteRead`
+//! let mut rtc = Ds3231::new_eh0(i2c);
+//! let now = rtc.get_datetime()?;
+//!
+//! let i2c = rtc.release_i2c().into_inner();
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! `embedded-hal` 0.2 has no transaction support, so a [`transaction`] is
+//! executed as separate bus operations, except that a write immediately
+//! followed by a read uses `WriteRead` (a repeated start). The driver itself
+//! only issues plain writes and write-reads, which map one to one.
+//!
+//! [`transaction`]: I2c::transaction
+
+use embedded_hal::i2c::{ErrorKind, ErrorType, I2c, Operation, SevenBitAddress};
+use embedded_hal_0_2::blocking::i2c::{Read, Write, WriteRead};
+
+use crate::Ds3231;
+
+/// Adapter implementing the `embedded-hal` 1.0 [`I2c`] trait for an
+/// `embedded-hal` 0.2 blocking I2C bus
+#[derive(Debug)]
+pub struct Eh0I2c<I2C> {
+    i2c: I2C,
+}
+
+impl<I2C> Eh0I2c<I2C> {
+    /// Wrap an `embedded-hal` 0.2 I2C bus.
+    pub fn new(i2c: I2C) -> Self {
+        Self { i2c }
+    }
+
+    /// Returns the wrapped bus, consuming the adapter.
+    pub fn into_inner(self) -> I2C {
+        self.i2c
+    }
+}
+
+/// Error from an `embedded-hal` 0.2 bus.
+///
+/// 0.2 errors are HAL specific types with no common classification, so the
+/// [`kind`](embedded_hal::i2c::Error::kind) is always [`ErrorKind::Other`];
+/// the original error is kept in the tuple field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Eh0Error<E>(pub E);
+
+impl<E: core::fmt::Debug> embedded_hal::i2c::Error for Eh0Error<E> {
+    fn kind(&self) -> ErrorKind {
+        ErrorKind::Other
+    }
+}
+
+impl<I2C, E> ErrorType for Eh0I2c<I2C>
+where
+    I2C: Write<Error = E> + Read<Error = E> + WriteRead<Error = E>,
+    E: core::fmt::Debug,
+{
+    type Error = Eh0Error<E>;
+}
+
+impl<I2C, E> I2c for Eh0I2c<I2C>
+where
+    I2C: Write<Error = E> + Read<Error = E> + WriteRead<Error = E>,
+    E: core::fmt::Debug,
+{
+    fn read(&mut self, address: SevenBitAddress, read: &mut [u8]) -> Result<(), Self::Error> {
+        self.i2c.read(address, read).map_err(Eh0Error)
+    }
+
+    fn write(&mut self, address: SevenBitAddress, write: &[u8]) -> Result<(), Self::Error> {
+        self.i2c.write(address, write).map_err(Eh0Error)
+    }
+
+    fn write_read(
+        &mut self,
+        address: SevenBitAddress,
+        write: &[u8],
+        read: &mut [u8],
+    ) -> Result<(), Self::Error> {
+        self.i2c.write_read(address, write, read).map_err(Eh0Error)
+    }
+
+    fn transaction(
+        &mut self,
+        address: SevenBitAddress,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        let mut operations = operations.iter_mut().peekable();
+        while let Some(operation) = operations.next() {
+            match operation {
+                Operation::Write(write) => {
+                    if let Some(Operation::Read(read)) = operations.peek_mut() {
+                        self.write_read(address, write, read)?;
+                        operations.next();
+                    } else {
+                        self.write(address, write)?;
+                    }
+                }
+                Operation::Read(read) => self.read(address, read)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<I2C, E> Ds3231<Eh0I2c<I2C>>
+where
+    I2C: Write<Error = E> + Read<Error = E> + WriteRead<Error = E>,
+    E: core::fmt::Debug,
+{
+    /// Create a driver on an `embedded-hal` 0.2 I2C bus.
+    ///
+    /// Shorthand for `Ds3231::new(Eh0I2c::new(i2c))`.
+    pub fn new_eh0(i2c: I2C) -> Self {
+        Ds3231::new(Eh0I2c::new(i2c))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registers::Register;
+    use embedded_hal_mock::eh0::i2c::{Mock as I2cMock, Transaction as I2cTrans};
+    use rtc_hal::datetime::DateTime;
+
+    #[test]
+    fn test_driver_over_eh0_bus() {
+        let data = vec![0x00, 0x30, 0x14, 0x05, 0x21, 0x08, 0x25];
+        let mut write = vec![Register::Seconds.addr()];
+        write.extend_from_slice(&data);
+        let expectations = [
+            I2cTrans::write_read(0x68, vec![Register::Seconds.addr()], data),
+            I2cTrans::write(0x68, write),
+        ];
+        let mut ds3231 = Ds3231::new_eh0(I2cMock::new(&expectations));
+
+        let dt = ds3231.get_datetime().unwrap();
+        assert_eq!(dt, DateTime::new(2025, 8, 21, 14, 30, 0).unwrap());
+        ds3231.set_datetime(&dt).unwrap();
+
+        ds3231.release_i2c().into_inner().done();
+    }
+
+    #[test]
+    fn test_transaction_merges_write_then_read() {
+        let expectations = [
+            I2cTrans::write_read(0x68, vec![0x0E], vec![0x1C]),
+            I2cTrans::write(0x68, vec![0x0E, 0x00]),
+            I2cTrans::read(0x68, vec![0xAB]),
+        ];
+        let mut i2c = Eh0I2c::new(I2cMock::new(&expectations));
+
+        let mut control = [0u8];
+        i2c.transaction(
+            0x68,
+            &mut [
+                Operation::Write(&[0x0E]),
+                Operation::Read(&mut control),
+                Operation::Write(&[0x0E, 0x00]),
+            ],
+        )
+        .unwrap();
+        assert_eq!(control, [0x1C]);
+
+        let mut extra = [0u8];
+        i2c.transaction(0x68, &mut [Operation::Read(&mut extra)])
+            .unwrap();
+        assert_eq!(extra, [0xAB]);
+
+        i2c.into_inner().done();
+    }
+}
@@ -2,18 +2,76 @@
 //!
 //! This module defines the `Error` enum and helper functions
 //! for classifying and handling DS3231-specific failures.
+//!
+//! Bus errors from the driver's own transfers arrive as
+//! [`Error::Transfer`], which names the register and the [`Operation`]
+//! that failed, so a single log line from deep inside a multi-register
+//! operation points at the transfer:
+//!
+//! ```text
+//! I2C write of register 0x0e failed: NoAcknowledge(Data)
+//! ```
 
 use rtc_hal::datetime::DateTimeError;
 
+use crate::registers::Register;
+use crate::trace::debug;
+
+/// Kind of bus transfer, as reported by [`Error::Transfer`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+pub enum Operation {
+    /// Reading one or more registers
+    Read,
+    /// Writing one or more registers
+    Write,
+    /// Read-modify-write of a single register
+    Modify,
+}
+
+impl core::fmt::Display for Operation {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Operation::Read => write!(f, "read"),
+            Operation::Write => write!(f, "write"),
+            Operation::Modify => write!(f, "read-modify-write"),
+        }
+    }
+}
+
 /// DS3231 driver errors
+///
+/// With the `defmt` feature, `Error<E>` implements `defmt::Format` when the
+/// bus error `E` does, so errors are logged field by field instead of as a
+/// rendered `Debug` string.
+///
+/// With the `ufmt` feature, it implements `uDisplay` and `uDebug` when the
+/// bus error implements the same trait.
+///
+/// New variants may be added in minor releases. Code that needs to react to
+/// whole classes of errors should match on [`kind`](Self::kind) or reach
+/// the bus error through [`as_bus_error`](Self::as_bus_error) rather than
+/// list the variants.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[non_exhaustive]
 pub enum Error<I2cError>
 where
     I2cError: core::fmt::Debug,
 {
-    /// I2C communication error
+    /// I2C communication error without transfer context, e.g. created with
+    /// [`Error::bus`]
     I2c(I2cError),
+    /// A bus transfer made by the driver failed
+    Transfer {
+        /// Error of the underlying bus
+        source: I2cError,
+        /// First register of the transfer
+        register: u8,
+        /// What the transfer did
+        operation: Operation,
+    },
     /// Invalid register address
     InvalidAddress,
     /// The specified square wave frequency is not supported by the RTC
@@ -22,6 +80,132 @@ where
     DateTime(DateTimeError),
     /// Invalid Base Century (It should be either 19,20,21)
     InvalidBaseCentury,
+    /// SRAM offset or length outside the device's NVRAM
+    NvramOutOfBounds,
+    /// A register read back after writing held a different value
+    VerificationFailed,
+    /// The device returned a value the register can't hold, e.g. a BCD
+    /// nibble above 9 after a brownout or ESD event
+    CorruptRegister {
+        /// Register that was read
+        register: Register,
+        /// Raw value it held
+        value: u8,
+    },
+    /// A datetime read lay outside the plausibility window around the
+    /// previous read
+    ImplausibleJump {
+        /// Difference to the previous read in seconds
+        seconds: i64,
+    },
+    /// No device acknowledged the probe at the driver's address
+    DeviceNotFound {
+        /// The bus error reporting the missing acknowledge
+        source: I2cError,
+    },
+    /// The device is busy with an operation of its own; retrying later may
+    /// succeed
+    Busy,
+    /// An operation didn't complete in the time or attempts allowed
+    Timeout,
+    /// The time read back after setting it differed from the time written
+    DateTimeMismatch {
+        /// Read-back time minus the requested time in seconds
+        seconds: i64,
+    },
+    /// A duration is outside the range the operation supports
+    InvalidDuration,
+    /// The scheduler already holds as many events as it has room for
+    SchedulerFull,
+}
+
+impl<I2cError> Error<I2cError>
+where
+    I2cError: core::fmt::Debug,
+{
+    /// Wrap an error of the bus or transport.
+    ///
+    /// There is deliberately no `From` impl for bus errors: a blanket one
+    /// would convert any type into an `Error` and rule out conversions
+    /// such as `From<DateTimeError>`.
+    pub const fn bus(error: I2cError) -> Self {
+        Error::I2c(error)
+    }
+
+    /// Category of the error, as also reported through `rtc-hal`.
+    pub fn kind(&self) -> rtc_hal::error::ErrorKind {
+        rtc_hal::error::Error::kind(self)
+    }
+
+    /// The underlying bus error, if the error came from a bus transfer.
+    pub fn as_bus_error(&self) -> Option<&I2cError> {
+        match self {
+            Error::I2c(e)
+            | Error::Transfer { source: e, .. }
+            | Error::DeviceNotFound { source: e } => Some(e),
+            _ => None,
+        }
+    }
+
+    /// Returns the underlying bus error, if the error came from a bus
+    /// transfer.
+    pub fn into_bus_error(self) -> Option<I2cError> {
+        match self {
+            Error::I2c(e)
+            | Error::Transfer { source: e, .. }
+            | Error::DeviceNotFound { source: e } => Some(e),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` for errors of the bus or the device's answers to it:
+    /// failed transfers, a missing device, failed read-back verification
+    /// and implausible reads. Same as `kind() == ErrorKind::Bus`.
+    pub fn is_bus_error(&self) -> bool {
+        self.kind() == rtc_hal::error::ErrorKind::Bus
+    }
+
+    /// Returns `true` if a date or time was invalid, whether passed in or
+    /// read from the device. Same as `kind() == ErrorKind::InvalidDateTime`.
+    pub fn is_invalid_datetime(&self) -> bool {
+        self.kind() == rtc_hal::error::ErrorKind::InvalidDateTime
+    }
+
+    /// Returns `true` if repeating the operation may succeed: transfer
+    /// errors, a busy device, timeouts and verifications a glitch may have
+    /// spoiled.
+    ///
+    /// Invalid arguments, a missing device or corrupt register contents
+    /// fail again the same way, and so does an
+    /// [`ImplausibleJump`](Error::ImplausibleJump) if the clock really
+    /// jumped; see the [plausibility filter](crate::plausibility) for when
+    /// such a jump is accepted.
+    pub fn is_recoverable(&self) -> bool {
+        matches!(
+            self,
+            Error::I2c(_)
+                | Error::Transfer { .. }
+                | Error::Busy
+                | Error::Timeout
+                | Error::VerificationFailed
+        )
+    }
+
+    /// Wrap a bus error of an `operation` starting at `register`, for
+    /// `map_err`.
+    pub(crate) fn transfer(operation: Operation, register: u8) -> impl FnOnce(I2cError) -> Self {
+        move |source| {
+            debug!(
+                "I2C {} of register {:#04x} failed: {:?}",
+                operation, register, source
+            );
+            Error::Transfer {
+                source,
+                register,
+                operation,
+            }
+        }
+    }
 }
 
 impl<I2cError> core::fmt::Display for Error<I2cError>
@@ -31,28 +215,214 @@ where
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
             Error::I2c(e) => write!(f, "I2C communication error: {e}"),
+            Error::Transfer {
+                source,
+                register,
+                operation,
+            } => write!(
+                f,
+                "I2C {operation} of register {register:#04x} failed: {source}"
+            ),
             Error::InvalidAddress => write!(f, "Invalid register address"),
-            Error::DateTime(e) => write!(f, "Invalid date/time values: {e}"),
+            Error::DateTime(_) => write!(f, "Invalid date/time values"),
             Error::UnsupportedSqwFrequency => write!(f, "Unsupported square wave frequency"),
             Error::InvalidBaseCentury => write!(f, "Base century must be 19 or greater"),
+            Error::NvramOutOfBounds => write!(f, "NVRAM address out of bounds"),
+            Error::VerificationFailed => write!(f, "Register verification failed"),
+            Error::CorruptRegister { register, value } => {
+                write!(f, "Corrupt value {value:#04x} in {register:?} register")
+            }
+            Error::ImplausibleJump { seconds } => {
+                write!(f, "Time jumped by {seconds} s since the previous read")
+            }
+            Error::DeviceNotFound { source } => {
+                write!(f, "No device found at the bus address: {source}")
+            }
+            Error::Busy => write!(f, "Device busy"),
+            Error::Timeout => write!(f, "Operation timed out"),
+            Error::DateTimeMismatch { seconds } => {
+                write!(f, "Stored time differs from the time set by {seconds} s")
+            }
+            Error::InvalidDuration => write!(f, "Duration out of range"),
+            Error::SchedulerFull => write!(f, "Scheduler is full"),
         }
     }
 }
 
-impl<I2cError> core::error::Error for Error<I2cError> where
-    I2cError: core::fmt::Debug + core::fmt::Display
+#[cfg(feature = "ufmt")]
+impl ufmt::uDisplay for Operation {
+    fn fmt<W>(&self, f: &mut ufmt::Formatter<'_, W>) -> Result<(), W::Error>
+    where
+        W: ufmt::uWrite + ?Sized,
+    {
+        f.write_str(match self {
+            Operation::Read => "read",
+            Operation::Write => "write",
+            Operation::Modify => "read-modify-write",
+        })
+    }
+}
+
+/// `DateTimeError` variant name, as `uDebug` of a foreign type is not
+/// possible
+#[cfg(feature = "ufmt")]
+const fn datetime_error_name(e: &DateTimeError) -> &'static str {
+    match e {
+        DateTimeError::InvalidMonth => "InvalidMonth",
+        DateTimeError::InvalidDay => "InvalidDay",
+        DateTimeError::InvalidHour => "InvalidHour",
+        DateTimeError::InvalidMinute => "InvalidMinute",
+        DateTimeError::InvalidSecond => "InvalidSecond",
+        DateTimeError::InvalidWeekday => "InvalidWeekday",
+        DateTimeError::InvalidYear => "InvalidYear",
+    }
+}
+
+#[cfg(feature = "ufmt")]
+impl<I2cError> ufmt::uDisplay for Error<I2cError>
+where
+    I2cError: core::fmt::Debug + ufmt::uDisplay,
 {
+    fn fmt<W>(&self, f: &mut ufmt::Formatter<'_, W>) -> Result<(), W::Error>
+    where
+        W: ufmt::uWrite + ?Sized,
+    {
+        match self {
+            Error::I2c(e) => ufmt::uwrite!(f, "I2C communication error: {}", e),
+            Error::Transfer {
+                source,
+                register,
+                operation,
+            } => ufmt::uwrite!(
+                f,
+                "I2C {} of register {:#04x} failed: {}",
+                operation,
+                *register,
+                source
+            ),
+            Error::InvalidAddress => f.write_str("Invalid register address"),
+            Error::DateTime(_) => f.write_str("Invalid date/time values"),
+            Error::UnsupportedSqwFrequency => f.write_str("Unsupported square wave frequency"),
+            Error::InvalidBaseCentury => f.write_str("Base century must be 19 or greater"),
+            Error::NvramOutOfBounds => f.write_str("NVRAM address out of bounds"),
+            Error::VerificationFailed => f.write_str("Register verification failed"),
+            Error::CorruptRegister { register, value } => ufmt::uwrite!(
+                f,
+                "Corrupt value {:#04x} in {:?} register",
+                *value,
+                register
+            ),
+            Error::ImplausibleJump { seconds } => {
+                ufmt::uwrite!(f, "Time jumped by {} s since the previous read", *seconds)
+            }
+            Error::DeviceNotFound { source } => {
+                ufmt::uwrite!(f, "No device found at the bus address: {}", source)
+            }
+            Error::Busy => f.write_str("Device busy"),
+            Error::Timeout => f.write_str("Operation timed out"),
+            Error::DateTimeMismatch { seconds } => {
+                ufmt::uwrite!(f, "Stored time differs from the time set by {} s", *seconds)
+            }
+            Error::InvalidDuration => f.write_str("Duration out of range"),
+            Error::SchedulerFull => f.write_str("Scheduler is full"),
+        }
+    }
 }
 
-// /// Converts an [`I2cError`] into an [`Error`] by wrapping it in the
-// /// [`Error::I2c`] variant.
-// ///
-impl<I2cError> From<I2cError> for Error<I2cError>
+#[cfg(feature = "ufmt")]
+impl<I2cError> ufmt::uDebug for Error<I2cError>
+where
+    I2cError: core::fmt::Debug + ufmt::uDebug,
+{
+    fn fmt<W>(&self, f: &mut ufmt::Formatter<'_, W>) -> Result<(), W::Error>
+    where
+        W: ufmt::uWrite + ?Sized,
+    {
+        match self {
+            Error::I2c(e) => f.debug_tuple("I2c")?.field(e)?.finish(),
+            Error::Transfer {
+                source,
+                register,
+                operation,
+            } => f
+                .debug_struct("Transfer")?
+                .field("source", source)?
+                .field("register", register)?
+                .field("operation", operation)?
+                .finish(),
+            Error::InvalidAddress => f.write_str("InvalidAddress"),
+            Error::DateTime(e) => f
+                .debug_tuple("DateTime")?
+                .field(&DebugName(datetime_error_name(e)))?
+                .finish(),
+            Error::UnsupportedSqwFrequency => f.write_str("UnsupportedSqwFrequency"),
+            Error::InvalidBaseCentury => f.write_str("InvalidBaseCentury"),
+            Error::NvramOutOfBounds => f.write_str("NvramOutOfBounds"),
+            Error::VerificationFailed => f.write_str("VerificationFailed"),
+            Error::CorruptRegister { register, value } => f
+                .debug_struct("CorruptRegister")?
+                .field("register", register)?
+                .field("value", value)?
+                .finish(),
+            Error::ImplausibleJump { seconds } => f
+                .debug_struct("ImplausibleJump")?
+                .field("seconds", seconds)?
+                .finish(),
+            Error::DeviceNotFound { source } => f
+                .debug_struct("DeviceNotFound")?
+                .field("source", source)?
+                .finish(),
+            Error::Busy => f.write_str("Busy"),
+            Error::Timeout => f.write_str("Timeout"),
+            Error::DateTimeMismatch { seconds } => f
+                .debug_struct("DateTimeMismatch")?
+                .field("seconds", seconds)?
+                .finish(),
+            Error::InvalidDuration => f.write_str("InvalidDuration"),
+            Error::SchedulerFull => f.write_str("SchedulerFull"),
+        }
+    }
+}
+
+/// Name written without the quotes `uDebug` puts around a `str`
+#[cfg(feature = "ufmt")]
+struct DebugName(&'static str);
+
+#[cfg(feature = "ufmt")]
+impl ufmt::uDebug for DebugName {
+    fn fmt<W>(&self, f: &mut ufmt::Formatter<'_, W>) -> Result<(), W::Error>
+    where
+        W: ufmt::uWrite + ?Sized,
+    {
+        f.write_str(self.0)
+    }
+}
+
+/// Usable with `?` into `Box<dyn Error>`, `anyhow` and other error
+/// reporting crates on any target.
+///
+/// `embedded-hal` bus errors rarely implement `Error` themselves, so the bus
+/// error is part of the `Display` text rather than the
+/// [`source`](core::error::Error::source); invalid dates report their
+/// [`DateTimeError`] as the source instead of repeating it in the text.
+impl<I2cError> core::error::Error for Error<I2cError>
+where
+    I2cError: core::fmt::Debug + core::fmt::Display,
+{
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            Error::DateTime(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl<I2cError> From<DateTimeError> for Error<I2cError>
 where
     I2cError: core::fmt::Debug,
 {
-    fn from(value: I2cError) -> Self {
-        Error::I2c(value)
+    fn from(value: DateTimeError) -> Self {
+        Error::DateTime(value)
     }
 }
 
@@ -63,10 +433,21 @@ where
     fn kind(&self) -> rtc_hal::error::ErrorKind {
         match self {
             Error::I2c(_) => rtc_hal::error::ErrorKind::Bus,
+            Error::Transfer { .. } => rtc_hal::error::ErrorKind::Bus,
             Error::InvalidAddress => rtc_hal::error::ErrorKind::InvalidAddress,
             Error::DateTime(_) => rtc_hal::error::ErrorKind::InvalidDateTime,
             Error::UnsupportedSqwFrequency => rtc_hal::error::ErrorKind::UnsupportedSqwFrequency,
             Error::InvalidBaseCentury => rtc_hal::error::ErrorKind::InvalidDateTime,
+            Error::NvramOutOfBounds => rtc_hal::error::ErrorKind::NvramOutOfBounds,
+            Error::VerificationFailed => rtc_hal::error::ErrorKind::Bus,
+            Error::CorruptRegister { .. } => rtc_hal::error::ErrorKind::InvalidDateTime,
+            Error::ImplausibleJump { .. } => rtc_hal::error::ErrorKind::Bus,
+            Error::DeviceNotFound { .. } => rtc_hal::error::ErrorKind::Bus,
+            Error::Busy => rtc_hal::error::ErrorKind::Other,
+            Error::Timeout => rtc_hal::error::ErrorKind::Other,
+            Error::DateTimeMismatch { .. } => rtc_hal::error::ErrorKind::Other,
+            Error::InvalidDuration => rtc_hal::error::ErrorKind::Other,
+            Error::SchedulerFull => rtc_hal::error::ErrorKind::Other,
         }
     }
 }
@@ -75,15 +456,18 @@ where
 mod tests {
     use super::*;
     use rtc_hal::datetime::DateTimeError;
-    use rtc_hal::error::{Error as RtcError, ErrorKind};
+    use rtc_hal::error::ErrorKind;
 
     #[test]
-    fn test_from_i2c_error() {
+    fn test_error_constructors() {
         #[derive(Debug, PartialEq, Eq)]
         struct DummyI2cError(u8);
 
-        let e = Error::from(DummyI2cError(42));
+        let e = Error::bus(DummyI2cError(42));
         assert_eq!(e, Error::I2c(DummyI2cError(42)));
+
+        let e: Error<DummyI2cError> = DateTimeError::InvalidDay.into();
+        assert_eq!(e, Error::DateTime(DateTimeError::InvalidDay));
     }
 
     #[test]
@@ -92,6 +476,10 @@ mod tests {
         let e: Error<&str> = Error::I2c("oops");
         assert_eq!(e.kind(), ErrorKind::Bus);
 
+        // Transfer
+        let e: Error<&str> = Error::transfer(Operation::Read, 0x0F)("oops");
+        assert_eq!(e.kind(), ErrorKind::Bus);
+
         // InvalidAddress
         let e: Error<&str> = Error::InvalidAddress;
         assert_eq!(e.kind(), ErrorKind::InvalidAddress);
@@ -107,6 +495,45 @@ mod tests {
         // InvalidBaseCentury
         let e: Error<&str> = Error::InvalidBaseCentury;
         assert_eq!(e.kind(), ErrorKind::InvalidDateTime);
+
+        // NvramOutOfBounds
+        let e: Error<&str> = Error::NvramOutOfBounds;
+        assert_eq!(e.kind(), ErrorKind::NvramOutOfBounds);
+
+        // VerificationFailed
+        let e: Error<&str> = Error::VerificationFailed;
+        assert_eq!(e.kind(), ErrorKind::Bus);
+
+        // CorruptRegister
+        let e: Error<&str> = Error::CorruptRegister {
+            register: Register::Seconds,
+            value: 0x5A,
+        };
+        assert_eq!(e.kind(), ErrorKind::InvalidDateTime);
+
+        // ImplausibleJump
+        let e: Error<&str> = Error::ImplausibleJump { seconds: -3600 };
+        assert_eq!(e.kind(), ErrorKind::Bus);
+
+        // DeviceNotFound
+        let e: Error<&str> = Error::DeviceNotFound { source: "nack" };
+        assert_eq!(e.kind(), ErrorKind::Bus);
+
+        // Busy and Timeout
+        let e: Error<&str> = Error::Busy;
+        assert_eq!(e.kind(), ErrorKind::Other);
+        let e: Error<&str> = Error::Timeout;
+        assert_eq!(e.kind(), ErrorKind::Other);
+
+        // DateTimeMismatch
+        let e: Error<&str> = Error::DateTimeMismatch { seconds: 60 };
+        assert_eq!(e.kind(), ErrorKind::Other);
+
+        // InvalidDuration and SchedulerFull
+        let e: Error<&str> = Error::InvalidDuration;
+        assert_eq!(e.kind(), ErrorKind::Other);
+        let e: Error<&str> = Error::SchedulerFull;
+        assert_eq!(e.kind(), ErrorKind::Other);
     }
 
     #[derive(Debug, PartialEq, Eq)]
@@ -131,10 +558,21 @@ mod tests {
                 }),
                 "I2C communication error: I2C Error 1: test",
             ),
+            (
+                Error::Transfer {
+                    source: MockI2cError {
+                        code: 2,
+                        message: "nack",
+                    },
+                    register: 0x0E,
+                    operation: Operation::Write,
+                },
+                "I2C write of register 0x0e failed: I2C Error 2: nack",
+            ),
             (Error::InvalidAddress, "Invalid register address"),
             (
                 Error::DateTime(DateTimeError::InvalidMonth),
-                "Invalid date/time values: invalid month",
+                "Invalid date/time values",
             ),
             (
                 Error::UnsupportedSqwFrequency,
@@ -144,10 +582,136 @@ mod tests {
                 Error::InvalidBaseCentury,
                 "Base century must be 19 or greater",
             ),
+            (Error::NvramOutOfBounds, "NVRAM address out of bounds"),
+            (Error::VerificationFailed, "Register verification failed"),
+            (
+                Error::CorruptRegister {
+                    register: Register::Minutes,
+                    value: 0x7A,
+                },
+                "Corrupt value 0x7a in Minutes register",
+            ),
+            (
+                Error::ImplausibleJump { seconds: -3600 },
+                "Time jumped by -3600 s since the previous read",
+            ),
+            (
+                Error::DeviceNotFound {
+                    source: MockI2cError {
+                        code: 2,
+                        message: "nack",
+                    },
+                },
+                "No device found at the bus address: I2C Error 2: nack",
+            ),
+            (Error::Busy, "Device busy"),
+            (Error::Timeout, "Operation timed out"),
+            (
+                Error::DateTimeMismatch { seconds: 60 },
+                "Stored time differs from the time set by 60 s",
+            ),
+            (Error::InvalidDuration, "Duration out of range"),
+            (Error::SchedulerFull, "Scheduler is full"),
         ];
 
         for (error, expected) in errors {
             assert_eq!(format!("{error}"), expected);
         }
     }
+
+    #[test]
+    fn test_core_error_integration() {
+        fn parse_and_report() -> Result<(), Box<dyn std::error::Error>> {
+            let mut datetime = rtc_hal::datetime::DateTime::new(2025, 1, 31, 0, 0, 0).unwrap();
+            datetime
+                .set_month(2)
+                .map_err(Error::<MockI2cError>::DateTime)?;
+            Ok(())
+        }
+
+        let error = parse_and_report().unwrap_err();
+        assert_eq!(error.to_string(), "Invalid date/time values");
+        assert_eq!(error.source().unwrap().to_string(), "invalid day");
+
+        let error: Error<MockI2cError> = Error::Timeout;
+        assert!(core::error::Error::source(&error).is_none());
+    }
+
+    #[test]
+    fn test_accessors() {
+        let e: Error<&str> = Error::transfer(Operation::Write, 0x0E)("nack");
+        assert_eq!(e.kind(), ErrorKind::Bus);
+        assert_eq!(e.as_bus_error(), Some(&"nack"));
+        assert_eq!(e.into_bus_error(), Some("nack"));
+        assert_eq!(Error::I2c("nack").into_bus_error(), Some("nack"));
+
+        let e: Error<&str> = Error::NvramOutOfBounds;
+        assert_eq!(e.kind(), rtc_hal::error::Error::kind(&e));
+        assert_eq!(e.as_bus_error(), None);
+    }
+
+    #[test]
+    fn test_classification() {
+        let e: Error<&str> = Error::transfer(Operation::Read, 0x00)("nack");
+        assert!(e.is_bus_error() && e.is_recoverable() && !e.is_invalid_datetime());
+
+        let e: Error<&str> = Error::DeviceNotFound { source: "nack" };
+        assert!(e.is_bus_error() && !e.is_recoverable());
+        assert_eq!(e.as_bus_error(), Some(&"nack"));
+
+        let e: Error<&str> = Error::CorruptRegister {
+            register: Register::Date,
+            value: 0xFF,
+        };
+        assert!(e.is_invalid_datetime() && !e.is_recoverable() && !e.is_bus_error());
+
+        let e: Error<&str> = Error::Busy;
+        assert!(e.is_recoverable() && !e.is_bus_error());
+
+        let e: Error<&str> = Error::ImplausibleJump { seconds: 3600 };
+        assert!(!e.is_recoverable());
+
+        let e: Error<&str> = Error::DateTime(DateTimeError::InvalidHour);
+        assert!(e.is_invalid_datetime() && !e.is_recoverable());
+    }
+
+    #[cfg(feature = "defmt")]
+    #[test]
+    fn test_defmt_format_is_native() {
+        fn assert_format<T: defmt::Format>() {}
+
+        assert_format::<Error<u8>>();
+        assert_format::<Operation>();
+        assert_format::<crate::repair::Repair>();
+        #[cfg(feature = "dst")]
+        assert_format::<crate::dst::DstRule>();
+    }
+
+    #[cfg(feature = "ufmt")]
+    #[test]
+    fn test_ufmt_matches_core_fmt() {
+        let errors: [Error<u8>; 4] = [
+            Error::Transfer {
+                source: 2,
+                register: 0x0E,
+                operation: Operation::Modify,
+            },
+            Error::DateTime(DateTimeError::InvalidHour),
+            Error::CorruptRegister {
+                register: Register::Minutes,
+                value: 0x7A,
+            },
+            Error::ImplausibleJump { seconds: -3600 },
+        ];
+
+        for e in errors {
+            let mut display = String::new();
+            ufmt::uwrite!(display, "{}", e).unwrap();
+            assert_eq!(display, format!("{e}"));
+
+            let mut debug = String::new();
+            ufmt::uwrite!(debug, "{:?}", e).unwrap();
+            assert_eq!(debug, format!("{e:?}"));
+        }
+    }
 }
@@ -0,0 +1,439 @@
+//! # Battery-Backed Event Log
+//!
+//! A circular log of timestamped events kept in the SRAM of a DS3232 or
+//! DS3234 (or any other [`RtcNvram`]), so a device remembers its last few
+//! events across resets without external flash.
+//!
+//! An [`EventLog`] occupies a fixed region of NVRAM: a header holding the
+//! position of the oldest record and the record count, followed by
+//! `capacity` records. Each record is a 4-byte Unix timestamp (big-endian,
+//! seconds) followed by a fixed-size payload. When the log is full, the
+//! oldest record is overwritten.
+//!
+//! The header is kept twice, each copy with a sequence number and a CRC-16.
+//! A record is written before the header that makes it visible, and each
+//! update overwrites the older copy, so a power failure part way through an
+//! append leaves the previous header intact and can only affect that one
+//! record. If neither copy checks out (a never-used or wiped SRAM), the log
+//! reads as empty.
+//!
+//! ```no_run
+//! # use ds3231_rtc::ds3232::Ds3232;
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! # let mut rtc = Ds3232::new(embedded_hal_mock::eh1::i2c::Mock::new(&[]));
+//! use ds3231_rtc::event_log::EventLog;
+//! # const EVENT_DOOR_OPEN: u8 = 1;
+//!
+//! // 16 records with a 2-byte payload, right after a 32-byte config area
+//! const LOG: EventLog = EventLog::new(32, 2, 16);
+//!
+//! LOG.log(&mut rtc, &[EVENT_DOOR_OPEN, 0])?;  // stamped with the RTC time
+//!
+//! let mut payload = [0u8; 2];
+//! for i in 0..LOG.len(&mut rtc)? {
+//!     let timestamp = LOG.read(&mut rtc, i, &mut payload)?;
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use rtc_hal::nvram::RtcNvram;
+
+use crate::blob::{CRC16_INIT, crc16};
+use crate::calendar;
+
+/// Size of the log header in bytes, both copies
+pub const LOG_HEADER_SIZE: usize = 2 * HEADER_COPY_SIZE;
+
+/// Size of the timestamp at the start of each record
+pub const TIMESTAMP_SIZE: usize = 4;
+
+/// Size of one header copy: sequence number, head, count and CRC-16
+const HEADER_COPY_SIZE: usize = 5;
+
+/// Leading byte covered by the header CRC, telling the log apart from
+/// other data
+const MAGIC: u8 = 0x4C;
+
+/// Errors returned by [`EventLog`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum LogError<E> {
+    /// Reading the clock or accessing NVRAM failed
+    Rtc(E),
+    /// The log doesn't fit in the device's NVRAM
+    InvalidLayout,
+    /// The payload length differs from the log's record payload size
+    PayloadSize,
+    /// No record at the requested index
+    IndexOutOfRange,
+}
+
+impl<E> core::fmt::Display for LogError<E>
+where
+    E: core::fmt::Display,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            LogError::Rtc(e) => write!(f, "RTC access failed: {e}"),
+            LogError::InvalidLayout => write!(f, "Event log doesn't fit in NVRAM"),
+            LogError::PayloadSize => write!(f, "Wrong event payload size"),
+            LogError::IndexOutOfRange => write!(f, "Event index out of range"),
+        }
+    }
+}
+
+impl<E> core::error::Error for LogError<E> where E: core::fmt::Debug + core::fmt::Display {}
+
+/// Position of the oldest record and number of records
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Header {
+    head: u8,
+    count: u8,
+}
+
+impl Header {
+    const EMPTY: Header = Header { head: 0, count: 0 };
+
+    /// Stored form of the copy with sequence number `sequence`.
+    fn encode(self, sequence: u8) -> [u8; HEADER_COPY_SIZE] {
+        let crc = crc16(CRC16_INIT, &[MAGIC, sequence, self.head, self.count]).to_be_bytes();
+        [sequence, self.head, self.count, crc[0], crc[1]]
+    }
+
+    /// Sequence number and header of a stored copy, if its CRC checks out.
+    fn decode(raw: [u8; HEADER_COPY_SIZE]) -> Option<(u8, Header)> {
+        let [sequence, head, count, ..] = raw;
+        let header = Header { head, count };
+        (header.encode(sequence) == raw).then_some((sequence, header))
+    }
+}
+
+/// Header copy in use and where the next update goes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct CurrentHeader {
+    header: Header,
+    /// Copy holding `header`, overwritten by the update after next
+    copy: usize,
+    sequence: u8,
+}
+
+/// Layout of a circular event log in a region of NVRAM
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct EventLog {
+    offset: u8,
+    payload_size: u8,
+    capacity: u8,
+}
+
+impl EventLog {
+    /// Log of `capacity` records with `payload_size` payload bytes each,
+    /// starting at NVRAM `offset`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` is zero or the log would need more than 256
+    /// bytes.
+    pub const fn new(offset: u8, payload_size: u8, capacity: u8) -> Self {
+        assert!(capacity > 0, "event log needs at least one record");
+        let log = Self {
+            offset,
+            payload_size,
+            capacity,
+        };
+        assert!(log.len_bytes() <= 256, "event log too large");
+        log
+    }
+
+    /// Size of one record in bytes
+    pub const fn record_size(&self) -> usize {
+        TIMESTAMP_SIZE + self.payload_size as usize
+    }
+
+    /// Maximum number of records kept
+    pub const fn capacity(&self) -> u8 {
+        self.capacity
+    }
+
+    /// NVRAM bytes covered by the log
+    pub const fn len_bytes(&self) -> usize {
+        LOG_HEADER_SIZE + self.record_size() * self.capacity as usize
+    }
+
+    fn check_layout<R: RtcNvram>(&self, rtc: &R) -> Result<(), LogError<R::Error>> {
+        // `new` rules out an empty log, but checking again here lets the
+        // compiler prove the ring arithmetic below can't divide by zero
+        if self.capacity == 0 || self.offset as usize + self.len_bytes() > rtc.nvram_size() as usize
+        {
+            return Err(LogError::InvalidLayout);
+        }
+        Ok(())
+    }
+
+    /// NVRAM offset of the record in ring position `position`.
+    fn record_offset(&self, position: u8) -> u8 {
+        (self.offset as usize + LOG_HEADER_SIZE + position as usize * self.record_size()) as u8
+    }
+
+    /// Ring position `steps` records after `head`.
+    fn ring_position(&self, head: u8, steps: u8) -> u8 {
+        // `new` rules out a zero capacity; `max` lets the compiler see it
+        ((head as usize + steps as usize) % self.capacity.max(1) as usize) as u8
+    }
+
+    /// The first `record_size` bytes of `buffer`.
+    ///
+    /// Records always fit since `new` limits the whole log to 256 bytes.
+    fn record_buffer<'a, E>(&self, buffer: &'a mut [u8; 256]) -> Result<&'a mut [u8], LogError<E>> {
+        buffer
+            .get_mut(..self.record_size())
+            .ok_or(LogError::InvalidLayout)
+    }
+
+    /// The newer of the header copies that check out, if any.
+    fn current_header<R: RtcNvram>(
+        &self,
+        rtc: &mut R,
+    ) -> Result<Option<CurrentHeader>, LogError<R::Error>> {
+        self.check_layout(rtc)?;
+        let mut raw = [0u8; LOG_HEADER_SIZE];
+        rtc.read_nvram(self.offset, &mut raw)
+            .map_err(LogError::Rtc)?;
+
+        let mut current: Option<CurrentHeader> = None;
+        for (copy, chunk) in raw.chunks_exact(HEADER_COPY_SIZE).enumerate() {
+            let mut stored = [0u8; HEADER_COPY_SIZE];
+            stored.copy_from_slice(chunk);
+            let Some((sequence, header)) = Header::decode(stored) else {
+                continue;
+            };
+            if header.head >= self.capacity || header.count > self.capacity {
+                continue;
+            }
+            // The copies are written alternately, so the newer one is one
+            // sequence number ahead
+            let newer = current.is_none_or(|other| sequence == other.sequence.wrapping_add(1));
+            if newer {
+                current = Some(CurrentHeader {
+                    header,
+                    copy,
+                    sequence,
+                });
+            }
+        }
+        Ok(current)
+    }
+
+    fn read_header<R: RtcNvram>(&self, rtc: &mut R) -> Result<Header, LogError<R::Error>> {
+        Ok(self
+            .current_header(rtc)?
+            .map_or(Header::EMPTY, |current| current.header))
+    }
+
+    /// Store `header` over the older copy, leaving `current` intact.
+    fn write_header<R: RtcNvram>(
+        &self,
+        rtc: &mut R,
+        current: Option<CurrentHeader>,
+        header: Header,
+    ) -> Result<(), LogError<R::Error>> {
+        let (copy, sequence) = match current {
+            Some(current) => (1 - current.copy, current.sequence.wrapping_add(1)),
+            None => (0, 0),
+        };
+        let offset = self.offset as usize + copy * HEADER_COPY_SIZE;
+        rtc.write_nvram(offset as u8, &header.encode(sequence))
+            .map_err(LogError::Rtc)
+    }
+
+    /// Number of records currently in the log.
+    pub fn len<R: RtcNvram>(&self, rtc: &mut R) -> Result<u8, LogError<R::Error>> {
+        Ok(self.read_header(rtc)?.count)
+    }
+
+    /// Remove all records.
+    pub fn clear<R: RtcNvram>(&self, rtc: &mut R) -> Result<(), LogError<R::Error>> {
+        let current = self.current_header(rtc)?;
+        self.write_header(rtc, current, Header::EMPTY)
+    }
+
+    /// Append an event stamped with the current RTC time.
+    pub fn log<R: RtcNvram>(&self, rtc: &mut R, payload: &[u8]) -> Result<(), LogError<R::Error>> {
+        let now = rtc.get_datetime().map_err(LogError::Rtc)?;
+        let timestamp = calendar::to_unix_timestamp(&now).min(u32::MAX as u64) as u32;
+        self.log_at(rtc, timestamp, payload)
+    }
+
+    /// Append an event with an explicit Unix timestamp.
+    ///
+    /// Overwrites the oldest record when the log is full.
+    pub fn log_at<R: RtcNvram>(
+        &self,
+        rtc: &mut R,
+        timestamp: u32,
+        payload: &[u8],
+    ) -> Result<(), LogError<R::Error>> {
+        if payload.len() != self.payload_size as usize {
+            return Err(LogError::PayloadSize);
+        }
+        let current = self.current_header(rtc)?;
+        let header = current.map_or(Header::EMPTY, |current| current.header);
+
+        let position = self.ring_position(header.head, header.count);
+        let mut buffer = [0u8; 256];
+        let record = self.record_buffer(&mut buffer)?;
+        let (stamp, data) = record.split_at_mut(TIMESTAMP_SIZE);
+        stamp.copy_from_slice(&timestamp.to_be_bytes());
+        data.copy_from_slice(payload);
+        rtc.write_nvram(self.record_offset(position), record)
+            .map_err(LogError::Rtc)?;
+
+        let header = if header.count < self.capacity {
+            Header {
+                head: header.head,
+                count: header.count + 1,
+            }
+        } else {
+            Header {
+                head: self.ring_position(header.head, 1),
+                count: header.count,
+            }
+        };
+        self.write_header(rtc, current, header)
+    }
+
+    /// Read record `index` (0 = oldest) into `payload`, returning its
+    /// Unix timestamp.
+    pub fn read<R: RtcNvram>(
+        &self,
+        rtc: &mut R,
+        index: u8,
+        payload: &mut [u8],
+    ) -> Result<u32, LogError<R::Error>> {
+        if payload.len() != self.payload_size as usize {
+            return Err(LogError::PayloadSize);
+        }
+        let header = self.read_header(rtc)?;
+        if index >= header.count {
+            return Err(LogError::IndexOutOfRange);
+        }
+
+        let position = self.ring_position(header.head, index);
+        let mut buffer = [0u8; 256];
+        let record = self.record_buffer(&mut buffer)?;
+        rtc.read_nvram(self.record_offset(position), record)
+            .map_err(LogError::Rtc)?;
+
+        let (stamp, data) = record.split_at(TIMESTAMP_SIZE);
+        payload.copy_from_slice(data);
+        Ok(u32::from_be_bytes([stamp[0], stamp[1], stamp[2], stamp[3]]))
+    }
+
+    /// Read record `index` counting back from the newest (0 = newest).
+    pub fn read_latest<R: RtcNvram>(
+        &self,
+        rtc: &mut R,
+        index: u8,
+        payload: &mut [u8],
+    ) -> Result<u32, LogError<R::Error>> {
+        let count = self.len(rtc)?;
+        if index >= count {
+            return Err(LogError::IndexOutOfRange);
+        }
+        self.read(rtc, count - 1 - index, payload)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blob::tests::MemNvram;
+
+    #[test]
+    fn test_fresh_sram_reads_as_empty() {
+        let mut rtc = MemNvram::new(236);
+        let log = EventLog::new(0, 1, 4);
+
+        assert_eq!(log.len(&mut rtc), Ok(0));
+        assert_eq!(
+            log.read(&mut rtc, 0, &mut [0]),
+            Err(LogError::IndexOutOfRange)
+        );
+    }
+
+    #[test]
+    fn test_log_wraps_and_keeps_newest() {
+        let mut rtc = MemNvram::new(236);
+        let log = EventLog::new(10, 1, 3);
+
+        for i in 0..5u8 {
+            log.log_at(&mut rtc, 1000 + i as u32, &[i]).unwrap();
+        }
+        assert_eq!(log.len(&mut rtc), Ok(3));
+
+        let mut payload = [0u8];
+        assert_eq!(log.read(&mut rtc, 0, &mut payload), Ok(1002));
+        assert_eq!(payload, [2]);
+        assert_eq!(log.read(&mut rtc, 2, &mut payload), Ok(1004));
+        assert_eq!(payload, [4]);
+        assert_eq!(log.read_latest(&mut rtc, 1, &mut payload), Ok(1003));
+
+        log.clear(&mut rtc).unwrap();
+        assert_eq!(log.len(&mut rtc), Ok(0));
+    }
+
+    #[test]
+    fn test_log_uses_rtc_time_and_survives_reopen() {
+        let mut rtc = MemNvram::new(236);
+        EventLog::new(0, 2, 4).log(&mut rtc, &[0xAB, 0xCD]).unwrap();
+
+        // A new handle with the same layout sees the persisted record
+        let log = EventLog::new(0, 2, 4);
+        let mut payload = [0u8; 2];
+        // 2025-08-21 14:30:00 UTC
+        assert_eq!(log.read(&mut rtc, 0, &mut payload), Ok(1_755_786_600));
+        assert_eq!(payload, [0xAB, 0xCD]);
+    }
+
+    #[test]
+    fn test_torn_header_write_keeps_previous_header() {
+        let mut rtc = MemNvram::new(236);
+        let log = EventLog::new(0, 1, 4);
+        for i in 0..3u8 {
+            log.log_at(&mut rtc, 1000 + i as u32, &[i]).unwrap();
+        }
+
+        // Power failed half way through the header update of a fourth append
+        let raw = rtc.ram[..LOG_HEADER_SIZE].to_vec();
+        log.log_at(&mut rtc, 1003, &[3]).unwrap();
+        assert_eq!(log.len(&mut rtc), Ok(4));
+        // The update went to the second copy; its CRC was never written
+        assert_eq!(rtc.ram[..HEADER_COPY_SIZE], raw[..HEADER_COPY_SIZE]);
+        let torn = LOG_HEADER_SIZE - 2;
+        rtc.ram[torn..LOG_HEADER_SIZE].copy_from_slice(&raw[torn..]);
+
+        assert_eq!(log.len(&mut rtc), Ok(3));
+        let mut payload = [0u8];
+        assert_eq!(log.read_latest(&mut rtc, 0, &mut payload), Ok(1002));
+
+        // Appending again carries on from the intact copy
+        log.log_at(&mut rtc, 1004, &[4]).unwrap();
+        assert_eq!(log.len(&mut rtc), Ok(4));
+        assert_eq!(log.read_latest(&mut rtc, 0, &mut payload), Ok(1004));
+    }
+
+    #[test]
+    fn test_layout_and_payload_checks() {
+        let mut rtc = MemNvram::new(236);
+
+        assert_eq!(
+            EventLog::new(0, 1, 2).log_at(&mut rtc, 0, &[1, 2]),
+            Err(LogError::PayloadSize)
+        );
+        assert_eq!(
+            EventLog::new(230, 1, 2).len(&mut rtc),
+            Err(LogError::InvalidLayout)
+        );
+    }
+}
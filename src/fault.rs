@@ -0,0 +1,299 @@
+//! # Fault Injection
+//!
+//! With the `test-util` feature, [`FaultInjector`] wraps any register
+//! transport and makes it misbehave on demand, so firmware built on this
+//! driver can unit-test its error handling against the driver itself
+//! instead of against hand-written mock sequences:
+//!
+//! ```
+//! use ds3231_rtc::{Ds3231, fault::FaultInjector};
+//! # let i2c = embedded_hal_mock::eh1::i2c::Mock::new(&[]);
+//!
+//! let mut rtc = Ds3231::new(FaultInjector::new(i2c));
+//!
+//! rtc.interface_mut().nack_next(2);
+//! assert!(rtc.get_datetime().is_err());
+//! assert!(rtc.get_datetime().is_err());
+//!
+//! // Corrupt the minutes in the next read
+//! rtc.interface_mut().flip_next_read(0x01, 0x40);
+//! # rtc.release_i2c().into_inner().0.done();
+//! ```
+//!
+//! Three kinds of faults are available:
+//!
+//! - [`nack_next`](FaultInjector::nack_next): the next transfers fail with
+//!   [`FaultError::Nack`] without reaching the wrapped transport
+//! - [`flip_next_read`](FaultInjector::flip_next_read): the next read
+//!   covering a register returns it with bits flipped, like noise on SDA
+//! - [`set_latency_us`](FaultInjector::set_latency_us): every transfer waits
+//!   on the delay first, like a slow or clock-stretching device
+//!
+//! Read-modify-writes are split into their read and write, so both can be
+//! hit by a fault.
+
+use embedded_hal::delay::DelayNs;
+#[cfg(feature = "async")]
+use embedded_hal_async::delay::DelayNs as AsyncDelayNs;
+
+#[cfg(feature = "async")]
+use crate::interface::AsyncRegisterInterface;
+use crate::interface::RegisterInterface;
+
+/// Error of a [`FaultInjector`] transport
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum FaultError<E> {
+    /// Injected with [`FaultInjector::nack_next`]
+    Nack,
+    /// Error of the wrapped transport
+    Inner(E),
+}
+
+impl<E: core::fmt::Display> core::fmt::Display for FaultError<E> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            FaultError::Nack => write!(f, "injected NACK"),
+            FaultError::Inner(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl<E> core::error::Error for FaultError<E> where E: core::fmt::Debug + core::fmt::Display {}
+
+/// Injected NACKs look like a missing device to code inspecting the kind.
+impl<E: embedded_hal::i2c::Error> embedded_hal::i2c::Error for FaultError<E> {
+    fn kind(&self) -> embedded_hal::i2c::ErrorKind {
+        match self {
+            FaultError::Nack => embedded_hal::i2c::ErrorKind::NoAcknowledge(
+                embedded_hal::i2c::NoAcknowledgeSource::Address,
+            ),
+            FaultError::Inner(e) => e.kind(),
+        }
+    }
+}
+
+/// Delay that returns immediately, for injectors without latency
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct NoDelay;
+
+impl DelayNs for NoDelay {
+    fn delay_ns(&mut self, _ns: u32) {}
+}
+
+#[cfg(feature = "async")]
+impl AsyncDelayNs for NoDelay {
+    async fn delay_ns(&mut self, _ns: u32) {}
+}
+
+/// Register transport that injects faults into the wrapped one
+#[derive(Debug)]
+pub struct FaultInjector<T, D = NoDelay> {
+    inner: T,
+    delay: D,
+    nacks: u32,
+    flip: Option<(u8, u8)>,
+    latency_us: u32,
+}
+
+impl<T> FaultInjector<T> {
+    /// Wrap `inner` without latency injection.
+    pub fn new(inner: T) -> Self {
+        Self::with_delay(inner, NoDelay)
+    }
+}
+
+impl<T, D> FaultInjector<T, D> {
+    /// Wrap `inner`, waiting on `delay` for injected latency.
+    pub fn with_delay(inner: T, delay: D) -> Self {
+        Self {
+            inner,
+            delay,
+            nacks: 0,
+            flip: None,
+            latency_us: 0,
+        }
+    }
+
+    /// Fail the next `count` transfers with [`FaultError::Nack`].
+    pub fn nack_next(&mut self, count: u32) {
+        self.nacks = count;
+    }
+
+    /// XOR `mask` into `register` in the next read that covers it.
+    pub fn flip_next_read(&mut self, register: u8, mask: u8) {
+        self.flip = Some((register, mask));
+    }
+
+    /// Wait `latency_us` before every transfer, 0 to stop.
+    pub fn set_latency_us(&mut self, latency_us: u32) {
+        self.latency_us = latency_us;
+    }
+
+    /// Cancel all pending and ongoing faults.
+    pub fn clear(&mut self) {
+        self.nacks = 0;
+        self.flip = None;
+        self.latency_us = 0;
+    }
+
+    /// Returns the wrapped transport.
+    pub fn inner_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+
+    /// Returns the wrapped transport and delay, consuming the injector.
+    pub fn into_inner(self) -> (T, D) {
+        (self.inner, self.delay)
+    }
+
+    /// Consume one pending NACK, if any.
+    fn take_nack(&mut self) -> bool {
+        let nack = self.nacks > 0;
+        self.nacks = self.nacks.saturating_sub(1);
+        nack
+    }
+}
+
+#[maybe_async_cfg::maybe(
+    idents(
+        AsyncDelayNs(sync = "DelayNs", async = "AsyncDelayNs"),
+        AsyncRegisterInterface(sync = "RegisterInterface", async = "AsyncRegisterInterface")
+    ),
+    sync(keep_self),
+    async(feature = "async", keep_self)
+)]
+impl<T: AsyncRegisterInterface, D: AsyncDelayNs> AsyncRegisterInterface for FaultInjector<T, D> {
+    type Error = FaultError<T::Error>;
+
+    async fn read_registers(
+        &mut self,
+        address: u8,
+        register: u8,
+        buffer: &mut [u8],
+    ) -> Result<(), Self::Error> {
+        if self.latency_us > 0 {
+            self.delay.delay_us(self.latency_us).await;
+        }
+        if self.take_nack() {
+            return Err(FaultError::Nack);
+        }
+        self.inner
+            .read_registers(address, register, buffer)
+            .await
+            .map_err(FaultError::Inner)?;
+
+        if let Some((flipped, mask)) = self.flip {
+            let offset = flipped.wrapping_sub(register) as usize;
+            if let Some(value) = buffer.get_mut(offset) {
+                *value ^= mask;
+                self.flip = None;
+            }
+        }
+        Ok(())
+    }
+
+    async fn write_registers(&mut self, address: u8, data: &[u8]) -> Result<(), Self::Error> {
+        if self.latency_us > 0 {
+            self.delay.delay_us(self.latency_us).await;
+        }
+        if self.take_nack() {
+            return Err(FaultError::Nack);
+        }
+        self.inner
+            .write_registers(address, data)
+            .await
+            .map_err(FaultError::Inner)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Ds3231;
+    use crate::error::{Error, Operation};
+    use crate::registers::Register;
+    use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
+    use rtc_hal::datetime::DateTime;
+
+    /// Delay that adds up the requested waits
+    #[derive(Default)]
+    struct TotalDelay {
+        total_us: u32,
+    }
+
+    impl DelayNs for TotalDelay {
+        fn delay_ns(&mut self, ns: u32) {
+            self.total_us += ns / 1000;
+        }
+    }
+
+    fn time_read() -> I2cTransaction {
+        I2cTransaction::write_read(
+            0x68,
+            vec![Register::Seconds.addr()],
+            vec![0x00, 0x30, 0x14, 0x05, 0x21, 0x08, 0x25],
+        )
+    }
+
+    #[test]
+    fn test_injected_nacks() {
+        let expectations = [time_read()];
+        let mut ds3231 = Ds3231::new(FaultInjector::new(I2cMock::new(&expectations)));
+
+        ds3231.interface_mut().nack_next(1);
+        assert_eq!(
+            ds3231.get_datetime(),
+            Err(Error::Transfer {
+                source: FaultError::Nack,
+                register: Register::Seconds.addr(),
+                operation: Operation::Read,
+            })
+        );
+        assert!(ds3231.get_datetime().is_ok());
+
+        let (mut i2c, _) = ds3231.release().into_inner();
+        i2c.done();
+    }
+
+    #[test]
+    fn test_bit_flip_hits_next_read_only() {
+        let expectations = [time_read(), time_read()];
+        let mut ds3231 = Ds3231::new(FaultInjector::new(I2cMock::new(&expectations)));
+
+        ds3231.interface_mut().flip_next_read(0x01, 0x40);
+        assert_eq!(
+            ds3231.get_datetime(),
+            Err(Error::CorruptRegister {
+                register: Register::Minutes,
+                value: 0x70,
+            })
+        );
+        assert_eq!(
+            ds3231.get_datetime(),
+            Ok(DateTime::new(2025, 8, 21, 14, 30, 0).unwrap())
+        );
+
+        let (mut i2c, _) = ds3231.release().into_inner();
+        i2c.done();
+    }
+
+    #[test]
+    fn test_latency() {
+        let expectations = [
+            I2cTransaction::write_read(0x68, vec![Register::Control.addr()], vec![0x1C]),
+            I2cTransaction::write(0x68, vec![Register::Control.addr(), 0x9C]),
+        ];
+        let mut ds3231 = Ds3231::new(FaultInjector::with_delay(
+            I2cMock::new(&expectations),
+            TotalDelay::default(),
+        ));
+
+        ds3231.interface_mut().set_latency_us(500);
+        ds3231.halt_clock().unwrap();
+
+        let (mut i2c, delay) = ds3231.release().into_inner();
+        assert_eq!(delay.total_us, 1000);
+        i2c.done();
+    }
+}
@@ -0,0 +1,267 @@
+//! # Control and Status Flags
+//!
+//! [`ControlFlags`] and [`StatusFlags`] name the bits of the control and
+//! status registers after the datasheet, so code inspecting the device state
+//! reads as `status.contains(StatusFlags::OSF)` instead of `status & 0x80`,
+//! and `Debug` prints `StatusFlags(OSF | A1F)` instead of a number.
+//!
+//! ```no_run
+//! # use ds3231_rtc::Ds3231;
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! # let mut rtc = Ds3231::new(embedded_hal_mock::eh1::i2c::Mock::new(&[]));
+//! use ds3231_rtc::flags::ControlFlags;
+//!
+//! let control = rtc.read_control()?;
+//! if !control.contains(ControlFlags::INTCN) {
+//!     // the INT/SQW pin outputs the square wave, alarms can't interrupt
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! The two-bit rate select field shows up as its individual bits `RS1` and
+//! `RS2`; [`ControlRegister`](crate::registers::ControlRegister) has it as a
+//! number.
+//!
+//! [`StatusFlags`] is also what
+//! [`get_datetime_with_status`](crate::Ds3231::get_datetime_with_status)
+//! returns. The decoded flags of the earlier struct of that name are the
+//! methods [`oscillator_stopped`](StatusFlags::oscillator_stopped),
+//! [`alarm_fired`](StatusFlags::alarm_fired) and [`busy`](StatusFlags::busy),
+//! and the deprecated `status_flags` forwards to `read_status`.
+
+use bitflags::bitflags;
+
+#[cfg(feature = "async")]
+use crate::interface::AsyncRegisterInterface;
+use crate::interface::RegisterInterface;
+
+use crate::Ds3231;
+use crate::alarm::Alarm;
+#[cfg(feature = "async")]
+use crate::ds3231::Ds3231Async;
+use crate::error::Error;
+use crate::registers::{
+    A1F_BIT, A1IE_BIT, A2F_BIT, A2IE_BIT, BBSQW_BIT, BSY_BIT, CONV_BIT, EN32KHZ_BIT, EOSC_BIT,
+    INTCN_BIT, OSF_BIT, Register,
+};
+
+bitflags! {
+    /// Control register (0x0E) bits
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+    pub struct ControlFlags: u8 {
+        /// Oscillator stopped while on battery
+        const EOSC = EOSC_BIT;
+        /// Square wave kept running on battery
+        const BBSQW = BBSQW_BIT;
+        /// Temperature conversion requested
+        const CONV = CONV_BIT;
+        /// Rate select, high bit
+        const RS2 = 1 << 4;
+        /// Rate select, low bit
+        const RS1 = 1 << 3;
+        /// INT/SQW pin signals alarms instead of the square wave
+        const INTCN = INTCN_BIT;
+        /// Alarm 2 drives the INT pin
+        const A2IE = A2IE_BIT;
+        /// Alarm 1 drives the INT pin
+        const A1IE = A1IE_BIT;
+    }
+}
+
+bitflags! {
+    /// Status register (0x0F) bits
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+    pub struct StatusFlags: u8 {
+        /// Oscillator stopped at some point since last cleared; the time is
+        /// invalid and must be set again
+        const OSF = OSF_BIT;
+        /// 32 kHz output enabled
+        const EN32KHZ = EN32KHZ_BIT;
+        /// Temperature conversion in progress
+        const BSY = BSY_BIT;
+        /// Alarm 2 matched
+        const A2F = A2F_BIT;
+        /// Alarm 1 matched
+        const A1F = A1F_BIT;
+    }
+}
+
+impl StatusFlags {
+    /// Decode the raw status register value, keeping unnamed bits.
+    #[deprecated(note = "use `StatusFlags::from_bits_retain`")]
+    pub const fn from_register(status: u8) -> Self {
+        Self::from_bits_retain(status)
+    }
+
+    /// Returns `true` if `alarm` has fired.
+    pub const fn alarm_fired(self, alarm: Alarm) -> bool {
+        self.bits() & alarm.flag_bit() != 0
+    }
+
+    /// Returns `true` if the Oscillator Stop Flag is set.
+    pub const fn oscillator_stopped(self) -> bool {
+        self.contains(Self::OSF)
+    }
+
+    /// Returns `true` while a temperature conversion is in progress.
+    pub const fn busy(self) -> bool {
+        self.contains(Self::BSY)
+    }
+}
+
+/// Write the names of the flags in `flags` as `A | B`
+#[cfg(feature = "defmt")]
+fn format_names<B: bitflags::Flags>(f: defmt::Formatter, flags: &B) {
+    for (i, (name, _)) in flags.iter_names().enumerate() {
+        if i > 0 {
+            defmt::write!(f, " | ");
+        }
+        defmt::write!(f, "{=str}", name);
+    }
+}
+
+#[cfg(feature = "defmt")]
+impl defmt::Format for ControlFlags {
+    fn format(&self, f: defmt::Formatter) {
+        defmt::write!(f, "ControlFlags(");
+        format_names(f, self);
+        defmt::write!(f, ")");
+    }
+}
+
+#[cfg(feature = "defmt")]
+impl defmt::Format for StatusFlags {
+    fn format(&self, f: defmt::Formatter) {
+        defmt::write!(f, "StatusFlags(");
+        format_names(f, self);
+        defmt::write!(f, ")");
+    }
+}
+
+#[cfg(feature = "ufmt")]
+fn uwrite_names<B, W>(f: &mut ufmt::Formatter<'_, W>, name: &str, flags: &B) -> Result<(), W::Error>
+where
+    B: bitflags::Flags,
+    W: ufmt::uWrite + ?Sized,
+{
+    f.write_str(name)?;
+    f.write_char('(')?;
+    for (i, (name, _)) in flags.iter_names().enumerate() {
+        if i > 0 {
+            f.write_str(" | ")?;
+        }
+        f.write_str(name)?;
+    }
+    f.write_char(')')
+}
+
+#[cfg(feature = "ufmt")]
+impl ufmt::uDebug for ControlFlags {
+    fn fmt<W>(&self, f: &mut ufmt::Formatter<'_, W>) -> Result<(), W::Error>
+    where
+        W: ufmt::uWrite + ?Sized,
+    {
+        uwrite_names(f, "ControlFlags", self)
+    }
+}
+
+#[cfg(feature = "ufmt")]
+impl ufmt::uDebug for StatusFlags {
+    fn fmt<W>(&self, f: &mut ufmt::Formatter<'_, W>) -> Result<(), W::Error>
+    where
+        W: ufmt::uWrite + ?Sized,
+    {
+        uwrite_names(f, "StatusFlags", self)
+    }
+}
+
+#[maybe_async_cfg::maybe(
+    idents(
+        AsyncRegisterInterface(sync = "RegisterInterface", async = "AsyncRegisterInterface"),
+        Ds3231(sync = "Ds3231", async = "Ds3231Async")
+    ),
+    sync(keep_self),
+    async(feature = "async")
+)]
+impl<I2C, V> Ds3231<I2C, V>
+where
+    I2C: AsyncRegisterInterface,
+{
+    /// Read the control register.
+    pub async fn read_control(&mut self) -> Result<ControlFlags, Error<I2C::Error>> {
+        let control = self.read_register(Register::Control).await?;
+        Ok(ControlFlags::from_bits_retain(control))
+    }
+
+    /// Read the status register.
+    pub async fn read_status(&mut self) -> Result<StatusFlags, Error<I2C::Error>> {
+        let status = self.read_register(Register::Status).await?;
+        Ok(StatusFlags::from_bits_retain(status))
+    }
+
+    /// Read and decode the status register.
+    #[deprecated(note = "use `read_status`")]
+    pub async fn status_flags(&mut self) -> Result<StatusFlags, Error<I2C::Error>> {
+        self.read_status().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
+
+    const DS3231_ADDR: u8 = 0x68;
+
+    #[test]
+    fn test_read_control_and_status() {
+        let expectations = [
+            I2cTransaction::write_read(DS3231_ADDR, vec![Register::Control.addr()], vec![0x1C]),
+            I2cTransaction::write_read(DS3231_ADDR, vec![Register::Status.addr()], vec![0x8A]),
+        ];
+        let mut ds3231 = Ds3231::new(I2cMock::new(&expectations));
+
+        let control = ds3231.read_control().unwrap();
+        assert_eq!(
+            control,
+            ControlFlags::RS2 | ControlFlags::RS1 | ControlFlags::INTCN
+        );
+
+        let status = ds3231.read_status().unwrap();
+        assert!(status.oscillator_stopped());
+        assert!(status.alarm_fired(Alarm::Two));
+        assert!(!status.alarm_fired(Alarm::One));
+        assert_eq!(format!("{status:?}"), "StatusFlags(OSF | EN32KHZ | A2F)");
+
+        ds3231.release_i2c().done();
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_status_flags_keeps_earlier_api() {
+        let expectations = [I2cTransaction::write_read(
+            DS3231_ADDR,
+            vec![Register::Status.addr()],
+            vec![0x8E],
+        )];
+        let mut ds3231 = Ds3231::new(I2cMock::new(&expectations));
+
+        let flags = ds3231.status_flags().unwrap();
+        assert_eq!(flags, StatusFlags::from_register(0x8E));
+        assert!(flags.oscillator_stopped());
+        assert!(flags.busy());
+        assert!(flags.alarm_fired(Alarm::Two));
+        assert!(!flags.alarm_fired(Alarm::One));
+
+        ds3231.release_i2c().done();
+    }
+
+    #[cfg(feature = "ufmt")]
+    #[test]
+    fn test_ufmt_debug() {
+        let mut s = String::new();
+        ufmt::uwrite!(s, "{:?}", StatusFlags::OSF | StatusFlags::A1F).unwrap();
+        assert_eq!(s, "StatusFlags(OSF | A1F)");
+    }
+}
@@ -0,0 +1,166 @@
+//! # GPS Time
+//!
+//! GNSS receivers often report time natively as a GPS week number plus the
+//! time of week (TOW) in seconds, counted from the GPS epoch
+//! (1980-01-06T00:00:00 UTC). GPS time does not apply leap seconds, so it
+//! runs ahead of UTC by the number of leap seconds inserted since 1980
+//! ([`GPS_UTC_LEAP_SECONDS`] at the time of writing). Receivers broadcast
+//! the current offset, so it is passed explicitly to every conversion.
+//!
+//! The DS3231 is expected to hold UTC. [`Ds3231::set_gps_time`] converts a
+//! GPS time and writes it to the chip in one step.
+
+use rtc_hal::datetime::{DateTime, DateTimeError};
+
+use crate::{Ds3231, calendar, error::Error, interface::RegisterInterface};
+
+/// GPS-UTC leap second offset in effect since 2017-01-01
+pub const GPS_UTC_LEAP_SECONDS: u8 = 18;
+
+/// Unix timestamp of the GPS epoch (1980-01-06T00:00:00 UTC)
+const GPS_EPOCH_UNIX: u64 = 315_964_800;
+
+/// Seconds in one GPS week
+pub const SECONDS_PER_WEEK: u32 = 604_800;
+
+/// A point in GPS time
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct GpsTime {
+    /// Full GPS week number since 1980-01-06 (not truncated to 10 or 13 bits)
+    pub week: u16,
+    /// Seconds into the week (0-604799)
+    pub time_of_week: u32,
+}
+
+impl GpsTime {
+    /// Build a GPS time from a week number and time of week in seconds.
+    ///
+    /// Returns `DateTimeError::InvalidSecond` if `time_of_week` is not less than
+    /// [`SECONDS_PER_WEEK`].
+    pub fn new(week: u16, time_of_week: u32) -> Result<Self, DateTimeError> {
+        if time_of_week >= SECONDS_PER_WEEK {
+            return Err(DateTimeError::InvalidSecond);
+        }
+        Ok(Self { week, time_of_week })
+    }
+
+    /// Convert a UTC datetime into GPS time.
+    ///
+    /// Returns `DateTimeError::InvalidYear` for datetimes before the GPS epoch.
+    pub fn from_utc(datetime: &DateTime, leap_seconds: u8) -> Result<Self, DateTimeError> {
+        let gps_seconds = (calendar::to_unix_timestamp(datetime) + leap_seconds as u64)
+            .checked_sub(GPS_EPOCH_UNIX)
+            .ok_or(DateTimeError::InvalidYear)?;
+
+        let week = u16::try_from(gps_seconds / SECONDS_PER_WEEK as u64)
+            .map_err(|_| DateTimeError::InvalidYear)?;
+        Ok(Self {
+            week,
+            time_of_week: (gps_seconds % SECONDS_PER_WEEK as u64) as u32,
+        })
+    }
+
+    /// Convert this GPS time into a UTC datetime.
+    pub fn to_utc(&self, leap_seconds: u8) -> Result<DateTime, DateTimeError> {
+        let gps_seconds = self.week as u64 * SECONDS_PER_WEEK as u64 + self.time_of_week as u64;
+        let unix = (GPS_EPOCH_UNIX + gps_seconds)
+            .checked_sub(leap_seconds as u64)
+            .ok_or(DateTimeError::InvalidYear)?;
+        calendar::from_unix_timestamp(unix)
+    }
+}
+
+impl<I2C, V> Ds3231<I2C, V>
+where
+    I2C: RegisterInterface,
+{
+    /// Set the RTC to UTC from a GPS week and time of week.
+    ///
+    /// `leap_seconds` is the current GPS-UTC offset as reported by the
+    /// receiver (see [`GPS_UTC_LEAP_SECONDS`]).
+    pub fn set_gps_time(
+        &mut self,
+        gps_time: &GpsTime,
+        leap_seconds: u8,
+    ) -> Result<(), Error<I2C::Error>> {
+        let datetime = gps_time.to_utc(leap_seconds).map_err(Error::DateTime)?;
+        self.set_datetime(&datetime)
+    }
+
+    /// Read the RTC (assumed to hold UTC) as GPS week and time of week.
+    pub fn get_gps_time(&mut self, leap_seconds: u8) -> Result<GpsTime, Error<I2C::Error>> {
+        let datetime = self.get_datetime()?;
+        GpsTime::from_utc(&datetime, leap_seconds).map_err(Error::DateTime)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registers::Register;
+    use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTrans};
+
+    #[test]
+    fn test_gps_epoch() {
+        let dt = DateTime::new(1980, 1, 6, 0, 0, 0).unwrap();
+        assert_eq!(
+            GpsTime::from_utc(&dt, 0).unwrap(),
+            GpsTime {
+                week: 0,
+                time_of_week: 0
+            }
+        );
+    }
+
+    #[test]
+    fn test_gps_known_time() {
+        // 2025-08-21 14:30:00 UTC with 18 leap seconds
+        let dt = DateTime::new(2025, 8, 21, 14, 30, 0).unwrap();
+        let gps = GpsTime::from_utc(&dt, GPS_UTC_LEAP_SECONDS).unwrap();
+        assert_eq!(gps.week, 2380);
+        assert_eq!(gps.time_of_week, 4 * 86_400 + 14 * 3600 + 30 * 60 + 18);
+
+        assert_eq!(gps.to_utc(GPS_UTC_LEAP_SECONDS).unwrap(), dt);
+    }
+
+    #[test]
+    fn test_gps_before_epoch() {
+        let dt = DateTime::new(1980, 1, 5, 23, 59, 59).unwrap();
+        assert_eq!(GpsTime::from_utc(&dt, 0), Err(DateTimeError::InvalidYear));
+    }
+
+    #[test]
+    fn test_gps_new_validates_time_of_week() {
+        assert!(GpsTime::new(2000, SECONDS_PER_WEEK - 1).is_ok());
+        assert_eq!(
+            GpsTime::new(2000, SECONDS_PER_WEEK),
+            Err(DateTimeError::InvalidSecond)
+        );
+    }
+
+    #[test]
+    fn test_set_gps_time() {
+        // 2025-08-21 14:30:00 UTC (Thursday)
+        let expectations = [I2cTrans::write(
+            0x68,
+            vec![
+                Register::Seconds.addr(),
+                0x00,
+                0x30,
+                0x14,
+                0x05,
+                0x21,
+                0x08,
+                0x25,
+            ],
+        )];
+        let mut i2c = I2cMock::new(&expectations);
+        let mut ds3231 = Ds3231::new(&mut i2c);
+
+        let gps = GpsTime::new(2380, 4 * 86_400 + 14 * 3600 + 30 * 60 + 18).unwrap();
+        ds3231.set_gps_time(&gps, GPS_UTC_LEAP_SECONDS).unwrap();
+
+        i2c.done();
+    }
+}
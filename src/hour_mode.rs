@@ -0,0 +1,297 @@
+//! # Hour Mode
+//!
+//! The DS3231 hours register (0x02) can hold the hour either in 24-hour
+//! format (00-23) or in 12-hour format (01-12) with an AM/PM flag.
+//!
+//! In the hours register, bit 6 selects 12-hour mode when set. In 12-hour
+//! mode bit 5 is the PM flag; in 24-hour mode it is the 20-hour digit.
+//!
+//! [`DateTime`](rtc_hal::datetime::DateTime) always uses 24-hour values; this
+//! module converts between that representation and the register encoding.
+//! Reads handle both modes transparently, while the mode used for writes is
+//! selected with [`Ds3231::set_hour_mode`].
+//!
+//! The chip may also have been put into 12-hour mode by other firmware (for
+//! example an Arduino sketch). [`Ds3231::hour_mode`] reports the mode currently
+//! stored, and [`Ds3231::convert_to_24h_mode`] / [`Ds3231::convert_to_12h_mode`]
+//! rewrite the time and alarm hour registers in place without losing the
+//! current time.
+
+use crate::bcd;
+
+#[cfg(feature = "async")]
+use crate::interface::AsyncRegisterInterface;
+use crate::interface::RegisterInterface;
+
+use crate::Ds3231;
+#[cfg(feature = "async")]
+use crate::ds3231::Ds3231Async;
+use crate::error::Error;
+use crate::registers::{HOUR_12H_BIT, HOUR_PM_BIT, HourRegister, Register};
+
+/// Format used by the DS3231 to store hours
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum HourMode {
+    /// 24-hour format (00-23)
+    #[default]
+    TwentyFourHour,
+    /// 12-hour format (01-12) with AM/PM flag
+    TwelveHour,
+}
+
+impl HourMode {
+    /// Hour mode stored in a raw hours register value
+    pub(crate) fn from_register(raw_hour: u8) -> Self {
+        if raw_hour & HOUR_12H_BIT != 0 {
+            HourMode::TwelveHour
+        } else {
+            HourMode::TwentyFourHour
+        }
+    }
+}
+
+/// Decode a raw hours register value (either mode) into a 24-hour value.
+pub(crate) fn decode_hour(raw_hour: u8) -> u8 {
+    match HourMode::from_register(raw_hour) {
+        HourMode::TwelveHour => {
+            // Extract the Hour part (4-0 bits)
+            let hr = bcd::from_bcd(raw_hour & 0b0001_1111);
+            // Extract the AM/PM (5th bit). if it is set, then it is PM
+            let pm = (raw_hour & HOUR_PM_BIT) != 0;
+
+            // Convert it to 24 hour format:
+            match (hr, pm) {
+                (12, false) => 0,    // 12 AM = 00:xx
+                (12, true) => 12,    // 12 PM = 12:xx
+                (h, false) => h,     // 1-11 AM
+                (h, true) => h + 12, // 1-11 PM
+            }
+        }
+        // Extract the hour value from 5-0 bits
+        HourMode::TwentyFourHour => bcd::from_bcd(raw_hour & 0b0011_1111),
+    }
+}
+
+/// Encode a 24-hour value (0-23) into the hours register format for `mode`.
+pub(crate) fn encode_hour(hour: u8, mode: HourMode) -> u8 {
+    match mode {
+        HourMode::TwentyFourHour => bcd::to_bcd(hour) & 0b0011_1111,
+        HourMode::TwelveHour => {
+            let (hr, pm) = match hour {
+                0 => (12, false),
+                1..=11 => (hour, false),
+                12 => (12, true),
+                h => (h - 12, true),
+            };
+            let mut raw = HOUR_12H_BIT | bcd::to_bcd(hr);
+            if pm {
+                raw |= HOUR_PM_BIT;
+            }
+            raw
+        }
+    }
+}
+
+/// Hour registers rewritten by a mode conversion
+const HOUR_REGISTERS: [Register; 3] = [
+    Register::Hours,
+    Register::Alarm1Hours,
+    Register::Alarm2Hours,
+];
+
+#[maybe_async_cfg::maybe(
+    idents(
+        AsyncRegisterInterface(sync = "RegisterInterface", async = "AsyncRegisterInterface"),
+        Ds3231(sync = "Ds3231", async = "Ds3231Async")
+    ),
+    sync(keep_self),
+    async(feature = "async")
+)]
+impl<I2C, V> Ds3231<I2C, V>
+where
+    I2C: AsyncRegisterInterface,
+{
+    /// Read the hour format currently stored in the DS3231 hours register.
+    pub async fn hour_mode(&mut self) -> Result<HourMode, Error<I2C::Error>> {
+        let raw_hour = self.read_register(Register::Hours).await?;
+        Ok(HourMode::from_register(raw_hour))
+    }
+
+    /// Convert the time and alarm hour registers to 24-hour format.
+    ///
+    /// Subsequent writes also use 24-hour format. See [`convert_hour_mode`](Self::convert_hour_mode).
+    pub async fn convert_to_24h_mode(&mut self) -> Result<(), Error<I2C::Error>> {
+        self.convert_hour_mode(HourMode::TwentyFourHour).await
+    }
+
+    /// Convert the time and alarm hour registers to 12-hour format.
+    ///
+    /// Subsequent writes also use 12-hour format. See [`convert_hour_mode`](Self::convert_hour_mode).
+    pub async fn convert_to_12h_mode(&mut self) -> Result<(), Error<I2C::Error>> {
+        self.convert_hour_mode(HourMode::TwelveHour).await
+    }
+
+    /// Rewrite the time and alarm hour registers in the given hour format.
+    ///
+    /// Each register is decoded and re-encoded so the stored hour is preserved;
+    /// the alarm mask bits are left untouched. Registers already in the target
+    /// format are not written. The configured write mode
+    /// (see [`set_hour_mode`](Self::set_hour_mode)) is updated to match.
+    ///
+    /// Avoid calling this right before the top of an hour: the hour could
+    /// roll over between the read and the write of the hours register.
+    pub async fn convert_hour_mode(&mut self, mode: HourMode) -> Result<(), Error<I2C::Error>> {
+        for register in HOUR_REGISTERS {
+            self.modify_register(register, |current| {
+                let mut value = HourRegister::from_bits(current);
+                value.set_hour(value.hour(), mode);
+                value.bits()
+            })
+            .await?;
+        }
+
+        self.hour_mode = mode;
+        Ok(())
+    }
+
+    /// Select the hour format used when writing the time to the DS3231.
+    ///
+    /// This only affects subsequent writes such as
+    /// [`set_datetime`](rtc_hal::rtc::Rtc::set_datetime); reads always decode
+    /// whichever format the chip currently stores. Defaults to
+    /// [`HourMode::TwentyFourHour`].
+    pub fn set_hour_mode(&mut self, mode: HourMode) {
+        self.hour_mode = mode;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
+
+    const DS3231_ADDR: u8 = 0x68;
+
+    #[test]
+    fn test_encode_hour_24h() {
+        assert_eq!(encode_hour(0, HourMode::TwentyFourHour), 0x00);
+        assert_eq!(encode_hour(9, HourMode::TwentyFourHour), 0x09);
+        assert_eq!(encode_hour(23, HourMode::TwentyFourHour), 0x23);
+    }
+
+    #[test]
+    fn test_encode_hour_12h() {
+        // 12 AM
+        assert_eq!(encode_hour(0, HourMode::TwelveHour), 0b0101_0010);
+        // 1 AM
+        assert_eq!(encode_hour(1, HourMode::TwelveHour), 0b0100_0001);
+        // 11 AM
+        assert_eq!(encode_hour(11, HourMode::TwelveHour), 0b0101_0001);
+        // 12 PM
+        assert_eq!(encode_hour(12, HourMode::TwelveHour), 0b0111_0010);
+        // 11 PM
+        assert_eq!(encode_hour(23, HourMode::TwelveHour), 0b0111_0001);
+    }
+
+    #[test]
+    fn test_hour_roundtrip_both_modes() {
+        for hour in 0..24 {
+            for mode in [HourMode::TwentyFourHour, HourMode::TwelveHour] {
+                let raw = encode_hour(hour, mode);
+                assert_eq!(HourMode::from_register(raw), mode);
+                assert_eq!(decode_hour(raw), hour, "hour {hour} in {mode:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_hour_mode_detection() {
+        let expectations = vec![
+            I2cTransaction::write_read(DS3231_ADDR, vec![Register::Hours.addr()], vec![0x15]),
+            I2cTransaction::write_read(
+                DS3231_ADDR,
+                vec![Register::Hours.addr()],
+                vec![0b0110_0011],
+            ),
+        ];
+
+        let mut i2c_mock = I2cMock::new(&expectations);
+        let mut ds3231 = Ds3231::new(&mut i2c_mock);
+
+        assert_eq!(ds3231.hour_mode().unwrap(), HourMode::TwentyFourHour);
+        assert_eq!(ds3231.hour_mode().unwrap(), HourMode::TwelveHour);
+
+        i2c_mock.done();
+    }
+
+    #[test]
+    fn test_convert_to_24h_mode() {
+        let expectations = vec![
+            // 3 PM -> 15
+            I2cTransaction::write_read(
+                DS3231_ADDR,
+                vec![Register::Hours.addr()],
+                vec![0b0110_0011],
+            ),
+            I2cTransaction::write(DS3231_ADDR, vec![Register::Hours.addr(), 0x15]),
+            // Alarm 1: 12 AM with mask bit set -> 00, mask preserved
+            I2cTransaction::write_read(
+                DS3231_ADDR,
+                vec![Register::Alarm1Hours.addr()],
+                vec![0b1101_0010],
+            ),
+            I2cTransaction::write(DS3231_ADDR, vec![Register::Alarm1Hours.addr(), 0x80]),
+            // Alarm 2: already 24h, no write
+            I2cTransaction::write_read(DS3231_ADDR, vec![Register::Alarm2Hours.addr()], vec![0x07]),
+        ];
+
+        let mut i2c_mock = I2cMock::new(&expectations);
+        let mut ds3231 = Ds3231::new(&mut i2c_mock);
+        ds3231.set_hour_mode(HourMode::TwelveHour);
+
+        ds3231.convert_to_24h_mode().unwrap();
+        assert_eq!(ds3231.hour_mode, HourMode::TwentyFourHour);
+
+        i2c_mock.done();
+    }
+
+    #[test]
+    fn test_convert_to_12h_mode() {
+        let expectations = vec![
+            // 23 -> 11 PM
+            I2cTransaction::write_read(DS3231_ADDR, vec![Register::Hours.addr()], vec![0x23]),
+            I2cTransaction::write(DS3231_ADDR, vec![Register::Hours.addr(), 0b0111_0001]),
+            // Alarm 1: 00 -> 12 AM
+            I2cTransaction::write_read(DS3231_ADDR, vec![Register::Alarm1Hours.addr()], vec![0x00]),
+            I2cTransaction::write(DS3231_ADDR, vec![Register::Alarm1Hours.addr(), 0b0101_0010]),
+            // Alarm 2: 12 (noon) with mask -> 12 PM with mask
+            I2cTransaction::write_read(DS3231_ADDR, vec![Register::Alarm2Hours.addr()], vec![0x92]),
+            I2cTransaction::write(DS3231_ADDR, vec![Register::Alarm2Hours.addr(), 0b1111_0010]),
+        ];
+
+        let mut i2c_mock = I2cMock::new(&expectations);
+        let mut ds3231 = Ds3231::new(&mut i2c_mock);
+
+        ds3231.convert_to_12h_mode().unwrap();
+        assert_eq!(ds3231.hour_mode, HourMode::TwelveHour);
+
+        i2c_mock.done();
+    }
+
+    #[test]
+    fn test_convert_hour_mode_read_error() {
+        let expectations = vec![
+            I2cTransaction::write_read(DS3231_ADDR, vec![Register::Hours.addr()], vec![0x00])
+                .with_error(embedded_hal::i2c::ErrorKind::Other),
+        ];
+
+        let mut i2c_mock = I2cMock::new(&expectations);
+        let mut ds3231 = Ds3231::new(&mut i2c_mock);
+
+        assert!(ds3231.convert_to_12h_mode().is_err());
+        assert_eq!(ds3231.hour_mode, HourMode::TwentyFourHour);
+
+        i2c_mock.done();
+    }
+}
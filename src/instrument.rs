@@ -0,0 +1,299 @@
+//! # Instrumentation Hooks
+//!
+//! [`Instrumented`] wraps any register transport and reports every register
+//! it reads or writes, and every failed transfer, to an [`Instrumentation`]
+//! implementation. Telemetry, a black-box recorder of the last accesses or
+//! the assertions of a hardware-in-the-loop test hook in there without
+//! touching the driver:
+//!
+//! ```no_run
+//! # use ds3231_rtc::Ds3231;
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! # let i2c = embedded_hal_mock::eh1::i2c::Mock::new(&[]);
+//! use ds3231_rtc::instrument::{Instrumentation, Instrumented};
+//!
+//! struct WriteCounter(u32);
+//!
+//! impl Instrumentation for WriteCounter {
+//!     fn on_write(&mut self, _register: u8, _value: u8) {
+//!         self.0 += 1;
+//!     }
+//! }
+//!
+//! let mut counter = WriteCounter(0);
+//! let mut rtc = Ds3231::new(Instrumented::new(i2c, &mut counter));
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Hooks are called once per register, after the transfer succeeded, so a
+//! burst read of the seven timekeeping registers calls
+//! [`on_read`](Instrumentation::on_read) seven times. A read-modify-write
+//! goes through the wrapped transport's
+//! [`modify_register`](RegisterInterface::modify_register), keeping an atomic
+//! override atomic, and reports the read and, if the value changed, the
+//! write.
+
+use core::fmt::Debug;
+
+#[cfg(feature = "async")]
+use crate::interface::AsyncRegisterInterface;
+use crate::interface::RegisterInterface;
+
+use crate::error::Operation;
+
+/// Callbacks for register accesses made through [`Instrumented`]
+///
+/// Every method defaults to doing nothing, so an implementation only
+/// overrides the events it cares about.
+pub trait Instrumentation {
+    /// `register` was read and held `value`.
+    fn on_read(&mut self, register: u8, value: u8) {
+        let _ = (register, value);
+    }
+
+    /// `value` was written to `register`.
+    fn on_write(&mut self, register: u8, value: u8) {
+        let _ = (register, value);
+    }
+
+    /// An `operation` starting at `register` failed with `error`.
+    fn on_error(&mut self, register: u8, operation: Operation, error: &dyn Debug) {
+        let _ = (register, operation, error);
+    }
+}
+
+/// Hooks borrowed for the lifetime of the transport
+impl<H: Instrumentation + ?Sized> Instrumentation for &mut H {
+    fn on_read(&mut self, register: u8, value: u8) {
+        H::on_read(self, register, value);
+    }
+
+    fn on_write(&mut self, register: u8, value: u8) {
+        H::on_write(self, register, value);
+    }
+
+    fn on_error(&mut self, register: u8, operation: Operation, error: &dyn Debug) {
+        H::on_error(self, register, operation, error);
+    }
+}
+
+/// Register transport that reports the accesses of the wrapped one
+#[derive(Debug)]
+pub struct Instrumented<T, H> {
+    inner: T,
+    hooks: H,
+}
+
+impl<T, H> Instrumented<T, H> {
+    /// Wrap `inner`, reporting its accesses to `hooks`.
+    pub fn new(inner: T, hooks: H) -> Self {
+        Self { inner, hooks }
+    }
+
+    /// Returns the hooks.
+    pub fn hooks(&self) -> &H {
+        &self.hooks
+    }
+
+    /// Returns the hooks mutably, e.g. to reset a recorder.
+    pub fn hooks_mut(&mut self) -> &mut H {
+        &mut self.hooks
+    }
+
+    /// Returns the wrapped transport and hooks, consuming the adapter.
+    pub fn into_inner(self) -> (T, H) {
+        (self.inner, self.hooks)
+    }
+}
+
+#[maybe_async_cfg::maybe(
+    idents(AsyncRegisterInterface(sync = "RegisterInterface", async = "AsyncRegisterInterface")),
+    sync(keep_self),
+    async(feature = "async", keep_self)
+)]
+impl<T: AsyncRegisterInterface, H: Instrumentation> AsyncRegisterInterface for Instrumented<T, H> {
+    type Error = T::Error;
+
+    async fn read_registers(
+        &mut self,
+        address: u8,
+        register: u8,
+        buffer: &mut [u8],
+    ) -> Result<(), Self::Error> {
+        match self.inner.read_registers(address, register, buffer).await {
+            Ok(()) => {
+                for (offset, &value) in buffer.iter().enumerate() {
+                    self.hooks
+                        .on_read(register.wrapping_add(offset as u8), value);
+                }
+                Ok(())
+            }
+            Err(e) => {
+                self.hooks.on_error(register, Operation::Read, &e);
+                Err(e)
+            }
+        }
+    }
+
+    async fn write_registers(&mut self, address: u8, data: &[u8]) -> Result<(), Self::Error> {
+        let register = data.first().copied().unwrap_or_default();
+        match self.inner.write_registers(address, data).await {
+            Ok(()) => {
+                for (offset, &value) in data.iter().skip(1).enumerate() {
+                    self.hooks
+                        .on_write(register.wrapping_add(offset as u8), value);
+                }
+                Ok(())
+            }
+            Err(e) => {
+                self.hooks.on_error(register, Operation::Write, &e);
+                Err(e)
+            }
+        }
+    }
+
+    async fn modify_register(
+        &mut self,
+        address: u8,
+        register: u8,
+        f: impl FnOnce(u8) -> u8,
+    ) -> Result<(), Self::Error> {
+        let mut values = None;
+        let result = self
+            .inner
+            .modify_register(address, register, |value| {
+                let new_value = f(value);
+                values = Some((value, new_value));
+                new_value
+            })
+            .await;
+
+        // A failed write still means the read went through
+        if let Some((value, new_value)) = values {
+            self.hooks.on_read(register, value);
+            if result.is_ok() && new_value != value {
+                self.hooks.on_write(register, new_value);
+            }
+        }
+        if let Err(e) = &result {
+            self.hooks.on_error(register, Operation::Modify, e);
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Ds3231;
+    use crate::registers::Register;
+    use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
+
+    #[derive(Debug, PartialEq)]
+    enum Event {
+        Read(u8, u8),
+        Write(u8, u8),
+        Error(u8, Operation),
+    }
+
+    /// Hooks that record every event
+    #[derive(Default)]
+    struct Recorder {
+        events: Vec<Event>,
+    }
+
+    impl Instrumentation for Recorder {
+        fn on_read(&mut self, register: u8, value: u8) {
+            self.events.push(Event::Read(register, value));
+        }
+
+        fn on_write(&mut self, register: u8, value: u8) {
+            self.events.push(Event::Write(register, value));
+        }
+
+        fn on_error(&mut self, register: u8, operation: Operation, _error: &dyn Debug) {
+            self.events.push(Event::Error(register, operation));
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "temperature")]
+    fn test_reports_each_register() {
+        use embedded_hal::i2c::ErrorKind;
+
+        let expectations = [
+            I2cTransaction::write_read(0x68, vec![Register::TempMsb.addr()], vec![0x19, 0x40]),
+            I2cTransaction::write_read(0x68, vec![Register::Control.addr()], vec![0x1C]),
+            I2cTransaction::write(0x68, vec![Register::Control.addr(), 0x9C]),
+            // Already halted: read only
+            I2cTransaction::write_read(0x68, vec![Register::Control.addr()], vec![0x9C]),
+            I2cTransaction::write_read(0x68, vec![Register::Status.addr()], vec![0x00])
+                .with_error(ErrorKind::Other),
+        ];
+        let mut recorder = Recorder::default();
+        let mut ds3231 = Ds3231::new(Instrumented::new(
+            I2cMock::new(&expectations),
+            &mut recorder,
+        ));
+
+        ds3231.temperature_quarters().unwrap();
+        ds3231.halt_clock().unwrap();
+        ds3231.halt_clock().unwrap();
+        assert!(ds3231.oscillator_stopped().is_err());
+
+        let (mut i2c, _) = ds3231.release().into_inner();
+        i2c.done();
+        assert_eq!(
+            recorder.events,
+            [
+                Event::Read(0x11, 0x19),
+                Event::Read(0x12, 0x40),
+                Event::Read(0x0E, 0x1C),
+                Event::Write(0x0E, 0x9C),
+                Event::Read(0x0E, 0x9C),
+                Event::Error(0x0F, Operation::Read),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_burst_write_reports_each_register() {
+        let expectations = [I2cTransaction::write(
+            0x68,
+            vec![
+                Register::Seconds.addr(),
+                0x00,
+                0x30,
+                0x14,
+                0x05,
+                0x21,
+                0x08,
+                0x25,
+            ],
+        )];
+        let mut ds3231 = Ds3231::new(Instrumented::new(
+            I2cMock::new(&expectations),
+            Recorder::default(),
+        ));
+
+        ds3231
+            .set_datetime(&rtc_hal::datetime::DateTime::new(2025, 8, 21, 14, 30, 0).unwrap())
+            .unwrap();
+
+        let (mut i2c, recorder) = ds3231.release().into_inner();
+        i2c.done();
+        assert_eq!(
+            recorder.events,
+            [
+                Event::Write(0x00, 0x00),
+                Event::Write(0x01, 0x30),
+                Event::Write(0x02, 0x14),
+                Event::Write(0x03, 0x05),
+                Event::Write(0x04, 0x21),
+                Event::Write(0x05, 0x08),
+                Event::Write(0x06, 0x25),
+            ]
+        );
+    }
+}
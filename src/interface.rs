@@ -0,0 +1,374 @@
+//! # Register Interface
+//!
+//! The driver core only ever reads and writes runs of consecutive registers.
+//! [`RegisterInterface`] captures exactly that, so the same driver runs on
+//! any transport:
+//!
+//! - every `embedded-hal` [`I2c`] bus (DS3231, DS3232) through a blanket impl
+//! - [`SpiInterface`](crate::ds3234::SpiInterface) for the DS3234
+//! - [`SharedBus`](crate::shared::SharedBus) (`critical-section` feature)
+//!   for a bus shared with interrupt handlers
+//! - [`Retry`](crate::retry::Retry), repeating failed transfers of another
+//!   transport
+//! - [`Instrumented`](crate::instrument::Instrumented), reporting the
+//!   accesses of another transport to user hooks
+//! - `fault::FaultInjector` (`test-util` feature), making another transport
+//!   fail on demand
+//! - user implementations, e.g. to log or count transfers, inject faults or
+//!   simulate the chip in host tests
+//!
+//! ## Fakes for host tests
+//!
+//! Application tests rarely care about the bytes on the bus, only about what
+//! the chip reports. Instead of scripting an `I2cTransaction` for every
+//! transfer, a test can hand the driver a register file of its own and set
+//! up each scenario by poking registers:
+//!
+//! ```
+//! use ds3231_rtc::{Ds3231, RegisterInterface};
+//!
+//! struct FakeRtc([u8; 0x13]);
+//!
+//! impl RegisterInterface for FakeRtc {
+//!     type Error = ();
+//!
+//!     fn read_registers(&mut self, _: u8, register: u8, buf: &mut [u8]) -> Result<(), ()> {
+//!         let start = register as usize;
+//!         buf.copy_from_slice(self.0.get(start..start + buf.len()).ok_or(())?);
+//!         Ok(())
+//!     }
+//!
+//!     fn write_registers(&mut self, _: u8, data: &[u8]) -> Result<(), ()> {
+//!         let (&register, values) = data.split_first().ok_or(())?;
+//!         let start = register as usize;
+//!         self.0.get_mut(start..start + values.len()).ok_or(())?.copy_from_slice(values);
+//!         Ok(())
+//!     }
+//! }
+//!
+//! let mut fake = FakeRtc([0; 0x13]);
+//! fake.0[0x0F] = 0x80; // battery failed: OSF set
+//! let mut rtc = Ds3231::new(fake);
+//! assert!(rtc.oscillator_stopped().unwrap());
+//! ```
+//!
+//! The trait is re-exported at the crate root, and [`Register`] gives the
+//! register addresses. For a fake that also keeps time and fires alarms,
+//! `sim::Ds3231Sim` (`sim` feature) implements the `rtc-hal` traits directly.
+//!
+//! [`Register`]: crate::registers::Register
+//!
+//! ## Sharing the bus
+//!
+//! The driver only needs an [`I2c`] implementation, so the shared-bus
+//! devices from [`embedded-hal-bus`](https://crates.io/crates/embedded-hal-bus)
+//! work out of the box: `RefCellDevice` within one thread or task,
+//! `CriticalSectionDevice` when an interrupt handler also uses the bus, and
+//! `AtomicDevice` where busy-waiting on a contended bus is not acceptable.
+//!
+//! ```no_run
+//! # use ds3231_rtc::{Ds3231, error::Error};
+//! # fn main() -> Result<(), Error<embedded_hal::i2c::ErrorKind>> {
+//! # let i2c = embedded_hal_mock::eh1::i2c::Mock::new(&[]);
+//! use core::cell::RefCell;
+//! use embedded_hal_bus::i2c::RefCellDevice;
+//!
+//! let bus = RefCell::new(i2c);
+//! let mut rtc = Ds3231::new(RefCellDevice::new(&bus));
+//! let now = rtc.get_datetime()?;
+//! // Other drivers take their own `RefCellDevice::new(&bus)`
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! These devices lock the bus per transfer only, so a read-modify-write of a
+//! control register is two transfers an interrupt handler can slip between.
+//! [`SharedBus`](crate::shared::SharedBus) takes the bus from the same
+//! mutex as `CriticalSectionDevice` but holds the critical section across
+//! both, which makes it the atomic path; other transports can close the gap
+//! the same way by overriding [`RegisterInterface::modify_register`].
+//!
+//! With the `async` feature, `AsyncRegisterInterface` is the same trait with
+//! `async fn`s, implemented for every `embedded-hal-async` I2C bus and used
+//! by [`asynch::Ds3231`](crate::asynch::Ds3231).
+
+use embedded_hal::i2c::I2c;
+#[cfg(feature = "async")]
+use embedded_hal_async::i2c::I2c as AsyncI2c;
+
+/// Transport for reading and writing DS323x registers
+///
+/// Both methods address a run of registers starting at a given register;
+/// the chip auto-increments the register pointer after each byte.
+#[maybe_async_cfg::maybe(
+    idents(AsyncRegisterInterface(sync = "RegisterInterface", async = "AsyncRegisterInterface")),
+    sync(keep_self),
+    async(feature = "async")
+)]
+#[allow(async_fn_in_trait)]
+pub trait AsyncRegisterInterface {
+    /// Transport error
+    type Error: core::fmt::Debug;
+
+    /// Read `buffer.len()` consecutive registers starting at `register`.
+    ///
+    /// `address` is the device's bus address; transports without addressing
+    /// ignore it.
+    async fn read_registers(
+        &mut self,
+        address: u8,
+        register: u8,
+        buffer: &mut [u8],
+    ) -> Result<(), Self::Error>;
+
+    /// Write consecutive registers: `data[0]` is the first register address,
+    /// followed by the values to store.
+    ///
+    /// `address` is the device's bus address; transports without addressing
+    /// ignore it.
+    async fn write_registers(&mut self, address: u8, data: &[u8]) -> Result<(), Self::Error>;
+
+    /// Replace the value of `register` with `f(value)`, writing only if it
+    /// changed.
+    ///
+    /// The driver routes every read-modify-write of a control or status
+    /// register through this method. `embedded-hal`'s `I2c::transaction`
+    /// takes all its operations up front, so a write computed from the byte
+    /// just read can't be part of the same transaction; the default therefore
+    /// reads and writes in two transfers, and another master or task sharing
+    /// the bus could change the register in between. Transports that can
+    /// hold the bus across both transfers, like
+    /// [`SharedBus`](crate::shared::SharedBus) or one behind an RTOS mutex,
+    /// override this method to make the update atomic.
+    async fn modify_register(
+        &mut self,
+        address: u8,
+        register: u8,
+        f: impl FnOnce(u8) -> u8,
+    ) -> Result<(), Self::Error> {
+        let mut current = [0u8; 1];
+        self.read_registers(address, register, &mut current).await?;
+        let new_value = f(current[0]);
+        if new_value != current[0] {
+            self.write_registers(address, &[register, new_value]).await
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[maybe_async_cfg::maybe(
+    idents(
+        AsyncI2c(sync = "I2c", async = "AsyncI2c"),
+        AsyncRegisterInterface(sync = "RegisterInterface", async = "AsyncRegisterInterface")
+    ),
+    sync(keep_self),
+    async(feature = "async")
+)]
+impl<I2C: AsyncI2c> AsyncRegisterInterface for I2C {
+    type Error = I2C::Error;
+
+    async fn read_registers(
+        &mut self,
+        address: u8,
+        register: u8,
+        buffer: &mut [u8],
+    ) -> Result<(), Self::Error> {
+        self.write_read(address, &[register], buffer).await
+    }
+
+    async fn write_registers(&mut self, address: u8, data: &[u8]) -> Result<(), Self::Error> {
+        self.write(address, data).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Ds3231;
+    use rtc_hal::datetime::DateTime;
+
+    /// Register file in memory, standing in for the chip
+    struct Simulated {
+        registers: [u8; 0x13],
+        transfers: usize,
+    }
+
+    impl RegisterInterface for Simulated {
+        type Error = ();
+
+        fn read_registers(
+            &mut self,
+            _address: u8,
+            register: u8,
+            buffer: &mut [u8],
+        ) -> Result<(), Self::Error> {
+            let start = register as usize;
+            let registers = self.registers.get(start..start + buffer.len()).ok_or(())?;
+            buffer.copy_from_slice(registers);
+            self.transfers += 1;
+            Ok(())
+        }
+
+        fn write_registers(&mut self, _address: u8, data: &[u8]) -> Result<(), Self::Error> {
+            let (&register, values) = data.split_first().ok_or(())?;
+            let start = register as usize;
+            let registers = self
+                .registers
+                .get_mut(start..start + values.len())
+                .ok_or(())?;
+            registers.copy_from_slice(values);
+            self.transfers += 1;
+            Ok(())
+        }
+    }
+
+    /// Transport that holds its bus lock across a whole read-modify-write
+    struct Exclusive {
+        sim: Simulated,
+        locked_updates: usize,
+    }
+
+    impl RegisterInterface for Exclusive {
+        type Error = ();
+
+        fn read_registers(
+            &mut self,
+            address: u8,
+            register: u8,
+            buffer: &mut [u8],
+        ) -> Result<(), Self::Error> {
+            self.sim.read_registers(address, register, buffer)
+        }
+
+        fn write_registers(&mut self, address: u8, data: &[u8]) -> Result<(), Self::Error> {
+            self.sim.write_registers(address, data)
+        }
+
+        fn modify_register(
+            &mut self,
+            _address: u8,
+            register: u8,
+            f: impl FnOnce(u8) -> u8,
+        ) -> Result<(), Self::Error> {
+            let value = self.sim.registers.get_mut(register as usize).ok_or(())?;
+            *value = f(*value);
+            self.locked_updates += 1;
+            Ok(())
+        }
+    }
+
+    mod shared_bus {
+        use core::cell::RefCell;
+
+        use embedded_hal::i2c::I2c;
+        use embedded_hal_bus::i2c::{AtomicDevice, CriticalSectionDevice, RefCellDevice};
+        use embedded_hal_bus::util::AtomicCell;
+        use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
+
+        use crate::Ds3231;
+        use crate::registers::{OSF_BIT, Register};
+
+        /// The RTC and another device take turns on the same bus
+        fn expectations() -> [I2cTransaction; 3] {
+            [
+                I2cTransaction::write_read(0x68, vec![Register::Status.addr()], vec![OSF_BIT]),
+                I2cTransaction::write(0x40, vec![0xF4, 0x27]),
+                I2cTransaction::write_read(0x68, vec![Register::Status.addr()], vec![0x00]),
+            ]
+        }
+
+        fn take_turns<A: I2c, B: I2c>(rtc_bus: A, mut other: B) {
+            let mut rtc = Ds3231::new(rtc_bus);
+            assert!(rtc.oscillator_stopped().unwrap());
+            other.write(0x40, &[0xF4, 0x27]).unwrap();
+            assert!(!rtc.oscillator_stopped().unwrap());
+        }
+
+        #[test]
+        fn test_refcell_device() {
+            let bus = RefCell::new(I2cMock::new(&expectations()));
+            take_turns(RefCellDevice::new(&bus), RefCellDevice::new(&bus));
+            bus.into_inner().done();
+        }
+
+        #[test]
+        fn test_critical_section_device() {
+            let bus = critical_section::Mutex::new(RefCell::new(I2cMock::new(&expectations())));
+            take_turns(
+                CriticalSectionDevice::new(&bus),
+                CriticalSectionDevice::new(&bus),
+            );
+            bus.into_inner().into_inner().done();
+        }
+
+        #[test]
+        fn test_atomic_device() {
+            // AtomicCell can't hand the bus back; the mock clone shares state
+            let mut i2c = I2cMock::new(&expectations());
+            let bus = AtomicCell::new(i2c.clone());
+            take_turns(AtomicDevice::new(&bus), AtomicDevice::new(&bus));
+            i2c.done();
+        }
+    }
+
+    #[test]
+    fn test_driver_on_custom_interface() {
+        let mut ds3231 = Ds3231::new(Simulated {
+            registers: [0; 0x13],
+            transfers: 0,
+        });
+
+        let dt = DateTime::new(2025, 8, 21, 14, 30, 0).unwrap();
+        ds3231.set_datetime(&dt).unwrap();
+        assert_eq!(ds3231.get_datetime().unwrap(), dt);
+
+        ds3231.halt_clock().unwrap();
+        assert!(ds3231.oscillator_stopped().is_ok());
+
+        let sim = ds3231.release_i2c();
+        assert_eq!(
+            &sim.registers[..7],
+            &[0x00, 0x30, 0x14, 0x05, 0x21, 0x08, 0x25]
+        );
+        assert_eq!(sim.registers[0x0E] & crate::registers::EOSC_BIT, 0x80);
+        assert_eq!(sim.transfers, 5);
+    }
+
+    #[test]
+    fn test_scenario_set_up_by_poking_registers() {
+        let mut registers = [0; 0x13];
+        registers[0x0F] = crate::registers::OSF_BIT;
+        let mut ds3231 = Ds3231::new(Simulated {
+            registers,
+            transfers: 0,
+        });
+
+        assert!(ds3231.oscillator_stopped().unwrap());
+        ds3231.clear_oscillator_stop_flag().unwrap();
+        assert!(!ds3231.oscillator_stopped().unwrap());
+    }
+
+    #[test]
+    fn test_read_modify_write_uses_transport_override() {
+        let mut ds3231 = Ds3231::new(Exclusive {
+            sim: Simulated {
+                registers: [0; 0x13],
+                transfers: 0,
+            },
+            locked_updates: 0,
+        });
+
+        ds3231.halt_clock().unwrap();
+        ds3231
+            .start_square_wave(rtc_hal::square_wave::SquareWaveFreq::Hz1024)
+            .unwrap();
+        ds3231.convert_to_12h_mode().unwrap();
+
+        let exclusive = ds3231.release_i2c();
+        assert_eq!(exclusive.sim.registers[0x0E], 0x88);
+        // Control twice, then the time and both alarm hour registers
+        assert_eq!(exclusive.locked_updates, 5);
+        assert_eq!(exclusive.sim.transfers, 0);
+    }
+}
@@ -4,15 +4,108 @@
 #![warn(missing_docs)]
 #![cfg_attr(not(doctest), doc = include_str!("../README.md"))]
 
+#[cfg(all(feature = "std", not(test)))]
+extern crate std;
+
+pub mod alarm;
+#[cfg(feature = "alarms")]
+pub mod alarm_manager;
+#[cfg(feature = "async")]
+pub mod asynch;
+pub mod batch;
+pub mod bcd;
+pub mod blob;
+pub mod boot_counter;
+pub mod builder;
+pub mod cache;
+pub mod calendar;
+pub mod compile_time;
+pub mod const_datetime;
 pub mod control;
 pub mod datetime;
+pub mod delay;
+#[cfg(feature = "fmt")]
+pub mod display;
 mod ds3231;
+pub mod ds3232;
+pub mod ds3234;
+#[cfg(feature = "dst")]
+pub mod dst;
+pub mod dump;
+#[cfg(feature = "eh0")]
+pub mod eh0;
 pub mod error;
+pub mod event_log;
+#[cfg(feature = "test-util")]
+pub mod fault;
+pub mod flags;
+pub mod gps;
+pub mod hour_mode;
+pub mod instrument;
+pub mod interface;
+#[cfg(all(feature = "std", target_os = "linux"))]
+pub mod linux;
+pub mod lock;
+pub mod monotonic;
+pub mod month;
+#[cfg(feature = "nmea")]
+pub mod nmea;
+pub mod packed;
+#[cfg(all(test, feature = "no-panic", not(debug_assertions)))]
+mod panic_free;
+pub mod plausibility;
+pub mod power_loss;
+#[cfg(feature = "raw-registers")]
+pub mod raw;
+pub mod read_strategy;
 pub mod registers;
+pub mod repair;
+pub mod retry;
+#[cfg(feature = "rtic")]
+pub mod rtic;
+#[cfg(feature = "scheduler")]
+pub mod scheduler;
+pub mod self_test;
+#[cfg(feature = "critical-section")]
+pub mod shared;
+#[cfg(feature = "sim")]
+pub mod sim;
+pub mod split;
 pub mod square_wave;
+pub mod state;
+pub mod stats;
+pub mod status;
+pub mod stopwatch;
+#[cfg(feature = "proptest")]
+pub mod strategies;
+pub mod subsecond;
+pub mod telemetry;
+#[cfg(feature = "temperature")]
+pub mod temperature;
+#[cfg(feature = "async")]
+pub mod ticks;
+#[cfg(feature = "embassy-time")]
+pub mod time_driver;
+#[cfg(feature = "alarms")]
+pub mod timer;
+#[cfg(feature = "defmt-timestamp")]
+pub mod timestamp;
+#[cfg(feature = "totp")]
+pub mod totp;
+mod trace;
+pub mod uptime;
+pub mod variant;
+pub mod verify;
+pub mod year_policy;
+pub mod zoned;
 
 // Re-export Ds3231
 pub use ds3231::Ds3231;
 
+// Re-export the transport traits, for implementing fakes and transports
+#[cfg(feature = "async")]
+pub use interface::AsyncRegisterInterface;
+pub use interface::RegisterInterface;
+
 // Re-export RTC HAL
 pub use rtc_hal::{datetime::DateTime, rtc::Rtc};
@@ -0,0 +1,65 @@
+//! # Linux Convenience Layer
+//!
+//! With the `std` feature on Linux, the driver opens an i2c-dev bus itself,
+//! so Raspberry Pi and other single-board computers need no HAL glue:
+//!
+//! ```no_run
+//! use ds3231_rtc::Ds3231;
+//!
+//! fn main() -> Result<(), Box<dyn std::error::Error>> {
+//!     let mut rtc = Ds3231::new_linux("/dev/i2c-1")?;
+//!     println!("{:?}", rtc.get_datetime()?);
+//!     Ok(())
+//! }
+//! ```
+//!
+//! The bus is a [`linux-embedded-hal`](https://crates.io/crates/linux-embedded-hal)
+//! [`I2cdev`], whose errors implement `std::error::Error`. So do the
+//! driver's [`Error`](crate::error::Error) and the other error types of this
+//! crate, so `?` works into `Box<dyn Error>`, `anyhow` and similar.
+//!
+//! On systems where the kernel's `rtc-ds1307` driver has claimed the chip,
+//! unbind it first or the bus reports the address as busy.
+
+use std::path::Path;
+
+pub use linux_embedded_hal::i2cdev::linux::LinuxI2CError;
+pub use linux_embedded_hal::{I2CError, I2cdev};
+
+use crate::Ds3231;
+use crate::ds3232::Ds3232;
+
+impl Ds3231<I2cdev> {
+    /// Open the i2c-dev bus at `path`, e.g. `/dev/i2c-1`, and create a
+    /// driver on it at the default address.
+    pub fn new_linux(path: impl AsRef<Path>) -> Result<Self, LinuxI2CError> {
+        Ok(Ds3231::new(I2cdev::new(path)?))
+    }
+}
+
+impl Ds3232<I2cdev> {
+    /// Open the i2c-dev bus at `path`, e.g. `/dev/i2c-1`, and create a
+    /// DS3232 driver on it at the default address.
+    pub fn new_linux(path: impl AsRef<Path>) -> Result<Self, LinuxI2CError> {
+        Ok(Ds3232::new(I2cdev::new(path)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::Error;
+
+    #[test]
+    fn test_missing_bus_is_an_error() {
+        assert!(Ds3231::new_linux("/dev/i2c-does-not-exist").is_err());
+        assert!(Ds3232::new_linux("/dev/i2c-does-not-exist").is_err());
+    }
+
+    #[test]
+    fn test_errors_box_into_std_error() {
+        fn assert_std_error<T: std::error::Error + Send + Sync + 'static>() {}
+        assert_std_error::<Error<I2CError>>();
+        assert_std_error::<LinuxI2CError>();
+    }
+}
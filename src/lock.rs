@@ -0,0 +1,173 @@
+//! # Lock-Based Access (RTIC)
+//!
+//! The driver holds nothing but the bus and a few configuration bytes, so
+//! [`Ds3231`](crate::Ds3231) (like [`Ds3232`](crate::ds3232::Ds3232) and the
+//! DS3234 driver) is `Send` whenever its bus is. That is all RTIC needs to
+//! place it in a `#[shared]` resource; tasks then reach it through the
+//! resource lock:
+//!
+//! ```ignore
+//! #[shared]
+//! struct Shared {
+//!     rtc: Ds3231<I2c1>,
+//! }
+//!
+//! #[task(binds = EXTI0, shared = [rtc])]
+//! fn rtc_int(mut cx: rtc_int::Context) {
+//!     cx.shared.rtc.lock(|rtc| rtc.clear_alarm_flags()).ok();
+//! }
+//! ```
+//!
+//! Code written against `rtc-hal` traits expects an [`Rtc`] it can call
+//! directly rather than a lock. [`Locked`] bridges the two: it implements
+//! [`Rtc`] by taking the lock for each call, so the lock is held only for
+//! one transfer at a time. [`Lock`] mirrors the signature of RTIC's `Mutex`
+//! trait, so connecting a resource takes three lines:
+//!
+//! ```ignore
+//! use ds3231_rtc::lock::{Lock, Locked};
+//!
+//! impl Lock for shared_resources::rtc_that_needs_to_be_locked<'_> {
+//!     type T = Ds3231<I2c1>;
+//!     fn lock<R>(&mut self, f: impl FnOnce(&mut Self::T) -> R) -> R {
+//!         rtic::Mutex::lock(self, f)
+//!     }
+//! }
+//!
+//! let mut clock = MonotonicRtc::new(Locked::new(cx.shared.rtc));
+//! ```
+//!
+//! The [`split`](crate::split) handles borrow a `RefCell` and are therefore
+//! not `Send`; use them within a single task.
+
+use rtc_hal::datetime::DateTime;
+use rtc_hal::error::ErrorType;
+use rtc_hal::rtc::Rtc;
+
+/// Exclusive access to a value through a closure, as provided by RTIC
+/// resource proxies
+pub trait Lock {
+    /// Value behind the lock
+    type T;
+
+    /// Run `f` with exclusive access to the value.
+    fn lock<R>(&mut self, f: impl FnOnce(&mut Self::T) -> R) -> R;
+}
+
+/// Plain mutable references need no locking.
+impl<T> Lock for &mut T {
+    type T = T;
+
+    fn lock<R>(&mut self, f: impl FnOnce(&mut T) -> R) -> R {
+        f(self)
+    }
+}
+
+/// [`Rtc`] implementation that takes a [`Lock`] for every call
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Locked<L> {
+    lock: L,
+}
+
+impl<L: Lock> Locked<L> {
+    /// Wrap a lock around an RTC.
+    pub fn new(lock: L) -> Self {
+        Self { lock }
+    }
+
+    /// Run `f` on the RTC with the lock held, for calls beyond [`Rtc`].
+    pub fn lock<R>(&mut self, f: impl FnOnce(&mut L::T) -> R) -> R {
+        self.lock.lock(f)
+    }
+
+    /// Returns the wrapped lock.
+    pub fn into_inner(self) -> L {
+        self.lock
+    }
+}
+
+impl<L> ErrorType for Locked<L>
+where
+    L: Lock,
+    L::T: ErrorType,
+{
+    type Error = <L::T as ErrorType>::Error;
+}
+
+impl<L> Rtc for Locked<L>
+where
+    L: Lock,
+    L::T: Rtc,
+{
+    fn get_datetime(&mut self) -> Result<DateTime, Self::Error> {
+        self.lock.lock(|rtc| rtc.get_datetime())
+    }
+
+    fn set_datetime(&mut self, datetime: &DateTime) -> Result<(), Self::Error> {
+        self.lock.lock(|rtc| rtc.set_datetime(datetime))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Ds3231;
+    use crate::ds3232::Ds3232;
+    use crate::ds3234::Ds3234;
+    use crate::registers::Register;
+    use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
+    use embedded_hal_mock::eh1::spi::Mock as SpiMock;
+
+    /// Lock that counts how often it was taken
+    struct CountingLock<'a> {
+        rtc: &'a mut Ds3231<I2cMock>,
+        taken: usize,
+    }
+
+    impl Lock for CountingLock<'_> {
+        type T = Ds3231<I2cMock>;
+
+        fn lock<R>(&mut self, f: impl FnOnce(&mut Self::T) -> R) -> R {
+            self.taken += 1;
+            f(self.rtc)
+        }
+    }
+
+    #[test]
+    fn test_drivers_are_send() {
+        fn assert_send<T: Send>() {}
+
+        assert_send::<Ds3231<I2cMock>>();
+        assert_send::<Ds3232<I2cMock>>();
+        assert_send::<Ds3234<SpiMock<u8>>>();
+        assert_send::<Locked<&mut Ds3231<I2cMock>>>();
+        #[cfg(feature = "async")]
+        assert_send::<crate::asynch::Ds3231<I2cMock>>();
+    }
+
+    #[test]
+    fn test_locked_takes_lock_per_call() {
+        let expectations = [
+            I2cTransaction::write_read(
+                0x68,
+                vec![0x00],
+                vec![0x00, 0x30, 0x14, 0x05, 0x21, 0x08, 0x25],
+            ),
+            I2cTransaction::write_read(0x68, vec![Register::Status.addr()], vec![0x00]),
+        ];
+        let mut rtc = Ds3231::new(I2cMock::new(&expectations));
+        let mut locked = Locked::new(CountingLock {
+            rtc: &mut rtc,
+            taken: 0,
+        });
+
+        assert_eq!(
+            locked.get_datetime(),
+            Ok(DateTime::new(2025, 8, 21, 14, 30, 0).unwrap())
+        );
+        assert_eq!(locked.lock(|rtc| rtc.oscillator_stopped()), Ok(false));
+        assert_eq!(locked.into_inner().taken, 2);
+
+        rtc.release().done();
+    }
+}
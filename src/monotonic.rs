@@ -0,0 +1,170 @@
+//! # Monotonic Read Wrapper
+//!
+//! The RTC can jump backwards: a GPS or NTP re-sync corrects a fast clock, a
+//! user sets the time, or a glitchy bus returns a stale value. Log pipelines
+//! and event ordering usually need timestamps that never decrease.
+//! [`MonotonicRtc`] remembers the latest time it has returned and never
+//! returns anything earlier; each backwards jump is counted and can be
+//! inspected so it isn't silently hidden.
+//!
+//! ```no_run
+//! # use ds3231_rtc::Ds3231;
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! # let i2c = embedded_hal_mock::eh1::i2c::Mock::new(&[]);
+//! use ds3231_rtc::monotonic::MonotonicRtc;
+//! use rtc_hal::rtc::Rtc;
+//!
+//! let mut rtc = MonotonicRtc::new(Ds3231::new(i2c));
+//! let t = rtc.get_datetime()?;  // never earlier than the previous read
+//! if rtc.backwards_jumps() > 0 {
+//!     // The underlying clock went backwards at least once
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use rtc_hal::datetime::DateTime;
+use rtc_hal::error::ErrorType;
+use rtc_hal::rtc::Rtc;
+
+use crate::calendar;
+use crate::error::Error;
+
+/// RTC wrapper whose reads never go backwards.
+#[derive(Debug)]
+pub struct MonotonicRtc<RTC> {
+    rtc: RTC,
+    last: Option<DateTime>,
+    backwards_jumps: u32,
+    last_jump_seconds: Option<u64>,
+}
+
+impl<RTC> MonotonicRtc<RTC> {
+    /// Wrap an RTC.
+    pub fn new(rtc: RTC) -> Self {
+        Self {
+            rtc,
+            last: None,
+            backwards_jumps: 0,
+            last_jump_seconds: None,
+        }
+    }
+
+    /// Latest time returned so far, if any.
+    pub fn last(&self) -> Option<&DateTime> {
+        self.last.as_ref()
+    }
+
+    /// Number of reads where the underlying clock was behind the latest
+    /// returned time.
+    pub fn backwards_jumps(&self) -> u32 {
+        self.backwards_jumps
+    }
+
+    /// Size in seconds of the most recent backwards jump.
+    pub fn last_jump_seconds(&self) -> Option<u64> {
+        self.last_jump_seconds
+    }
+
+    /// Forget the cached time and jump statistics, e.g. after deliberately
+    /// setting the clock backwards.
+    pub fn reset(&mut self) {
+        self.last = None;
+        self.backwards_jumps = 0;
+        self.last_jump_seconds = None;
+    }
+
+    /// Borrow the wrapped RTC.
+    pub fn inner(&mut self) -> &mut RTC {
+        &mut self.rtc
+    }
+
+    /// Returns the wrapped RTC, consuming the wrapper.
+    pub fn into_inner(self) -> RTC {
+        self.rtc
+    }
+}
+
+impl<RTC, E> ErrorType for MonotonicRtc<RTC>
+where
+    RTC: Rtc<Error = Error<E>>,
+    E: core::fmt::Debug,
+{
+    type Error = Error<E>;
+}
+
+impl<RTC, E> Rtc for MonotonicRtc<RTC>
+where
+    RTC: Rtc<Error = Error<E>>,
+    E: core::fmt::Debug,
+{
+    /// Read the time, clamped so it is never earlier than the previous read.
+    fn get_datetime(&mut self) -> Result<DateTime, Self::Error> {
+        let now = self.rtc.get_datetime()?;
+
+        if let Some(last) = self.last {
+            let now_ts = calendar::to_unix_timestamp(&now);
+            let last_ts = calendar::to_unix_timestamp(&last);
+            if now_ts < last_ts {
+                self.backwards_jumps = self.backwards_jumps.saturating_add(1);
+                self.last_jump_seconds = Some(last_ts - now_ts);
+                return Ok(last);
+            }
+        }
+
+        self.last = Some(now);
+        Ok(now)
+    }
+
+    /// Set the underlying clock.
+    ///
+    /// The cached time is kept, so setting the clock backwards makes reads
+    /// hold at the previous latest time until the clock catches up. Call
+    /// [`reset`](MonotonicRtc::reset) to start over instead.
+    fn set_datetime(&mut self, datetime: &DateTime) -> Result<(), Self::Error> {
+        self.rtc.set_datetime(datetime)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Ds3231;
+    use crate::registers::Register;
+    use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTrans};
+
+    fn read(data: [u8; 7]) -> I2cTrans {
+        I2cTrans::write_read(0x68, vec![Register::Seconds.addr()], data.to_vec())
+    }
+
+    #[test]
+    fn test_backwards_jump_is_clamped_and_counted() {
+        let expectations = [
+            // 14:30:10, then 14:29:50 (jumped back 20s), then 14:30:20
+            read([0x10, 0x30, 0x14, 0x05, 0x21, 0x08, 0x25]),
+            read([0x50, 0x29, 0x14, 0x05, 0x21, 0x08, 0x25]),
+            read([0x20, 0x30, 0x14, 0x05, 0x21, 0x08, 0x25]),
+        ];
+        let mut i2c = I2cMock::new(&expectations);
+        let mut rtc = MonotonicRtc::new(Ds3231::new(&mut i2c));
+
+        let first = rtc.get_datetime().unwrap();
+        assert_eq!(first, DateTime::new(2025, 8, 21, 14, 30, 10).unwrap());
+
+        assert_eq!(rtc.get_datetime().unwrap(), first);
+        assert_eq!(rtc.backwards_jumps(), 1);
+        assert_eq!(rtc.last_jump_seconds(), Some(20));
+
+        assert_eq!(
+            rtc.get_datetime().unwrap(),
+            DateTime::new(2025, 8, 21, 14, 30, 20).unwrap()
+        );
+        assert_eq!(rtc.backwards_jumps(), 1);
+
+        rtc.reset();
+        assert_eq!(rtc.last(), None);
+        assert_eq!(rtc.backwards_jumps(), 0);
+
+        i2c.done();
+    }
+}
@@ -0,0 +1,176 @@
+//! # Month
+//!
+//! A strongly typed month of the year, used instead of bare `u8` values
+//! where the driver API takes or returns a month.
+//!
+//! Months are numbered 1-12 (January = 1), matching both [`DateTime`] and
+//! the DS3231 month register.
+
+use rtc_hal::datetime::{DateTime, DateTimeError, days_in_month};
+
+/// Month of the year
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[repr(u8)]
+pub enum Month {
+    /// January (1)
+    January = 1,
+    /// February (2)
+    February = 2,
+    /// March (3)
+    March = 3,
+    /// April (4)
+    April = 4,
+    /// May (5)
+    May = 5,
+    /// June (6)
+    June = 6,
+    /// July (7)
+    July = 7,
+    /// August (8)
+    August = 8,
+    /// September (9)
+    September = 9,
+    /// October (10)
+    October = 10,
+    /// November (11)
+    November = 11,
+    /// December (12)
+    December = 12,
+}
+
+impl Month {
+    /// All months in calendar order
+    pub const ALL: [Month; 12] = [
+        Month::January,
+        Month::February,
+        Month::March,
+        Month::April,
+        Month::May,
+        Month::June,
+        Month::July,
+        Month::August,
+        Month::September,
+        Month::October,
+        Month::November,
+        Month::December,
+    ];
+
+    /// Create a month from its number (1 = January, 12 = December).
+    ///
+    /// Returns `DateTimeError::InvalidMonth` for values outside 1-12.
+    pub const fn from_number(n: u8) -> Result<Self, DateTimeError> {
+        if n == 0 || n > 12 {
+            return Err(DateTimeError::InvalidMonth);
+        }
+        Ok(Self::ALL[(n - 1) as usize])
+    }
+
+    /// Month of the given datetime
+    pub fn of(datetime: &DateTime) -> Self {
+        // DateTime guarantees a valid month
+        Self::ALL[((datetime.month() + 11) % 12) as usize]
+    }
+
+    /// Month number (1 = January, 12 = December)
+    pub const fn to_number(self) -> u8 {
+        self as u8
+    }
+
+    /// Following month, wrapping from December to January
+    pub const fn next(self) -> Self {
+        Self::ALL[(self as u8 % 12) as usize]
+    }
+
+    /// Preceding month, wrapping from January to December
+    pub const fn prev(self) -> Self {
+        Self::ALL[((self as u8 + 10) % 12) as usize]
+    }
+
+    /// Number of days in this month for the given year
+    pub fn days(self, year: u16) -> u8 {
+        days_in_month(year, self as u8)
+    }
+
+    /// English name of the month
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Month::January => "January",
+            Month::February => "February",
+            Month::March => "March",
+            Month::April => "April",
+            Month::May => "May",
+            Month::June => "June",
+            Month::July => "July",
+            Month::August => "August",
+            Month::September => "September",
+            Month::October => "October",
+            Month::November => "November",
+            Month::December => "December",
+        }
+    }
+}
+
+impl From<Month> for u8 {
+    fn from(month: Month) -> Self {
+        month.to_number()
+    }
+}
+
+impl TryFrom<u8> for Month {
+    type Error = DateTimeError;
+
+    fn try_from(n: u8) -> Result<Self, Self::Error> {
+        Month::from_number(n)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_month_number_roundtrip() {
+        for n in 1..=12 {
+            assert_eq!(Month::from_number(n).unwrap().to_number(), n);
+        }
+        assert_eq!(Month::from_number(0), Err(DateTimeError::InvalidMonth));
+        assert_eq!(Month::from_number(13), Err(DateTimeError::InvalidMonth));
+    }
+
+    #[test]
+    fn test_month_next_prev() {
+        assert_eq!(Month::January.next(), Month::February);
+        assert_eq!(Month::December.next(), Month::January);
+        assert_eq!(Month::January.prev(), Month::December);
+        assert_eq!(Month::March.prev(), Month::February);
+
+        for month in Month::ALL {
+            assert_eq!(month.next().prev(), month);
+        }
+    }
+
+    #[test]
+    fn test_month_of_datetime() {
+        let dt = DateTime::new(2025, 12, 1, 0, 0, 0).unwrap();
+        assert_eq!(Month::of(&dt), Month::December);
+
+        let dt = DateTime::new(2025, 1, 1, 0, 0, 0).unwrap();
+        assert_eq!(Month::of(&dt), Month::January);
+    }
+
+    #[test]
+    fn test_month_days() {
+        assert_eq!(Month::February.days(2024), 29);
+        assert_eq!(Month::February.days(2025), 28);
+        assert_eq!(Month::April.days(2025), 30);
+        assert_eq!(Month::December.days(2025), 31);
+    }
+
+    #[test]
+    fn test_month_conversions() {
+        assert_eq!(u8::from(Month::June), 6);
+        assert_eq!(Month::try_from(7), Ok(Month::July));
+        assert_eq!(Month::July.as_str(), "July");
+    }
+}
@@ -0,0 +1,267 @@
+//! # NMEA Time Sync
+//!
+//! A minimal parser (enabled with the `nmea` feature) for the two NMEA 0183
+//! sentences that carry the UTC date and time, so a GPS clock can keep the
+//! DS3231 in sync without pulling in a full NMEA crate:
+//!
+//! - `RMC` (Recommended Minimum): `$GPRMC,hhmmss.ss,A,...,ddmmyy,...*hh`
+//! - `ZDA` (Time & Date): `$GPZDA,hhmmss.ss,dd,mm,yyyy,zz,zz*hh`
+//!
+//! Any talker ID is accepted (`GP`, `GN`, `GL`, ...). The checksum is
+//! required and verified. Fractional seconds are truncated.
+//!
+//! ```no_run
+//! # use ds3231_rtc::Ds3231;
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! # let mut rtc = Ds3231::new(embedded_hal_mock::eh1::i2c::Mock::new(&[]));
+//! # let line = "$GPZDA,201530.00,04,07,2002,00,00*60";
+//! // Feed every line from the receiver; unrelated sentences are ignored
+//! if rtc.set_datetime_from_nmea(line)? {
+//!     // RTC now holds the GPS UTC time
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use rtc_hal::datetime::{DateTime, DateTimeError};
+
+use crate::{Ds3231, error::Error, interface::RegisterInterface};
+
+/// Errors returned by [`parse_datetime`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum NmeaError {
+    /// Missing `$`/`*` framing or a field could not be parsed
+    Malformed,
+    /// The checksum does not match the sentence contents
+    Checksum,
+    /// Not an `RMC` or `ZDA` sentence
+    Unsupported,
+    /// The receiver flagged the data as invalid (`RMC` status `V`) or left
+    /// the time fields empty
+    NoFix,
+    /// The fields were parsed but do not form a valid datetime
+    DateTime(DateTimeError),
+}
+
+impl core::fmt::Display for NmeaError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            NmeaError::Malformed => write!(f, "Malformed NMEA sentence"),
+            NmeaError::Checksum => write!(f, "NMEA checksum mismatch"),
+            NmeaError::Unsupported => write!(f, "Unsupported NMEA sentence"),
+            NmeaError::NoFix => write!(f, "NMEA sentence has no valid time"),
+            NmeaError::DateTime(e) => write!(f, "Invalid date/time values: {e}"),
+        }
+    }
+}
+
+impl core::error::Error for NmeaError {}
+
+/// Parse a run of up to four ASCII digits.
+fn digits(s: &str) -> Result<u16, NmeaError> {
+    if s.is_empty() || s.len() > 4 {
+        return Err(NmeaError::Malformed);
+    }
+    s.bytes().try_fold(0u16, |acc, b| {
+        if b.is_ascii_digit() {
+            Ok(acc * 10 + (b - b'0') as u16)
+        } else {
+            Err(NmeaError::Malformed)
+        }
+    })
+}
+
+/// Parse a two-digit field starting at `at`.
+fn two_digits(s: &str, at: usize) -> Result<u8, NmeaError> {
+    let field = s.get(at..at + 2).ok_or(NmeaError::Malformed)?;
+    Ok(digits(field)? as u8)
+}
+
+/// Parse `hhmmss[.sss]` into hour, minute and second.
+fn parse_time(field: &str) -> Result<(u8, u8, u8), NmeaError> {
+    if field.is_empty() {
+        return Err(NmeaError::NoFix);
+    }
+    let whole = field.split('.').next().unwrap_or(field);
+    if whole.len() != 6 {
+        return Err(NmeaError::Malformed);
+    }
+    Ok((
+        two_digits(whole, 0)?,
+        two_digits(whole, 2)?,
+        two_digits(whole, 4)?,
+    ))
+}
+
+/// Verify the framing and checksum, returning the body between `$` and `*`.
+fn checked_body(sentence: &str) -> Result<&str, NmeaError> {
+    let sentence = sentence.trim_end_matches(['\r', '\n']);
+    let rest = sentence.strip_prefix('$').ok_or(NmeaError::Malformed)?;
+    let (body, checksum) = rest.split_once('*').ok_or(NmeaError::Malformed)?;
+
+    if checksum.len() != 2 {
+        return Err(NmeaError::Malformed);
+    }
+    let expected = u8::from_str_radix(checksum, 16).map_err(|_| NmeaError::Malformed)?;
+    let actual = body.bytes().fold(0u8, |acc, b| acc ^ b);
+    if actual != expected {
+        return Err(NmeaError::Checksum);
+    }
+    Ok(body)
+}
+
+/// Extract the UTC date and time from an `RMC` or `ZDA` sentence.
+///
+/// `RMC` carries a two-digit year, which is interpreted as 2000-2099.
+pub fn parse_datetime(sentence: &str) -> Result<DateTime, NmeaError> {
+    let body = checked_body(sentence)?;
+    let mut fields = body.split(',');
+
+    let address = fields.next().ok_or(NmeaError::Malformed)?;
+    let kind = address.get(2..).ok_or(NmeaError::Malformed)?;
+
+    let (year, month, day, (hour, minute, second)) = match kind {
+        "RMC" => {
+            let time = fields.next().ok_or(NmeaError::Malformed)?;
+            let status = fields.next().ok_or(NmeaError::Malformed)?;
+            if status != "A" {
+                return Err(NmeaError::NoFix);
+            }
+            // Skip latitude, N/S, longitude, E/W, speed and course
+            let date = fields.nth(6).ok_or(NmeaError::Malformed)?;
+            if date.is_empty() {
+                return Err(NmeaError::NoFix);
+            }
+            if date.len() != 6 {
+                return Err(NmeaError::Malformed);
+            }
+            let year = 2000 + two_digits(date, 4)? as u16;
+            (
+                year,
+                two_digits(date, 2)?,
+                two_digits(date, 0)?,
+                parse_time(time)?,
+            )
+        }
+        "ZDA" => {
+            let time = parse_time(fields.next().ok_or(NmeaError::Malformed)?)?;
+            let mut next = || fields.next().ok_or(NmeaError::Malformed);
+            let (day, month, year) = (next()?, next()?, next()?);
+            if day.is_empty() || month.is_empty() || year.is_empty() {
+                return Err(NmeaError::NoFix);
+            }
+            if day.len() != 2 || month.len() != 2 {
+                return Err(NmeaError::Malformed);
+            }
+            let field = |s| u8::try_from(digits(s)?).map_err(|_| NmeaError::Malformed);
+            (digits(year)?, field(month)?, field(day)?, time)
+        }
+        _ => return Err(NmeaError::Unsupported),
+    };
+
+    DateTime::new(year, month, day, hour, minute, second).map_err(NmeaError::DateTime)
+}
+
+impl<I2C, V> Ds3231<I2C, V>
+where
+    I2C: RegisterInterface,
+{
+    /// Set the RTC from an NMEA `RMC` or `ZDA` sentence.
+    ///
+    /// Returns `Ok(false)` without touching the RTC if the sentence does not
+    /// carry a usable time (other sentence types, bad checksum, no fix), so
+    /// every line from the receiver can be passed in. Use [`parse_datetime`]
+    /// to find out why a sentence was rejected.
+    pub fn set_datetime_from_nmea(&mut self, sentence: &str) -> Result<bool, Error<I2C::Error>> {
+        match parse_datetime(sentence) {
+            Ok(datetime) => {
+                self.set_datetime(&datetime)?;
+                Ok(true)
+            }
+            Err(_) => Ok(false),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registers::Register;
+    use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTrans};
+
+    const RMC: &str = "$GPRMC,143000.00,A,4807.038,N,01131.000,E,0.0,0.0,210825,,,A*56\r\n";
+    const ZDA: &str = "$GNZDA,143000.50,21,08,2025,00,00*75";
+
+    #[test]
+    fn test_parse_rmc() {
+        assert_eq!(
+            parse_datetime(RMC),
+            Ok(DateTime::new(2025, 8, 21, 14, 30, 0).unwrap())
+        );
+    }
+
+    #[test]
+    fn test_parse_zda() {
+        assert_eq!(
+            parse_datetime(ZDA),
+            Ok(DateTime::new(2025, 8, 21, 14, 30, 0).unwrap())
+        );
+    }
+
+    #[test]
+    fn test_parse_rejections() {
+        assert_eq!(
+            parse_datetime("$GPRMC,143000.00,V,,,,,,,210825,,,N*77"),
+            Err(NmeaError::NoFix)
+        );
+        assert_eq!(
+            parse_datetime("$GNZDA,143000.50,21,08,2025,00,00*74"),
+            Err(NmeaError::Checksum)
+        );
+        assert_eq!(
+            parse_datetime("$GPGGA,143000.00,,,,,0,00,,,,,,,*4E"),
+            Err(NmeaError::Unsupported)
+        );
+        assert_eq!(
+            parse_datetime("GNZDA,143000.50,21,08,2025,00,00"),
+            Err(NmeaError::Malformed)
+        );
+        assert_eq!(
+            parse_datetime("$GNZDA,143000.50,31,02,2025,00,00*7E"),
+            Err(NmeaError::DateTime(DateTimeError::InvalidDay))
+        );
+        // 277 would wrap to day 21 as a u8
+        assert_eq!(
+            parse_datetime("$GNZDA,143000.50,0277,08,2025,00,00*74"),
+            Err(NmeaError::Malformed)
+        );
+    }
+
+    #[test]
+    fn test_set_datetime_from_nmea() {
+        let expectations = [I2cTrans::write(
+            0x68,
+            vec![
+                Register::Seconds.addr(),
+                0x00,
+                0x30,
+                0x14,
+                0x05,
+                0x21,
+                0x08,
+                0x25,
+            ],
+        )];
+        let mut ds3231 = Ds3231::new(I2cMock::new(&expectations));
+
+        assert!(ds3231.set_datetime_from_nmea(ZDA).unwrap());
+        assert!(
+            !ds3231
+                .set_datetime_from_nmea("$GPGGA,143000.00,,,,,0,00,,,,,,,*4E")
+                .unwrap()
+        );
+
+        ds3231.release_i2c().done();
+    }
+}
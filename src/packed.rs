@@ -0,0 +1,159 @@
+//! # Packed DateTime Encoding
+//!
+//! This module provides a compact, stable bit-packed encoding of a [`DateTime`]
+//! into a `u64`, intended for radio telemetry (LoRa, BLE advertisements, etc.)
+//! where every byte of payload counts.
+//!
+//! ## Layout
+//!
+//! | Bits    | Width | Field        | Range     |
+//! |---------|-------|--------------|-----------|
+//! | 0-5     | 6     | second       | 0-59      |
+//! | 6-11    | 6     | minute       | 0-59      |
+//! | 12-16   | 5     | hour         | 0-23      |
+//! | 17-21   | 5     | day of month | 1-31      |
+//! | 22-25   | 4     | month        | 1-12      |
+//! | 26-41   | 16    | year         | 1970-65535|
+//! | 42-63   | 22    | reserved     | always 0  |
+//!
+//! The layout is part of the public API and will not change. Because the most
+//! significant fields occupy the highest bits, comparing two packed values gives
+//! the same ordering as comparing the datetimes themselves.
+//!
+//! Only the lower 42 bits are used, so the value can be transmitted in 6 bytes
+//! (e.g. the first 6 bytes of `to_le_bytes()`). Reserved bits are always written
+//! as zero and ignored when decoding.
+
+use rtc_hal::datetime::{DateTime, DateTimeError};
+
+const SECOND_SHIFT: u32 = 0;
+const MINUTE_SHIFT: u32 = 6;
+const HOUR_SHIFT: u32 = 12;
+const DAY_SHIFT: u32 = 17;
+const MONTH_SHIFT: u32 = 22;
+const YEAR_SHIFT: u32 = 26;
+
+const SIX_BIT_MASK: u64 = 0x3F;
+const FIVE_BIT_MASK: u64 = 0x1F;
+const FOUR_BIT_MASK: u64 = 0x0F;
+const YEAR_MASK: u64 = 0xFFFF;
+
+/// Extension trait adding the packed `u64` encoding to [`DateTime`].
+///
+/// See the [module documentation](self) for the exact bit layout.
+pub trait PackedDateTime: Sized {
+    /// Encode the datetime into its packed `u64` representation.
+    fn to_packed_u64(&self) -> u64;
+
+    /// Decode a datetime from its packed `u64` representation.
+    ///
+    /// # Returns
+    ///
+    /// Returns a [`DateTimeError`] if any decoded field is out of range
+    /// (e.g. a corrupted payload with month 15).
+    fn from_packed_u64(packed: u64) -> Result<Self, DateTimeError>;
+}
+
+impl PackedDateTime for DateTime {
+    fn to_packed_u64(&self) -> u64 {
+        ((self.second() as u64) << SECOND_SHIFT)
+            | ((self.minute() as u64) << MINUTE_SHIFT)
+            | ((self.hour() as u64) << HOUR_SHIFT)
+            | ((self.day_of_month() as u64) << DAY_SHIFT)
+            | ((self.month() as u64) << MONTH_SHIFT)
+            | ((self.year() as u64) << YEAR_SHIFT)
+    }
+
+    fn from_packed_u64(packed: u64) -> Result<Self, DateTimeError> {
+        let second = ((packed >> SECOND_SHIFT) & SIX_BIT_MASK) as u8;
+        let minute = ((packed >> MINUTE_SHIFT) & SIX_BIT_MASK) as u8;
+        let hour = ((packed >> HOUR_SHIFT) & FIVE_BIT_MASK) as u8;
+        let day_of_month = ((packed >> DAY_SHIFT) & FIVE_BIT_MASK) as u8;
+        let month = ((packed >> MONTH_SHIFT) & FOUR_BIT_MASK) as u8;
+        let year = ((packed >> YEAR_SHIFT) & YEAR_MASK) as u16;
+
+        DateTime::new(year, month, day_of_month, hour, minute, second)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_packed_layout() {
+        let dt = DateTime::new(2025, 8, 21, 14, 30, 45).unwrap();
+        let expected = 45
+            | (30 << MINUTE_SHIFT)
+            | (14 << HOUR_SHIFT)
+            | (21 << DAY_SHIFT)
+            | (8 << MONTH_SHIFT)
+            | (2025 << YEAR_SHIFT);
+
+        assert_eq!(dt.to_packed_u64(), expected);
+    }
+
+    #[test]
+    fn test_packed_fits_in_six_bytes() {
+        let dt = DateTime::new(65535, 12, 31, 23, 59, 59).unwrap();
+        assert_eq!(dt.to_packed_u64() >> 48, 0);
+    }
+
+    #[test]
+    fn test_packed_roundtrip() {
+        let cases = [
+            DateTime::new(1970, 1, 1, 0, 0, 0).unwrap(),
+            DateTime::new(2000, 2, 29, 12, 0, 0).unwrap(),
+            DateTime::new(2025, 8, 21, 14, 30, 45).unwrap(),
+            DateTime::new(2099, 12, 31, 23, 59, 59).unwrap(),
+            DateTime::new(2150, 6, 15, 7, 8, 9).unwrap(),
+        ];
+
+        for dt in cases {
+            let packed = dt.to_packed_u64();
+            assert_eq!(DateTime::from_packed_u64(packed).unwrap(), dt);
+        }
+    }
+
+    #[test]
+    fn test_packed_ordering_matches_chronology() {
+        let earlier = DateTime::new(2024, 12, 31, 23, 59, 59).unwrap();
+        let later = DateTime::new(2025, 1, 1, 0, 0, 0).unwrap();
+
+        assert!(earlier.to_packed_u64() < later.to_packed_u64());
+    }
+
+    #[test]
+    fn test_from_packed_ignores_reserved_bits() {
+        let dt = DateTime::new(2025, 3, 10, 8, 0, 0).unwrap();
+        let packed = dt.to_packed_u64() | (0xABCD << 48);
+
+        assert_eq!(DateTime::from_packed_u64(packed).unwrap(), dt);
+    }
+
+    #[test]
+    fn test_from_packed_invalid_fields() {
+        let dt = DateTime::new(2025, 1, 1, 0, 0, 0).unwrap();
+        let base = dt.to_packed_u64();
+
+        // Month 13
+        let bad_month = (base & !(FOUR_BIT_MASK << MONTH_SHIFT)) | (13 << MONTH_SHIFT);
+        assert_eq!(
+            DateTime::from_packed_u64(bad_month),
+            Err(DateTimeError::InvalidMonth)
+        );
+
+        // Second 60
+        let bad_second = (base & !SIX_BIT_MASK) | 60;
+        assert_eq!(
+            DateTime::from_packed_u64(bad_second),
+            Err(DateTimeError::InvalidSecond)
+        );
+
+        // All zero decodes to year 0
+        assert_eq!(
+            DateTime::from_packed_u64(0),
+            Err(DateTimeError::InvalidYear)
+        );
+    }
+}
@@ -0,0 +1,242 @@
+//! Panic-freedom check, built with the `no-panic` feature.
+//!
+//! Every function here wraps part of the driver API in `#[no_panic]`, which
+//! fails the link unless the compiler proves the wrapped code cannot panic.
+//! The proof needs the optimizer to see through the whole call graph,
+//! `rtc-hal` included, so it runs in the `panic-check` profile (release
+//! with fat LTO and one codegen unit):
+//!
+//! ```text
+//! cargo test --profile panic-check --features no-panic panic_free
+//! ```
+//!
+//! The bus is a register file whose contents the optimizer can't see, so
+//! every decode path has to cope with arbitrary register values.
+
+use core::hint::black_box;
+
+use no_panic::no_panic;
+use rtc_hal::nvram::RtcNvram;
+use rtc_hal::square_wave::SquareWaveFreq;
+
+use crate::Ds3231;
+use crate::alarm::Alarm;
+use crate::bcd;
+use crate::blob::BlobStore;
+use crate::calendar;
+use crate::ds3232::Ds3232;
+use crate::dst::DstRule;
+use crate::dump::RegisterSnapshot;
+use crate::event_log::EventLog;
+use crate::hour_mode::HourMode;
+use crate::interface::RegisterInterface;
+use crate::power_loss::PowerLossJournal;
+use crate::totp::Totp;
+use crate::year_policy::YearPolicy;
+use crate::zoned::ZonedRtc;
+
+/// Register file of a DS3232, SRAM included
+struct Bus {
+    registers: [u8; 0x100],
+}
+
+impl RegisterInterface for Bus {
+    type Error = ();
+
+    fn read_registers(&mut self, _: u8, register: u8, buffer: &mut [u8]) -> Result<(), ()> {
+        let start = register as usize;
+        let registers = self.registers.get(start..start + buffer.len()).ok_or(())?;
+        buffer.copy_from_slice(registers);
+        Ok(())
+    }
+
+    fn write_registers(&mut self, _: u8, data: &[u8]) -> Result<(), ()> {
+        let (&register, values) = data.split_first().ok_or(())?;
+        let start = register as usize;
+        let registers = self
+            .registers
+            .get_mut(start..start + values.len())
+            .ok_or(())?;
+        registers.copy_from_slice(values);
+        Ok(())
+    }
+}
+
+type Rtc = Ds3231<Bus>;
+
+fn bus() -> Bus {
+    Bus {
+        registers: black_box([0; 0x100]),
+    }
+}
+
+#[no_panic]
+fn read_time(rtc: &mut Rtc) -> bool {
+    rtc.get_datetime().is_ok() && rtc.get_unix_timestamp().is_ok() && rtc.get_weekday().is_ok()
+}
+
+#[no_panic]
+fn write_time(rtc: &mut Rtc, timestamp: u64, policy: YearPolicy) -> bool {
+    rtc.set_year_policy(policy);
+    match calendar::from_unix_timestamp(timestamp) {
+        Ok(datetime) => {
+            rtc.set_datetime(&datetime).is_ok()
+                && rtc.set_time(12, 30, 0).is_ok()
+                && rtc.set_date(2025, crate::month::Month::August, 21).is_ok()
+        }
+        Err(_) => false,
+    }
+}
+
+#[no_panic]
+fn adjust_time(rtc: &mut Rtc, seconds: i32, hours: i8) -> bool {
+    rtc.adjust_by_seconds(seconds).is_ok() && rtc.shift_clock(hours).is_ok()
+}
+
+#[no_panic]
+fn hour_modes(rtc: &mut Rtc) -> bool {
+    rtc.convert_hour_mode(HourMode::TwelveHour).is_ok() && rtc.hour_mode().is_ok()
+}
+
+#[no_panic]
+fn repair(rtc: &mut Rtc) -> bool {
+    rtc.repair_datetime().is_ok()
+}
+
+#[no_panic]
+fn configuration(rtc: &mut Rtc, hz: u32) -> bool {
+    rtc.halt_clock().is_ok()
+        && rtc.start_clock().is_ok()
+        && rtc.start_square_wave(SquareWaveFreq::from_hz(hz)).is_ok()
+        && rtc.disable_square_wave().is_ok()
+        && rtc.reset_to_defaults().is_ok()
+}
+
+#[no_panic]
+fn flags(rtc: &mut Rtc) -> bool {
+    rtc.alarm_fired(Alarm::One).is_ok()
+        && rtc.clear_alarm_flags().is_ok()
+        && rtc.set_alarm_interrupt(Alarm::Two, true).is_ok()
+        && rtc.oscillator_stopped().is_ok()
+        && rtc.clear_oscillator_stop_flag().is_ok()
+}
+
+#[no_panic]
+fn readouts(rtc: &mut Rtc) -> bool {
+    rtc.temperature_quarters().is_ok()
+        && rtc.dump_registers().is_ok()
+        && rtc.device_state().is_ok()
+        && rtc.telemetry().is_ok()
+}
+
+#[no_panic]
+fn sram(rtc: &mut Ds3232<Bus>, offset: u8, len: usize, chunk_size: usize) -> bool {
+    let mut buffer = [0u8; 64];
+    let data = buffer.get_mut(..len.min(64)).unwrap_or_default();
+    rtc.set_sram_chunk_size(chunk_size);
+    rtc.read_nvram(offset, data).is_ok() && rtc.write_nvram(offset, data).is_ok()
+}
+
+#[no_panic]
+fn event_log(rtc: &mut Ds3232<Bus>, log: &EventLog, index: u8) -> bool {
+    log.log(rtc, &[1, 2]).is_ok() && log.read(rtc, index, &mut [0; 2]).is_ok()
+}
+
+#[no_panic]
+fn journal(rtc: &mut Ds3232<Bus>, journal: &PowerLossJournal) -> bool {
+    journal.check(rtc).is_ok() && journal.total(rtc).is_ok()
+}
+
+#[no_panic]
+fn blobs(rtc: &mut Ds3232<Bus>, store: &BlobStore, slot: u8, len: usize) -> bool {
+    let mut buffer = [0u8; 64];
+    let data = buffer.get_mut(..len.min(64)).unwrap_or_default();
+    store.store_blob(rtc, slot, data).is_ok() && store.load_blob(rtc, slot, data).is_ok()
+}
+
+#[no_panic]
+fn snapshot_diff(before: &RegisterSnapshot, after: &RegisterSnapshot) -> usize {
+    before.diff(after).fold(0, |total, change| {
+        total
+            .wrapping_add(change.name().len())
+            .wrapping_add(change.hardware_bits() as usize)
+            .wrapping_add(change.register().addr() as usize)
+    })
+}
+
+#[no_panic]
+fn bcd_conversions(value: u8) -> u8 {
+    bcd::to_bcd(value) ^ bcd::from_bcd(value)
+}
+
+#[no_panic]
+fn zoned(rtc: Rtc, offset_minutes: i16, rule: DstRule) -> bool {
+    let mut rtc = ZonedRtc::new(rtc, offset_minutes);
+    rtc.set_dst_rule(Some(rule));
+    match rtc.get_utc_datetime() {
+        Ok(utc) => rtc.to_local(&utc).is_ok() && rtc.to_utc(&utc).is_ok(),
+        Err(_) => false,
+    }
+}
+
+#[no_panic]
+fn totp(rtc: &mut Rtc, totp: &Totp<'_>, code: u32, window: u8) -> bool {
+    rtc.totp(totp).is_ok() && rtc.verify_totp(totp, code, window).is_ok()
+}
+
+#[test]
+fn test_driver_cannot_panic() {
+    black_box(read_time(&mut Ds3231::new(bus())));
+    black_box(write_time(
+        &mut Ds3231::new(bus()),
+        black_box(1_755_786_600),
+        black_box(YearPolicy::Clamp),
+    ));
+    black_box(adjust_time(
+        &mut Ds3231::new(bus()),
+        black_box(-90),
+        black_box(1),
+    ));
+    black_box(hour_modes(&mut Ds3231::new(bus())));
+    black_box(repair(&mut Ds3231::new(bus())));
+    black_box(configuration(&mut Ds3231::new(bus()), black_box(1024)));
+    black_box(flags(&mut Ds3231::new(bus())));
+    black_box(readouts(&mut Ds3231::new(bus())));
+    black_box(sram(
+        &mut Ds3232::new(bus()),
+        black_box(0xF0),
+        black_box(32),
+        black_box(7),
+    ));
+    black_box(event_log(
+        &mut Ds3232::new(bus()),
+        &black_box(EventLog::new(0x20, 2, 4)),
+        black_box(1),
+    ));
+    black_box(journal(
+        &mut Ds3232::new(bus()),
+        &black_box(PowerLossJournal::new(0x40, 4)),
+    ));
+    black_box(blobs(
+        &mut Ds3232::new(bus()),
+        &black_box(BlobStore::new(0x60, 16, 4)),
+        black_box(2),
+        black_box(8),
+    ));
+    black_box(snapshot_diff(
+        &black_box(RegisterSnapshot::from_bytes([0; 19])),
+        &black_box(RegisterSnapshot::from_bytes([0xFF; 19])),
+    ));
+    black_box(bcd_conversions(black_box(0xA5)));
+    black_box(zoned(
+        Ds3231::new(bus()),
+        black_box(i16::MAX),
+        black_box(DstRule::EU),
+    ));
+    black_box(totp(
+        &mut Ds3231::new(bus()),
+        &black_box(Totp::new(b"12345678901234567890")),
+        black_box(287_082),
+        black_box(1),
+    ));
+}
@@ -0,0 +1,247 @@
+//! # Plausibility Filter
+//!
+//! A transfer corrupted by noise on the bus can still decode to a valid
+//! datetime, just a wrong one, and end up in logs or event timestamps.
+//! With a plausibility window set, the driver remembers the last time it
+//! read and rejects a read that lies more than the window before or after
+//! it with [`Error::ImplausibleJump`]:
+//!
+//! ```no_run
+//! # use ds3231_rtc::Ds3231;
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! # let mut rtc = Ds3231::new(embedded_hal_mock::eh1::i2c::Mock::new(&[]));
+//! use ds3231_rtc::error::Error;
+//! # let handle = |_| ();
+//!
+//! // Reads happen at least every 10 s; allow for some slack
+//! rtc.set_plausibility_window(Some(60));
+//!
+//! match rtc.get_datetime() {
+//!     Err(Error::ImplausibleJump { .. }) => { /* glitch: read again */ }
+//!     result => handle(result?),
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! The window should cover the usual gap between two reads, as the filter
+//! has no other clock to compare against. A rejected read leaves the
+//! reference unchanged, so the next good read is accepted. A real jump, e.g.
+//! after the MCU slept for longer than the window, is accepted once
+//! [`JUMP_CONFIRMATIONS`] reads in a row agree with each other within the
+//! window, so the filter never locks the driver out. Setting the time
+//! through the driver, or changing the base century, starts over without a
+//! reference; after the clock was changed behind the driver's back,
+//! [`reset_plausibility`](crate::Ds3231::reset_plausibility) accepts the
+//! next read right away.
+
+use rtc_hal::datetime::DateTime;
+
+#[cfg(feature = "async")]
+use crate::interface::AsyncRegisterInterface;
+use crate::interface::RegisterInterface;
+
+use crate::Ds3231;
+use crate::calendar;
+#[cfg(feature = "async")]
+use crate::ds3231::Ds3231Async;
+use crate::error::Error;
+
+/// Consecutive reads outside the window, agreeing with each other, after
+/// which the jump is taken as real and the last of them accepted
+pub const JUMP_CONFIRMATIONS: u8 = 3;
+
+/// Configured window, the last accepted read and the rejected reads since
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub(crate) struct Plausibility {
+    window_secs: Option<u32>,
+    last: Option<u64>,
+    /// Latest rejected read and how many agreeing reads led up to it
+    rejected: Option<(u64, u8)>,
+}
+
+impl Plausibility {
+    /// Accept `datetime` as the new reference, or reject it if it is more
+    /// than the window away from the previous one and not yet confirmed by
+    /// the reads before it.
+    pub(crate) fn check<E>(&mut self, datetime: &DateTime) -> Result<(), Error<E>>
+    where
+        E: core::fmt::Debug,
+    {
+        let Some(window) = self.window_secs else {
+            return Ok(());
+        };
+        let now = calendar::to_unix_timestamp(datetime);
+        if let Some(last) = self.last {
+            let seconds = now as i64 - last as i64;
+            if seconds.unsigned_abs() > u64::from(window) {
+                let agreeing = match self.rejected {
+                    Some((previous, count)) if now.abs_diff(previous) <= u64::from(window) => {
+                        count + 1
+                    }
+                    _ => 1,
+                };
+                if agreeing < JUMP_CONFIRMATIONS {
+                    self.rejected = Some((now, agreeing));
+                    return Err(Error::ImplausibleJump { seconds });
+                }
+            }
+        }
+        self.last = Some(now);
+        self.rejected = None;
+        Ok(())
+    }
+
+    /// Forget the reference, accepting any next read.
+    pub(crate) fn reset(&mut self) {
+        self.last = None;
+        self.rejected = None;
+    }
+}
+
+#[maybe_async_cfg::maybe(
+    idents(
+        AsyncRegisterInterface(sync = "RegisterInterface", async = "AsyncRegisterInterface"),
+        Ds3231(sync = "Ds3231", async = "Ds3231Async")
+    ),
+    sync(keep_self),
+    async(feature = "async")
+)]
+impl<I2C, V> Ds3231<I2C, V>
+where
+    I2C: AsyncRegisterInterface,
+{
+    /// Reject datetime reads more than `window_secs` seconds away from the
+    /// previous read, `None` to accept every read (the default).
+    ///
+    /// See the [module documentation](crate::plausibility).
+    pub fn set_plausibility_window(&mut self, window_secs: Option<u32>) {
+        self.plausibility = Plausibility {
+            window_secs,
+            ..Plausibility::default()
+        };
+    }
+
+    /// Returns the configured plausibility window in seconds.
+    pub fn plausibility_window(&self) -> Option<u32> {
+        self.plausibility.window_secs
+    }
+
+    /// Forget the previous read, so the next one is accepted whatever it
+    /// reads.
+    pub fn reset_plausibility(&mut self) {
+        self.plausibility.reset();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registers::Register;
+    use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
+
+    fn time_read(registers: [u8; 7]) -> I2cTransaction {
+        I2cTransaction::write_read(0x68, vec![Register::Seconds.addr()], registers.to_vec())
+    }
+
+    #[test]
+    fn test_rejects_jumps_outside_window() {
+        let expectations = [
+            time_read([0x00, 0x30, 0x14, 0x05, 0x21, 0x08, 0x25]),
+            time_read([0x45, 0x30, 0x14, 0x05, 0x21, 0x08, 0x25]),
+            // Flipped bit in the minutes register
+            time_read([0x46, 0x50, 0x14, 0x05, 0x21, 0x08, 0x25]),
+            time_read([0x47, 0x30, 0x14, 0x05, 0x21, 0x08, 0x25]),
+        ];
+        let mut ds3231 = Ds3231::new(I2cMock::new(&expectations));
+        ds3231.set_plausibility_window(Some(60));
+        assert_eq!(ds3231.plausibility_window(), Some(60));
+
+        ds3231.get_datetime().unwrap();
+        ds3231.get_datetime().unwrap();
+        // 14:50:46 is 20 minutes after 14:30:45
+        assert_eq!(
+            ds3231.get_datetime(),
+            Err(Error::ImplausibleJump { seconds: 1201 })
+        );
+        // The rejected read didn't move the reference
+        assert_eq!(
+            ds3231.get_datetime(),
+            Ok(DateTime::new(2025, 8, 21, 14, 30, 47).unwrap())
+        );
+
+        ds3231.release_i2c().done();
+    }
+
+    #[test]
+    fn test_confirmed_jump_moves_reference() {
+        let expectations = [
+            time_read([0x00, 0x30, 0x14, 0x05, 0x21, 0x08, 0x25]),
+            // The MCU slept for an hour
+            time_read([0x00, 0x30, 0x15, 0x05, 0x21, 0x08, 0x25]),
+            // A glitch in between starts the count again
+            time_read([0x00, 0x30, 0x17, 0x05, 0x21, 0x08, 0x25]),
+            time_read([0x01, 0x30, 0x15, 0x05, 0x21, 0x08, 0x25]),
+            time_read([0x02, 0x30, 0x15, 0x05, 0x21, 0x08, 0x25]),
+            time_read([0x03, 0x30, 0x15, 0x05, 0x21, 0x08, 0x25]),
+            time_read([0x04, 0x30, 0x15, 0x05, 0x21, 0x08, 0x25]),
+        ];
+        let mut ds3231 = Ds3231::new(I2cMock::new(&expectations));
+        ds3231.set_plausibility_window(Some(60));
+
+        ds3231.get_datetime().unwrap();
+        for seconds in [3600, 10800, 3601, 3602] {
+            assert_eq!(
+                ds3231.get_datetime(),
+                Err(Error::ImplausibleJump { seconds })
+            );
+        }
+        assert_eq!(
+            ds3231.get_datetime(),
+            Ok(DateTime::new(2025, 8, 21, 15, 30, 3).unwrap())
+        );
+        ds3231.get_datetime().unwrap();
+
+        ds3231.release_i2c().done();
+    }
+
+    #[test]
+    fn test_setting_the_time_resets_reference() {
+        let expectations = [
+            time_read([0x00, 0x30, 0x14, 0x05, 0x21, 0x08, 0x25]),
+            I2cTransaction::write(
+                0x68,
+                vec![
+                    Register::Seconds.addr(),
+                    0x00,
+                    0x00,
+                    0x00,
+                    0x04,
+                    0x01,
+                    0x01,
+                    0x25,
+                ],
+            ),
+            time_read([0x01, 0x00, 0x00, 0x04, 0x01, 0x01, 0x25]),
+            time_read([0x00, 0x00, 0x12, 0x04, 0x01, 0x01, 0x25]),
+            time_read([0x00, 0x00, 0x12, 0x04, 0x01, 0x01, 0x25]),
+        ];
+        let mut ds3231 = Ds3231::new(I2cMock::new(&expectations));
+        ds3231.set_plausibility_window(Some(10));
+
+        ds3231.get_datetime().unwrap();
+        ds3231
+            .set_datetime(&DateTime::new(2025, 1, 1, 0, 0, 0).unwrap())
+            .unwrap();
+        ds3231.get_datetime().unwrap();
+
+        assert_eq!(
+            ds3231.get_datetime(),
+            Err(Error::ImplausibleJump { seconds: 43199 })
+        );
+        ds3231.reset_plausibility();
+        ds3231.get_datetime().unwrap();
+
+        ds3231.release_i2c().done();
+    }
+}
@@ -0,0 +1,216 @@
+//! # Power-Loss Journal
+//!
+//! Records every time the clock lost its time, so operators can see when and
+//! how often the backup battery failed. The journal combines the Oscillator
+//! Stop Flag with the battery-backed SRAM of a DS3232 or DS3234: call
+//! [`PowerLossJournal::check`] during initialization, and if OSF is set it
+//! appends a timestamped event to an [`EventLog`] in SRAM before clearing
+//! the flag.
+//!
+//! The timestamp is the RTC time at the moment of detection. The clock
+//! freezes while the oscillator is stopped, so if the registers kept their
+//! contents this is close to when time was lost; after a complete power loss
+//! it is the power-on reset time instead. Each event also carries the running
+//! total of power losses, which keeps counting after old events have been
+//! overwritten.
+//!
+//! Note that a power loss serious enough to drop VBAT may also wipe the SRAM.
+//! The journal then reads as empty and starts again with that event.
+//!
+//! ```no_run
+//! # use ds3231_rtc::ds3232::Ds3232;
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! # let mut rtc = Ds3232::new(embedded_hal_mock::eh1::i2c::Mock::new(&[]));
+//! use ds3231_rtc::power_loss::PowerLossJournal;
+//!
+//! const JOURNAL: PowerLossJournal = PowerLossJournal::new(0xC0, 8);
+//!
+//! if JOURNAL.check(&mut rtc)? {
+//!     // time was lost: resynchronize the clock
+//! }
+//! let failures = JOURNAL.total(&mut rtc)?;
+//! # Ok(())
+//! # }
+//! ```
+
+use rtc_hal::error::ErrorType;
+use rtc_hal::nvram::RtcNvram;
+
+use crate::ds3232::Ds3232;
+use crate::event_log::{EventLog, LogError};
+use crate::{Ds3231, error::Error, interface::RegisterInterface};
+
+/// Payload of each journal record: the running total as a big-endian `u16`
+const PAYLOAD_SIZE: u8 = 2;
+
+/// Access to an RTC's Oscillator Stop Flag
+///
+/// Implemented for every blocking [`Ds3231`] (including the DS3234) and for
+/// [`Ds3232`], so [`PowerLossJournal`] works on any of them.
+pub trait OscillatorStopFlag: ErrorType {
+    /// Returns `true` if the oscillator has stopped since the flag was last
+    /// cleared.
+    fn oscillator_stopped(&mut self) -> Result<bool, Self::Error>;
+
+    /// Clear the Oscillator Stop Flag.
+    fn clear_oscillator_stop_flag(&mut self) -> Result<(), Self::Error>;
+}
+
+impl<I2C: RegisterInterface, V> OscillatorStopFlag for Ds3231<I2C, V> {
+    fn oscillator_stopped(&mut self) -> Result<bool, Error<I2C::Error>> {
+        Ds3231::oscillator_stopped(self)
+    }
+
+    fn clear_oscillator_stop_flag(&mut self) -> Result<(), Error<I2C::Error>> {
+        Ds3231::clear_oscillator_stop_flag(self)
+    }
+}
+
+impl<I2C: RegisterInterface> OscillatorStopFlag for Ds3232<I2C> {
+    fn oscillator_stopped(&mut self) -> Result<bool, Error<I2C::Error>> {
+        Ds3231::oscillator_stopped(self)
+    }
+
+    fn clear_oscillator_stop_flag(&mut self) -> Result<(), Error<I2C::Error>> {
+        Ds3231::clear_oscillator_stop_flag(self)
+    }
+}
+
+/// Journal of power-loss events kept in a region of NVRAM
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct PowerLossJournal {
+    log: EventLog,
+}
+
+impl PowerLossJournal {
+    /// Journal keeping the last `capacity` events, starting at NVRAM
+    /// `offset`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` is zero or the journal would need more than 256
+    /// bytes.
+    pub const fn new(offset: u8, capacity: u8) -> Self {
+        Self {
+            log: EventLog::new(offset, PAYLOAD_SIZE, capacity),
+        }
+    }
+
+    /// NVRAM bytes covered by the journal
+    pub const fn len_bytes(&self) -> usize {
+        self.log.len_bytes()
+    }
+
+    /// Record a power-loss event if the Oscillator Stop Flag is set.
+    ///
+    /// Returns `true` if time was lost. The flag is only cleared after the
+    /// event has been stored, so a failed write is retried on the next
+    /// check. The clock itself is left alone; set it again once a good time
+    /// source is available.
+    pub fn check<R>(&self, rtc: &mut R) -> Result<bool, LogError<R::Error>>
+    where
+        R: RtcNvram + OscillatorStopFlag,
+    {
+        if !rtc.oscillator_stopped().map_err(LogError::Rtc)? {
+            return Ok(false);
+        }
+
+        let total = self.total(rtc)?.saturating_add(1);
+        self.log.log(rtc, &total.to_be_bytes())?;
+        rtc.clear_oscillator_stop_flag().map_err(LogError::Rtc)?;
+        Ok(true)
+    }
+
+    /// Number of events currently held, at most the capacity.
+    pub fn len<R: RtcNvram>(&self, rtc: &mut R) -> Result<u8, LogError<R::Error>> {
+        self.log.len(rtc)
+    }
+
+    /// Total number of power losses recorded since the journal was last
+    /// cleared, including events that have since been overwritten.
+    pub fn total<R: RtcNvram>(&self, rtc: &mut R) -> Result<u16, LogError<R::Error>> {
+        let mut payload = [0u8; PAYLOAD_SIZE as usize];
+        match self.log.read_latest(rtc, 0, &mut payload) {
+            Ok(_) => Ok(u16::from_be_bytes(payload)),
+            Err(LogError::IndexOutOfRange) => Ok(0),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Unix timestamp of event `index` (0 = oldest), as read from the RTC
+    /// when the power loss was detected.
+    pub fn read<R: RtcNvram>(&self, rtc: &mut R, index: u8) -> Result<u32, LogError<R::Error>> {
+        self.log.read(rtc, index, &mut [0u8; PAYLOAD_SIZE as usize])
+    }
+
+    /// Remove all events and reset the total.
+    pub fn clear<R: RtcNvram>(&self, rtc: &mut R) -> Result<(), LogError<R::Error>> {
+        self.log.clear(rtc)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blob::tests::MemNvram;
+    use rtc_hal::datetime::DateTime;
+
+    impl OscillatorStopFlag for MemNvram {
+        fn oscillator_stopped(&mut self) -> Result<bool, Self::Error> {
+            Ok(self.oscillator_stopped)
+        }
+
+        fn clear_oscillator_stop_flag(&mut self) -> Result<(), Self::Error> {
+            self.oscillator_stopped = false;
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_check_records_and_clears_osf() {
+        let mut rtc = MemNvram::new(236);
+        let journal = PowerLossJournal::new(0x40, 2);
+
+        assert_eq!(journal.check(&mut rtc), Ok(false));
+        assert_eq!(journal.len(&mut rtc), Ok(0));
+
+        rtc.oscillator_stopped = true;
+        assert_eq!(journal.check(&mut rtc), Ok(true));
+        assert!(!rtc.oscillator_stopped);
+        // 2025-08-21 14:30:00 UTC
+        assert_eq!(journal.read(&mut rtc, 0), Ok(1_755_786_600));
+        assert_eq!(journal.check(&mut rtc), Ok(false));
+    }
+
+    #[test]
+    fn test_total_outlives_overwritten_events() {
+        let mut rtc = MemNvram::new(236);
+        let journal = PowerLossJournal::new(0, 2);
+
+        for year in [2000, 2001, 2002] {
+            rtc.now = DateTime::new(year, 1, 1, 0, 0, 0).unwrap();
+            rtc.oscillator_stopped = true;
+            journal.check(&mut rtc).unwrap();
+        }
+
+        assert_eq!(journal.len(&mut rtc), Ok(2));
+        assert_eq!(journal.total(&mut rtc), Ok(3));
+        assert_eq!(journal.read(&mut rtc, 0), Ok(978_307_200));
+
+        journal.clear(&mut rtc).unwrap();
+        assert_eq!(journal.total(&mut rtc), Ok(0));
+    }
+
+    #[test]
+    fn test_failed_write_keeps_osf() {
+        let mut rtc = MemNvram::new(16);
+        rtc.oscillator_stopped = true;
+
+        assert_eq!(
+            PowerLossJournal::new(0, 4).check(&mut rtc),
+            Err(LogError::InvalidLayout)
+        );
+        assert!(rtc.oscillator_stopped);
+    }
+}
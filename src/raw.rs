@@ -0,0 +1,134 @@
+//! # Raw Register Access
+//!
+//! With the `raw-registers` feature the driver also reads and writes
+//! registers by plain address, for board bring-up, debugging and bits the
+//! typed API doesn't cover (such as the aging offset at 0x10):
+//!
+//! ```no_run
+//! # use ds3231_rtc::Ds3231;
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! # let mut rtc = Ds3231::new(embedded_hal_mock::eh1::i2c::Mock::new(&[]));
+//! let aging = rtc.read_raw_register(0x10)? as i8;
+//! rtc.write_raw_register(0x10, (aging - 1) as u8)?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! These methods take no care of what they touch: writing the wrong value
+//! can stop the oscillator or change the stored time. The control register
+//! cache is dropped whenever a write covers the control register.
+
+#[cfg(feature = "async")]
+use crate::interface::AsyncRegisterInterface;
+use crate::interface::RegisterInterface;
+
+use crate::Ds3231;
+#[cfg(feature = "async")]
+use crate::ds3231::Ds3231Async;
+use crate::ds3231::MAX_BURST;
+use crate::error::Error;
+use crate::registers::Register;
+
+#[maybe_async_cfg::maybe(
+    idents(
+        AsyncRegisterInterface(sync = "RegisterInterface", async = "AsyncRegisterInterface"),
+        Ds3231(sync = "Ds3231", async = "Ds3231Async")
+    ),
+    sync(keep_self),
+    async(feature = "async")
+)]
+impl<I2C, V> Ds3231<I2C, V>
+where
+    I2C: AsyncRegisterInterface,
+{
+    /// Read the register at `address`.
+    pub async fn read_raw_register(&mut self, address: u8) -> Result<u8, Error<I2C::Error>> {
+        let mut value = [0u8; 1];
+        self.read_bytes_at_address(address, &mut value).await?;
+        Ok(value[0])
+    }
+
+    /// Write `value` to the register at `address`.
+    pub async fn write_raw_register(
+        &mut self,
+        address: u8,
+        value: u8,
+    ) -> Result<(), Error<I2C::Error>> {
+        self.write_raw_registers(address, &[value]).await
+    }
+
+    /// Read `buffer.len()` consecutive registers starting at `address`.
+    pub async fn read_raw_registers(
+        &mut self,
+        address: u8,
+        buffer: &mut [u8],
+    ) -> Result<(), Error<I2C::Error>> {
+        self.read_chunked(address, buffer, MAX_BURST, true).await
+    }
+
+    /// Write `values` to consecutive registers starting at `address`.
+    ///
+    /// Runs longer than 32 bytes are split into several transfers.
+    pub async fn write_raw_registers(
+        &mut self,
+        address: u8,
+        values: &[u8],
+    ) -> Result<(), Error<I2C::Error>> {
+        let control = Register::Control.addr();
+        let end = address as usize + values.len();
+        if address <= control && (control as usize) < end {
+            self.control_cache.invalidate();
+        }
+        self.write_chunked(address, values, MAX_BURST, true).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
+
+    const DS3231_ADDR: u8 = 0x68;
+
+    #[test]
+    fn test_raw_access() {
+        let expectations = [
+            I2cTransaction::write_read(DS3231_ADDR, vec![0x10], vec![0xFE]),
+            I2cTransaction::write(DS3231_ADDR, vec![0x10, 0xFD]),
+            I2cTransaction::write_read(DS3231_ADDR, vec![0x0E], vec![0x1C, 0x88]),
+            I2cTransaction::write(DS3231_ADDR, vec![0x0D, 0x00, 0x04]),
+        ];
+        let mut ds3231 = Ds3231::new(I2cMock::new(&expectations));
+
+        let aging = ds3231.read_raw_register(0x10).unwrap();
+        ds3231.write_raw_register(0x10, aging - 1).unwrap();
+
+        let mut buffer = [0u8; 2];
+        ds3231.read_raw_registers(0x0E, &mut buffer).unwrap();
+        assert_eq!(buffer, [0x1C, 0x88]);
+        ds3231.write_raw_registers(0x0D, &[0x00, 0x04]).unwrap();
+
+        ds3231.release_i2c().done();
+    }
+
+    #[test]
+    fn test_write_over_control_invalidates_cache() {
+        let expectations = [
+            I2cTransaction::write_read(DS3231_ADDR, vec![Register::Control.addr()], vec![0x1C]),
+            I2cTransaction::write(DS3231_ADDR, vec![Register::Control.addr(), 0x18]),
+            I2cTransaction::write(DS3231_ADDR, vec![Register::Control.addr(), 0x1C]),
+            I2cTransaction::write_read(DS3231_ADDR, vec![Register::Control.addr()], vec![0x1C]),
+            I2cTransaction::write(DS3231_ADDR, vec![Register::Control.addr(), 0x18]),
+        ];
+        let mut ds3231 = Ds3231::new(I2cMock::new(&expectations));
+        ds3231.set_control_cache(true);
+
+        ds3231.enable_square_wave().unwrap();
+        ds3231
+            .write_raw_register(Register::Control.addr(), 0x1C)
+            .unwrap();
+        ds3231.enable_square_wave().unwrap();
+
+        ds3231.release_i2c().done();
+    }
+}
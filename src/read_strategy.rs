@@ -0,0 +1,187 @@
+//! # Time Read Strategy
+//!
+//! The DS3231 copies its counters into a buffer on every I2C START, so the
+//! single burst read [`get_datetime`](rtc_hal::rtc::Rtc::get_datetime) uses
+//! by default is always consistent. That guarantee is lost when the read is
+//! split into several transfers, e.g. by a bridge or custom
+//! [`RegisterInterface`] with a small transfer limit: a rollover between the
+//! parts yields 12:59:59 for 13:00:00 or, at midnight, a whole day off.
+//!
+//! [`ReadStrategy::Verified`] guards against that by reading the seconds
+//! register once more after the block and reading again if it changed.
+
+#[cfg(feature = "async")]
+use crate::interface::AsyncRegisterInterface;
+use crate::interface::RegisterInterface;
+
+use crate::Ds3231;
+#[cfg(feature = "async")]
+use crate::ds3231::Ds3231Async;
+use crate::error::Error;
+use crate::registers::Register;
+
+/// Block reads attempted by [`ReadStrategy::Verified`]
+///
+/// The seconds change at most once a second, so a second attempt only fails
+/// if reading the block takes about a second itself. When every attempt
+/// sees the seconds change, the read fails with [`Error::Timeout`].
+const VERIFIED_READ_ATTEMPTS: usize = 3;
+
+/// How [`get_datetime`](rtc_hal::rtc::Rtc::get_datetime) reads the
+/// timekeeping registers
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum ReadStrategy {
+    /// One burst read of all seven registers.
+    #[default]
+    Burst,
+    /// Burst read followed by a read of the seconds register; the block is
+    /// read again if the seconds changed in between, up to three times.
+    ///
+    /// Costs one extra one-byte read per call, plus a block read after a
+    /// rollover.
+    Verified,
+}
+
+#[maybe_async_cfg::maybe(
+    idents(
+        AsyncRegisterInterface(sync = "RegisterInterface", async = "AsyncRegisterInterface"),
+        Ds3231(sync = "Ds3231", async = "Ds3231Async")
+    ),
+    sync(keep_self),
+    async(feature = "async")
+)]
+impl<I2C, V> Ds3231<I2C, V>
+where
+    I2C: AsyncRegisterInterface,
+{
+    /// Select how the time registers are read.
+    ///
+    /// Defaults to [`ReadStrategy::Burst`].
+    pub fn set_read_strategy(&mut self, strategy: ReadStrategy) {
+        self.read_strategy = strategy;
+    }
+
+    /// Returns the configured read strategy.
+    pub fn read_strategy(&self) -> ReadStrategy {
+        self.read_strategy
+    }
+
+    /// Read the seven timekeeping registers with the configured strategy.
+    pub(crate) async fn read_time_registers(&mut self) -> Result<[u8; 7], Error<I2C::Error>> {
+        let mut data = [0; 7];
+        self.read_time_block(&mut data).await?;
+        Ok(data)
+    }
+
+    /// Read `data.len()` registers from the seconds register on, retrying
+    /// as configured if the seconds changed meanwhile.
+    pub(crate) async fn read_time_block(
+        &mut self,
+        data: &mut [u8],
+    ) -> Result<(), Error<I2C::Error>> {
+        self.read_register_bytes(Register::Seconds, data).await?;
+
+        if self.read_strategy == ReadStrategy::Verified {
+            for attempt in 1..=VERIFIED_READ_ATTEMPTS {
+                let seconds = self.read_register(Register::Seconds).await?;
+                if seconds == data[0] {
+                    return Ok(());
+                }
+                if attempt < VERIFIED_READ_ATTEMPTS {
+                    // Rolled over while reading; the new block is at least as recent
+                    self.stats.count_retry();
+                    self.read_register_bytes(Register::Seconds, data).await?;
+                }
+            }
+            return Err(Error::Timeout);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
+    use rtc_hal::datetime::DateTime;
+
+    const DS3231_ADDR: u8 = 0x68;
+
+    #[test]
+    fn test_verified_read_retries_after_rollover() {
+        let expectations = [
+            // Torn block: seconds from before the rollover, minutes after it
+            I2cTransaction::write_read(
+                DS3231_ADDR,
+                vec![0x00],
+                vec![0x59, 0x00, 0x13, 0x05, 0x21, 0x08, 0x25],
+            ),
+            I2cTransaction::write_read(DS3231_ADDR, vec![0x00], vec![0x00]),
+            I2cTransaction::write_read(
+                DS3231_ADDR,
+                vec![0x00],
+                vec![0x00, 0x00, 0x13, 0x05, 0x21, 0x08, 0x25],
+            ),
+            I2cTransaction::write_read(DS3231_ADDR, vec![0x00], vec![0x00]),
+        ];
+        let mut ds3231 = Ds3231::new(I2cMock::new(&expectations));
+        assert_eq!(ds3231.read_strategy(), ReadStrategy::Burst);
+        ds3231.set_read_strategy(ReadStrategy::Verified);
+
+        assert_eq!(
+            ds3231.get_datetime(),
+            Ok(DateTime::new(2025, 8, 21, 13, 0, 0).unwrap())
+        );
+
+        ds3231.release_i2c().done();
+    }
+
+    #[test]
+    fn test_verified_read_without_rollover() {
+        let expectations = [
+            I2cTransaction::write_read(
+                DS3231_ADDR,
+                vec![0x00],
+                vec![0x15, 0x30, 0x14, 0x05, 0x21, 0x08, 0x25],
+            ),
+            I2cTransaction::write_read(DS3231_ADDR, vec![0x00], vec![0x15]),
+        ];
+        let mut ds3231 = Ds3231::new(I2cMock::new(&expectations));
+        ds3231.set_read_strategy(ReadStrategy::Verified);
+
+        assert_eq!(
+            ds3231.get_datetime(),
+            Ok(DateTime::new(2025, 8, 21, 14, 30, 15).unwrap())
+        );
+
+        ds3231.release_i2c().done();
+    }
+
+    #[test]
+    fn test_verified_read_times_out_if_never_stable() {
+        let block = |seconds| {
+            I2cTransaction::write_read(
+                DS3231_ADDR,
+                vec![0x00],
+                vec![seconds, 0x30, 0x14, 0x05, 0x21, 0x08, 0x25],
+            )
+        };
+        let seconds = |seconds| I2cTransaction::write_read(DS3231_ADDR, vec![0x00], vec![seconds]);
+        let expectations = [
+            block(0x10),
+            seconds(0x11),
+            block(0x12),
+            seconds(0x13),
+            block(0x14),
+            seconds(0x15),
+        ];
+        let mut ds3231 = Ds3231::new(I2cMock::new(&expectations));
+        ds3231.set_read_strategy(ReadStrategy::Verified);
+
+        assert_eq!(ds3231.get_datetime(), Err(Error::Timeout));
+
+        ds3231.release_i2c().done();
+    }
+}
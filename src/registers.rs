@@ -1,8 +1,17 @@
 //! DS3231 Registers
+//!
+//! Register addresses and bit masks, plus typed values for the control,
+//! status, hour and alarm registers ([`ControlRegister`], [`StatusRegister`],
+//! [`HourRegister`], [`AlarmRegister`]) with a named getter and setter per
+//! field, for code that would otherwise juggle the masks by hand.
+
+use crate::alarm::Alarm;
 
 /// DS3231 Registers
 #[repr(u8)]
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
 pub enum Register {
     /// Seconds register (0x00) - BCD format 00-59, bit 7 = Clock Halt
     Seconds = 0x00,
@@ -19,21 +28,460 @@ pub enum Register {
     /// Year register (0x06) - BCD format 00-99 (2000-2099)
     Year = 0x06,
 
+    /// Alarm 1 seconds register (0x07) - BCD 00-59, bit 7 = A1M1 mask
+    Alarm1Seconds = 0x07,
+    /// Alarm 1 minutes register (0x08) - BCD 00-59, bit 7 = A1M2 mask
+    Alarm1Minutes = 0x08,
+    /// Alarm 1 hours register (0x09) - same format as Hours, bit 7 = A1M3 mask
+    Alarm1Hours = 0x09,
+    /// Alarm 1 day/date register (0x0A) - bit 7 = A1M4 mask, bit 6 = DY/DT
+    Alarm1DayDate = 0x0A,
+    /// Alarm 2 minutes register (0x0B) - BCD 00-59, bit 7 = A2M2 mask
+    Alarm2Minutes = 0x0B,
+    /// Alarm 2 hours register (0x0C) - same format as Hours, bit 7 = A2M3 mask
+    Alarm2Hours = 0x0C,
+    /// Alarm 2 day/date register (0x0D) - bit 7 = A2M4 mask, bit 6 = DY/DT
+    Alarm2DayDate = 0x0D,
+
     /// Control register (0x0E)
     Control = 0x0E,
+    /// Status register (0x0F)
+    Status = 0x0F,
+    /// Aging offset register (0x10) - signed trim of the oscillator
+    AgingOffset = 0x10,
+    /// Temperature MSB (0x11) - signed integer part in degrees Celsius
+    TempMsb = 0x11,
+    /// Temperature LSB (0x12) - fraction in steps of 0.25 °C in bits 7-6
+    TempLsb = 0x12,
 }
 
 impl Register {
+    /// Every register, in address order
+    pub const ALL: [Register; 19] = [
+        Register::Seconds,
+        Register::Minutes,
+        Register::Hours,
+        Register::Day,
+        Register::Date,
+        Register::Month,
+        Register::Year,
+        Register::Alarm1Seconds,
+        Register::Alarm1Minutes,
+        Register::Alarm1Hours,
+        Register::Alarm1DayDate,
+        Register::Alarm2Minutes,
+        Register::Alarm2Hours,
+        Register::Alarm2DayDate,
+        Register::Control,
+        Register::Status,
+        Register::AgingOffset,
+        Register::TempMsb,
+        Register::TempLsb,
+    ];
+
     /// Returns the raw 7-bit register address as `u8`.
     pub const fn addr(self) -> u8 {
         self as u8
     }
+
+    /// Register at `address`, `None` past the register map.
+    pub const fn from_addr(address: u8) -> Option<Register> {
+        if (address as usize) < Self::ALL.len() {
+            Some(Self::ALL[address as usize])
+        } else {
+            None
+        }
+    }
+
+    /// Bits software can change; the others are read-only or always zero.
+    ///
+    /// The status flags OSF, A2F and A1F count as writable although they
+    /// can only be cleared.
+    pub const fn writable_mask(self) -> u8 {
+        match self {
+            Register::Seconds | Register::Minutes => 0x7F,
+            Register::Hours => 0x7F,
+            Register::Day => 0x07,
+            Register::Date => 0x3F,
+            Register::Month => CENTURY_BIT | 0x1F,
+            Register::Year => 0xFF,
+            Register::Alarm1Seconds
+            | Register::Alarm1Minutes
+            | Register::Alarm1Hours
+            | Register::Alarm1DayDate
+            | Register::Alarm2Minutes
+            | Register::Alarm2Hours
+            | Register::Alarm2DayDate => 0xFF,
+            Register::Control => 0xFF,
+            Register::Status => OSF_BIT | EN32KHZ_BIT | A2F_BIT | A1F_BIT,
+            Register::AgingOffset => 0xFF,
+            Register::TempMsb | Register::TempLsb => 0x00,
+        }
+    }
+
+    /// Value after the first power-up according to the datasheet, `None`
+    /// where it is undefined or measured.
+    ///
+    /// The clock starts at 00:00:00 on Sunday 2000-01-01 (weekday register
+    /// 1) with the oscillator stop flag set.
+    pub const fn power_on_default(self) -> Option<u8> {
+        match self {
+            Register::Seconds | Register::Minutes | Register::Hours | Register::Year => Some(0x00),
+            Register::Day | Register::Date | Register::Month => Some(0x01),
+            Register::Control => Some(INTCN_BIT | RS_MASK),
+            Register::Status => Some(OSF_BIT | EN32KHZ_BIT),
+            Register::AgingOffset => Some(0x00),
+            Register::Alarm1Seconds
+            | Register::Alarm1Minutes
+            | Register::Alarm1Hours
+            | Register::Alarm1DayDate
+            | Register::Alarm2Minutes
+            | Register::Alarm2Hours
+            | Register::Alarm2DayDate
+            | Register::TempMsb
+            | Register::TempLsb => None,
+        }
+    }
 }
 
+/// Hours register (0x02) bit flags
+/// 12/24 hour mode select (1 = 12-hour mode)
+pub const HOUR_12H_BIT: u8 = 1 << 6;
+/// AM/PM flag in 12-hour mode (1 = PM)
+pub const HOUR_PM_BIT: u8 = 1 << 5;
+/// Alarm mask bit in the alarm hours registers (A1M3 / A2M3)
+pub const ALARM_MASK_BIT: u8 = 1 << 7;
+
+/// Month register (0x05) bit flags
+/// Century bit, toggled by the chip when the year rolls over from 99 to 00
+pub const CENTURY_BIT: u8 = 1 << 7;
+
 /// Control register (0x0E) bit flags
 /// Enable Oscillator
 pub const EOSC_BIT: u8 = 1 << 7;
+/// Battery-Backed Square-Wave Enable
+pub const BBSQW_BIT: u8 = 1 << 6;
+/// Convert Temperature
+pub const CONV_BIT: u8 = 1 << 5;
 ///  Interrupt Control
 pub const INTCN_BIT: u8 = 1 << 2;
 /// Rate Select mask
 pub const RS_MASK: u8 = 0b0001_1000;
+/// Alarm 2 Interrupt Enable
+pub const A2IE_BIT: u8 = 1 << 1;
+/// Alarm 1 Interrupt Enable
+pub const A1IE_BIT: u8 = 1 << 0;
+
+/// Status register (0x0F) bit flags
+/// Oscillator Stop Flag, set whenever the oscillator stopped (e.g. power loss)
+pub const OSF_BIT: u8 = 1 << 7;
+/// Enable 32kHz Output
+pub const EN32KHZ_BIT: u8 = 1 << 3;
+/// Busy, set while a temperature conversion is in progress
+pub const BSY_BIT: u8 = 1 << 2;
+/// Alarm 2 Flag, set when the time matched alarm 2
+pub const A2F_BIT: u8 = 1 << 1;
+/// Alarm 1 Flag, set when the time matched alarm 1
+pub const A1F_BIT: u8 = 1 << 0;
+
+/// Decode the temperature registers into quarter degrees Celsius.
+pub(crate) fn decode_quarters(msb: u8, lsb: u8) -> i16 {
+    // 10-bit two's complement value, left-aligned over both registers
+    i16::from_be_bytes([msb, lsb]) >> 6
+}
+
+/// Define a `bool` getter and a setter for each named bit mask.
+macro_rules! bit_accessors {
+    ($($(#[$doc:meta])* $get:ident, $set:ident = $mask:expr;)*) => {$(
+        $(#[$doc])*
+        pub const fn $get(self) -> bool {
+            self.0 & $mask != 0
+        }
+
+        #[doc = concat!("Set or clear the bit reported by [`", stringify!($get), "`](Self::", stringify!($get), ").")]
+        pub const fn $set(&mut self, value: bool) {
+            if value {
+                self.0 |= $mask;
+            } else {
+                self.0 &= !$mask;
+            }
+        }
+    )*};
+}
+
+/// Define the raw conversions shared by all typed register values.
+macro_rules! raw_conversions {
+    ($($ty:ident),*) => {$(
+        impl $ty {
+            /// Wrap a raw register value.
+            pub const fn from_bits(bits: u8) -> Self {
+                Self(bits)
+            }
+
+            /// Returns the raw register value.
+            pub const fn bits(self) -> u8 {
+                self.0
+            }
+        }
+
+        impl From<u8> for $ty {
+            fn from(bits: u8) -> Self {
+                Self(bits)
+            }
+        }
+
+        impl From<$ty> for u8 {
+            fn from(value: $ty) -> u8 {
+                value.0
+            }
+        }
+    )*};
+}
+
+/// Control register (0x0E) value
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ControlRegister(u8);
+
+/// Status register (0x0F) value
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct StatusRegister(u8);
+
+/// Hours (0x02) or alarm hours (0x09, 0x0C) register value
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct HourRegister(u8);
+
+/// Alarm seconds, minutes or day/date register value
+///
+/// Bit 6 is the tens digit in the seconds and minutes registers and the
+/// DY/DT bit in the day/date registers, so the value is read with
+/// [`seconds_or_minutes`](Self::seconds_or_minutes) or
+/// [`day_or_date`](Self::day_or_date) depending on the register.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct AlarmRegister(u8);
+
+raw_conversions!(ControlRegister, StatusRegister, HourRegister, AlarmRegister);
+
+impl ControlRegister {
+    bit_accessors! {
+        /// EOSC: oscillator stopped while on battery
+        oscillator_disabled, set_oscillator_disabled = EOSC_BIT;
+        /// BBSQW: square wave kept running on battery
+        battery_backed_square_wave, set_battery_backed_square_wave = BBSQW_BIT;
+        /// CONV: temperature conversion requested
+        convert_temperature, set_convert_temperature = CONV_BIT;
+        /// INTCN: INT/SQW pin signals alarms instead of the square wave
+        interrupt_control, set_interrupt_control = INTCN_BIT;
+        /// A2IE: alarm 2 drives the INT pin
+        alarm2_interrupt, set_alarm2_interrupt = A2IE_BIT;
+        /// A1IE: alarm 1 drives the INT pin
+        alarm1_interrupt, set_alarm1_interrupt = A1IE_BIT;
+    }
+
+    /// RS2:RS1 rate select field (0-3).
+    pub const fn rate_select(self) -> u8 {
+        (self.0 & RS_MASK) >> 3
+    }
+
+    /// Set the rate select field; only the low two bits of `rate` are used.
+    pub const fn set_rate_select(&mut self, rate: u8) {
+        self.0 = (self.0 & !RS_MASK) | ((rate << 3) & RS_MASK);
+    }
+
+    /// Returns `true` if `alarm` drives the INT pin (A1IE/A2IE).
+    pub const fn alarm_interrupt(self, alarm: Alarm) -> bool {
+        match alarm {
+            Alarm::One => self.alarm1_interrupt(),
+            Alarm::Two => self.alarm2_interrupt(),
+        }
+    }
+
+    /// Enable or disable the INT pin interrupt of `alarm`.
+    pub const fn set_alarm_interrupt(&mut self, alarm: Alarm, enabled: bool) {
+        match alarm {
+            Alarm::One => self.set_alarm1_interrupt(enabled),
+            Alarm::Two => self.set_alarm2_interrupt(enabled),
+        }
+    }
+}
+
+impl StatusRegister {
+    bit_accessors! {
+        /// OSF: oscillator stopped at some point since last cleared
+        oscillator_stop_flag, set_oscillator_stop_flag = OSF_BIT;
+        /// EN32kHz: 32 kHz output enabled
+        enable_32khz, set_enable_32khz = EN32KHZ_BIT;
+        /// BSY: temperature conversion in progress
+        busy, set_busy = BSY_BIT;
+        /// A2F: alarm 2 matched
+        alarm2_flag, set_alarm2_flag = A2F_BIT;
+        /// A1F: alarm 1 matched
+        alarm1_flag, set_alarm1_flag = A1F_BIT;
+    }
+
+    /// Returns `true` if the flag of `alarm` (A1F/A2F) is set.
+    pub const fn alarm_flag(self, alarm: Alarm) -> bool {
+        match alarm {
+            Alarm::One => self.alarm1_flag(),
+            Alarm::Two => self.alarm2_flag(),
+        }
+    }
+
+    /// Set or clear the flag of `alarm`.
+    pub const fn set_alarm_flag(&mut self, alarm: Alarm, value: bool) {
+        match alarm {
+            Alarm::One => self.set_alarm1_flag(value),
+            Alarm::Two => self.set_alarm2_flag(value),
+        }
+    }
+}
+
+impl HourRegister {
+    bit_accessors! {
+        /// A1M3/A2M3 alarm mask bit; always clear in the timekeeping register
+        alarm_mask, set_alarm_mask = ALARM_MASK_BIT;
+    }
+
+    /// Hour format the register is stored in.
+    pub fn hour_mode(self) -> crate::hour_mode::HourMode {
+        crate::hour_mode::HourMode::from_register(self.0)
+    }
+
+    /// Stored hour as a 24-hour value, in either format.
+    pub fn hour(self) -> u8 {
+        crate::hour_mode::decode_hour(self.0 & !ALARM_MASK_BIT)
+    }
+
+    /// Store `hour` (0-23) in the format `mode`, keeping the alarm mask bit.
+    pub fn set_hour(&mut self, hour: u8, mode: crate::hour_mode::HourMode) {
+        self.0 = (self.0 & ALARM_MASK_BIT) | crate::hour_mode::encode_hour(hour, mode);
+    }
+}
+
+/// DY/DT bit of the alarm day/date registers: match the weekday, not the date
+const ALARM_DAY_BIT: u8 = 1 << 6;
+
+impl AlarmRegister {
+    bit_accessors! {
+        /// AxMy alarm mask bit: ignore this field when matching
+        mask, set_mask = ALARM_MASK_BIT;
+        /// DY/DT (day/date registers only): match the weekday, not the date
+        day_of_week, set_day_of_week = ALARM_DAY_BIT;
+    }
+
+    /// Seconds or minutes, from the alarm seconds (0x07) and minutes (0x08,
+    /// 0x0B) registers, without the mask bit.
+    pub fn seconds_or_minutes(self) -> u8 {
+        crate::bcd::from_bcd(self.0 & !ALARM_MASK_BIT)
+    }
+
+    /// Weekday (1-7) or date (1-31), from the alarm day/date registers
+    /// (0x0A, 0x0D), without the mask and DY/DT bits.
+    pub fn day_or_date(self) -> u8 {
+        let field = if self.day_of_week() { 0x0F } else { 0x3F };
+        crate::bcd::from_bcd(self.0 & field)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hour_mode::HourMode;
+
+    #[test]
+    fn test_decode_quarters() {
+        assert_eq!(decode_quarters(0x19, 0x40), 101);
+        assert_eq!(decode_quarters(0x00, 0x00), 0);
+        // -0.25 °C and the -128 °C minimum
+        assert_eq!(decode_quarters(0xFF, 0xC0), -1);
+        assert_eq!(decode_quarters(0x80, 0x00), -512);
+    }
+
+    #[test]
+    fn test_register_metadata() {
+        for (address, register) in Register::ALL.iter().enumerate() {
+            assert_eq!(register.addr() as usize, address);
+            assert_eq!(Register::from_addr(address as u8), Some(*register));
+        }
+        assert_eq!(Register::from_addr(0x13), None);
+
+        assert_eq!(Register::Status.writable_mask() & BSY_BIT, 0);
+        assert_eq!(Register::TempMsb.writable_mask(), 0);
+        assert_eq!(Register::Control.power_on_default(), Some(0x1C));
+        assert_eq!(Register::Status.power_on_default(), Some(0x88));
+        assert_eq!(Register::Alarm1Hours.power_on_default(), None);
+    }
+
+    #[test]
+    fn test_control_register_fields() {
+        let mut control = ControlRegister::from_bits(0x1C);
+        assert!(control.interrupt_control());
+        assert_eq!(control.rate_select(), 3);
+        assert!(!control.oscillator_disabled());
+
+        control.set_rate_select(1);
+        control.set_interrupt_control(false);
+        control.set_alarm1_interrupt(true);
+        assert_eq!(control.bits(), 0x09);
+        assert_eq!(u8::from(control), 0x09);
+
+        control.set_alarm_interrupt(Alarm::Two, true);
+        assert!(control.alarm_interrupt(Alarm::One) && control.alarm_interrupt(Alarm::Two));
+    }
+
+    #[test]
+    fn test_status_register_fields() {
+        let mut status = StatusRegister::from(0x8B);
+        assert!(status.oscillator_stop_flag());
+        assert!(status.enable_32khz());
+        assert!(status.alarm2_flag() && status.alarm1_flag());
+        assert!(!status.busy());
+
+        status.set_oscillator_stop_flag(false);
+        status.set_alarm1_flag(false);
+        assert_eq!(status.bits(), 0x0A);
+
+        status.set_alarm_flag(Alarm::Two, false);
+        assert!(!status.alarm_flag(Alarm::Two));
+        assert_eq!(status.bits(), 0x08);
+    }
+
+    #[test]
+    fn test_hour_register_keeps_alarm_mask() {
+        let mut hour = HourRegister::from_bits(ALARM_MASK_BIT | 0x14);
+        assert_eq!(hour.hour(), 14);
+        assert_eq!(hour.hour_mode(), HourMode::TwentyFourHour);
+
+        hour.set_hour(14, HourMode::TwelveHour);
+        assert!(hour.alarm_mask());
+        assert_eq!(hour.hour(), 14);
+        assert_eq!(
+            hour.bits(),
+            ALARM_MASK_BIT | HOUR_12H_BIT | HOUR_PM_BIT | 0x02
+        );
+    }
+
+    #[test]
+    fn test_alarm_register_fields() {
+        let date = AlarmRegister::from_bits(0x31);
+        assert_eq!(date.day_or_date(), 31);
+        assert!(!date.mask());
+
+        let mut day = AlarmRegister::from_bits(ALARM_DAY_BIT | 0x05);
+        assert!(day.day_of_week());
+        assert_eq!(day.day_or_date(), 5);
+        day.set_mask(true);
+        assert_eq!(day.bits(), 0xC5);
+
+        // Bit 6 is a tens digit in the seconds and minutes registers
+        assert_eq!(AlarmRegister::from_bits(0x45).seconds_or_minutes(), 45);
+        assert_eq!(AlarmRegister::from_bits(0x59).seconds_or_minutes(), 59);
+        assert_eq!(
+            AlarmRegister::from_bits(0x80 | 0x59).seconds_or_minutes(),
+            59
+        );
+    }
+}
@@ -0,0 +1,279 @@
+//! # Repairing the Stored Time
+//!
+//! After ESD or a brownout the timekeeping registers can hold values the
+//! chip could never have counted to, and every [`get_datetime`] then fails
+//! with [`Error::CorruptRegister`] or [`Error::DateTime`]. Instead of giving
+//! up, [`repair_datetime`] pulls each field back into range (digits above 9
+//! become 9, February 30th becomes February 28th or 29th, ...) and writes the
+//! result back, so the device keeps running and the application can
+//! resynchronize the time when it gets the chance:
+//!
+//! ```no_run
+//! # use ds3231_rtc::Ds3231;
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! # let mut rtc = Ds3231::new(embedded_hal_mock::eh1::i2c::Mock::new(&[]));
+//! use ds3231_rtc::repair::Repair;
+//!
+//! match rtc.repair_datetime()? {
+//!     Repair::Intact(_) => {}
+//!     Repair::Clamped(now) | Repair::Fallback(now) => println!("clock repaired to {:?}", now),
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! When a clamped time would be misleading, [`repair_datetime_with`] writes
+//! a known-good time instead, e.g. the firmware build time from
+//! [`compile_time`](mod@crate::compile_time). Both leave a readable clock
+//! untouched.
+//!
+//! [`get_datetime`]: crate::Ds3231::get_datetime
+//! [`repair_datetime`]: crate::Ds3231::repair_datetime
+//! [`repair_datetime_with`]: crate::Ds3231::repair_datetime_with
+
+use rtc_hal::datetime::{DateTime, DateTimeError, days_in_month};
+
+#[cfg(feature = "async")]
+use crate::interface::AsyncRegisterInterface;
+use crate::interface::RegisterInterface;
+
+#[cfg(feature = "defmt")]
+use crate::display::DateTimeDisplay;
+#[cfg(feature = "async")]
+use crate::ds3231::Ds3231Async;
+use crate::{
+    Ds3231, bcd,
+    datetime::decode_datetime,
+    error::Error,
+    hour_mode::{self, HourMode},
+    registers::{CENTURY_BIT, HOUR_12H_BIT, HOUR_PM_BIT},
+};
+
+/// Outcome of [`Ds3231::repair_datetime`] and
+/// [`Ds3231::repair_datetime_with`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Repair {
+    /// The registers held a valid time; nothing was written
+    Intact(DateTime),
+    /// Invalid fields were pulled into range and written back
+    Clamped(DateTime),
+    /// The registers were invalid and the fallback time was written
+    Fallback(DateTime),
+}
+
+impl Repair {
+    /// The time now stored in the device.
+    pub const fn datetime(&self) -> DateTime {
+        match self {
+            Repair::Intact(datetime) | Repair::Clamped(datetime) | Repair::Fallback(datetime) => {
+                *datetime
+            }
+        }
+    }
+
+    /// Returns `true` if the registers had to be rewritten.
+    pub const fn was_repaired(&self) -> bool {
+        !matches!(self, Repair::Intact(_))
+    }
+}
+
+#[cfg(feature = "defmt")]
+impl defmt::Format for Repair {
+    fn format(&self, f: defmt::Formatter) {
+        let datetime = DateTimeDisplay(self.datetime());
+        match self {
+            Repair::Intact(_) => defmt::write!(f, "Intact({})", datetime),
+            Repair::Clamped(_) => defmt::write!(f, "Clamped({})", datetime),
+            Repair::Fallback(_) => defmt::write!(f, "Fallback({})", datetime),
+        }
+    }
+}
+
+/// Value of a BCD field with every digit and the result capped, so noise
+/// in any bit still yields a number in `0..=max`.
+fn clamp_bcd(value: u8, max: u8) -> u8 {
+    let tens = (value >> 4).min(9);
+    let ones = (value & 0x0F).min(9);
+    (tens * 10 + ones).min(max)
+}
+
+/// Nearest valid datetime to the contents of the timekeeping registers.
+///
+/// Every field is clamped into range, so this only fails if the year is
+/// outside what [`DateTime`] supports.
+fn clamp_datetime(data: &[u8; 7], base_century: u8) -> Result<DateTime, DateTimeError> {
+    let second = clamp_bcd(data[0] & 0x7F, 59);
+    let minute = clamp_bcd(data[1] & 0x7F, 59);
+    let hour = match HourMode::from_register(data[2]) {
+        HourMode::TwelveHour => {
+            let hour = clamp_bcd(data[2] & 0b0001_1111, 12).max(1);
+            let flags = data[2] & (HOUR_12H_BIT | HOUR_PM_BIT);
+            hour_mode::decode_hour(flags | bcd::to_bcd(hour))
+        }
+        HourMode::TwentyFourHour => clamp_bcd(data[2] & 0b0011_1111, 23),
+    };
+
+    let mut century = base_century as u16;
+    if data[5] & CENTURY_BIT != 0 {
+        century += 1;
+    }
+    let year = century * 100 + clamp_bcd(data[6], 99) as u16;
+    let month = clamp_bcd(data[5] & 0x1F, 12).max(1);
+    let day_of_month = clamp_bcd(data[4] & 0x3F, days_in_month(year, month)).max(1);
+
+    DateTime::new(year, month, day_of_month, hour, minute, second)
+}
+
+#[maybe_async_cfg::maybe(
+    idents(
+        AsyncRegisterInterface(sync = "RegisterInterface", async = "AsyncRegisterInterface"),
+        Ds3231(sync = "Ds3231", async = "Ds3231Async")
+    ),
+    sync(keep_self),
+    async(feature = "async")
+)]
+impl<I2C, V> Ds3231<I2C, V>
+where
+    I2C: AsyncRegisterInterface,
+{
+    /// Read the time and, if the registers are invalid, replace them with
+    /// the nearest valid datetime.
+    ///
+    /// See the [module documentation](crate::repair).
+    pub async fn repair_datetime(&mut self) -> Result<Repair, Error<I2C::Error>> {
+        let data = self.read_time_registers().await?;
+        match decode_datetime(&data, self.base_century) {
+            Ok(datetime) => Ok(Repair::Intact(datetime)),
+            Err(Error::CorruptRegister { .. } | Error::DateTime(_)) => {
+                let datetime = clamp_datetime(&data, self.base_century).map_err(Error::DateTime)?;
+                self.set_datetime(&datetime).await?;
+                Ok(Repair::Clamped(datetime))
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Read the time and, if the registers are invalid, replace them with
+    /// `fallback`.
+    pub async fn repair_datetime_with(
+        &mut self,
+        fallback: &DateTime,
+    ) -> Result<Repair, Error<I2C::Error>> {
+        let data = self.read_time_registers().await?;
+        match decode_datetime(&data, self.base_century) {
+            Ok(datetime) => Ok(Repair::Intact(datetime)),
+            Err(Error::CorruptRegister { .. } | Error::DateTime(_)) => {
+                self.set_datetime(fallback).await?;
+                Ok(Repair::Fallback(*fallback))
+            }
+            Err(e) => Err(e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registers::Register;
+    use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
+
+    #[test]
+    fn test_clamp_datetime() {
+        // Seconds 0x5A, 12h mode hour 0x1F PM, 31 February
+        let data = [0x5A, 0x30, 0x7F, 0x01, 0x31, 0x02, 0x24];
+        assert_eq!(
+            clamp_datetime(&data, 20),
+            DateTime::new(2024, 2, 29, 12, 30, 59)
+        );
+
+        let data = [0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x1F, 0xFF];
+        assert_eq!(
+            clamp_datetime(&data, 20),
+            DateTime::new(2099, 12, 31, 12, 59, 59)
+        );
+
+        let data = [0x00; 7];
+        assert_eq!(
+            clamp_datetime(&data, 20),
+            DateTime::new(2000, 1, 1, 0, 0, 0)
+        );
+    }
+
+    #[test]
+    fn test_repair_datetime() {
+        let valid = vec![0x00, 0x30, 0x14, 0x05, 0x21, 0x08, 0x25];
+        let expectations = [
+            I2cTransaction::write_read(0x68, vec![Register::Seconds.addr()], valid),
+            I2cTransaction::write_read(
+                0x68,
+                vec![Register::Seconds.addr()],
+                vec![0x00, 0x3C, 0x14, 0x05, 0x31, 0x06, 0x25],
+            ),
+            // 2025-06-30 14:39:00 is a Monday
+            I2cTransaction::write(
+                0x68,
+                vec![
+                    Register::Seconds.addr(),
+                    0x00,
+                    0x39,
+                    0x14,
+                    0x02,
+                    0x30,
+                    0x06,
+                    0x25,
+                ],
+            ),
+        ];
+        let mut ds3231 = Ds3231::new(I2cMock::new(&expectations));
+
+        let repair = ds3231.repair_datetime().unwrap();
+        assert!(!repair.was_repaired());
+        assert_eq!(
+            repair.datetime(),
+            DateTime::new(2025, 8, 21, 14, 30, 0).unwrap()
+        );
+
+        assert_eq!(
+            ds3231.repair_datetime(),
+            Ok(Repair::Clamped(
+                DateTime::new(2025, 6, 30, 14, 39, 0).unwrap()
+            ))
+        );
+
+        ds3231.release_i2c().done();
+    }
+
+    #[test]
+    fn test_repair_datetime_with_fallback() {
+        let fallback = DateTime::new(2025, 1, 1, 0, 0, 0).unwrap();
+        let expectations = [
+            I2cTransaction::write_read(
+                0x68,
+                vec![Register::Seconds.addr()],
+                vec![0xEE, 0x30, 0x14, 0x05, 0x21, 0x08, 0x25],
+            ),
+            // 2025-01-01 is a Wednesday
+            I2cTransaction::write(
+                0x68,
+                vec![
+                    Register::Seconds.addr(),
+                    0x00,
+                    0x00,
+                    0x00,
+                    0x04,
+                    0x01,
+                    0x01,
+                    0x25,
+                ],
+            ),
+        ];
+        let mut ds3231 = Ds3231::new(I2cMock::new(&expectations));
+
+        assert_eq!(
+            ds3231.repair_datetime_with(&fallback),
+            Ok(Repair::Fallback(fallback))
+        );
+
+        ds3231.release_i2c().done();
+    }
+}
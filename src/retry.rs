@@ -0,0 +1,233 @@
+//! # Retrying Transport
+//!
+//! Long wires and contended buses occasionally NACK a transfer that would
+//! succeed a moment later. [`Retry`] wraps any register transport and
+//! repeats failed transfers according to a [`RetryPolicy`], waiting on a
+//! [`DelayNs`] between attempts with exponential backoff. Only the error of
+//! the last attempt is returned.
+//!
+//! ```no_run
+//! # use ds3231_rtc::Ds3231;
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! # let i2c = embedded_hal_mock::eh1::i2c::Mock::new(&[]);
+//! use ds3231_rtc::retry::{Retry, RetryPolicy};
+//! # let delay = embedded_hal_mock::eh1::delay::NoopDelay::new();
+//!
+//! let mut rtc = Ds3231::new(Retry::new(i2c, delay, RetryPolicy::default()));
+//! let now = rtc.get_datetime()?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Each register transfer is retried on its own; a read-modify-write is
+//! split into its read and write, so an atomic
+//! [`modify_register`](RegisterInterface::modify_register) override of the
+//! wrapped transport is not used. Wrap the bus in `Retry` *inside* such a
+//! transport instead.
+
+use embedded_hal::delay::DelayNs;
+#[cfg(feature = "async")]
+use embedded_hal_async::delay::DelayNs as AsyncDelayNs;
+
+#[cfg(feature = "async")]
+use crate::interface::AsyncRegisterInterface;
+use crate::interface::RegisterInterface;
+use crate::trace::debug;
+
+/// How often and how patiently [`Retry`] repeats a failed transfer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct RetryPolicy {
+    /// Total attempts per transfer, including the first; 0 is treated as 1
+    pub attempts: u8,
+    /// Wait before the first retry in microseconds, doubled for each
+    /// further retry
+    pub backoff_us: u32,
+}
+
+impl RetryPolicy {
+    /// Policy with `attempts` tries per transfer, waiting `backoff_us`
+    /// before the first retry.
+    pub const fn new(attempts: u8, backoff_us: u32) -> Self {
+        Self {
+            attempts,
+            backoff_us,
+        }
+    }
+
+    /// Wait in microseconds before retry number `retry` (starting at 0).
+    fn backoff_for(&self, retry: u8) -> u32 {
+        let factor = 1u32.checked_shl(retry as u32).unwrap_or(u32::MAX);
+        self.backoff_us.saturating_mul(factor)
+    }
+}
+
+/// Three attempts, 100 µs before the first retry
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::new(3, 100)
+    }
+}
+
+/// Register transport that retries failed transfers of the wrapped one
+#[derive(Debug)]
+pub struct Retry<T, D> {
+    inner: T,
+    delay: D,
+    policy: RetryPolicy,
+    retries: u32,
+}
+
+impl<T, D> Retry<T, D> {
+    /// Wrap `inner`, waiting on `delay` between attempts.
+    pub fn new(inner: T, delay: D, policy: RetryPolicy) -> Self {
+        Self {
+            inner,
+            delay,
+            policy,
+            retries: 0,
+        }
+    }
+
+    /// Returns the configured policy.
+    pub fn policy(&self) -> RetryPolicy {
+        self.policy
+    }
+
+    /// Returns how many transfers were repeated, wrapping around at
+    /// `u32::MAX`.
+    pub fn retries(&self) -> u32 {
+        self.retries
+    }
+
+    /// Returns the wrapped transport and delay, consuming the adapter.
+    pub fn into_inner(self) -> (T, D) {
+        (self.inner, self.delay)
+    }
+}
+
+#[maybe_async_cfg::maybe(
+    idents(
+        AsyncDelayNs(sync = "DelayNs", async = "AsyncDelayNs"),
+        AsyncRegisterInterface(sync = "RegisterInterface", async = "AsyncRegisterInterface")
+    ),
+    sync(keep_self),
+    async(feature = "async", keep_self)
+)]
+impl<T: AsyncRegisterInterface, D: AsyncDelayNs> AsyncRegisterInterface for Retry<T, D> {
+    type Error = T::Error;
+
+    async fn read_registers(
+        &mut self,
+        address: u8,
+        register: u8,
+        buffer: &mut [u8],
+    ) -> Result<(), Self::Error> {
+        let mut retry = 0;
+        loop {
+            match self.inner.read_registers(address, register, buffer).await {
+                Err(_) if retry + 1 < self.policy.attempts => {
+                    debug!("retrying read of register {:#04x}", register);
+                    self.delay.delay_us(self.policy.backoff_for(retry)).await;
+                    self.retries = self.retries.wrapping_add(1);
+                    retry += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    async fn write_registers(&mut self, address: u8, data: &[u8]) -> Result<(), Self::Error> {
+        let mut retry = 0;
+        loop {
+            match self.inner.write_registers(address, data).await {
+                Err(_) if retry + 1 < self.policy.attempts => {
+                    debug!(
+                        "retrying write of register {:#04x}",
+                        data.first().copied().unwrap_or_default()
+                    );
+                    self.delay.delay_us(self.policy.backoff_for(retry)).await;
+                    self.retries = self.retries.wrapping_add(1);
+                    retry += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Ds3231;
+    use crate::registers::Register;
+    use embedded_hal::i2c::ErrorKind;
+    use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
+
+    /// Delay that records the requested waits
+    #[derive(Default)]
+    struct RecordingDelay {
+        waits_us: Vec<u32>,
+    }
+
+    impl DelayNs for RecordingDelay {
+        fn delay_ns(&mut self, ns: u32) {
+            self.waits_us.push(ns / 1000);
+        }
+    }
+
+    fn status_read() -> I2cTransaction {
+        I2cTransaction::write_read(0x68, vec![Register::Status.addr()], vec![0x80])
+    }
+
+    #[test]
+    fn test_transient_errors_are_retried_with_backoff() {
+        let expectations = [
+            status_read().with_error(ErrorKind::Other),
+            status_read().with_error(ErrorKind::Other),
+            status_read(),
+        ];
+        let retry = Retry::new(
+            I2cMock::new(&expectations),
+            RecordingDelay::default(),
+            RetryPolicy::new(3, 100),
+        );
+        let mut ds3231 = Ds3231::new(retry);
+
+        assert_eq!(ds3231.oscillator_stopped(), Ok(true));
+        assert_eq!(ds3231.stats().bus_errors, 0);
+
+        let retry = ds3231.release();
+        assert_eq!(retry.retries(), 2);
+        let (mut i2c, delay) = retry.into_inner();
+        assert_eq!(delay.waits_us, [100, 200]);
+        i2c.done();
+    }
+
+    #[test]
+    fn test_last_error_is_returned() {
+        let expectations = [
+            status_read().with_error(ErrorKind::Other),
+            status_read().with_error(ErrorKind::Other),
+        ];
+        let retry = Retry::new(
+            I2cMock::new(&expectations),
+            RecordingDelay::default(),
+            RetryPolicy::new(2, 50),
+        );
+        let mut ds3231 = Ds3231::new(retry);
+
+        assert_eq!(
+            ds3231.oscillator_stopped(),
+            Err(crate::error::Error::Transfer {
+                source: ErrorKind::Other,
+                register: Register::Status.addr(),
+                operation: crate::error::Operation::Read,
+            })
+        );
+
+        let (mut i2c, delay) = ds3231.release().into_inner();
+        assert_eq!(delay.waits_us, [50]);
+        i2c.done();
+    }
+}
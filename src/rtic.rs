@@ -0,0 +1,270 @@
+//! # RTIC Monotonic
+//!
+//! With the `rtic` feature, [`sqw_monotonic!`](crate::sqw_monotonic!)
+//! defines an RTIC [`Monotonic`](rtic_time::Monotonic) counting edges of the
+//! DS3231's INT/SQW pin, so software tasks keep their schedule while the
+//! MCU's own timers are powered down in deep sleep. One tick is one period
+//! of the square wave, and the pin's interrupt handler reports the edges:
+//!
+//! ```ignore
+//! use rtic_monotonics::Monotonic;
+//!
+//! // Ticks at 1 Hz, matching the square wave
+//! ds3231_rtc::sqw_monotonic!(Mono, 1);
+//!
+//! #[init]
+//! fn init(cx: init::Context) -> (Shared, Local) {
+//!     rtc.start_square_wave(SquareWaveFreq::Hz1).unwrap();
+//!     Mono::start();
+//!     // ...
+//! }
+//!
+//! #[task(binds = EXTI0)]
+//! fn sqw(_: sqw::Context) {
+//!     clear_pin_interrupt();
+//!     Mono::on_tick();
+//! }
+//!
+//! #[task]
+//! async fn blink(_: blink::Context) {
+//!     loop {
+//!         toggle_led();
+//!         Mono::delay(10.secs()).await;
+//!     }
+//! }
+//! ```
+//!
+//! To sleep through several ticks without a wakeup per edge, switch the pin
+//! to alarms, program an alarm for [`next_wake`](SqwMonotonicState::next_wake)
+//! (e.g. with [`RtcTimer`](crate::timer::RtcTimer)), and report the ticks
+//! slept with `Mono::advance` from the alarm's interrupt handler.
+//!
+//! A task scheduled ahead of all others is picked up at the next edge, so
+//! delays resolve to one tick and, like all RTIC monotonics, last at least
+//! one tick longer than asked.
+
+use core::cell::Cell;
+
+use critical_section::Mutex;
+
+#[doc(hidden)]
+pub use {fugit, rtic_time};
+
+#[derive(Clone, Copy)]
+struct Ticks {
+    now: u64,
+    compare: Option<u64>,
+    pending: bool,
+}
+
+/// Tick count and next deadline of a monotonic from
+/// [`sqw_monotonic!`](crate::sqw_monotonic!)
+pub struct SqwMonotonicState {
+    ticks: Mutex<Cell<Ticks>>,
+}
+
+impl Default for SqwMonotonicState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SqwMonotonicState {
+    /// State at tick 0 with no deadline.
+    pub const fn new() -> Self {
+        Self {
+            ticks: Mutex::new(Cell::new(Ticks {
+                now: 0,
+                compare: None,
+                pending: false,
+            })),
+        }
+    }
+
+    /// Ticks so far.
+    pub fn now(&self) -> u64 {
+        self.with(|ticks| ticks.now)
+    }
+
+    /// Earliest tick a task waits for, if any.
+    ///
+    /// While a newly scheduled task hasn't been picked up yet, this is the
+    /// next tick.
+    pub fn next_wake(&self) -> Option<u64> {
+        self.with(|ticks| match ticks.pending {
+            true => Some(ticks.now + 1),
+            false => ticks.compare,
+        })
+    }
+
+    /// Move forward by `ticks` and return `true` if the timer queue has
+    /// tasks to wake or pick up.
+    pub fn advance(&self, ticks: u64) -> bool {
+        self.with(|state| {
+            state.now = state.now.saturating_add(ticks);
+            let due = state.pending || state.compare.is_some_and(|at| at <= state.now);
+            state.pending = false;
+            due
+        })
+    }
+
+    /// Wake the tasks waiting for `instant`.
+    pub fn set_compare(&self, instant: u64) {
+        self.with(|ticks| ticks.compare = Some(instant));
+    }
+
+    /// Forget the deadline once no task waits.
+    pub fn clear_compare(&self) {
+        self.with(|ticks| ticks.compare = None);
+    }
+
+    /// Pick up the timer queue at the next tick.
+    pub fn pend(&self) {
+        self.with(|ticks| ticks.pending = true);
+    }
+
+    fn with<R>(&self, f: impl FnOnce(&mut Ticks) -> R) -> R {
+        critical_section::with(|cs| {
+            let cell = self.ticks.borrow(cs);
+            let mut ticks = cell.get();
+            let result = f(&mut ticks);
+            cell.set(ticks);
+            result
+        })
+    }
+}
+
+/// Define an RTIC monotonic `$name` ticking on edges of a square wave
+/// running at `$sqw_hz`.
+///
+/// See the [module documentation](crate::rtic) for the interrupt handler
+/// it needs.
+#[macro_export]
+macro_rules! sqw_monotonic {
+    ($name:ident, $sqw_hz:expr) => {
+        /// RTIC monotonic counting edges of the DS3231 square wave
+        pub struct $name;
+
+        impl $name {
+            /// Start the monotonic; the square wave must already be running.
+            pub fn start() {
+                <Self as $crate::rtic::rtic_time::timer_queue::TimerQueueBackend>::timer_queue()
+                    .initialize(Self);
+            }
+
+            /// Report one edge of the square wave, from its interrupt
+            /// handler.
+            pub fn on_tick() {
+                Self::advance(1);
+            }
+
+            /// Move forward by `ticks` and wake the tasks now due, e.g.
+            /// after sleeping until an alarm.
+            #[allow(unsafe_code)]
+            pub fn advance(ticks: u64) {
+                if Self::state().advance(ticks) {
+                    // SAFETY: the edges and alarms are this monotonic's
+                    // timer interrupt
+                    unsafe {
+                        <Self as $crate::rtic::rtic_time::timer_queue::TimerQueueBackend>::timer_queue()
+                            .on_monotonic_interrupt();
+                    }
+                }
+            }
+
+            /// Earliest tick a task waits for, if any.
+            pub fn next_wake() -> Option<u64> {
+                Self::state().next_wake()
+            }
+
+            fn state() -> &'static $crate::rtic::SqwMonotonicState {
+                static STATE: $crate::rtic::SqwMonotonicState =
+                    $crate::rtic::SqwMonotonicState::new();
+                &STATE
+            }
+        }
+
+        impl $crate::rtic::rtic_time::timer_queue::TimerQueueBackend for $name {
+            type Ticks = u64;
+
+            fn now() -> u64 {
+                Self::state().now()
+            }
+
+            fn set_compare(instant: u64) {
+                Self::state().set_compare(instant);
+            }
+
+            fn clear_compare_flag() {}
+
+            fn pend_interrupt() {
+                Self::state().pend();
+            }
+
+            fn disable_timer() {
+                Self::state().clear_compare();
+            }
+
+            fn timer_queue() -> &'static $crate::rtic::rtic_time::timer_queue::TimerQueue<Self> {
+                static QUEUE: $crate::rtic::rtic_time::timer_queue::TimerQueue<$name> =
+                    $crate::rtic::rtic_time::timer_queue::TimerQueue::new();
+                &QUEUE
+            }
+        }
+
+        impl $crate::rtic::rtic_time::monotonic::TimerQueueBasedMonotonic for $name {
+            type Backend = Self;
+            type Instant = $crate::rtic::fugit::Instant<u64, 1, { $sqw_hz }>;
+            type Duration = $crate::rtic::fugit::Duration<u64, 1, { $sqw_hz }>;
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::pin::pin;
+    use core::task::{Context, Poll, Waker};
+    use rtic_time::Monotonic;
+
+    #[test]
+    fn test_state_runs_queue_when_due_or_pended() {
+        let state = SqwMonotonicState::new();
+        assert!(!state.advance(1));
+        assert_eq!(state.next_wake(), None);
+
+        state.pend();
+        assert_eq!(state.next_wake(), Some(2));
+        assert!(state.advance(1));
+        assert!(!state.advance(0));
+
+        state.set_compare(4);
+        assert!(!state.advance(1));
+        assert!(state.advance(5));
+        assert_eq!(state.now(), 8);
+
+        state.clear_compare();
+        assert!(!state.advance(1));
+    }
+
+    #[test]
+    fn test_delay_completes_after_edges() {
+        crate::sqw_monotonic!(Mono, 1);
+        Mono::start();
+
+        let mut cx = Context::from_waker(Waker::noop());
+        let mut delay = pin!(Mono::delay(fugit::Duration::<u64, 1, 1>::from_ticks(2)));
+        assert_eq!(delay.as_mut().poll(&mut cx), Poll::Pending);
+
+        // The queue picks the task up at the first edge
+        Mono::on_tick();
+        assert_eq!(Mono::next_wake(), Some(3));
+        Mono::advance(1);
+        assert_eq!(delay.as_mut().poll(&mut cx), Poll::Pending);
+
+        Mono::on_tick();
+        assert_eq!(delay.as_mut().poll(&mut cx), Poll::Ready(()));
+        assert_eq!(Mono::now().ticks(), 3);
+        assert_eq!(Mono::next_wake(), None);
+    }
+}
@@ -0,0 +1,391 @@
+//! # Wakeup Scheduler
+//!
+//! With the `scheduler` feature, [`Scheduler`] holds up to `N` events, each
+//! a date and time with an [`EventId`], and keeps Alarm 1 programmed for
+//! the earliest one, so the MCU can sleep until the INT pin wakes it for
+//! the next event however many are queued. After each wakeup,
+//! [`poll`](Scheduler::poll) returns the events that are due and arms the
+//! alarm for the next:
+//!
+//! ```no_run
+//! # use ds3231_rtc::Ds3231;
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! # let mut rtc = Ds3231::new(embedded_hal_mock::eh1::i2c::Mock::new(&[]));
+//! use ds3231_rtc::scheduler::Scheduler;
+//! use rtc_hal::datetime::DateTime;
+//! # let sleep_until_int_pin = || ();
+//! # let upload = || ();
+//!
+//! const SAMPLE: u16 = 1;
+//! const UPLOAD: u16 = 2;
+//!
+//! let mut scheduler: Scheduler<8> = Scheduler::new();
+//! scheduler.schedule(&mut rtc, DateTime::new(2025, 8, 21, 7, 0, 0)?, SAMPLE)?;
+//! scheduler.schedule(&mut rtc, DateTime::new(2025, 8, 21, 12, 0, 0)?, UPLOAD)?;
+//!
+//! loop {
+//!     sleep_until_int_pin();
+//!     for event in scheduler.poll(&mut rtc)? {
+//!         match event {
+//!             SAMPLE => {
+//!                 let next = ds3231_rtc::calendar::add_seconds(&rtc.get_datetime()?, 600)?;
+//!                 scheduler.schedule(&mut rtc, next, SAMPLE)?;
+//!             }
+//!             UPLOAD => upload(),
+//!             _ => {}
+//!         }
+//!     }
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! The scheduler owns Alarm 1 and its interrupt; Alarm 2 stays free for
+//! other uses. Events with the same time are returned in the order they
+//! were scheduled. The alarm matches the day of the month, so an event more
+//! than 28 days ahead may cause early wakeups in the months before; `poll`
+//! returns nothing for those and leaves the alarm armed. An event scheduled
+//! at or before the current time can't trigger the alarm, but the next
+//! `poll` returns it.
+
+use heapless::Vec;
+use rtc_hal::datetime::DateTime;
+
+use crate::Ds3231;
+use crate::alarm::Alarm;
+use crate::calendar;
+use crate::error::Error;
+use crate::interface::RegisterInterface;
+use crate::timer::arm_alarm;
+
+/// Application-chosen identifier of a scheduled event
+pub type EventId = u16;
+
+/// Up to `N` events waking the MCU through Alarm 1
+#[derive(Debug, Clone)]
+pub struct Scheduler<const N: usize> {
+    /// Unix timestamp, time and id of each event, latest first so the
+    /// earliest pops off the end
+    events: Vec<(u64, DateTime, EventId), N>,
+    /// Timestamp Alarm 1 is programmed for
+    armed: Option<u64>,
+}
+
+impl<const N: usize> Default for Scheduler<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> Scheduler<N> {
+    /// Empty scheduler. Alarm 1 is left alone until the first event.
+    pub const fn new() -> Self {
+        Self {
+            events: Vec::new(),
+            armed: None,
+        }
+    }
+
+    /// Number of events waiting.
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    /// Returns `true` if no event is waiting.
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    /// Earliest waiting event.
+    pub fn next(&self) -> Option<(DateTime, EventId)> {
+        self.events.last().map(|&(_, at, id)| (at, id))
+    }
+
+    /// Add event `id` at `at`, arming Alarm 1 for it if it is now the
+    /// earliest.
+    ///
+    /// Fails with [`Error::SchedulerFull`] if `N` events are already
+    /// waiting. The event is not added if arming the alarm fails, and the
+    /// alarm is armed for the previous earliest event again.
+    pub fn schedule<I2C, V>(
+        &mut self,
+        rtc: &mut Ds3231<I2C, V>,
+        at: DateTime,
+        id: EventId,
+    ) -> Result<(), Error<I2C::Error>>
+    where
+        I2C: RegisterInterface,
+    {
+        let timestamp = calendar::to_unix_timestamp(&at);
+        // Before any event at the same time, so those pop first
+        let index = self
+            .events
+            .iter()
+            .position(|&(t, _, _)| t <= timestamp)
+            .unwrap_or(self.events.len());
+        self.events
+            .insert(index, (timestamp, at, id))
+            .map_err(|_| Error::SchedulerFull)?;
+
+        if index + 1 < self.events.len() {
+            return Ok(());
+        }
+        self.arm(rtc).inspect_err(|_| {
+            self.events.pop();
+            // The alarm registers may already hold the rejected event; a
+            // failure here leaves `armed` unset, so the next poll retries
+            let _ = self.arm(rtc);
+        })
+    }
+
+    /// Remove every event with `id`, returning `true` if there was one.
+    ///
+    /// Alarm 1 moves on to the next event, or its interrupt is disabled if
+    /// none is left.
+    pub fn cancel<I2C, V>(
+        &mut self,
+        rtc: &mut Ds3231<I2C, V>,
+        id: EventId,
+    ) -> Result<bool, Error<I2C::Error>>
+    where
+        I2C: RegisterInterface,
+    {
+        let len = self.events.len();
+        self.events.retain(|&(_, _, event)| event != id);
+        if self.events.len() == len {
+            return Ok(false);
+        }
+
+        match self.events.last() {
+            Some(&(timestamp, _, _)) if self.armed == Some(timestamp) => {}
+            Some(_) => self.arm(rtc)?,
+            None => self.disarm(rtc)?,
+        }
+        Ok(true)
+    }
+
+    /// Remove and return the events that are due, in time order, and arm
+    /// Alarm 1 for the next one.
+    ///
+    /// Call after the INT pin signalled the alarm, or periodically.
+    pub fn poll<I2C, V>(
+        &mut self,
+        rtc: &mut Ds3231<I2C, V>,
+    ) -> Result<Vec<EventId, N>, Error<I2C::Error>>
+    where
+        I2C: RegisterInterface,
+    {
+        let mut due = Vec::new();
+        if self.armed.is_some() {
+            // Release the match that woke us, or an early match of a far-off
+            // event, before reading the time: a match right after the read
+            // then still raises the flag and the INT pin
+            rtc.clear_alarm_flag(Alarm::One)?;
+        }
+        let mut now = rtc.get_unix_timestamp()?;
+        loop {
+            while let Some(&(timestamp, _, id)) = self.events.last() {
+                if timestamp > now {
+                    break;
+                }
+                self.events.pop();
+                // Never full: it holds at most the N events popped
+                let _ = due.push(id);
+            }
+
+            let next = match self.events.last() {
+                Some(&(timestamp, _, _)) => timestamp,
+                None => {
+                    self.disarm(rtc)?;
+                    return Ok(due);
+                }
+            };
+            if self.armed == Some(next) {
+                return Ok(due);
+            }
+
+            self.arm(rtc)?;
+            // The next event may have fallen due while arming the alarm
+            now = rtc.get_unix_timestamp()?;
+            if now < next {
+                return Ok(due);
+            }
+        }
+    }
+
+    /// Program Alarm 1 for the earliest event.
+    fn arm<I2C, V>(&mut self, rtc: &mut Ds3231<I2C, V>) -> Result<(), Error<I2C::Error>>
+    where
+        I2C: RegisterInterface,
+    {
+        if let Some(&(timestamp, at, _)) = self.events.last() {
+            self.armed = None;
+            arm_alarm(rtc, Alarm::One, &at)?;
+            self.armed = Some(timestamp);
+        }
+        Ok(())
+    }
+
+    /// Disable Alarm 1 once no event is left.
+    fn disarm<I2C, V>(&mut self, rtc: &mut Ds3231<I2C, V>) -> Result<(), Error<I2C::Error>>
+    where
+        I2C: RegisterInterface,
+    {
+        if self.armed.take().is_some() {
+            rtc.set_alarm_interrupt(Alarm::One, false)?;
+            rtc.clear_alarm_flag(Alarm::One)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registers::Register;
+    use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
+
+    const DS3231_ADDR: u8 = 0x68;
+
+    /// 2025-09-01 at `hh:mm:ss`, BCD
+    fn time_read(hours: u8, minutes: u8, seconds: u8) -> I2cTransaction {
+        I2cTransaction::write_read(
+            DS3231_ADDR,
+            vec![Register::Seconds.addr()],
+            vec![seconds, minutes, hours, 0x02, 0x01, 0x09, 0x25],
+        )
+    }
+
+    /// Alarm 1 programmed for 2025-09-01 at `hh:mm:00`, with the flag
+    /// already clear and the interrupt already enabled
+    fn arm(hours: u8, minutes: u8) -> [I2cTransaction; 3] {
+        [
+            I2cTransaction::write(
+                DS3231_ADDR,
+                vec![Register::Alarm1Seconds.addr(), 0x00, minutes, hours, 0x01],
+            ),
+            I2cTransaction::write_read(DS3231_ADDR, vec![Register::Status.addr()], vec![0x88]),
+            I2cTransaction::write_read(DS3231_ADDR, vec![Register::Control.addr()], vec![0x1D]),
+        ]
+    }
+
+    fn at(hour: u8, minute: u8) -> DateTime {
+        DateTime::new(2025, 9, 1, hour, minute, 0).unwrap()
+    }
+
+    #[test]
+    fn test_alarm_follows_earliest_event() {
+        let mut expectations = arm(0x12, 0x00).to_vec();
+        // Only the earlier event re-arms
+        expectations.extend(arm(0x08, 0x00));
+        // Polling at 08:00:00 pops it and arms the next
+        expectations.extend([
+            I2cTransaction::write_read(DS3231_ADDR, vec![Register::Status.addr()], vec![0x89]),
+            I2cTransaction::write(DS3231_ADDR, vec![Register::Status.addr(), 0x8A]),
+            time_read(0x08, 0x00, 0x00),
+        ]);
+        expectations.extend(arm(0x12, 0x00));
+        expectations.push(time_read(0x08, 0x00, 0x01));
+        let mut ds3231 = Ds3231::new(I2cMock::new(&expectations));
+
+        let mut scheduler: Scheduler<3> = Scheduler::new();
+        scheduler.schedule(&mut ds3231, at(12, 0), 1).unwrap();
+        scheduler.schedule(&mut ds3231, at(8, 0), 2).unwrap();
+        scheduler.schedule(&mut ds3231, at(12, 0), 3).unwrap();
+        assert_eq!(scheduler.next(), Some((at(8, 0), 2)));
+        assert_eq!(
+            scheduler.schedule(&mut ds3231, at(9, 0), 4),
+            Err(Error::SchedulerFull)
+        );
+
+        assert_eq!(scheduler.poll(&mut ds3231).unwrap(), [2]);
+        assert_eq!(scheduler.len(), 2);
+
+        ds3231.release_i2c().done();
+    }
+
+    #[test]
+    fn test_poll_returns_same_time_events_in_order() {
+        let mut expectations = arm(0x12, 0x00).to_vec();
+        expectations.extend([
+            // Early wakeup: nothing due, the alarm stays armed. The flag is
+            // cleared before the time is read, so a match right after the
+            // read still raises it.
+            I2cTransaction::write_read(DS3231_ADDR, vec![Register::Status.addr()], vec![0x89]),
+            I2cTransaction::write(DS3231_ADDR, vec![Register::Status.addr(), 0x8A]),
+            time_read(0x11, 0x59, 0x59),
+            // Both due: disarm
+            I2cTransaction::write_read(DS3231_ADDR, vec![Register::Status.addr()], vec![0x89]),
+            I2cTransaction::write(DS3231_ADDR, vec![Register::Status.addr(), 0x8A]),
+            time_read(0x12, 0x00, 0x00),
+            I2cTransaction::write_read(DS3231_ADDR, vec![Register::Control.addr()], vec![0x1D]),
+            I2cTransaction::write(DS3231_ADDR, vec![Register::Control.addr(), 0x1C]),
+            I2cTransaction::write_read(DS3231_ADDR, vec![Register::Status.addr()], vec![0x88]),
+            // Nothing armed any more
+            time_read(0x12, 0x00, 0x05),
+        ]);
+        let mut ds3231 = Ds3231::new(I2cMock::new(&expectations));
+
+        let mut scheduler: Scheduler<2> = Scheduler::new();
+        scheduler.schedule(&mut ds3231, at(12, 0), 7).unwrap();
+        scheduler.schedule(&mut ds3231, at(12, 0), 5).unwrap();
+
+        assert!(scheduler.poll(&mut ds3231).unwrap().is_empty());
+        assert_eq!(scheduler.poll(&mut ds3231).unwrap(), [7, 5]);
+        assert!(scheduler.poll(&mut ds3231).unwrap().is_empty());
+        assert!(scheduler.is_empty());
+
+        ds3231.release_i2c().done();
+    }
+
+    #[test]
+    fn test_cancel_moves_alarm_on() {
+        let mut expectations = arm(0x12, 0x00).to_vec();
+        expectations.extend(arm(0x08, 0x00));
+        expectations.extend(arm(0x12, 0x00));
+        expectations.extend([
+            I2cTransaction::write_read(DS3231_ADDR, vec![Register::Control.addr()], vec![0x1D]),
+            I2cTransaction::write(DS3231_ADDR, vec![Register::Control.addr(), 0x1C]),
+            I2cTransaction::write_read(DS3231_ADDR, vec![Register::Status.addr()], vec![0x88]),
+        ]);
+        let mut ds3231 = Ds3231::new(I2cMock::new(&expectations));
+
+        let mut scheduler: Scheduler<2> = Scheduler::new();
+        scheduler.schedule(&mut ds3231, at(12, 0), 1).unwrap();
+        scheduler.schedule(&mut ds3231, at(8, 0), 2).unwrap();
+
+        assert_eq!(scheduler.cancel(&mut ds3231, 9), Ok(false));
+        assert_eq!(scheduler.cancel(&mut ds3231, 2), Ok(true));
+        assert_eq!(scheduler.cancel(&mut ds3231, 1), Ok(true));
+        assert!(scheduler.is_empty());
+
+        ds3231.release_i2c().done();
+    }
+
+    #[test]
+    fn test_failed_schedule_rearms_previous_event() {
+        let mut expectations = arm(0x12, 0x00).to_vec();
+        expectations.push(
+            I2cTransaction::write(
+                DS3231_ADDR,
+                vec![Register::Alarm1Seconds.addr(), 0x00, 0x00, 0x08, 0x01],
+            )
+            .with_error(embedded_hal::i2c::ErrorKind::Other),
+        );
+        expectations.extend(arm(0x12, 0x00));
+        // The previous event is armed, so polling only releases the flag
+        expectations.extend([
+            I2cTransaction::write_read(DS3231_ADDR, vec![Register::Status.addr()], vec![0x88]),
+            time_read(0x11, 0x00, 0x00),
+        ]);
+        let mut ds3231 = Ds3231::new(I2cMock::new(&expectations));
+
+        let mut scheduler: Scheduler<2> = Scheduler::new();
+        scheduler.schedule(&mut ds3231, at(12, 0), 1).unwrap();
+        assert!(scheduler.schedule(&mut ds3231, at(8, 0), 2).is_err());
+        assert_eq!(scheduler.next(), Some((at(12, 0), 1)));
+        assert!(scheduler.poll(&mut ds3231).unwrap().is_empty());
+
+        ds3231.release_i2c().done();
+    }
+}
@@ -0,0 +1,221 @@
+//! # Self-Test
+//!
+//! [`self_test`](crate::Ds3231::self_test) runs the checks a production test
+//! fixture needs to accept a board, with nothing attached but the bus:
+//!
+//! 1. the device acknowledges its address
+//! 2. the seconds register advances, i.e. the oscillator runs
+//! 3. the control and status registers take a write
+//!
+//! ```no_run
+//! # use ds3231_rtc::Ds3231;
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! # let mut rtc = Ds3231::new(embedded_hal_mock::eh1::i2c::Mock::new(&[]));
+//! # let mut delay = embedded_hal_mock::eh1::delay::NoopDelay::new();
+//! let report = rtc.self_test(&mut delay)?;
+//! if !report.passed() {
+//!     println!("RTC self-test failed: {:?}", report);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! The write checks toggle BBSQW in the control register and EN32kHz in the
+//! status register, read them back and restore the previous value, so the
+//! configuration is unchanged afterwards. The status writes leave the alarm
+//! and oscillator stop flags alone. Checks that fail are reported in
+//! the [`SelfTestReport`]; only a bus error after the device answered aborts
+//! the test with an error. Waiting for the seconds to advance takes up to
+//! 1.1 s.
+
+use embedded_hal::delay::DelayNs;
+#[cfg(feature = "async")]
+use embedded_hal_async::delay::DelayNs as AsyncDelayNs;
+
+#[cfg(feature = "async")]
+use crate::interface::AsyncRegisterInterface;
+use crate::interface::RegisterInterface;
+
+use crate::Ds3231;
+#[cfg(feature = "async")]
+use crate::ds3231::Ds3231Async;
+use crate::error::Error;
+use crate::registers::{A1F_BIT, A2F_BIT, BBSQW_BIT, EN32KHZ_BIT, OSF_BIT, Register};
+
+/// Longest wait for the seconds register to advance
+const TICK_TIMEOUT_MS: u32 = 1100;
+
+/// Wait between two reads of the seconds register
+const TICK_POLL_MS: u32 = 100;
+
+/// Outcome of [`Ds3231::self_test`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct SelfTestReport {
+    /// The device acknowledged a read at its address
+    pub present: bool,
+    /// The seconds register advanced while waiting
+    pub oscillator_running: bool,
+    /// A bit written to the control register read back as written
+    pub control_writable: bool,
+    /// A bit written to the status register read back as written
+    pub status_writable: bool,
+}
+
+impl SelfTestReport {
+    /// Returns `true` if every check passed.
+    pub const fn passed(&self) -> bool {
+        self.present && self.oscillator_running && self.control_writable && self.status_writable
+    }
+}
+
+#[maybe_async_cfg::maybe(
+    idents(
+        AsyncDelayNs(sync = "DelayNs", async = "AsyncDelayNs"),
+        AsyncRegisterInterface(sync = "RegisterInterface", async = "AsyncRegisterInterface"),
+        Ds3231(sync = "Ds3231", async = "Ds3231Async")
+    ),
+    sync(keep_self),
+    async(feature = "async")
+)]
+impl<I2C, V> Ds3231<I2C, V>
+where
+    I2C: AsyncRegisterInterface,
+{
+    /// Check presence, oscillator and register writes, waiting on `delay`.
+    ///
+    /// See the [module documentation](crate::self_test).
+    pub async fn self_test<D: AsyncDelayNs>(
+        &mut self,
+        delay: &mut D,
+    ) -> Result<SelfTestReport, Error<I2C::Error>> {
+        let mut report = SelfTestReport::default();
+        let start = match self.read_register(Register::Seconds).await {
+            Ok(seconds) => seconds,
+            Err(_) => return Ok(report),
+        };
+        report.present = true;
+
+        let mut waited_ms = 0;
+        while waited_ms < TICK_TIMEOUT_MS {
+            delay.delay_ms(TICK_POLL_MS).await;
+            waited_ms += TICK_POLL_MS;
+            if self.read_register(Register::Seconds).await? != start {
+                report.oscillator_running = true;
+                break;
+            }
+        }
+
+        report.control_writable = self.toggle_test(Register::Control, BBSQW_BIT).await?;
+        // Written around the cache, so read it again on the next change
+        self.control_cache.invalidate();
+        report.status_writable = self.toggle_test(Register::Status, EN32KHZ_BIT).await?;
+
+        Ok(report)
+    }
+
+    /// Write `register` with `bit` flipped, check it reads back flipped and
+    /// restore the previous value.
+    async fn toggle_test(
+        &mut self,
+        register: Register,
+        bit: u8,
+    ) -> Result<bool, Error<I2C::Error>> {
+        // Writing 1 leaves the status flags alone; writing the 0 just read
+        // would clear one the chip raised in the meantime
+        let keep = match register {
+            Register::Status => A1F_BIT | A2F_BIT | OSF_BIT,
+            _ => 0,
+        };
+        let original = self.read_register(register).await?;
+        let writable = match self.write_register(register, (original ^ bit) | keep).await {
+            Ok(()) => (self.read_register(register).await? ^ original) & bit != 0,
+            // Write verification already read it back
+            Err(Error::VerificationFailed) => false,
+            Err(e) => return Err(e),
+        };
+        self.write_register(register, original | keep).await?;
+        Ok(writable)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use embedded_hal::i2c::ErrorKind;
+    use embedded_hal_mock::eh1::delay::NoopDelay;
+    use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
+
+    fn read(register: Register, value: u8) -> I2cTransaction {
+        I2cTransaction::write_read(0x68, vec![register.addr()], vec![value])
+    }
+
+    fn write(register: Register, value: u8) -> I2cTransaction {
+        I2cTransaction::write(0x68, vec![register.addr(), value])
+    }
+
+    #[test]
+    fn test_healthy_device_passes() {
+        let expectations = [
+            read(Register::Seconds, 0x10),
+            read(Register::Seconds, 0x10),
+            read(Register::Seconds, 0x11),
+            read(Register::Control, 0x1C),
+            write(Register::Control, 0x5C),
+            read(Register::Control, 0x5C),
+            write(Register::Control, 0x1C),
+            read(Register::Status, 0x88),
+            write(Register::Status, 0x83),
+            read(Register::Status, 0x84),
+            write(Register::Status, 0x8B),
+        ];
+        let mut ds3231 = Ds3231::new(I2cMock::new(&expectations));
+
+        let report = ds3231.self_test(&mut NoopDelay::new()).unwrap();
+        assert!(report.passed());
+
+        ds3231.release_i2c().done();
+    }
+
+    #[test]
+    fn test_failures_are_reported() {
+        let mut expectations = vec![read(Register::Seconds, 0x10)];
+        expectations.extend((0..11).map(|_| read(Register::Seconds, 0x10)));
+        expectations.extend([
+            read(Register::Control, 0x1C),
+            write(Register::Control, 0x5C),
+            read(Register::Control, 0x5C),
+            write(Register::Control, 0x1C),
+            // EN32kHz stuck low
+            read(Register::Status, 0x80),
+            write(Register::Status, 0x8B),
+            read(Register::Status, 0x80),
+            write(Register::Status, 0x83),
+        ]);
+        let mut ds3231 = Ds3231::new(I2cMock::new(&expectations));
+
+        assert_eq!(
+            ds3231.self_test(&mut NoopDelay::new()),
+            Ok(SelfTestReport {
+                present: true,
+                oscillator_running: false,
+                control_writable: true,
+                status_writable: false,
+            })
+        );
+
+        ds3231.release_i2c().done();
+    }
+
+    #[test]
+    fn test_missing_device_is_reported() {
+        let expectations = [read(Register::Seconds, 0x00).with_error(ErrorKind::Other)];
+        let mut ds3231 = Ds3231::new(I2cMock::new(&expectations));
+
+        let report = ds3231.self_test(&mut NoopDelay::new()).unwrap();
+        assert_eq!(report, SelfTestReport::default());
+        assert!(!report.passed());
+
+        ds3231.release_i2c().done();
+    }
+}
@@ -0,0 +1,213 @@
+//! # Interrupt-Safe Shared Driver
+//!
+//! [`SharedDs3231`] (enabled with the `critical-section` feature) keeps the
+//! driver in a `critical-section` mutex, so it can live in a `static` and be
+//! used both from the main loop and from interrupt handlers, typically the
+//! INT pin handler clearing an alarm flag. Every access runs inside a
+//! critical section, which also keeps an interrupt from cutting into a
+//! transfer the main loop has started.
+//!
+//! ```no_run
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! use ds3231_rtc::{Ds3231, alarm::Alarm, shared::SharedDs3231};
+//! # type I2c0 = embedded_hal_mock::eh1::i2c::Mock;
+//! # let i2c = I2c0::new(&[]);
+//!
+//! static RTC: SharedDs3231<I2c0> = SharedDs3231::new();
+//!
+//! // main
+//! RTC.init(Ds3231::new(i2c));
+//! let now = RTC.with(|rtc| rtc.get_datetime()).unwrap()?;
+//!
+//! // INT pin interrupt handler
+//! RTC.with(|rtc| rtc.clear_alarm_flag(Alarm::One));
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Critical sections block interrupts for the length of the transfer, so keep
+//! the work done inside [`with`](SharedDs3231::with) short.
+//!
+//! ## Sharing only the bus
+//!
+//! When other devices on the bus are used from interrupt handlers but the
+//! driver itself isn't, [`SharedBus`] is the register transport to give it.
+//! Like `embedded-hal-bus`'s `CriticalSectionDevice` it takes the bus from a
+//! `critical-section` mutex, but it also holds that critical section across
+//! both transfers of a
+//! [`modify_register`](RegisterInterface::modify_register), so no handler
+//! can write to the bus between the read and the write of a control or
+//! status update. This is the atomic path for read-modify-writes on a bus
+//! shared with interrupt handlers:
+//!
+//! ```no_run
+//! # use ds3231_rtc::Ds3231;
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! # let i2c = embedded_hal_mock::eh1::i2c::Mock::new(&[]);
+//! use core::cell::RefCell;
+//! use critical_section::Mutex;
+//! use ds3231_rtc::shared::SharedBus;
+//!
+//! let bus = Mutex::new(RefCell::new(i2c));
+//! let mut rtc = Ds3231::new(SharedBus::new(&bus));
+//! // Other drivers take an `embedded_hal_bus::i2c::CriticalSectionDevice::new(&bus)`
+//! rtc.disable_square_wave()?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Another bus master can still write to the DS3231 between the two
+//! transfers; only the MCU's own users of the bus are kept out.
+
+use core::cell::RefCell;
+
+use critical_section::Mutex;
+
+use embedded_hal::i2c::I2c;
+
+use crate::Ds3231;
+use crate::interface::RegisterInterface;
+use crate::variant::AnyVariant;
+
+/// DS3231 driver behind a `critical-section` mutex
+pub struct SharedDs3231<I2C, V = AnyVariant> {
+    rtc: Mutex<RefCell<Option<Ds3231<I2C, V>>>>,
+}
+
+impl<I2C, V> SharedDs3231<I2C, V> {
+    /// Create an empty handle, suitable for a `static`.
+    pub const fn new() -> Self {
+        Self {
+            rtc: Mutex::new(RefCell::new(None)),
+        }
+    }
+
+    /// Store the driver, returning the one held before, if any.
+    pub fn init(&self, rtc: Ds3231<I2C, V>) -> Option<Ds3231<I2C, V>> {
+        critical_section::with(|cs| self.rtc.borrow_ref_mut(cs).replace(rtc))
+    }
+
+    /// Remove the driver, e.g. to release the bus.
+    pub fn take(&self) -> Option<Ds3231<I2C, V>> {
+        critical_section::with(|cs| self.rtc.borrow_ref_mut(cs).take())
+    }
+
+    /// Run `f` on the driver inside a critical section.
+    ///
+    /// Returns `None` if no driver has been stored yet.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called again from within `f`.
+    pub fn with<R>(&self, f: impl FnOnce(&mut Ds3231<I2C, V>) -> R) -> Option<R> {
+        critical_section::with(|cs| self.rtc.borrow_ref_mut(cs).as_mut().map(f))
+    }
+}
+
+impl<I2C, V> Default for SharedDs3231<I2C, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Register transport on an I2C bus in a `critical-section` mutex
+///
+/// Each transfer runs inside a critical section, and a read-modify-write
+/// keeps one critical section across its read and its write.
+pub struct SharedBus<'a, I2C> {
+    bus: &'a Mutex<RefCell<I2C>>,
+}
+
+impl<'a, I2C> SharedBus<'a, I2C> {
+    /// Transport on the bus in `bus`.
+    pub const fn new(bus: &'a Mutex<RefCell<I2C>>) -> Self {
+        Self { bus }
+    }
+
+    fn with<R>(&self, f: impl FnOnce(&mut I2C) -> R) -> R {
+        critical_section::with(|cs| f(&mut self.bus.borrow_ref_mut(cs)))
+    }
+}
+
+impl<I2C: I2c> RegisterInterface for SharedBus<'_, I2C> {
+    type Error = I2C::Error;
+
+    fn read_registers(
+        &mut self,
+        address: u8,
+        register: u8,
+        buffer: &mut [u8],
+    ) -> Result<(), Self::Error> {
+        self.with(|bus| bus.read_registers(address, register, buffer))
+    }
+
+    fn write_registers(&mut self, address: u8, data: &[u8]) -> Result<(), Self::Error> {
+        self.with(|bus| bus.write_registers(address, data))
+    }
+
+    fn modify_register(
+        &mut self,
+        address: u8,
+        register: u8,
+        f: impl FnOnce(u8) -> u8,
+    ) -> Result<(), Self::Error> {
+        self.with(|bus| bus.modify_register(address, register, f))
+    }
+}
+
+#[cfg(all(test, feature = "alarms"))]
+mod tests {
+    use super::*;
+    use crate::alarm::Alarm;
+    use crate::registers::Register;
+    use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
+
+    #[test]
+    fn test_shared_access() {
+        let shared: SharedDs3231<I2cMock> = SharedDs3231::new();
+        assert!(shared.with(|_| ()).is_none());
+
+        let expectations = [
+            I2cTransaction::write_read(0x68, vec![Register::Status.addr()], vec![0x01]),
+            I2cTransaction::write_read(0x68, vec![Register::Status.addr()], vec![0x01]),
+            I2cTransaction::write(0x68, vec![Register::Status.addr(), 0x82]),
+        ];
+        assert!(
+            shared
+                .init(Ds3231::new(I2cMock::new(&expectations)))
+                .is_none()
+        );
+
+        // Main loop and interrupt handler each take a turn
+        assert_eq!(
+            shared.with(|rtc| rtc.alarm_fired(Alarm::One)),
+            Some(Ok(true))
+        );
+        shared
+            .with(|rtc| rtc.clear_alarm_flag(Alarm::One))
+            .unwrap()
+            .unwrap();
+
+        shared.take().unwrap().release().done();
+        assert!(shared.take().is_none());
+    }
+
+    #[test]
+    fn test_shared_bus_holds_bus_across_update() {
+        let expectations = [
+            I2cTransaction::write_read(0x68, vec![Register::Control.addr()], vec![0x1C]),
+            I2cTransaction::write(0x68, vec![Register::Control.addr(), 0x9C]),
+        ];
+        let bus = Mutex::new(RefCell::new(I2cMock::new(&expectations)));
+        let mut rtc = Ds3231::new(SharedBus::new(&bus));
+
+        rtc.modify_register(Register::Control, |value| {
+            // An interrupt handler would find the bus taken
+            critical_section::with(|cs| assert!(bus.borrow(cs).try_borrow_mut().is_err()));
+            value | crate::registers::EOSC_BIT
+        })
+        .unwrap();
+
+        bus.into_inner().into_inner().done();
+    }
+}
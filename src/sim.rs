@@ -0,0 +1,397 @@
+//! # Software Simulator
+//!
+//! With the `sim` feature, [`Ds3231Sim`] implements the same `rtc-hal`
+//! traits as the driver purely in software, so application logic can be
+//! tested on the host without mocking I2C transactions. Time only moves when
+//! the test says so:
+//!
+//! ```
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! use core::time::Duration;
+//! use ds3231_rtc::{alarm::Alarm, sim::Ds3231Sim};
+//! use rtc_hal::{datetime::DateTime, rtc::Rtc};
+//!
+//! let mut rtc = Ds3231Sim::new();
+//! rtc.set_datetime(&DateTime::new(2025, 8, 21, 6, 59, 0)?)?;
+//! rtc.set_alarm(Alarm::One, &DateTime::new(2025, 8, 21, 7, 0, 0)?);
+//!
+//! rtc.advance(Duration::from_secs(60));
+//! assert!(rtc.alarm_fired(Alarm::One));
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! The simulator keeps the chip's observable behaviour:
+//!
+//! - it powers up at 2000-01-01 00:00:00 with the Oscillator Stop Flag set
+//!   and the INT/SQW pin in interrupt mode
+//! - [`set_datetime`](Rtc::set_datetime) accepts the same years as the
+//!   driver with its default configuration and restarts the current second
+//! - a halted clock ignores [`advance`](Ds3231Sim::advance)
+//! - an alarm fires when [`advance`](Ds3231Sim::advance) moves the clock
+//!   onto or past its time; setting the clock past it does not fire it.
+//!   Alarm 2 has minutes resolution and fires at second 0.
+//! - [`interrupt_active`](Ds3231Sim::interrupt_active) reports the INT pin
+//!   the way the chip drives it
+//!
+//! Errors use the driver's [`Error`] type, so code generic over
+//! `Rtc<Error = Error<E>>` accepts the simulator as well.
+
+use core::convert::Infallible;
+use core::time::Duration;
+
+use rtc_hal::control::RtcPowerControl;
+use rtc_hal::datetime::DateTime;
+use rtc_hal::error::ErrorType;
+use rtc_hal::rtc::Rtc;
+use rtc_hal::square_wave::{SquareWave, SquareWaveFreq};
+
+use crate::alarm::Alarm;
+use crate::calendar;
+use crate::datetime::encode_datetime;
+use crate::ds3231::DEFAULT_BASE_CENTURY;
+use crate::error::Error;
+use crate::hour_mode::HourMode;
+use crate::power_loss::OscillatorStopFlag;
+use crate::square_wave::freq_to_bits;
+use crate::year_policy::YearPolicy;
+
+/// Power-on time, 2000-01-01 00:00:00, in seconds since 1970
+const POWER_ON_TIMESTAMP: u64 = 946_684_800;
+
+/// Power-on temperature reading, 25 °C
+const DEFAULT_TEMPERATURE_QUARTERS: i16 = 100;
+
+/// A DS3231 simulated in software
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Ds3231Sim {
+    now_ms: u64,
+    running: bool,
+    oscillator_stopped: bool,
+    alarms: [Option<u64>; 2],
+    alarm_flags: [bool; 2],
+    alarm_interrupts: [bool; 2],
+    square_wave: bool,
+    frequency: SquareWaveFreq,
+    temperature_quarters: i16,
+}
+
+impl Default for Ds3231Sim {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Ds3231Sim {
+    /// Create a simulator in the chip's power-on state.
+    pub const fn new() -> Self {
+        Self {
+            now_ms: POWER_ON_TIMESTAMP * 1000,
+            running: true,
+            oscillator_stopped: true,
+            alarms: [None; 2],
+            alarm_flags: [false; 2],
+            alarm_interrupts: [false; 2],
+            square_wave: false,
+            frequency: SquareWaveFreq::Hz8192,
+            temperature_quarters: DEFAULT_TEMPERATURE_QUARTERS,
+        }
+    }
+
+    /// Let `elapsed` pass, firing the alarms the clock reaches.
+    ///
+    /// Does nothing while the clock is halted.
+    pub fn advance(&mut self, elapsed: Duration) {
+        if !self.running {
+            return;
+        }
+        let before = self.now_ms / 1000;
+        let millis = u64::try_from(elapsed.as_millis()).unwrap_or(u64::MAX);
+        self.now_ms = self.now_ms.saturating_add(millis);
+        let after = self.now_ms / 1000;
+
+        for (at, flag) in self.alarms.iter().zip(self.alarm_flags.iter_mut()) {
+            if let Some(at) = *at
+                && before < at
+                && at <= after
+            {
+                *flag = true;
+            }
+        }
+    }
+
+    /// Milliseconds since 1970-01-01 00:00:00.
+    pub const fn timestamp_millis(&self) -> u64 {
+        self.now_ms
+    }
+
+    /// Returns `true` unless the clock was halted.
+    pub const fn is_running(&self) -> bool {
+        self.running
+    }
+
+    /// Schedule `alarm` for `datetime`, replacing its previous time.
+    ///
+    /// Alarm 2 ignores the seconds of `datetime`.
+    pub fn set_alarm(&mut self, alarm: Alarm, datetime: &DateTime) {
+        let mut at = calendar::to_unix_timestamp(datetime);
+        if alarm == Alarm::Two {
+            at -= at % 60;
+        }
+        self.alarms[Self::index(alarm)] = Some(at);
+    }
+
+    /// Remove the time of `alarm`, so it no longer fires.
+    pub fn cancel_alarm(&mut self, alarm: Alarm) {
+        self.alarms[Self::index(alarm)] = None;
+    }
+
+    /// Returns `true` if `alarm` has fired since its flag was last cleared.
+    pub const fn alarm_fired(&self, alarm: Alarm) -> bool {
+        self.alarm_flags[Self::index(alarm)]
+    }
+
+    /// Clear the flag of `alarm`.
+    pub fn clear_alarm_flag(&mut self, alarm: Alarm) {
+        self.alarm_flags[Self::index(alarm)] = false;
+    }
+
+    /// Clear the flags of both alarms.
+    pub fn clear_alarm_flags(&mut self) {
+        self.alarm_flags = [false; 2];
+    }
+
+    /// Enable or disable the INT pin interrupt of `alarm`.
+    pub fn set_alarm_interrupt(&mut self, alarm: Alarm, enabled: bool) {
+        self.alarm_interrupts[Self::index(alarm)] = enabled;
+    }
+
+    /// Returns `true` if the INT/SQW pin is pulled low by an alarm.
+    ///
+    /// That is the case while an alarm's flag and interrupt are both set and
+    /// the pin is not outputting the square wave.
+    pub fn interrupt_active(&self) -> bool {
+        !self.square_wave
+            && self
+                .alarm_flags
+                .iter()
+                .zip(self.alarm_interrupts)
+                .any(|(&flag, enabled)| flag && enabled)
+    }
+
+    /// Frequency on the INT/SQW pin, or `None` in interrupt mode.
+    pub fn square_wave(&self) -> Option<SquareWaveFreq> {
+        self.square_wave.then_some(self.frequency)
+    }
+
+    /// Stop the oscillator as a drained backup battery would.
+    ///
+    /// Sets the Oscillator Stop Flag and puts the time and configuration
+    /// back into the power-on state.
+    pub fn simulate_power_loss(&mut self) {
+        *self = Self {
+            temperature_quarters: self.temperature_quarters,
+            ..Self::new()
+        };
+    }
+
+    /// Last measured temperature in quarter degrees Celsius.
+    pub const fn temperature_quarters(&self) -> i16 {
+        self.temperature_quarters
+    }
+
+    /// Set the temperature the simulator reports, in quarter degrees Celsius.
+    pub fn set_temperature_quarters(&mut self, quarters: i16) {
+        self.temperature_quarters = quarters;
+    }
+
+    const fn index(alarm: Alarm) -> usize {
+        match alarm {
+            Alarm::One => 0,
+            Alarm::Two => 1,
+        }
+    }
+}
+
+impl ErrorType for Ds3231Sim {
+    type Error = Error<Infallible>;
+}
+
+impl Rtc for Ds3231Sim {
+    fn get_datetime(&mut self) -> Result<DateTime, Self::Error> {
+        Ok(calendar::from_unix_timestamp(self.now_ms / 1000)?)
+    }
+
+    /// Set the time, rejecting years the driver would reject.
+    fn set_datetime(&mut self, datetime: &DateTime) -> Result<(), Self::Error> {
+        encode_datetime(
+            datetime,
+            DEFAULT_BASE_CENTURY,
+            HourMode::default(),
+            YearPolicy::default(),
+        )?;
+        // Writing the seconds register restarts the countdown chain
+        self.now_ms = calendar::to_unix_timestamp(datetime) * 1000;
+        Ok(())
+    }
+}
+
+impl RtcPowerControl for Ds3231Sim {
+    fn start_clock(&mut self) -> Result<(), Self::Error> {
+        self.running = true;
+        Ok(())
+    }
+
+    fn halt_clock(&mut self) -> Result<(), Self::Error> {
+        self.running = false;
+        Ok(())
+    }
+}
+
+impl SquareWave for Ds3231Sim {
+    fn start_square_wave(&mut self, freq: SquareWaveFreq) -> Result<(), Self::Error> {
+        self.set_square_wave_frequency(freq)?;
+        self.enable_square_wave()
+    }
+
+    fn enable_square_wave(&mut self) -> Result<(), Self::Error> {
+        self.square_wave = true;
+        Ok(())
+    }
+
+    fn disable_square_wave(&mut self) -> Result<(), Self::Error> {
+        self.square_wave = false;
+        Ok(())
+    }
+
+    fn set_square_wave_frequency(&mut self, freq: SquareWaveFreq) -> Result<(), Self::Error> {
+        freq_to_bits::<Infallible>(freq)?;
+        self.frequency = freq;
+        Ok(())
+    }
+}
+
+impl OscillatorStopFlag for Ds3231Sim {
+    fn oscillator_stopped(&mut self) -> Result<bool, Self::Error> {
+        Ok(self.oscillator_stopped)
+    }
+
+    fn clear_oscillator_stop_flag(&mut self) -> Result<(), Self::Error> {
+        self.oscillator_stopped = false;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rtc_hal::datetime::DateTimeError;
+
+    fn datetime(hour: u8, minute: u8, second: u8) -> DateTime {
+        DateTime::new(2025, 8, 21, hour, minute, second).unwrap()
+    }
+
+    #[test]
+    fn test_power_on_state() {
+        let mut sim = Ds3231Sim::new();
+        assert_eq!(
+            sim.get_datetime(),
+            Ok(DateTime::new(2000, 1, 1, 0, 0, 0).unwrap())
+        );
+        assert_eq!(sim.oscillator_stopped(), Ok(true));
+        assert_eq!(sim.square_wave(), None);
+        assert!(sim.is_running());
+    }
+
+    #[test]
+    fn test_advance_and_halt() {
+        let mut sim = Ds3231Sim::new();
+        sim.set_datetime(&datetime(23, 59, 59)).unwrap();
+
+        sim.advance(Duration::from_millis(999));
+        assert_eq!(sim.get_datetime(), Ok(datetime(23, 59, 59)));
+        sim.advance(Duration::from_millis(1));
+        assert_eq!(
+            sim.get_datetime(),
+            Ok(DateTime::new(2025, 8, 22, 0, 0, 0).unwrap())
+        );
+
+        sim.halt_clock().unwrap();
+        sim.advance(Duration::from_secs(3600));
+        assert_eq!(sim.timestamp_millis() % 1000, 0);
+        assert_eq!(sim.get_datetime().unwrap().hour(), 0);
+    }
+
+    #[test]
+    fn test_set_datetime_rejects_years_outside_window() {
+        let mut sim = Ds3231Sim::new();
+        assert_eq!(
+            sim.set_datetime(&DateTime::new(1999, 12, 31, 0, 0, 0).unwrap()),
+            Err(Error::DateTime(DateTimeError::InvalidYear))
+        );
+    }
+
+    #[test]
+    fn test_alarm_fires_when_reached() {
+        let mut sim = Ds3231Sim::new();
+        sim.set_datetime(&datetime(6, 59, 0)).unwrap();
+        sim.set_alarm(Alarm::One, &datetime(7, 0, 0));
+        sim.set_alarm(Alarm::Two, &datetime(7, 1, 30));
+
+        sim.advance(Duration::from_secs(59));
+        assert!(!sim.alarm_fired(Alarm::One));
+        sim.advance(Duration::from_secs(1));
+        assert!(sim.alarm_fired(Alarm::One));
+        assert!(!sim.interrupt_active());
+
+        // Alarm 2 matches at second 0 of its minute
+        sim.set_alarm_interrupt(Alarm::Two, true);
+        sim.advance(Duration::from_secs(60));
+        assert!(sim.alarm_fired(Alarm::Two));
+        assert!(sim.interrupt_active());
+
+        sim.clear_alarm_flags();
+        assert!(!sim.interrupt_active());
+    }
+
+    #[test]
+    fn test_setting_time_past_alarm_does_not_fire() {
+        let mut sim = Ds3231Sim::new();
+        sim.set_alarm(Alarm::One, &datetime(7, 0, 0));
+        sim.set_datetime(&datetime(8, 0, 0)).unwrap();
+        sim.advance(Duration::from_secs(1));
+        assert!(!sim.alarm_fired(Alarm::One));
+    }
+
+    #[test]
+    fn test_square_wave_masks_interrupt() {
+        let mut sim = Ds3231Sim::new();
+        sim.set_datetime(&datetime(6, 59, 59)).unwrap();
+        sim.set_alarm(Alarm::One, &datetime(7, 0, 0));
+        sim.set_alarm_interrupt(Alarm::One, true);
+        sim.start_square_wave(SquareWaveFreq::Hz1).unwrap();
+
+        sim.advance(Duration::from_secs(1));
+        assert!(sim.alarm_fired(Alarm::One));
+        assert!(!sim.interrupt_active());
+        assert_eq!(sim.square_wave(), Some(SquareWaveFreq::Hz1));
+
+        assert_eq!(
+            sim.set_square_wave_frequency(SquareWaveFreq::Hz32768),
+            Err(Error::UnsupportedSqwFrequency)
+        );
+    }
+
+    #[test]
+    fn test_power_loss() {
+        let mut sim = Ds3231Sim::new();
+        sim.set_datetime(&datetime(12, 0, 0)).unwrap();
+        sim.clear_oscillator_stop_flag().unwrap();
+        sim.set_temperature_quarters(-8);
+
+        sim.simulate_power_loss();
+        assert_eq!(sim.oscillator_stopped(), Ok(true));
+        assert_eq!(sim.get_datetime().unwrap().year(), 2000);
+        assert_eq!(sim.temperature_quarters(), -8);
+    }
+}
@@ -0,0 +1,205 @@
+//! # Split Handles
+//!
+//! [`Ds3231::split`] hands out separate handles for timekeeping
+//! ([`Clock`]), the alarm flags (`Alarms`, `alarms` feature) and the
+//! temperature sensor (`Temperature`, `temperature` feature), so different
+//! parts of an application can each own the piece they use instead of
+//! passing one `&mut Ds3231` around.
+//!
+//! The handles share one driver through a [`RefCell`] the caller provides,
+//! so they all see the same settings (base century, hour mode, ...) and
+//! never interleave transfers. Each call borrows the driver only for its own
+//! duration; the handles are `Copy` and can be handed to as many owners as
+//! needed within one thread or task. Across interrupts, use
+//! `shared::SharedDs3231` (`critical-section` feature) instead.
+//!
+//! ```no_run
+//! # use ds3231_rtc::Ds3231;
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! # let i2c = embedded_hal_mock::eh1::i2c::Mock::new(&[]);
+//! use core::cell::RefCell;
+//! use rtc_hal::rtc::Rtc;
+//!
+//! let rtc = RefCell::new(Ds3231::new(i2c));
+//! let parts = Ds3231::split(&rtc);
+//!
+//! // Each handle can go to a different owner
+//! let mut clock = parts.clock;
+//! let now = clock.get_datetime()?;
+//! let celsius = parts.temperature.temperature()?;
+//! parts.alarms.clear_alarm_flags()?;
+//! # Ok(())
+//! # }
+//! ```
+
+use core::cell::RefCell;
+
+use rtc_hal::datetime::DateTime;
+use rtc_hal::error::ErrorType;
+use rtc_hal::rtc::Rtc;
+
+use crate::Ds3231;
+#[cfg(feature = "alarms")]
+use crate::alarm::Alarm;
+use crate::error::Error;
+use crate::interface::RegisterInterface;
+use crate::variant::AnyVariant;
+
+/// Handles returned by [`Ds3231::split`]
+pub struct Parts<'a, I2C, V = AnyVariant> {
+    /// Date, time and oscillator status
+    pub clock: Clock<'a, I2C, V>,
+    /// Alarm flags
+    #[cfg(feature = "alarms")]
+    pub alarms: Alarms<'a, I2C, V>,
+    /// Temperature sensor
+    #[cfg(feature = "temperature")]
+    pub temperature: Temperature<'a, I2C, V>,
+}
+
+/// Timekeeping handle; implements `rtc-hal`'s [`Rtc`]
+pub struct Clock<'a, I2C, V = AnyVariant> {
+    rtc: &'a RefCell<Ds3231<I2C, V>>,
+}
+
+/// Alarm flag handle
+#[cfg(feature = "alarms")]
+pub struct Alarms<'a, I2C, V = AnyVariant> {
+    rtc: &'a RefCell<Ds3231<I2C, V>>,
+}
+
+/// Temperature sensor handle
+#[cfg(feature = "temperature")]
+pub struct Temperature<'a, I2C, V = AnyVariant> {
+    rtc: &'a RefCell<Ds3231<I2C, V>>,
+}
+
+// Derived Clone/Copy would needlessly require `I2C: Copy`
+macro_rules! impl_copy {
+    ($($(#[$cfg:meta])* $handle:ident),*) => {$(
+        $(#[$cfg])*
+        impl<I2C, V> Clone for $handle<'_, I2C, V> {
+            fn clone(&self) -> Self {
+                *self
+            }
+        }
+
+        $(#[$cfg])*
+        impl<I2C, V> Copy for $handle<'_, I2C, V> {}
+    )*};
+}
+
+impl_copy!(
+    Clock,
+    #[cfg(feature = "alarms")]
+    Alarms,
+    #[cfg(feature = "temperature")]
+    Temperature
+);
+
+impl<I2C, V> Ds3231<I2C, V> {
+    /// Split the driver in `rtc` into independently owned handles.
+    ///
+    /// See the [module documentation](crate::split).
+    pub fn split(rtc: &RefCell<Self>) -> Parts<'_, I2C, V> {
+        Parts {
+            clock: Clock { rtc },
+            #[cfg(feature = "alarms")]
+            alarms: Alarms { rtc },
+            #[cfg(feature = "temperature")]
+            temperature: Temperature { rtc },
+        }
+    }
+}
+
+impl<I2C: RegisterInterface, V> Clock<'_, I2C, V> {
+    /// Returns `true` if the Oscillator Stop Flag is set.
+    pub fn oscillator_stopped(&self) -> Result<bool, Error<I2C::Error>> {
+        self.rtc.borrow_mut().oscillator_stopped()
+    }
+
+    /// Clear the Oscillator Stop Flag after the time has been set.
+    pub fn clear_oscillator_stop_flag(&self) -> Result<(), Error<I2C::Error>> {
+        self.rtc.borrow_mut().clear_oscillator_stop_flag()
+    }
+}
+
+impl<I2C: RegisterInterface, V> ErrorType for Clock<'_, I2C, V> {
+    type Error = Error<I2C::Error>;
+}
+
+impl<I2C: RegisterInterface, V> Rtc for Clock<'_, I2C, V> {
+    fn get_datetime(&mut self) -> Result<DateTime, Self::Error> {
+        self.rtc.borrow_mut().get_datetime()
+    }
+
+    fn set_datetime(&mut self, datetime: &DateTime) -> Result<(), Self::Error> {
+        self.rtc.borrow_mut().set_datetime(datetime)
+    }
+}
+
+#[cfg(feature = "alarms")]
+impl<I2C: RegisterInterface, V> Alarms<'_, I2C, V> {
+    /// Returns `true` if `alarm` has fired since its flag was last cleared.
+    pub fn alarm_fired(&self, alarm: Alarm) -> Result<bool, Error<I2C::Error>> {
+        self.rtc.borrow_mut().alarm_fired(alarm)
+    }
+
+    /// Clear the flag of `alarm`.
+    pub fn clear_alarm_flag(&self, alarm: Alarm) -> Result<(), Error<I2C::Error>> {
+        self.rtc.borrow_mut().clear_alarm_flag(alarm)
+    }
+
+    /// Clear both alarm flags.
+    pub fn clear_alarm_flags(&self) -> Result<(), Error<I2C::Error>> {
+        self.rtc.borrow_mut().clear_alarm_flags()
+    }
+}
+
+#[cfg(feature = "temperature")]
+impl<I2C: RegisterInterface, V> Temperature<'_, I2C, V> {
+    /// Last measured temperature in degrees Celsius.
+    pub fn temperature(&self) -> Result<f32, Error<I2C::Error>> {
+        self.rtc.borrow_mut().temperature()
+    }
+
+    /// Last measured temperature in quarter degrees Celsius.
+    pub fn temperature_quarters(&self) -> Result<i16, Error<I2C::Error>> {
+        self.rtc.borrow_mut().temperature_quarters()
+    }
+}
+
+#[cfg(all(test, feature = "alarms", feature = "temperature"))]
+mod tests {
+    use super::*;
+    use crate::registers::Register;
+    use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
+
+    #[test]
+    fn test_handles_share_the_driver() {
+        let expectations = [
+            I2cTransaction::write_read(0x68, vec![Register::TempMsb.addr()], vec![0x19, 0x40]),
+            I2cTransaction::write_read(
+                0x68,
+                vec![0x00],
+                vec![0x00, 0x30, 0x14, 0x05, 0x21, 0x08, 0x25],
+            ),
+            I2cTransaction::write_read(0x68, vec![Register::Status.addr()], vec![0x02]),
+        ];
+        let rtc = RefCell::new(Ds3231::new(I2cMock::new(&expectations)));
+        let Parts {
+            mut clock,
+            alarms,
+            temperature,
+        } = Ds3231::split(&rtc);
+
+        assert_eq!(temperature.temperature_quarters(), Ok(101));
+        assert_eq!(
+            clock.get_datetime(),
+            Ok(DateTime::new(2025, 8, 21, 14, 30, 0).unwrap())
+        );
+        assert_eq!(alarms.alarm_fired(Alarm::Two), Ok(true));
+
+        rtc.into_inner().release().done();
+    }
+}
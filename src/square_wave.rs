@@ -13,14 +13,21 @@
 pub use rtc_hal::square_wave::SquareWave;
 pub use rtc_hal::square_wave::SquareWaveFreq;
 
+#[cfg(feature = "async")]
+use crate::interface::AsyncRegisterInterface;
+use crate::interface::RegisterInterface;
+
 use crate::Ds3231;
+#[cfg(feature = "async")]
+use crate::ds3231::Ds3231Async;
 use crate::error::Error;
-use crate::registers::{INTCN_BIT, RS_MASK, Register};
+use crate::registers::ControlRegister;
+use crate::trace::debug;
 
 /// Convert a [`SquareWaveFreq`] into the corresponding Ds3231 RS bits.
 ///
 /// Returns an error if the frequency is not supported by the Ds3231.
-fn freq_to_bits<E>(freq: SquareWaveFreq) -> Result<u8, Error<E>>
+pub(crate) fn freq_to_bits<E>(freq: SquareWaveFreq) -> Result<u8, Error<E>>
 where
     E: core::fmt::Debug,
 {
@@ -33,66 +40,96 @@ where
     }
 }
 
-impl<I2C> SquareWave for Ds3231<I2C>
+/// RS bits from [`freq_to_bits`] as the control register's rate select field.
+pub(crate) const fn rate_select(rs_bits: u8) -> u8 {
+    ControlRegister::from_bits(rs_bits).rate_select()
+}
+
+#[maybe_async_cfg::maybe(
+    idents(
+        AsyncRegisterInterface(sync = "RegisterInterface", async = "AsyncRegisterInterface"),
+        Ds3231(sync = "Ds3231", async = "Ds3231Async")
+    ),
+    sync(keep_self),
+    async(feature = "async")
+)]
+impl<I2C, V> Ds3231<I2C, V>
 where
-    I2C: embedded_hal::i2c::I2c,
+    I2C: AsyncRegisterInterface,
 {
     /// Enable the square wave output
-    fn enable_square_wave(&mut self) -> Result<(), Self::Error> {
+    pub async fn enable_square_wave(&mut self) -> Result<(), Error<I2C::Error>> {
         // Clear INTCN bit to enable square wave mode (0 = square wave, 1 = interrupt)
-        self.clear_register_bits(Register::Control, INTCN_BIT)
+        self.modify_control(|control| control.set_interrupt_control(false))
+            .await?;
+        debug!("enable_square_wave");
+        Ok(())
     }
 
     /// Disable the square wave output.
-    fn disable_square_wave(&mut self) -> Result<(), Self::Error> {
+    pub async fn disable_square_wave(&mut self) -> Result<(), Error<I2C::Error>> {
         // Set INTCN bit to enable interrupt mode (disable square wave)
-        self.set_register_bits(Register::Control, INTCN_BIT)
+        self.modify_control(|control| control.set_interrupt_control(true))
+            .await?;
+        debug!("disable_square_wave");
+        Ok(())
     }
 
-    fn set_square_wave_frequency(&mut self, freq: SquareWaveFreq) -> Result<(), Self::Error> {
-        // Convert frequency to RS bits
-        let rs_bits = freq_to_bits(freq)?;
-
-        // Read current control register
-        let current = self.read_register(Register::Control)?;
-        let mut new_value = current;
-
-        // Clear existing RS bits and set new ones
-        new_value &= !RS_MASK;
-        new_value |= rs_bits; // Set the new RS bits
+    /// Set the square wave frequency without changing whether it is enabled
+    pub async fn set_square_wave_frequency(
+        &mut self,
+        freq: SquareWaveFreq,
+    ) -> Result<(), Error<I2C::Error>> {
+        let rate = rate_select(self.variant.square_wave_bits(freq)?);
+        self.modify_control(|control| control.set_rate_select(rate))
+            .await?;
+        debug!("set_square_wave_frequency: {:?}", freq);
+        Ok(())
+    }
 
-        // Only write if changed
-        if new_value != current {
-            self.write_register(Register::Control, new_value)
-        } else {
-            Ok(())
-        }
+    /// Set the square wave frequency and enable the output
+    pub async fn start_square_wave(
+        &mut self,
+        freq: SquareWaveFreq,
+    ) -> Result<(), Error<I2C::Error>> {
+        let rate = rate_select(self.variant.square_wave_bits(freq)?);
+        // Set the new frequency and clear INTCN to enable the output
+        self.modify_control(|control| {
+            control.set_rate_select(rate);
+            control.set_interrupt_control(false);
+        })
+        .await?;
+        debug!("start_square_wave: {:?}", freq);
+        Ok(())
     }
+}
 
-    fn start_square_wave(&mut self, freq: SquareWaveFreq) -> Result<(), Self::Error> {
-        let rs_bits = freq_to_bits(freq)?;
-        let current = self.read_register(Register::Control)?;
-        let mut new_value = current;
+/// Blocking `rtc-hal` implementation, delegating to the inherent methods.
+impl<I2C, V> SquareWave for Ds3231<I2C, V>
+where
+    I2C: RegisterInterface,
+{
+    fn enable_square_wave(&mut self) -> Result<(), Self::Error> {
+        Ds3231::enable_square_wave(self)
+    }
 
-        // Clear frequency bits and set new ones
-        new_value &= !RS_MASK;
-        new_value |= rs_bits;
+    fn disable_square_wave(&mut self) -> Result<(), Self::Error> {
+        Ds3231::disable_square_wave(self)
+    }
 
-        // Enable square wave
-        new_value &= !INTCN_BIT;
+    fn set_square_wave_frequency(&mut self, freq: SquareWaveFreq) -> Result<(), Self::Error> {
+        Ds3231::set_square_wave_frequency(self, freq)
+    }
 
-        // Only write if changed
-        if new_value != current {
-            self.write_register(Register::Control, new_value)
-        } else {
-            Ok(())
-        }
+    fn start_square_wave(&mut self, freq: SquareWaveFreq) -> Result<(), Self::Error> {
+        Ds3231::start_square_wave(self, freq)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::registers::Register;
     use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
     use rtc_hal::square_wave::{SquareWave, SquareWaveFreq};
 
@@ -521,4 +558,19 @@ mod tests {
 
         i2c_mock.done();
     }
+
+    #[test]
+    fn test_square_wave_trait_delegates() {
+        let expectations = vec![
+            I2cTransaction::write_read(DS3231_ADDR, vec![Register::Control.addr()], vec![0x1C]),
+            I2cTransaction::write(DS3231_ADDR, vec![Register::Control.addr(), 0b0000_1000]),
+        ];
+
+        let mut i2c_mock = I2cMock::new(&expectations);
+        let mut ds3231 = Ds3231::new(&mut i2c_mock);
+
+        SquareWave::start_square_wave(&mut ds3231, SquareWaveFreq::Hz1024).unwrap();
+
+        i2c_mock.done();
+    }
 }
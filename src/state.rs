@@ -0,0 +1,165 @@
+//! # Device State
+//!
+//! [`device_state`](crate::Ds3231::device_state) captures everything a
+//! telemetry report or debug dashboard shows about the chip: the time, the
+//! control and status flags, the aging offset and the temperature. All 19
+//! registers are read in one burst, so the values belong to the same moment.
+//! The configured [read strategy](crate::read_strategy) applies to the
+//! burst as for [`get_datetime`](crate::Ds3231::get_datetime).
+//!
+//! ```no_run
+//! # use ds3231_rtc::Ds3231;
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! # let mut rtc = Ds3231::new(embedded_hal_mock::eh1::i2c::Mock::new(&[]));
+//! let state = rtc.device_state()?;
+//! println!("{:?}", state);
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! For the raw register contents, including the alarms, see
+//! [`dump_registers`](crate::Ds3231::dump_registers).
+
+use rtc_hal::datetime::DateTime;
+
+#[cfg(feature = "async")]
+use crate::interface::AsyncRegisterInterface;
+use crate::interface::RegisterInterface;
+
+#[cfg(any(feature = "defmt", feature = "ufmt"))]
+use crate::display::DateTimeDisplay;
+#[cfg(feature = "async")]
+use crate::ds3231::Ds3231Async;
+use crate::{
+    Ds3231,
+    datetime::decode_datetime,
+    dump::REGISTER_COUNT,
+    error::Error,
+    flags::{ControlFlags, StatusFlags},
+    registers::{Register, decode_quarters},
+};
+
+/// Time, flags, aging offset and temperature, read at one point in time
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DeviceState {
+    /// Current date and time
+    pub datetime: DateTime,
+    /// Control register
+    pub control: ControlFlags,
+    /// Status register
+    pub status: StatusFlags,
+    /// Aging offset in the chip's units (about 0.1 ppm each)
+    pub aging_offset: i8,
+    /// Last measured temperature in quarter degrees Celsius
+    pub temperature_quarters: i16,
+}
+
+impl DeviceState {
+    /// Last measured temperature in degrees Celsius.
+    pub fn temperature(&self) -> f32 {
+        self.temperature_quarters as f32 / 4.0
+    }
+}
+
+#[cfg(feature = "defmt")]
+impl defmt::Format for DeviceState {
+    fn format(&self, f: defmt::Formatter) {
+        defmt::write!(
+            f,
+            "DeviceState {{ datetime: {}, control: {}, status: {}, aging_offset: {=i8}, temperature_quarters: {=i16} }}",
+            DateTimeDisplay(self.datetime),
+            self.control,
+            self.status,
+            self.aging_offset,
+            self.temperature_quarters
+        );
+    }
+}
+
+#[cfg(feature = "ufmt")]
+impl ufmt::uDebug for DeviceState {
+    fn fmt<W>(&self, f: &mut ufmt::Formatter<'_, W>) -> Result<(), W::Error>
+    where
+        W: ufmt::uWrite + ?Sized,
+    {
+        f.debug_struct("DeviceState")?
+            .field("datetime", &DateTimeDisplay(self.datetime))?
+            .field("control", &self.control)?
+            .field("status", &self.status)?
+            .field("aging_offset", &self.aging_offset)?
+            .field("temperature_quarters", &self.temperature_quarters)?
+            .finish()
+    }
+}
+
+#[maybe_async_cfg::maybe(
+    idents(
+        AsyncRegisterInterface(sync = "RegisterInterface", async = "AsyncRegisterInterface"),
+        Ds3231(sync = "Ds3231", async = "Ds3231Async")
+    ),
+    sync(keep_self),
+    async(feature = "async")
+)]
+impl<I2C, V> Ds3231<I2C, V>
+where
+    I2C: AsyncRegisterInterface,
+{
+    /// Read the time, flags, aging offset and temperature in one burst.
+    ///
+    /// See the [module documentation](crate::state).
+    pub async fn device_state(&mut self) -> Result<DeviceState, Error<I2C::Error>> {
+        let mut data = [0u8; REGISTER_COUNT];
+        self.read_time_block(&mut data).await?;
+        let register = |register: Register| data[register.addr() as usize];
+
+        let mut time = [0u8; 7];
+        time.copy_from_slice(&data[..7]);
+        let datetime = decode_datetime(&time, self.base_century)?;
+        self.plausibility.check(&datetime)?;
+
+        Ok(DeviceState {
+            datetime,
+            control: ControlFlags::from_bits_retain(register(Register::Control)),
+            status: StatusFlags::from_bits_retain(register(Register::Status)),
+            aging_offset: register(Register::AgingOffset) as i8,
+            temperature_quarters: decode_quarters(
+                register(Register::TempMsb),
+                register(Register::TempLsb),
+            ),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
+
+    #[test]
+    fn test_device_state_reads_one_burst() {
+        let expectations = [I2cTransaction::write_read(
+            0x68,
+            vec![Register::Seconds.addr()],
+            vec![
+                0x00, 0x30, 0x14, 0x05, 0x21, 0x08, 0x25, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+                0x1C, 0x89, 0xFE, 0x19, 0x40,
+            ],
+        )];
+        let mut ds3231 = Ds3231::new(I2cMock::new(&expectations));
+
+        let state = ds3231.device_state().unwrap();
+        assert_eq!(
+            state,
+            DeviceState {
+                datetime: DateTime::new(2025, 8, 21, 14, 30, 0).unwrap(),
+                control: ControlFlags::RS2 | ControlFlags::RS1 | ControlFlags::INTCN,
+                status: StatusFlags::OSF | StatusFlags::EN32KHZ | StatusFlags::A1F,
+                aging_offset: -2,
+                temperature_quarters: 101,
+            }
+        );
+        assert_eq!(state.temperature(), 25.25);
+
+        ds3231.release_i2c().done();
+    }
+}
@@ -0,0 +1,172 @@
+//! # Transfer Statistics
+//!
+//! The driver counts its bus transfers, so a long-running device can report
+//! the health of its I2C link upstream without extra instrumentation:
+//!
+//! ```no_run
+//! # use ds3231_rtc::Ds3231;
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! # let mut rtc = Ds3231::new(embedded_hal_mock::eh1::i2c::Mock::new(&[]));
+//! let last = rtc.stats();
+//! // ...
+//! let stats = rtc.stats();
+//! if stats.bus_errors > last.bus_errors {
+//!     println!("{} failed transfers", stats.bus_errors - last.bus_errors);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Counters advance by one per transfer, not per register: a burst read of
+//! the seven timekeeping registers is one read. A read-modify-write counts
+//! as a read plus, if the value changed, a write. Failed transfers count
+//! both as the attempted read or write and as a bus error. Counters wrap
+//! around at `u32::MAX`.
+//!
+//! [`retries`](Stats::retries) counts reads the driver repeats itself, i.e.
+//! timekeeping blocks re-read by
+//! [`ReadStrategy::Verified`](crate::read_strategy::ReadStrategy::Verified).
+//! A [`Retry`](crate::retry::Retry) transport hides its repeats from the
+//! driver and counts them in [`Retry::retries`](crate::retry::Retry::retries).
+
+#[cfg(feature = "async")]
+use crate::interface::AsyncRegisterInterface;
+use crate::interface::RegisterInterface;
+
+use crate::Ds3231;
+#[cfg(feature = "async")]
+use crate::ds3231::Ds3231Async;
+
+/// Transfer counters, returned by [`Ds3231::stats`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Stats {
+    /// Read transfers, failed ones included
+    pub reads: u32,
+    /// Write transfers, failed ones included
+    pub writes: u32,
+    /// Transfers the bus reported an error for
+    pub bus_errors: u32,
+    /// Reads repeated by the driver
+    pub retries: u32,
+}
+
+impl Stats {
+    pub(crate) fn count_read(&mut self) {
+        self.reads = self.reads.wrapping_add(1);
+    }
+
+    pub(crate) fn count_write(&mut self) {
+        self.writes = self.writes.wrapping_add(1);
+    }
+
+    pub(crate) fn count_bus_error(&mut self) {
+        self.bus_errors = self.bus_errors.wrapping_add(1);
+    }
+
+    pub(crate) fn count_retry(&mut self) {
+        self.retries = self.retries.wrapping_add(1);
+    }
+}
+
+#[maybe_async_cfg::maybe(
+    idents(
+        AsyncRegisterInterface(sync = "RegisterInterface", async = "AsyncRegisterInterface"),
+        Ds3231(sync = "Ds3231", async = "Ds3231Async")
+    ),
+    sync(keep_self),
+    async(feature = "async")
+)]
+impl<I2C, V> Ds3231<I2C, V>
+where
+    I2C: AsyncRegisterInterface,
+{
+    /// Returns the transfer counters since the driver was created or
+    /// [`reset_stats`](Self::reset_stats) was called.
+    ///
+    /// See the [module documentation](crate::stats).
+    pub fn stats(&self) -> Stats {
+        self.stats
+    }
+
+    /// Set all transfer counters back to zero.
+    pub fn reset_stats(&mut self) {
+        self.stats = Stats::default();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::read_strategy::ReadStrategy;
+    use crate::registers::Register;
+    use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
+
+    #[test]
+    #[cfg(feature = "temperature")]
+    fn test_counts_transfers_and_errors() {
+        use embedded_hal::i2c::ErrorKind;
+
+        let expectations = [
+            I2cTransaction::write_read(0x68, vec![Register::TempMsb.addr()], vec![0x19, 0x40]),
+            I2cTransaction::write_read(0x68, vec![Register::Control.addr()], vec![0x1C]),
+            I2cTransaction::write(0x68, vec![Register::Control.addr(), 0x9C]),
+            I2cTransaction::write_read(0x68, vec![Register::Control.addr()], vec![0x9C]),
+            I2cTransaction::write_read(0x68, vec![Register::Status.addr()], vec![0x00])
+                .with_error(ErrorKind::Other),
+            I2cTransaction::write(0x68, vec![Register::AgingOffset.addr(), 0x00])
+                .with_error(ErrorKind::Other),
+        ];
+        let mut ds3231 = Ds3231::new(I2cMock::new(&expectations));
+
+        ds3231.temperature_quarters().unwrap();
+        ds3231.halt_clock().unwrap();
+        ds3231.halt_clock().unwrap();
+        assert!(ds3231.oscillator_stopped().is_err());
+        assert!(
+            ds3231
+                .write_raw_bytes(&[Register::AgingOffset.addr(), 0x00])
+                .is_err()
+        );
+
+        assert_eq!(
+            ds3231.stats(),
+            Stats {
+                reads: 4,
+                writes: 2,
+                bus_errors: 2,
+                retries: 0,
+            }
+        );
+        ds3231.reset_stats();
+        assert_eq!(ds3231.stats(), Stats::default());
+
+        ds3231.release_i2c().done();
+    }
+
+    #[test]
+    fn test_counts_verified_rereads() {
+        let time = vec![0x59, 0x30, 0x14, 0x05, 0x21, 0x08, 0x25];
+        let rolled = vec![0x00, 0x31, 0x14, 0x05, 0x21, 0x08, 0x25];
+        let expectations = [
+            I2cTransaction::write_read(0x68, vec![Register::Seconds.addr()], time),
+            I2cTransaction::write_read(0x68, vec![Register::Seconds.addr()], vec![0x00]),
+            I2cTransaction::write_read(0x68, vec![Register::Seconds.addr()], rolled),
+            I2cTransaction::write_read(0x68, vec![Register::Seconds.addr()], vec![0x00]),
+        ];
+        let mut ds3231 = Ds3231::new(I2cMock::new(&expectations));
+        ds3231.set_read_strategy(ReadStrategy::Verified);
+
+        ds3231.get_datetime().unwrap();
+        assert_eq!(
+            ds3231.stats(),
+            Stats {
+                reads: 4,
+                retries: 1,
+                ..Stats::default()
+            }
+        );
+
+        ds3231.release_i2c().done();
+    }
+}
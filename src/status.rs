@@ -0,0 +1,133 @@
+//! DS3231 Status Register Support
+//!
+//! The status register (0Fh) reports conditions the chip has detected. The
+//! most important is the Oscillator Stop Flag (OSF): it is set when the
+//! oscillator stops for any reason (first power-up, both VCC and VBAT lost,
+//! EOSC set while on battery) and stays set until cleared by software. A set
+//! OSF means the timekeeping registers can no longer be trusted and the clock
+//! should be set again.
+
+use rtc_hal::datetime::DateTime;
+
+#[cfg(feature = "async")]
+use crate::interface::AsyncRegisterInterface;
+use crate::interface::RegisterInterface;
+
+#[cfg(feature = "async")]
+use crate::ds3231::Ds3231Async;
+use crate::{
+    Ds3231,
+    datetime::decode_datetime,
+    error::Error,
+    flags::StatusFlags,
+    registers::{OSF_BIT, Register},
+    trace::debug,
+};
+
+#[maybe_async_cfg::maybe(
+    idents(
+        AsyncRegisterInterface(sync = "RegisterInterface", async = "AsyncRegisterInterface"),
+        Ds3231(sync = "Ds3231", async = "Ds3231Async")
+    ),
+    sync(keep_self),
+    async(feature = "async")
+)]
+impl<I2C, V> Ds3231<I2C, V>
+where
+    I2C: AsyncRegisterInterface,
+{
+    /// Returns `true` if the Oscillator Stop Flag is set, i.e. the time is
+    /// invalid and must be set again.
+    pub async fn oscillator_stopped(&mut self) -> Result<bool, Error<I2C::Error>> {
+        let status = self.read_register(Register::Status).await?;
+        Ok(status & OSF_BIT != 0)
+    }
+
+    /// Clear the Oscillator Stop Flag after the time has been set.
+    pub async fn clear_oscillator_stop_flag(&mut self) -> Result<(), Error<I2C::Error>> {
+        self.clear_status_flags(OSF_BIT).await?;
+        debug!("clear_oscillator_stop_flag");
+        Ok(())
+    }
+
+    /// Read the date and time together with the status flags.
+    ///
+    /// Registers 0x00 to 0x0F are read in one burst, so polling for the time
+    /// and for fired alarms takes a single transfer instead of two. The
+    /// configured [read strategy](crate::read_strategy) applies as for
+    /// [`get_datetime`](Self::get_datetime).
+    pub async fn get_datetime_with_status(
+        &mut self,
+    ) -> Result<(DateTime, StatusFlags), Error<I2C::Error>> {
+        let mut data = [0u8; Register::Status.addr() as usize + 1];
+        self.read_time_block(&mut data).await?;
+
+        let mut time = [0u8; 7];
+        time.copy_from_slice(&data[..7]);
+        let datetime = decode_datetime(&time, self.base_century)?;
+        self.plausibility.check(&datetime)?;
+        Ok((
+            datetime,
+            StatusFlags::from_bits_retain(data[Register::Status.addr() as usize]),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
+
+    const DS3231_ADDR: u8 = 0x68;
+
+    #[test]
+    fn test_oscillator_stopped() {
+        let expectations = vec![
+            I2cTransaction::write_read(DS3231_ADDR, vec![Register::Status.addr()], vec![0x88]),
+            I2cTransaction::write_read(DS3231_ADDR, vec![Register::Status.addr()], vec![0x08]),
+        ];
+        let mut ds3231 = Ds3231::new(I2cMock::new(&expectations));
+
+        assert!(ds3231.oscillator_stopped().unwrap());
+        assert!(!ds3231.oscillator_stopped().unwrap());
+
+        ds3231.release_i2c().done();
+    }
+
+    #[test]
+    fn test_clear_oscillator_stop_flag_preserves_other_bits() {
+        let expectations = vec![
+            I2cTransaction::write_read(DS3231_ADDR, vec![Register::Status.addr()], vec![0x8B]),
+            I2cTransaction::write(DS3231_ADDR, vec![Register::Status.addr(), 0x0B]),
+            // Alarm flags read as 0 are written as 1, in case one fired meanwhile
+            I2cTransaction::write_read(DS3231_ADDR, vec![Register::Status.addr()], vec![0x88]),
+            I2cTransaction::write(DS3231_ADDR, vec![Register::Status.addr(), 0x0B]),
+        ];
+        let mut ds3231 = Ds3231::new(I2cMock::new(&expectations));
+
+        ds3231.clear_oscillator_stop_flag().unwrap();
+        ds3231.clear_oscillator_stop_flag().unwrap();
+
+        ds3231.release_i2c().done();
+    }
+
+    #[test]
+    fn test_get_datetime_with_status_is_one_transfer() {
+        let mut registers = vec![0x00, 0x30, 0x14, 0x05, 0x21, 0x08, 0x25];
+        // Alarm and control registers, then status with alarm 1 fired
+        registers.extend([0x00; 8]);
+        registers.push(0x01);
+        let expectations = [I2cTransaction::write_read(
+            DS3231_ADDR,
+            vec![Register::Seconds.addr()],
+            registers,
+        )];
+        let mut ds3231 = Ds3231::new(I2cMock::new(&expectations));
+
+        let (datetime, flags) = ds3231.get_datetime_with_status().unwrap();
+        assert_eq!(datetime, DateTime::new(2025, 8, 21, 14, 30, 0).unwrap());
+        assert_eq!(flags, StatusFlags::A1F);
+
+        ds3231.release_i2c().done();
+    }
+}
@@ -0,0 +1,148 @@
+//! # Stopwatch
+//!
+//! [`Stopwatch`] measures elapsed time on the RTC, with laps, so callers
+//! don't have to keep a start time around and subtract epoch seconds
+//! themselves. It works with any [`Rtc`] and only remembers timestamps, so
+//! it doesn't borrow the clock between readings:
+//!
+//! ```no_run
+//! # use ds3231_rtc::Ds3231;
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! # let mut rtc = Ds3231::new(embedded_hal_mock::eh1::i2c::Mock::new(&[]));
+//! use ds3231_rtc::stopwatch::Stopwatch;
+//! # let fill_tank = || ();
+//! # let drain_tank = || ();
+//!
+//! let mut watch = Stopwatch::start(&mut rtc)?;
+//! fill_tank();
+//! let filling = watch.lap(&mut rtc)?;
+//! drain_tank();
+//! let draining = watch.lap(&mut rtc)?;
+//! let total = watch.elapsed(&mut rtc)?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! The RTC counts whole seconds, so each reading is accurate to one second.
+//! If the clock is set backwards while the stopwatch runs, the readings
+//! saturate at [`Duration::ZERO`].
+
+use core::time::Duration;
+
+use rtc_hal::rtc::Rtc;
+
+use crate::calendar;
+
+/// Elapsed-time measurement with laps, as Unix timestamps
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Stopwatch {
+    started: u64,
+    lap_started: u64,
+    laps: u32,
+}
+
+impl Stopwatch {
+    /// Start a stopwatch at the current RTC time.
+    pub fn start<R: Rtc>(rtc: &mut R) -> Result<Self, R::Error> {
+        Ok(Self::started_at(now(rtc)?))
+    }
+
+    /// Stopwatch started at the Unix timestamp `timestamp`, e.g. one kept
+    /// across a reset.
+    pub const fn started_at(timestamp: u64) -> Self {
+        Self {
+            started: timestamp,
+            lap_started: timestamp,
+            laps: 0,
+        }
+    }
+
+    /// Unix timestamp at which the stopwatch was started.
+    pub const fn start_time(&self) -> u64 {
+        self.started
+    }
+
+    /// Number of laps completed so far.
+    pub const fn laps(&self) -> u32 {
+        self.laps
+    }
+
+    /// Time since the start.
+    pub fn elapsed<R: Rtc>(&self, rtc: &mut R) -> Result<Duration, R::Error> {
+        Ok(since(self.started, now(rtc)?))
+    }
+
+    /// Time since the start of the current lap.
+    pub fn lap_elapsed<R: Rtc>(&self, rtc: &mut R) -> Result<Duration, R::Error> {
+        Ok(since(self.lap_started, now(rtc)?))
+    }
+
+    /// Complete the current lap and start the next one, returning the
+    /// lap's duration.
+    pub fn lap<R: Rtc>(&mut self, rtc: &mut R) -> Result<Duration, R::Error> {
+        let now = now(rtc)?;
+        let lap = since(self.lap_started, now);
+        self.lap_started = now;
+        self.laps = self.laps.saturating_add(1);
+        Ok(lap)
+    }
+
+    /// Start again from zero, returning the time elapsed until now.
+    pub fn restart<R: Rtc>(&mut self, rtc: &mut R) -> Result<Duration, R::Error> {
+        let now = now(rtc)?;
+        let elapsed = since(self.started, now);
+        *self = Self::started_at(now);
+        Ok(elapsed)
+    }
+}
+
+fn now<R: Rtc>(rtc: &mut R) -> Result<u64, R::Error> {
+    let now = rtc.get_datetime()?;
+    Ok(calendar::to_unix_timestamp(&now))
+}
+
+fn since(then: u64, now: u64) -> Duration {
+    Duration::from_secs(now.saturating_sub(then))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blob::tests::MemNvram;
+    use rtc_hal::datetime::DateTime;
+
+    fn at(rtc: &mut MemNvram, minute: u8, second: u8) {
+        rtc.now = DateTime::new(2025, 8, 21, 14, minute, second).unwrap();
+    }
+
+    #[test]
+    fn test_laps_and_elapsed() {
+        let mut rtc = MemNvram::new(0);
+        let mut watch = Stopwatch::start(&mut rtc).unwrap();
+        assert_eq!(watch.start_time(), 1_755_786_600);
+
+        at(&mut rtc, 30, 45);
+        assert_eq!(watch.lap(&mut rtc), Ok(Duration::from_secs(45)));
+        at(&mut rtc, 32, 0);
+        assert_eq!(watch.lap_elapsed(&mut rtc), Ok(Duration::from_secs(75)));
+        assert_eq!(watch.lap(&mut rtc), Ok(Duration::from_secs(75)));
+        assert_eq!(watch.laps(), 2);
+        assert_eq!(watch.elapsed(&mut rtc), Ok(Duration::from_secs(120)));
+
+        at(&mut rtc, 33, 0);
+        assert_eq!(watch.restart(&mut rtc), Ok(Duration::from_secs(180)));
+        assert_eq!(watch.laps(), 0);
+        assert_eq!(watch.elapsed(&mut rtc), Ok(Duration::ZERO));
+    }
+
+    #[test]
+    fn test_clock_set_backwards_saturates() {
+        let mut rtc = MemNvram::new(0);
+        let mut watch = Stopwatch::start(&mut rtc).unwrap();
+
+        at(&mut rtc, 0, 0);
+        assert_eq!(watch.elapsed(&mut rtc), Ok(Duration::ZERO));
+        assert_eq!(watch.lap(&mut rtc), Ok(Duration::ZERO));
+    }
+}
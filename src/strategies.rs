@@ -0,0 +1,152 @@
+//! # Property-Testing Strategies
+//!
+//! With the `proptest` feature, this module provides `proptest` strategies
+//! for the values the driver deals in, so firmware can property-test its
+//! own time handling against the same conversions the driver uses
+//! ([`bcd::datetime_to_registers`](crate::bcd::datetime_to_registers) and
+//! [`bcd::registers_to_datetime`](crate::bcd::registers_to_datetime)):
+//!
+//! ```
+//! use ds3231_rtc::{calendar, strategies};
+//! use proptest::prelude::*;
+//! # use rtc_hal::datetime::DateTime;
+//! # fn next_wakeup(dt: &DateTime) -> DateTime {
+//! #     calendar::add_seconds(dt, 60).unwrap()
+//! # }
+//!
+//! proptest! {
+//!     // Marked `#[test]` in the firmware's test suite
+//!     fn schedule_survives_roundtrip(dt in strategies::datetime()) {
+//!         let next = next_wakeup(&dt);
+//!         prop_assert!(calendar::to_unix_timestamp(&next) > calendar::to_unix_timestamp(&dt));
+//!     }
+//! }
+//! # schedule_survives_roundtrip();
+//! ```
+//!
+//! Uniformly drawn datetimes almost never hit the dates where calendar code
+//! breaks, so [`datetime`] and [`datetime_in_years`] draw a quarter of their
+//! values from [`month_boundary_in_years`]: the first and last second of a
+//! month, which includes every February 29th and every year and century
+//! rollover in range.
+//!
+//! Quickcheck users can wrap the same conversions; only the generators are
+//! specific to proptest.
+
+use core::ops::RangeInclusive;
+
+use proptest::prelude::*;
+use rtc_hal::datetime::DateTime;
+
+use crate::bcd::datetime_to_registers;
+use crate::calendar::days_in_month;
+use crate::ds3231::DEFAULT_BASE_CENTURY;
+use crate::hour_mode::HourMode;
+
+/// Years the driver accepts with its default base century
+pub const DEFAULT_YEARS: RangeInclusive<u16> = 2000..=2199;
+
+/// Any datetime the driver accepts with its default configuration.
+pub fn datetime() -> impl Strategy<Value = DateTime> {
+    datetime_in_years(DEFAULT_YEARS)
+}
+
+/// Any datetime in `years`, biased towards month boundaries.
+pub fn datetime_in_years(years: RangeInclusive<u16>) -> impl Strategy<Value = DateTime> {
+    prop_oneof![
+        3 => uniform_datetime_in_years(years.clone()),
+        1 => month_boundary_in_years(years),
+    ]
+}
+
+/// Any datetime in `years`, every second equally likely within a month.
+pub fn uniform_datetime_in_years(years: RangeInclusive<u16>) -> impl Strategy<Value = DateTime> {
+    (years, 1..=12u8)
+        .prop_flat_map(|(year, month)| {
+            (
+                Just(year),
+                Just(month),
+                1..=days_in_month(year, month),
+                0..24u8,
+                0..60u8,
+                0..60u8,
+            )
+        })
+        .prop_map(|(year, month, day, hour, minute, second)| {
+            DateTime::new(year, month, day, hour, minute, second).unwrap()
+        })
+}
+
+/// The first or last second of a month in `years`.
+pub fn month_boundary_in_years(years: RangeInclusive<u16>) -> impl Strategy<Value = DateTime> {
+    (years, 1..=12u8, any::<bool>()).prop_map(|(year, month, last)| {
+        if last {
+            DateTime::new(year, month, days_in_month(year, month), 23, 59, 59).unwrap()
+        } else {
+            DateTime::new(year, month, 1, 0, 0, 0).unwrap()
+        }
+    })
+}
+
+/// Either hour mode.
+pub fn hour_mode() -> impl Strategy<Value = HourMode> {
+    prop_oneof![Just(HourMode::TwentyFourHour), Just(HourMode::TwelveHour)]
+}
+
+/// A valid BCD byte with a tens digit of at most `max_tens`.
+pub fn bcd_byte(max_tens: u8) -> impl Strategy<Value = u8> {
+    (0..=max_tens, 0..=9u8).prop_map(|(tens, ones)| (tens << 4) | ones)
+}
+
+/// Contents of the timekeeping registers 0x00-0x06 as the chip holds them
+/// with the default base century, in either hour mode.
+pub fn time_registers() -> impl Strategy<Value = [u8; 7]> {
+    (datetime(), hour_mode()).prop_map(|(datetime, hour_mode)| {
+        datetime_to_registers(&datetime, DEFAULT_BASE_CENTURY, hour_mode).unwrap()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bcd::{from_bcd, is_bcd, registers_to_datetime, to_bcd};
+    use crate::calendar::{add_seconds, from_unix_timestamp, to_unix_timestamp};
+
+    proptest! {
+        #[test]
+        fn test_bcd_roundtrip(value in 0..=99u8) {
+            prop_assert_eq!(from_bcd(to_bcd(value)), value);
+        }
+
+        #[test]
+        fn test_bcd_byte_is_bcd(value in bcd_byte(9)) {
+            prop_assert!(is_bcd(value));
+            prop_assert_eq!(to_bcd(from_bcd(value)), value);
+        }
+
+        #[test]
+        fn test_register_roundtrip(dt in datetime(), hour_mode in hour_mode()) {
+            let registers = datetime_to_registers(&dt, DEFAULT_BASE_CENTURY, hour_mode).unwrap();
+            prop_assert_eq!(registers_to_datetime(&registers, DEFAULT_BASE_CENTURY), Ok(dt));
+        }
+
+        #[test]
+        fn test_registers_decode(registers in time_registers()) {
+            prop_assert!(registers_to_datetime(&registers, DEFAULT_BASE_CENTURY).is_ok());
+        }
+
+        #[test]
+        fn test_unix_timestamp_roundtrip(dt in datetime()) {
+            prop_assert_eq!(from_unix_timestamp(to_unix_timestamp(&dt)), Ok(dt));
+        }
+
+        #[test]
+        fn test_next_second_rolls_over(dt in month_boundary_in_years(DEFAULT_YEARS)) {
+            let next = add_seconds(&dt, 1).unwrap();
+            prop_assert_eq!(to_unix_timestamp(&next), to_unix_timestamp(&dt) + 1);
+            if dt.second() == 59 {
+                prop_assert_eq!((next.day_of_month(), next.hour(), next.minute()), (1, 0, 0));
+            }
+        }
+    }
+}
@@ -0,0 +1,335 @@
+//! # Sub-Second Timestamps
+//!
+//! The DS3231 counts whole seconds only. Its SQW output, however, is divided
+//! down from the same oscillator, and its falling edges line up with the
+//! seconds register ticking over. [`SubSecondClock`] counts those edges
+//! through a [`SqwCounter`] the application provides (a timer clocked from
+//! the pin, or an interrupt incrementing a counter) and turns them into
+//! `(DateTime, milliseconds)` timestamps without touching the bus:
+//!
+//! ```no_run
+//! # use ds3231_rtc::Ds3231;
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! # let mut rtc = Ds3231::new(embedded_hal_mock::eh1::i2c::Mock::new(&[]));
+//! use ds3231_rtc::subsecond::SubSecondClock;
+//! use rtc_hal::square_wave::SquareWaveFreq;
+//! # struct Timer;
+//! # impl Timer { fn count(&self) -> u32 { 0 } }
+//! # static TIMER: Timer = Timer;
+//! # let mut delay = embedded_hal_mock::eh1::delay::NoopDelay::new();
+//!
+//! rtc.start_square_wave(SquareWaveFreq::Hz1024)?;
+//!
+//! let mut clock = SubSecondClock::new(|| TIMER.count(), SquareWaveFreq::Hz1024)?;
+//! clock.sync(&mut rtc, &mut delay)?;
+//!
+//! let (datetime, millis) = clock.now().unwrap();
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`sync`](SubSecondClock::sync) waits for the seconds register to tick
+//! over and records the edge count at that moment; from then on, every
+//! `rate` edges are one second. The counter runs off the RTC's own
+//! oscillator, so the timestamps don't drift from the RTC. The resolution is
+//! one edge (about 1 ms at 1024 Hz), plus the bus latency of the sync.
+//!
+//! At 1 Hz, every edge is a second boundary and the milliseconds come from
+//! [`SqwCounter::micros_since_edge`], e.g. an input-capture timer that is
+//! reset on every edge. Counters without one report 0 ms.
+//!
+//! The anchor is moved forward on every [`now`](SubSecondClock::now), so
+//! the counter may wrap as long as `now` is called at least once per wrap
+//! (48 days for a 32-bit counter at 1024 Hz).
+
+use core::convert::Infallible;
+
+use embedded_hal::delay::DelayNs;
+use rtc_hal::datetime::DateTime;
+use rtc_hal::square_wave::SquareWaveFreq;
+
+use crate::Ds3231;
+use crate::calendar;
+use crate::error::Error;
+use crate::interface::RegisterInterface;
+use crate::registers::Register;
+use crate::square_wave::freq_to_bits;
+use crate::trace::debug;
+
+/// Wait between two polls of the seconds register during a sync
+const SYNC_POLL_US: u32 = 250;
+
+/// Longest a sync waits for the seconds register to tick over
+const SYNC_TIMEOUT_US: u32 = 1_100_000;
+
+/// Source of the number of SQW falling edges seen so far
+pub trait SqwCounter {
+    /// Edges counted so far, wrapping on overflow.
+    fn edges(&mut self) -> u32;
+
+    /// Microseconds since the last counted edge, if the hardware captures
+    /// it.
+    ///
+    /// Only needed for sub-second resolution at 1 Hz.
+    fn micros_since_edge(&mut self) -> Option<u32> {
+        None
+    }
+}
+
+impl<F: FnMut() -> u32> SqwCounter for F {
+    fn edges(&mut self) -> u32 {
+        self()
+    }
+}
+
+/// Edge count at a known second
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Anchor {
+    /// Seconds since the Unix epoch
+    epoch_seconds: u64,
+    /// Counter value at the start of that second
+    edges: u32,
+}
+
+/// RTC time with sub-second resolution from counted SQW edges.
+///
+/// See the [module documentation](self).
+#[derive(Debug)]
+pub struct SubSecondClock<C> {
+    counter: C,
+    rate: u32,
+    anchor: Option<Anchor>,
+}
+
+impl<C: SqwCounter> SubSecondClock<C> {
+    /// Count edges of the SQW output running at `freq`.
+    ///
+    /// Fails with [`Error::UnsupportedSqwFrequency`] for frequencies the SQW
+    /// pin can't output.
+    pub fn new(counter: C, freq: SquareWaveFreq) -> Result<Self, Error<Infallible>> {
+        freq_to_bits::<Infallible>(freq)?;
+        Ok(Self {
+            counter,
+            rate: freq.to_hz(),
+            anchor: None,
+        })
+    }
+
+    /// Returns `true` once [`sync`](Self::sync) has succeeded.
+    pub fn is_synced(&self) -> bool {
+        self.anchor.is_some()
+    }
+
+    /// Wait for the seconds register of `rtc` to tick over and anchor the
+    /// edge count to the new second.
+    ///
+    /// Polls every 250 µs on `delay` and fails with [`Error::Timeout`] if
+    /// the second doesn't change within 1.1 s, e.g. because the oscillator
+    /// is halted.
+    pub fn sync<I2C, V, D>(
+        &mut self,
+        rtc: &mut Ds3231<I2C, V>,
+        delay: &mut D,
+    ) -> Result<(), Error<I2C::Error>>
+    where
+        I2C: RegisterInterface,
+        D: DelayNs,
+    {
+        let start = rtc.read_register(Register::Seconds)?;
+
+        let mut waited_us = 0;
+        loop {
+            if rtc.read_register(Register::Seconds)? != start {
+                break;
+            }
+            if waited_us >= SYNC_TIMEOUT_US {
+                return Err(Error::Timeout);
+            }
+            delay.delay_us(SYNC_POLL_US);
+            waited_us += SYNC_POLL_US;
+        }
+        let edges = self.counter.edges();
+
+        let datetime = rtc.get_datetime()?;
+        self.anchor = Some(Anchor {
+            epoch_seconds: calendar::to_unix_timestamp(&datetime),
+            edges,
+        });
+        debug!("subsecond sync: edge {}", edges);
+        Ok(())
+    }
+
+    /// Current time and milliseconds into the second.
+    ///
+    /// Returns `None` before the first [`sync`](Self::sync).
+    pub fn now(&mut self) -> Option<(DateTime, u16)> {
+        let (epoch_seconds, millis) = self.now_parts()?;
+        let datetime = calendar::from_unix_timestamp(epoch_seconds).ok()?;
+        Some((datetime, millis))
+    }
+
+    /// Current time in milliseconds since the Unix epoch.
+    ///
+    /// Returns `None` before the first [`sync`](Self::sync).
+    pub fn timestamp_millis(&mut self) -> Option<u64> {
+        let (epoch_seconds, millis) = self.now_parts()?;
+        Some(epoch_seconds * 1000 + millis as u64)
+    }
+
+    /// Forget the anchor, e.g. after setting the RTC.
+    pub fn reset(&mut self) {
+        self.anchor = None;
+    }
+
+    /// Borrow the counter.
+    pub fn counter_mut(&mut self) -> &mut C {
+        &mut self.counter
+    }
+
+    /// Returns the counter, consuming the clock.
+    pub fn into_inner(self) -> C {
+        self.counter
+    }
+
+    /// Seconds since the Unix epoch and milliseconds into the second,
+    /// moving the anchor up to the current second.
+    fn now_parts(&mut self) -> Option<(u64, u16)> {
+        let anchor = self.anchor.as_mut()?;
+        let elapsed = self.counter.edges().wrapping_sub(anchor.edges);
+        let seconds = elapsed / self.rate;
+        anchor.epoch_seconds += seconds as u64;
+        anchor.edges = anchor.edges.wrapping_add(seconds * self.rate);
+
+        let edge_us = (elapsed % self.rate) as u64 * 1_000_000 / self.rate as u64;
+        let period_us = 1_000_000 / self.rate as u64;
+        let since_edge_us = self
+            .counter
+            .micros_since_edge()
+            .map_or(0, |us| (us as u64).min(period_us - 1));
+        let millis = ((edge_us + since_edge_us) / 1000).min(999) as u16;
+
+        Some((anchor.epoch_seconds, millis))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::cell::Cell;
+    use embedded_hal_mock::eh1::delay::NoopDelay;
+    use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
+
+    const DS3231_ADDR: u8 = 0x68;
+
+    fn seconds_read(value: u8) -> I2cTransaction {
+        I2cTransaction::write_read(DS3231_ADDR, vec![Register::Seconds.addr()], vec![value])
+    }
+
+    fn sync_expectations() -> [I2cTransaction; 4] {
+        [
+            seconds_read(0x59),
+            seconds_read(0x59),
+            seconds_read(0x00),
+            // 2025-08-21 14:30:00
+            I2cTransaction::write_read(
+                DS3231_ADDR,
+                vec![Register::Seconds.addr()],
+                vec![0x00, 0x30, 0x14, 0x05, 0x21, 0x08, 0x25],
+            ),
+        ]
+    }
+
+    /// Counter that also captures the time since the last edge
+    struct Capture {
+        edges: u32,
+        micros: u32,
+    }
+
+    impl SqwCounter for Capture {
+        fn edges(&mut self) -> u32 {
+            self.edges
+        }
+
+        fn micros_since_edge(&mut self) -> Option<u32> {
+            Some(self.micros)
+        }
+    }
+
+    #[test]
+    fn test_millis_from_1024_hz_edges() {
+        let edges = Cell::new(u32::MAX - 100);
+        let mut clock = SubSecondClock::new(|| edges.get(), SquareWaveFreq::Hz1024).unwrap();
+        assert_eq!(clock.now(), None);
+
+        let expectations = sync_expectations();
+        let mut ds3231 = Ds3231::new(I2cMock::new(&expectations));
+        clock.sync(&mut ds3231, &mut NoopDelay).unwrap();
+        assert!(clock.is_synced());
+
+        edges.set(edges.get().wrapping_add(512));
+        assert_eq!(
+            clock.now(),
+            Some((DateTime::new(2025, 8, 21, 14, 30, 0).unwrap(), 500))
+        );
+
+        // Across the counter wrap and three more seconds
+        edges.set(edges.get().wrapping_add(3 * 1024 + 256));
+        assert_eq!(
+            clock.now(),
+            Some((DateTime::new(2025, 8, 21, 14, 30, 3).unwrap(), 750))
+        );
+        assert_eq!(clock.timestamp_millis(), Some(1_755_786_603_750));
+
+        clock.reset();
+        assert_eq!(clock.timestamp_millis(), None);
+
+        ds3231.release_i2c().done();
+    }
+
+    #[test]
+    fn test_millis_from_1_hz_capture() {
+        let mut clock = SubSecondClock::new(
+            Capture {
+                edges: 7,
+                micros: 0,
+            },
+            SquareWaveFreq::Hz1,
+        )
+        .unwrap();
+
+        let expectations = sync_expectations();
+        let mut ds3231 = Ds3231::new(I2cMock::new(&expectations));
+        clock.sync(&mut ds3231, &mut NoopDelay).unwrap();
+
+        let counter = clock.counter_mut();
+        counter.edges = 9;
+        counter.micros = 123_456;
+        assert_eq!(
+            clock.now(),
+            Some((DateTime::new(2025, 8, 21, 14, 30, 2).unwrap(), 123))
+        );
+
+        ds3231.release_i2c().done();
+    }
+
+    #[test]
+    fn test_sync_times_out_on_halted_clock() {
+        // The first read, then one per poll until 1.1 s have passed
+        let expectations = vec![seconds_read(0x12); 2 + 4400];
+        let mut ds3231 = Ds3231::new(I2cMock::new(&expectations));
+        let mut clock = SubSecondClock::new(|| 0, SquareWaveFreq::Hz1024).unwrap();
+
+        assert_eq!(clock.sync(&mut ds3231, &mut NoopDelay), Err(Error::Timeout));
+        assert!(!clock.is_synced());
+
+        ds3231.release_i2c().done();
+    }
+
+    #[test]
+    fn test_unsupported_frequency() {
+        assert_eq!(
+            SubSecondClock::new(|| 0, SquareWaveFreq::Hz32768).err(),
+            Some(Error::UnsupportedSqwFrequency)
+        );
+    }
+}
@@ -0,0 +1,174 @@
+//! # Telemetry Records
+//!
+//! [`Telemetry`] is the compact record a logger ships upstream: Unix time,
+//! temperature and status flags in 11 bytes. It implements `serde`'s
+//! `Serialize`/`Deserialize` with the `serde` feature and `defmt::Format`
+//! with the `defmt` feature, and has a fixed little-endian byte layout for
+//! links that carry raw payloads:
+//!
+//! ```no_run
+//! # use ds3231_rtc::Ds3231;
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! # let mut rtc = Ds3231::new(embedded_hal_mock::eh1::i2c::Mock::new(&[]));
+//! let record = rtc.telemetry()?;
+//! let payload: [u8; 11] = record.to_bytes(); // ready for the radio
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! ## Layout
+//!
+//! | Bytes | Field                  | Encoding                  |
+//! |-------|------------------------|---------------------------|
+//! | 0-7   | `timestamp`            | `u64`, seconds since 1970 |
+//! | 8-9   | `temperature_quarters` | `i16`, 0.25 °C units      |
+//! | 10    | `status`               | status register bits      |
+//!
+//! The layout is part of the public API and will not change.
+
+#[cfg(feature = "async")]
+use crate::interface::AsyncRegisterInterface;
+use crate::interface::RegisterInterface;
+
+use crate::Ds3231;
+use crate::calendar;
+#[cfg(feature = "async")]
+use crate::ds3231::Ds3231Async;
+use crate::error::Error;
+use crate::flags::StatusFlags;
+use crate::state::DeviceState;
+
+/// Length of [`Telemetry::to_bytes`]
+pub const TELEMETRY_LEN: usize = 11;
+
+/// Time, temperature and status flags at one point in time
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Telemetry {
+    /// Seconds since 1970-01-01 00:00:00
+    pub timestamp: u64,
+    /// Temperature in quarter degrees Celsius
+    pub temperature_quarters: i16,
+    /// Raw status register (see [`StatusFlags`])
+    pub status: u8,
+}
+
+impl Telemetry {
+    /// Status register bits as flags.
+    pub const fn status_flags(&self) -> StatusFlags {
+        StatusFlags::from_bits_retain(self.status)
+    }
+
+    /// Temperature in degrees Celsius.
+    pub fn temperature(&self) -> f32 {
+        self.temperature_quarters as f32 / 4.0
+    }
+
+    /// Encode the record in its fixed [layout](self#layout).
+    pub fn to_bytes(&self) -> [u8; TELEMETRY_LEN] {
+        let mut bytes = [0u8; TELEMETRY_LEN];
+        bytes[..8].copy_from_slice(&self.timestamp.to_le_bytes());
+        bytes[8..10].copy_from_slice(&self.temperature_quarters.to_le_bytes());
+        bytes[10] = self.status;
+        bytes
+    }
+
+    /// Decode a record from its fixed [layout](self#layout).
+    pub fn from_bytes(bytes: &[u8; TELEMETRY_LEN]) -> Self {
+        let mut timestamp = [0u8; 8];
+        timestamp.copy_from_slice(&bytes[..8]);
+        Self {
+            timestamp: u64::from_le_bytes(timestamp),
+            temperature_quarters: i16::from_le_bytes([bytes[8], bytes[9]]),
+            status: bytes[10],
+        }
+    }
+}
+
+impl From<DeviceState> for Telemetry {
+    fn from(state: DeviceState) -> Self {
+        Self {
+            timestamp: calendar::to_unix_timestamp(&state.datetime),
+            temperature_quarters: state.temperature_quarters,
+            status: state.status.bits(),
+        }
+    }
+}
+
+#[maybe_async_cfg::maybe(
+    idents(
+        AsyncRegisterInterface(sync = "RegisterInterface", async = "AsyncRegisterInterface"),
+        Ds3231(sync = "Ds3231", async = "Ds3231Async")
+    ),
+    sync(keep_self),
+    async(feature = "async")
+)]
+impl<I2C, V> Ds3231<I2C, V>
+where
+    I2C: AsyncRegisterInterface,
+{
+    /// Read a telemetry record in one burst.
+    ///
+    /// See [`device_state`](Self::device_state), which this is built on.
+    pub async fn telemetry(&mut self) -> Result<Telemetry, Error<I2C::Error>> {
+        Ok(self.device_state().await?.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registers::Register;
+    use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
+
+    #[test]
+    fn test_telemetry_from_registers() {
+        let expectations = [I2cTransaction::write_read(
+            0x68,
+            vec![Register::Seconds.addr()],
+            vec![
+                0x00, 0x30, 0x14, 0x05, 0x21, 0x08, 0x25, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+                0x1C, 0x89, 0x00, 0xF9, 0x40,
+            ],
+        )];
+        let mut ds3231 = Ds3231::new(I2cMock::new(&expectations));
+
+        let record = ds3231.telemetry().unwrap();
+        assert_eq!(
+            record,
+            Telemetry {
+                timestamp: 1_755_786_600,
+                temperature_quarters: -27,
+                status: 0x89,
+            }
+        );
+        assert!(record.status_flags().oscillator_stopped());
+
+        ds3231.release_i2c().done();
+    }
+
+    #[test]
+    fn test_byte_layout() {
+        let record = Telemetry {
+            timestamp: 0x0102_0304_0506_0708,
+            temperature_quarters: -2,
+            status: 0x88,
+        };
+        let bytes = record.to_bytes();
+        assert_eq!(
+            bytes,
+            [
+                0x08, 0x07, 0x06, 0x05, 0x04, 0x03, 0x02, 0x01, 0xFE, 0xFF, 0x88
+            ]
+        );
+        assert_eq!(Telemetry::from_bytes(&bytes), record);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_derives() {
+        fn assert_serde<T: serde::Serialize + for<'de> serde::Deserialize<'de>>() {}
+        assert_serde::<Telemetry>();
+    }
+}
@@ -0,0 +1,172 @@
+//! # Temperature Sensor
+//!
+//! The DS3231 measures its die temperature to compensate the oscillator and
+//! exposes the result in registers 0x11-0x12 with a resolution of 0.25 °C.
+//! The chip converts on its own schedule (see
+//! [`Variant::temperature_interval_secs`](crate::variant::Variant::temperature_interval_secs)),
+//! so reading more often than that returns the same value.
+//!
+//! A fresh reading can be forced with
+//! [`convert_temperature`](crate::Ds3231::convert_temperature), which sets the
+//! CONV bit and polls until the chip clears it. A conversion the chip is
+//! already running (BSY set) is reported as [`Error::Busy`], one that doesn't
+//! finish within the datasheet's 200 ms as [`Error::Timeout`].
+
+use embedded_hal::delay::DelayNs;
+#[cfg(feature = "async")]
+use embedded_hal_async::delay::DelayNs as AsyncDelayNs;
+
+#[cfg(feature = "async")]
+use crate::interface::AsyncRegisterInterface;
+use crate::interface::RegisterInterface;
+
+use crate::Ds3231;
+#[cfg(feature = "async")]
+use crate::ds3231::Ds3231Async;
+use crate::error::Error;
+use crate::registers::{ControlRegister, Register, StatusRegister, decode_quarters};
+use crate::trace::debug;
+
+/// Longest a forced conversion takes (tCONV)
+const CONVERSION_TIMEOUT_MS: u32 = 200;
+
+/// Wait between two polls of the CONV bit
+const CONVERSION_POLL_MS: u32 = 10;
+
+#[maybe_async_cfg::maybe(
+    idents(
+        AsyncDelayNs(sync = "DelayNs", async = "AsyncDelayNs"),
+        AsyncRegisterInterface(sync = "RegisterInterface", async = "AsyncRegisterInterface"),
+        Ds3231(sync = "Ds3231", async = "Ds3231Async")
+    ),
+    sync(keep_self),
+    async(feature = "async")
+)]
+impl<I2C, V> Ds3231<I2C, V>
+where
+    I2C: AsyncRegisterInterface,
+{
+    /// Last measured temperature in quarter degrees Celsius (e.g. 101 =
+    /// 25.25 °C), without any floating point.
+    pub async fn temperature_quarters(&mut self) -> Result<i16, Error<I2C::Error>> {
+        let mut data = [0u8; 2];
+        self.read_register_bytes(Register::TempMsb, &mut data)
+            .await?;
+        let quarters = decode_quarters(data[0], data[1]);
+        debug!("temperature_quarters: {}", quarters);
+        Ok(quarters)
+    }
+
+    /// Last measured temperature in degrees Celsius.
+    pub async fn temperature(&mut self) -> Result<f32, Error<I2C::Error>> {
+        Ok(self.temperature_quarters().await? as f32 / 4.0)
+    }
+
+    /// Start a temperature conversion (set CONV) without waiting for it.
+    ///
+    /// Fails with [`Error::Busy`] while the chip is running a conversion of
+    /// its own, as the datasheet asks not to force one then.
+    pub async fn start_temperature_conversion(&mut self) -> Result<(), Error<I2C::Error>> {
+        let status = StatusRegister::from_bits(self.read_register(Register::Status).await?);
+        if status.busy() {
+            return Err(Error::Busy);
+        }
+        self.modify_control(|control| control.set_convert_temperature(true))
+            .await?;
+        // The chip clears CONV when done; a cached copy would start another
+        self.control_cache.invalidate();
+        debug!("start_temperature_conversion");
+        Ok(())
+    }
+
+    /// Returns `true` while a forced conversion is still running.
+    pub async fn temperature_conversion_pending(&mut self) -> Result<bool, Error<I2C::Error>> {
+        let control = self.read_register(Register::Control).await?;
+        Ok(ControlRegister::from_bits(control).convert_temperature())
+    }
+
+    /// Force a temperature conversion, wait for it and return the new
+    /// reading in quarter degrees Celsius.
+    ///
+    /// Polls every 10 ms on `delay` and fails with [`Error::Timeout`] if the
+    /// conversion hasn't finished after 200 ms.
+    pub async fn convert_temperature<D: AsyncDelayNs>(
+        &mut self,
+        delay: &mut D,
+    ) -> Result<i16, Error<I2C::Error>> {
+        self.start_temperature_conversion().await?;
+
+        let mut waited_ms = 0;
+        while self.temperature_conversion_pending().await? {
+            if waited_ms >= CONVERSION_TIMEOUT_MS {
+                return Err(Error::Timeout);
+            }
+            delay.delay_ms(CONVERSION_POLL_MS).await;
+            waited_ms += CONVERSION_POLL_MS;
+        }
+
+        self.temperature_quarters().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use embedded_hal_mock::eh1::delay::NoopDelay;
+    use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
+
+    #[test]
+    fn test_temperature() {
+        let expectations = [I2cTransaction::write_read(
+            0x68,
+            vec![Register::TempMsb.addr()],
+            vec![0xF6, 0x80],
+        )];
+        let mut ds3231 = Ds3231::new(I2cMock::new(&expectations));
+
+        assert_eq!(ds3231.temperature().unwrap(), -9.5);
+
+        ds3231.release_i2c().done();
+    }
+
+    #[test]
+    fn test_convert_temperature() {
+        let expectations = [
+            I2cTransaction::write_read(0x68, vec![Register::Status.addr()], vec![0x88]),
+            I2cTransaction::write_read(0x68, vec![Register::Control.addr()], vec![0x1C]),
+            I2cTransaction::write(0x68, vec![Register::Control.addr(), 0x3C]),
+            I2cTransaction::write_read(0x68, vec![Register::Control.addr()], vec![0x3C]),
+            I2cTransaction::write_read(0x68, vec![Register::Control.addr()], vec![0x1C]),
+            I2cTransaction::write_read(0x68, vec![Register::TempMsb.addr()], vec![0x19, 0x40]),
+        ];
+        let mut ds3231 = Ds3231::new(I2cMock::new(&expectations));
+
+        assert_eq!(ds3231.convert_temperature(&mut NoopDelay), Ok(101));
+
+        ds3231.release_i2c().done();
+    }
+
+    #[test]
+    fn test_convert_temperature_busy_and_timeout() {
+        let mut expectations = vec![
+            I2cTransaction::write_read(0x68, vec![Register::Status.addr()], vec![0x8C]),
+            I2cTransaction::write_read(0x68, vec![Register::Status.addr()], vec![0x88]),
+            I2cTransaction::write_read(0x68, vec![Register::Control.addr()], vec![0x3C]),
+        ];
+        // CONV never clears: polled every 10 ms for 200 ms
+        expectations.extend(
+            (0..=CONVERSION_TIMEOUT_MS / CONVERSION_POLL_MS).map(|_| {
+                I2cTransaction::write_read(0x68, vec![Register::Control.addr()], vec![0x3C])
+            }),
+        );
+        let mut ds3231 = Ds3231::new(I2cMock::new(&expectations));
+
+        assert_eq!(ds3231.convert_temperature(&mut NoopDelay), Err(Error::Busy));
+        assert_eq!(
+            ds3231.convert_temperature(&mut NoopDelay),
+            Err(Error::Timeout)
+        );
+
+        ds3231.release_i2c().done();
+    }
+}
@@ -0,0 +1,174 @@
+//! # Async SQW Ticks
+//!
+//! With the `async` feature, [`Ds3231::ticks`](crate::asynch::Ds3231::ticks)
+//! starts the square wave output and returns [`SqwTicks`], which awaits its
+//! falling edges on a GPIO implementing [`embedded_hal_async::digital::Wait`].
+//! The MCU can sleep between edges, so the SQW pin works as a low-power tick
+//! source for schedulers, driven by the RTC's temperature-compensated
+//! oscillator:
+//!
+//! ```no_run
+//! use ds3231_rtc::asynch::Ds3231;
+//! use rtc_hal::square_wave::SquareWaveFreq;
+//! # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+//! # let i2c = embedded_hal_mock::eh1::i2c::Mock::new(&[]);
+//! # let sqw_pin = embedded_hal_mock::eh1::digital::Mock::new(&[]);
+//!
+//! let mut rtc = Ds3231::new(i2c);
+//! let mut ticks = rtc.ticks(sqw_pin, SquareWaveFreq::Hz1).await?;
+//!
+//! loop {
+//!     let tick = ticks.next().await?;
+//!     // run whatever is due at second `tick`
+//! }
+//! # }
+//! ```
+//!
+//! The SQW pin is open drain and needs a pull-up. Falling edges line up with
+//! the seconds register ticking over, so at 1 Hz every tick is the start of
+//! a new second. [`SqwTicks`] also implements
+//! [`SqwCounter`], for use with
+//! [`SubSecondClock`](crate::subsecond::SubSecondClock).
+
+use core::convert::Infallible;
+
+use embedded_hal_async::digital::Wait;
+use rtc_hal::square_wave::SquareWaveFreq;
+
+use crate::ds3231::Ds3231Async;
+use crate::error::Error;
+use crate::interface::AsyncRegisterInterface;
+use crate::square_wave::freq_to_bits;
+use crate::subsecond::SqwCounter;
+
+/// Falling edges of the SQW output, awaited on a GPIO
+#[derive(Debug)]
+pub struct SqwTicks<P> {
+    pin: P,
+    freq: SquareWaveFreq,
+    count: u64,
+}
+
+impl<P: Wait> SqwTicks<P> {
+    /// Count edges on `pin` of a square wave already running at `freq`.
+    ///
+    /// Fails with [`Error::UnsupportedSqwFrequency`] for frequencies the SQW
+    /// pin can't output.
+    pub fn new(pin: P, freq: SquareWaveFreq) -> Result<Self, Error<Infallible>> {
+        freq_to_bits::<Infallible>(freq)?;
+        Ok(Self {
+            pin,
+            freq,
+            count: 0,
+        })
+    }
+
+    /// Wait for the next falling edge and return the number of ticks so
+    /// far, starting at 1.
+    pub async fn next(&mut self) -> Result<u64, P::Error> {
+        self.pin.wait_for_falling_edge().await?;
+        self.count += 1;
+        Ok(self.count)
+    }
+
+    /// Frequency of the square wave.
+    pub fn frequency(&self) -> SquareWaveFreq {
+        self.freq
+    }
+
+    /// Ticks received so far.
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// Time covered by the ticks received so far, in milliseconds.
+    pub fn elapsed_millis(&self) -> u64 {
+        self.count * 1000 / self.freq.to_hz() as u64
+    }
+
+    /// Returns the pin, consuming the stream.
+    pub fn release(self) -> P {
+        self.pin
+    }
+}
+
+impl<P> SqwCounter for SqwTicks<P> {
+    fn edges(&mut self) -> u32 {
+        self.count as u32
+    }
+}
+
+impl<I2C, V> Ds3231Async<I2C, V>
+where
+    I2C: AsyncRegisterInterface,
+{
+    /// Start the square wave at `freq` and return its ticks on `pin`.
+    pub async fn ticks<P: Wait>(
+        &mut self,
+        pin: P,
+        freq: SquareWaveFreq,
+    ) -> Result<SqwTicks<P>, Error<I2C::Error>> {
+        self.start_square_wave(freq).await?;
+        Ok(SqwTicks {
+            pin,
+            freq,
+            count: 0,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registers::Register;
+    use core::pin::pin;
+    use core::task::{Context, Poll, Waker};
+    use embedded_hal_mock::eh1::digital::{Edge, Mock as PinMock, Transaction as PinTransaction};
+    use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
+
+    /// The mocks complete immediately, so a single poll suffices.
+    fn block_on<F: Future>(future: F) -> F::Output {
+        let mut future = pin!(future);
+        match future
+            .as_mut()
+            .poll(&mut Context::from_waker(Waker::noop()))
+        {
+            Poll::Ready(output) => output,
+            Poll::Pending => panic!("mock future was not ready"),
+        }
+    }
+
+    #[test]
+    fn test_ticks_start_square_wave_and_count_edges() {
+        let expectations = [
+            I2cTransaction::write_read(0x68, vec![Register::Control.addr()], vec![0x1C]),
+            // 1.024 kHz, INTCN cleared
+            I2cTransaction::write(0x68, vec![Register::Control.addr(), 0x08]),
+        ];
+        let mut ds3231 = Ds3231Async::new(I2cMock::new(&expectations));
+        let edges = vec![PinTransaction::wait_for_edge(Edge::Falling); 3];
+
+        let mut ticks =
+            block_on(ds3231.ticks(PinMock::new(&edges), SquareWaveFreq::Hz1024)).unwrap();
+        assert_eq!(ticks.frequency(), SquareWaveFreq::Hz1024);
+        for expected in 1..=3 {
+            assert_eq!(block_on(ticks.next()), Ok(expected));
+        }
+        assert_eq!(ticks.count(), 3);
+        assert_eq!(ticks.edges(), 3);
+        assert_eq!(ticks.elapsed_millis(), 2);
+
+        ticks.release().done();
+        ds3231.release_i2c().done();
+    }
+
+    #[test]
+    fn test_unsupported_frequency() {
+        let mut pin = PinMock::new(&[]);
+        assert_eq!(
+            SqwTicks::new(pin.clone(), SquareWaveFreq::Hz32768).err(),
+            Some(Error::UnsupportedSqwFrequency)
+        );
+        pin.done();
+    }
+}
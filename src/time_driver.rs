@@ -0,0 +1,200 @@
+//! # embassy-time Driver
+//!
+//! With the `embassy-time` feature, [`RtcTimeDriver`] implements
+//! [`embassy_time_driver::Driver`] on edges of the DS3231's INT/SQW pin, so
+//! `embassy-time` keeps running while the MCU's own timers are powered down
+//! in deep sleep. The application registers it as the time driver and
+//! reports the edges from the pin's interrupt handler:
+//!
+//! ```ignore
+//! use ds3231_rtc::time_driver::RtcTimeDriver;
+//!
+//! embassy_time_driver::time_driver_impl!(
+//!     static DRIVER: RtcTimeDriver<8> = RtcTimeDriver::new(1)
+//! );
+//!
+//! // Once at startup: the 1 Hz square wave wakes the MCU every second
+//! rtc.start_square_wave(SquareWaveFreq::Hz1)?;
+//!
+//! #[interrupt]
+//! fn EXTI0() {
+//!     clear_pin_interrupt();
+//!     DRIVER.on_tick();
+//! }
+//! ```
+//!
+//! Each edge advances the time by `TICK_HZ / sqw_hz` ticks, so the
+//! embassy-time tick rate (its `tick-hz-*` feature) must be a multiple of
+//! the square wave frequency; `tick-hz-1` with the 1 Hz square wave gives
+//! the fewest wakeups. Timers resolve to one edge.
+//!
+//! To sleep through several seconds without a wakeup per edge, switch the
+//! pin to alarms, program an alarm for [`next_wake`](RtcTimeDriver::next_wake)
+//! (e.g. with [`RtcTimer`](crate::timer::RtcTimer)), and report the time
+//! slept with [`advance`](RtcTimeDriver::advance) after waking.
+//!
+//! Up to `N` tasks can wait at the same time. A task scheduled while all
+//! slots are taken is woken straight away and polls again, which costs
+//! power but never loses a wakeup.
+
+use core::cell::{Cell, RefCell};
+use core::task::Waker;
+
+use critical_section::Mutex;
+use embassy_time_driver::{Driver, TICK_HZ};
+
+/// Deadline and waker of a waiting task
+type Slot = Option<(u64, Waker)>;
+
+/// embassy-time driver counting edges of the SQW output
+pub struct RtcTimeDriver<const N: usize> {
+    ticks_per_edge: u64,
+    now: Mutex<Cell<u64>>,
+    wakers: Mutex<RefCell<[Slot; N]>>,
+}
+
+impl<const N: usize> RtcTimeDriver<N> {
+    /// Driver for a square wave running at `sqw_hz`.
+    ///
+    /// Fails to compile when used in a `static` if `TICK_HZ` is not a
+    /// multiple of `sqw_hz`.
+    pub const fn new(sqw_hz: u32) -> Self {
+        assert!(
+            sqw_hz > 0 && TICK_HZ.is_multiple_of(sqw_hz as u64),
+            "the embassy-time tick rate must be a multiple of the square wave frequency"
+        );
+        Self {
+            ticks_per_edge: TICK_HZ / sqw_hz as u64,
+            now: Mutex::new(Cell::new(0)),
+            wakers: Mutex::new(RefCell::new([const { None }; N])),
+        }
+    }
+
+    /// Report one edge of the square wave, from its interrupt handler.
+    pub fn on_tick(&self) {
+        self.advance(self.ticks_per_edge);
+    }
+
+    /// Move the time forward by `ticks` and wake the tasks now due, e.g.
+    /// after sleeping until an alarm.
+    pub fn advance(&self, ticks: u64) {
+        critical_section::with(|cs| {
+            let now = self.now.borrow(cs).get().saturating_add(ticks);
+            self.now.borrow(cs).set(now);
+
+            for slot in self.wakers.borrow_ref_mut(cs).iter_mut() {
+                if let Some((_, waker)) = slot.take_if(|(at, _)| *at <= now) {
+                    waker.wake();
+                }
+            }
+        });
+    }
+
+    /// Earliest tick a task waits for, if any.
+    pub fn next_wake(&self) -> Option<u64> {
+        critical_section::with(|cs| {
+            self.wakers
+                .borrow_ref(cs)
+                .iter()
+                .flatten()
+                .map(|(at, _)| *at)
+                .min()
+        })
+    }
+}
+
+impl<const N: usize> Driver for RtcTimeDriver<N> {
+    fn now(&self) -> u64 {
+        critical_section::with(|cs| self.now.borrow(cs).get())
+    }
+
+    fn schedule_wake(&self, at: u64, waker: &Waker) {
+        critical_section::with(|cs| {
+            if at <= self.now.borrow(cs).get() {
+                waker.wake_by_ref();
+                return;
+            }
+
+            let mut wakers = self.wakers.borrow_ref_mut(cs);
+            if let Some((scheduled, _)) = wakers
+                .iter_mut()
+                .flatten()
+                .find(|(_, scheduled)| scheduled.will_wake(waker))
+            {
+                *scheduled = (*scheduled).min(at);
+            } else if let Some(slot) = wakers.iter_mut().find(|slot| slot.is_none()) {
+                *slot = Some((at, waker.clone()));
+            } else {
+                waker.wake_by_ref();
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::task::Wake;
+
+    /// Waker counting its wakeups
+    #[derive(Default)]
+    struct Counter(AtomicU32);
+
+    impl Wake for Counter {
+        fn wake(self: Arc<Self>) {
+            self.wake_by_ref();
+        }
+
+        fn wake_by_ref(self: &Arc<Self>) {
+            self.0.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    fn counter() -> (Arc<Counter>, Waker) {
+        let counter = Arc::new(Counter::default());
+        (counter.clone(), Waker::from(counter))
+    }
+
+    #[test]
+    fn test_edges_advance_time_and_wake_due_tasks() {
+        let driver: RtcTimeDriver<2> = RtcTimeDriver::new(1);
+        let (first, first_waker) = counter();
+        let (second, second_waker) = counter();
+
+        driver.schedule_wake(2 * TICK_HZ, &first_waker);
+        driver.schedule_wake(5 * TICK_HZ, &second_waker);
+        // Rescheduling the same task keeps the earlier time
+        driver.schedule_wake(3 * TICK_HZ, &first_waker);
+        assert_eq!(driver.next_wake(), Some(2 * TICK_HZ));
+
+        driver.on_tick();
+        assert_eq!(driver.now(), TICK_HZ);
+        assert_eq!(first.0.load(Ordering::SeqCst), 0);
+
+        driver.on_tick();
+        assert_eq!(first.0.load(Ordering::SeqCst), 1);
+        assert_eq!(driver.next_wake(), Some(5 * TICK_HZ));
+
+        driver.advance(3 * TICK_HZ);
+        assert_eq!(second.0.load(Ordering::SeqCst), 1);
+        assert_eq!(driver.next_wake(), None);
+    }
+
+    #[test]
+    fn test_past_and_overflowing_wakes_fire_immediately() {
+        let driver: RtcTimeDriver<1> = RtcTimeDriver::new(1);
+        let (past, past_waker) = counter();
+        let (queued, queued_waker) = counter();
+        let (overflow, overflow_waker) = counter();
+
+        driver.schedule_wake(0, &past_waker);
+        assert_eq!(past.0.load(Ordering::SeqCst), 1);
+
+        driver.schedule_wake(TICK_HZ, &queued_waker);
+        driver.schedule_wake(TICK_HZ, &overflow_waker);
+        assert_eq!(overflow.0.load(Ordering::SeqCst), 1);
+        assert_eq!(queued.0.load(Ordering::SeqCst), 0);
+    }
+}
@@ -0,0 +1,355 @@
+//! # Alarm-Backed Timers
+//!
+//! [`RtcTimer`] turns one of the two alarms into a countdown: [`start`]
+//! programs the alarm for the current time plus a duration and enables its
+//! interrupt, so the MCU can sleep until the INT pin wakes it; [`is_expired`]
+//! checks the alarm flag and the time afterwards and [`cancel`] stops the
+//! timer again.
+//! The timer only remembers its deadline, so one timer per alarm can run at
+//! the same time on one driver:
+//!
+//! ```no_run
+//! # use ds3231_rtc::Ds3231;
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! # let mut rtc = Ds3231::new(embedded_hal_mock::eh1::i2c::Mock::new(&[]));
+//! use core::time::Duration;
+//! use ds3231_rtc::{alarm::Alarm, timer::RtcTimer};
+//! # let sleep_until_int_pin = || ();
+//!
+//! let mut timer = RtcTimer::new(Alarm::One);
+//! timer.start(&mut rtc, Duration::from_secs(90))?;
+//!
+//! while !timer.is_expired(&mut rtc)? {
+//!     sleep_until_int_pin();
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Alarm 1 matches to the second. Alarm 2 has minutes resolution, so its
+//! deadline is rounded up to the next whole minute. Both match the date, the
+//! hour and the minute, so durations are limited to less than 28 days, the
+//! shortest month. The INT pin only signals alarms while the square wave
+//! output is disabled.
+//!
+//! A short timer can fall due while the alarm is being programmed, before
+//! the alarm registers hold the deadline or just before its flag is
+//! cleared, so the INT pin never signals it. [`start`] therefore reads the
+//! time again after arming, and [`is_expired`] also reports a timer whose
+//! deadline the clock has reached.
+//!
+//! [`start`]: RtcTimer::start
+//! [`is_expired`]: RtcTimer::is_expired
+//! [`cancel`]: RtcTimer::cancel
+
+use core::time::Duration;
+
+use rtc_hal::datetime::DateTime;
+
+use crate::Ds3231;
+use crate::alarm::Alarm;
+use crate::bcd::to_bcd;
+use crate::calendar;
+use crate::error::Error;
+use crate::hour_mode::encode_hour;
+use crate::interface::RegisterInterface;
+use crate::registers::Register;
+use crate::trace::debug;
+
+/// Longest duration a timer can run, just under 28 days
+pub const MAX_DURATION: Duration = Duration::from_secs(28 * 86_400 - 1);
+
+/// Countdown on one of the alarms
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RtcTimer {
+    alarm: Alarm,
+    deadline: Option<DateTime>,
+    /// The deadline had passed by the time the alarm was armed
+    expired: bool,
+}
+
+impl RtcTimer {
+    /// Timer running on `alarm`, initially stopped.
+    pub const fn new(alarm: Alarm) -> Self {
+        Self {
+            alarm,
+            deadline: None,
+            expired: false,
+        }
+    }
+
+    /// Alarm the timer runs on.
+    pub const fn alarm(&self) -> Alarm {
+        self.alarm
+    }
+
+    /// Time at which the running timer expires.
+    pub const fn deadline(&self) -> Option<DateTime> {
+        self.deadline
+    }
+
+    /// Returns `true` between [`start`](Self::start) and
+    /// [`cancel`](Self::cancel).
+    pub const fn is_running(&self) -> bool {
+        self.deadline.is_some()
+    }
+
+    /// Start the timer, replacing a running one.
+    ///
+    /// `duration` is rounded up to a whole second, at least one, and for
+    /// Alarm 2 the deadline to a whole minute. Fails with
+    /// [`Error::InvalidDuration`] if `duration` exceeds [`MAX_DURATION`].
+    pub fn start<I2C, V>(
+        &mut self,
+        rtc: &mut Ds3231<I2C, V>,
+        duration: Duration,
+    ) -> Result<(), Error<I2C::Error>>
+    where
+        I2C: RegisterInterface,
+    {
+        if duration > MAX_DURATION {
+            return Err(Error::InvalidDuration);
+        }
+        let mut seconds = duration.as_secs() + u64::from(duration.subsec_nanos() > 0);
+        seconds = seconds.max(1);
+
+        let now = rtc.get_datetime()?;
+        let mut deadline = calendar::add_seconds(&now, seconds as i64)?;
+        if self.alarm == Alarm::Two && deadline.second() != 0 {
+            deadline = calendar::add_seconds(&deadline, 60 - deadline.second() as i64)?;
+        }
+
+        // Stopped until the alarm holds the new deadline
+        *self = Self::new(self.alarm);
+        arm_alarm(rtc, self.alarm, &deadline)?;
+        self.deadline = Some(deadline);
+        // The match may have come before the alarm was written or before
+        // its flag was cleared
+        self.expired = reached(rtc, &deadline)?;
+        debug!("timer on {:?}: {} s", self.alarm, seconds);
+        Ok(())
+    }
+
+    /// Returns `true` once the running timer has expired, i.e. its alarm
+    /// fired or the clock reached the deadline.
+    ///
+    /// A stopped timer never expires.
+    pub fn is_expired<I2C, V>(&self, rtc: &mut Ds3231<I2C, V>) -> Result<bool, Error<I2C::Error>>
+    where
+        I2C: RegisterInterface,
+    {
+        let Some(deadline) = self.deadline else {
+            return Ok(false);
+        };
+        if self.expired || rtc.alarm_fired(self.alarm)? {
+            return Ok(true);
+        }
+        reached(rtc, &deadline)
+    }
+
+    /// Stop the timer, disabling the alarm interrupt and clearing its flag.
+    pub fn cancel<I2C, V>(&mut self, rtc: &mut Ds3231<I2C, V>) -> Result<(), Error<I2C::Error>>
+    where
+        I2C: RegisterInterface,
+    {
+        rtc.set_alarm_interrupt(self.alarm, false)?;
+        rtc.clear_alarm_flag(self.alarm)?;
+        self.deadline = None;
+        self.expired = false;
+        Ok(())
+    }
+}
+
+/// Returns `true` if the RTC time is at or past `deadline`.
+fn reached<I2C, V>(rtc: &mut Ds3231<I2C, V>, deadline: &DateTime) -> Result<bool, Error<I2C::Error>>
+where
+    I2C: RegisterInterface,
+{
+    Ok(rtc.get_unix_timestamp()? >= calendar::to_unix_timestamp(deadline))
+}
+
+/// Program `alarm` to match the date and time of `deadline` (Alarm 2
+/// ignores the seconds), clear its flag and enable its interrupt.
+pub(crate) fn arm_alarm<I2C, V>(
+    rtc: &mut Ds3231<I2C, V>,
+    alarm: Alarm,
+    deadline: &DateTime,
+) -> Result<(), Error<I2C::Error>>
+where
+    I2C: RegisterInterface,
+{
+    let date = to_bcd(deadline.day_of_month());
+    let hours = encode_hour(deadline.hour(), rtc.hour_mode);
+    let minutes = to_bcd(deadline.minute());
+    match alarm {
+        Alarm::One => rtc.write_raw_bytes(&[
+            Register::Alarm1Seconds.addr(),
+            to_bcd(deadline.second()),
+            minutes,
+            hours,
+            date,
+        ])?,
+        Alarm::Two => {
+            rtc.write_raw_bytes(&[Register::Alarm2Minutes.addr(), minutes, hours, date])?
+        }
+    }
+
+    // The flag may still be set from an earlier match
+    rtc.clear_alarm_flag(alarm)?;
+    rtc.set_alarm_interrupt(alarm, true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
+
+    const DS3231_ADDR: u8 = 0x68;
+
+    /// 2025-08-31 23:59:30
+    fn time_read() -> I2cTransaction {
+        I2cTransaction::write_read(
+            DS3231_ADDR,
+            vec![Register::Seconds.addr()],
+            vec![0x30, 0x59, 0x23, 0x01, 0x31, 0x08, 0x25],
+        )
+    }
+
+    fn register_update(register: Register, before: u8, after: u8) -> [I2cTransaction; 2] {
+        [
+            I2cTransaction::write_read(DS3231_ADDR, vec![register.addr()], vec![before]),
+            I2cTransaction::write(DS3231_ADDR, vec![register.addr(), after]),
+        ]
+    }
+
+    #[test]
+    fn test_alarm_1_timer() {
+        let mut expectations = vec![
+            time_read(),
+            // 2025-09-01 00:01:00
+            I2cTransaction::write(
+                DS3231_ADDR,
+                vec![Register::Alarm1Seconds.addr(), 0x00, 0x01, 0x00, 0x01],
+            ),
+        ];
+        expectations.extend(register_update(Register::Status, 0x89, 0x8A));
+        expectations.extend(register_update(Register::Control, 0x1C, 0x1D));
+        expectations.extend([
+            time_read(),
+            I2cTransaction::write_read(DS3231_ADDR, vec![Register::Status.addr()], vec![0x88]),
+            time_read(),
+            I2cTransaction::write_read(DS3231_ADDR, vec![Register::Status.addr()], vec![0x89]),
+        ]);
+        expectations.extend(register_update(Register::Control, 0x1D, 0x1C));
+        expectations.extend(register_update(Register::Status, 0x89, 0x8A));
+        let mut ds3231 = Ds3231::new(I2cMock::new(&expectations));
+
+        let mut timer = RtcTimer::new(Alarm::One);
+        assert!(!timer.is_expired(&mut ds3231).unwrap());
+
+        timer
+            .start(&mut ds3231, Duration::from_millis(89_500))
+            .unwrap();
+        assert_eq!(
+            timer.deadline(),
+            Some(DateTime::new(2025, 9, 1, 0, 1, 0).unwrap())
+        );
+        assert!(!timer.is_expired(&mut ds3231).unwrap());
+        assert!(timer.is_expired(&mut ds3231).unwrap());
+
+        timer.cancel(&mut ds3231).unwrap();
+        assert!(!timer.is_running());
+
+        ds3231.release_i2c().done();
+    }
+
+    #[test]
+    fn test_alarm_2_rounds_up_to_the_minute() {
+        let mut expectations = vec![
+            time_read(),
+            // 2025-09-01 00:00:00
+            I2cTransaction::write(
+                DS3231_ADDR,
+                vec![Register::Alarm2Minutes.addr(), 0x00, 0x00, 0x01],
+            ),
+        ];
+        expectations.extend(register_update(Register::Status, 0x8A, 0x89));
+        expectations.extend(register_update(Register::Control, 0x1C, 0x1E));
+        expectations.push(time_read());
+        let mut ds3231 = Ds3231::new(I2cMock::new(&expectations));
+
+        let mut timer = RtcTimer::new(Alarm::Two);
+        timer.start(&mut ds3231, Duration::ZERO).unwrap();
+        assert_eq!(
+            timer.deadline(),
+            Some(DateTime::new(2025, 9, 1, 0, 0, 0).unwrap())
+        );
+
+        assert_eq!(
+            timer.start(&mut ds3231, MAX_DURATION + Duration::from_secs(1)),
+            Err(Error::InvalidDuration)
+        );
+        assert!(timer.is_running());
+
+        ds3231.release_i2c().done();
+    }
+
+    #[test]
+    fn test_deadline_passed_while_arming() {
+        let mut expectations = vec![
+            time_read(),
+            // 2025-08-31 23:59:31
+            I2cTransaction::write(
+                DS3231_ADDR,
+                vec![Register::Alarm1Seconds.addr(), 0x31, 0x59, 0x23, 0x31],
+            ),
+            // The match came before the flag was cleared
+            I2cTransaction::write_read(DS3231_ADDR, vec![Register::Status.addr()], vec![0x89]),
+            I2cTransaction::write(DS3231_ADDR, vec![Register::Status.addr(), 0x8A]),
+        ];
+        expectations.extend(register_update(Register::Control, 0x1C, 0x1D));
+        expectations.push(I2cTransaction::write_read(
+            DS3231_ADDR,
+            vec![Register::Seconds.addr()],
+            vec![0x31, 0x59, 0x23, 0x01, 0x31, 0x08, 0x25],
+        ));
+        let mut ds3231 = Ds3231::new(I2cMock::new(&expectations));
+
+        let mut timer = RtcTimer::new(Alarm::One);
+        timer.start(&mut ds3231, Duration::from_secs(1)).unwrap();
+        // Expired without waiting for an interrupt that won't come
+        assert!(timer.is_expired(&mut ds3231).unwrap());
+
+        ds3231.release_i2c().done();
+    }
+
+    #[test]
+    fn test_is_expired_once_clock_reaches_deadline() {
+        let mut expectations = vec![
+            time_read(),
+            I2cTransaction::write(
+                DS3231_ADDR,
+                vec![Register::Alarm1Seconds.addr(), 0x40, 0x59, 0x23, 0x31],
+            ),
+            I2cTransaction::write_read(DS3231_ADDR, vec![Register::Status.addr()], vec![0x88]),
+        ];
+        expectations.extend(register_update(Register::Control, 0x1C, 0x1D));
+        expectations.extend([
+            time_read(),
+            // Flag clear, but the clock is past the deadline
+            I2cTransaction::write_read(DS3231_ADDR, vec![Register::Status.addr()], vec![0x88]),
+            I2cTransaction::write_read(
+                DS3231_ADDR,
+                vec![Register::Seconds.addr()],
+                vec![0x41, 0x59, 0x23, 0x01, 0x31, 0x08, 0x25],
+            ),
+        ]);
+        let mut ds3231 = Ds3231::new(I2cMock::new(&expectations));
+
+        let mut timer = RtcTimer::new(Alarm::One);
+        timer.start(&mut ds3231, Duration::from_secs(10)).unwrap();
+        assert!(timer.is_expired(&mut ds3231).unwrap());
+
+        ds3231.release_i2c().done();
+    }
+}
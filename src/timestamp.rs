@@ -0,0 +1,170 @@
+//! # defmt Timestamps from the DS3231
+//!
+//! This module (enabled with the `defmt-timestamp` feature) lets defmt log
+//! lines carry RTC time, expressed as seconds since the Unix epoch.
+//!
+//! Reading the RTC for every log line would mean an I2C transaction inside the
+//! logger, which is both slow and unsafe from interrupt context. Instead,
+//! [`RtcTimestamp`] stores one RTC reading together with the value of a
+//! free-running tick counter at that moment, and interpolates from the tick
+//! counter afterwards. Call [`RtcTimestamp::sync`] at startup and periodically
+//! (e.g. once a minute) to correct drift between the tick source and the RTC.
+//!
+//! ```ignore
+//! use ds3231_rtc::timestamp::RtcTimestamp;
+//!
+//! fn ticks() -> u64 { /* read a free-running 1 MHz timer */ }
+//!
+//! static RTC_TIME: RtcTimestamp = RtcTimestamp::new(1_000_000, ticks);
+//!
+//! defmt::timestamp!("{=u64:ts}", RTC_TIME.now());
+//!
+//! // In main, once the RTC is initialized:
+//! RTC_TIME.sync(&mut rtc)?;
+//! ```
+
+use core::cell::Cell;
+
+use critical_section::Mutex;
+use rtc_hal::rtc::Rtc;
+
+use crate::calendar;
+
+#[derive(Clone, Copy)]
+struct Anchor {
+    /// Seconds since the Unix epoch at the time of the last sync
+    epoch_seconds: u64,
+    /// Tick counter value at the time of the last sync
+    ticks: u64,
+}
+
+/// Interpolated RTC clock for defmt timestamps.
+///
+/// See the [module documentation](self) for usage.
+pub struct RtcTimestamp {
+    tick_hz: u32,
+    ticks: fn() -> u64,
+    anchor: Mutex<Cell<Option<Anchor>>>,
+}
+
+impl RtcTimestamp {
+    /// Create a new timestamp provider.
+    ///
+    /// # Parameters
+    /// * `tick_hz` - Frequency of the tick source in Hz
+    /// * `ticks` - Function returning the current value of a free-running tick counter
+    ///
+    /// # Panics
+    ///
+    /// Panics if `tick_hz` is zero.
+    pub const fn new(tick_hz: u32, ticks: fn() -> u64) -> Self {
+        assert!(tick_hz > 0, "tick source needs a non-zero frequency");
+        Self {
+            tick_hz,
+            ticks,
+            anchor: Mutex::new(Cell::new(None)),
+        }
+    }
+
+    /// Read the RTC once and re-anchor the interpolation to it.
+    pub fn sync<RTC: Rtc>(&self, rtc: &mut RTC) -> Result<(), RTC::Error> {
+        let datetime = rtc.get_datetime()?;
+        let ticks = (self.ticks)();
+        self.set_anchor(calendar::to_unix_timestamp(&datetime), ticks);
+        Ok(())
+    }
+
+    /// Anchor the interpolation to a known epoch time without reading the RTC.
+    ///
+    /// Useful right after setting the RTC, when the time is already known.
+    pub fn sync_to(&self, epoch_seconds: u64) {
+        let ticks = (self.ticks)();
+        self.set_anchor(epoch_seconds, ticks);
+    }
+
+    /// Returns `true` once the provider has been synced at least once.
+    pub fn is_synced(&self) -> bool {
+        critical_section::with(|cs| self.anchor.borrow(cs).get().is_some())
+    }
+
+    /// Current time in seconds since the Unix epoch.
+    ///
+    /// Returns 0 until the first [`sync`](Self::sync), so early log lines are
+    /// still emitted.
+    pub fn now(&self) -> u64 {
+        let anchor = critical_section::with(|cs| self.anchor.borrow(cs).get());
+        match anchor {
+            Some(anchor) => {
+                let elapsed = (self.ticks)().wrapping_sub(anchor.ticks);
+                // `new` rules out a zero frequency; `max` lets the compiler see it
+                anchor
+                    .epoch_seconds
+                    .saturating_add(elapsed / self.tick_hz.max(1) as u64)
+            }
+            None => 0,
+        }
+    }
+
+    fn set_anchor(&self, epoch_seconds: u64, ticks: u64) {
+        critical_section::with(|cs| {
+            self.anchor.borrow(cs).set(Some(Anchor {
+                epoch_seconds,
+                ticks,
+            }))
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Ds3231;
+    use crate::registers::Register;
+    use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTrans};
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    static TICKS: AtomicU64 = AtomicU64::new(0);
+
+    fn ticks() -> u64 {
+        TICKS.load(Ordering::SeqCst)
+    }
+
+    #[test]
+    fn test_timestamp_interpolation() {
+        let clock = RtcTimestamp::new(1000, ticks);
+        assert!(!clock.is_synced());
+        assert_eq!(clock.now(), 0);
+
+        TICKS.store(5_000, Ordering::SeqCst);
+
+        // 2025-08-21 14:30:00
+        let data = [0x00, 0x30, 0x14, 0x05, 0x21, 0x08, 0x25];
+        let expectations = [I2cTrans::write_read(
+            0x68,
+            vec![Register::Seconds.addr()],
+            data.to_vec(),
+        )];
+        let mut i2c = I2cMock::new(&expectations);
+        let mut ds3231 = Ds3231::new(&mut i2c);
+
+        clock.sync(&mut ds3231).unwrap();
+        assert!(clock.is_synced());
+        assert_eq!(clock.now(), 1_755_786_600);
+
+        // 2.5 seconds of ticks later, no I2C traffic is needed
+        TICKS.store(7_500, Ordering::SeqCst);
+        assert_eq!(clock.now(), 1_755_786_602);
+
+        clock.sync_to(100);
+        TICKS.store(10_500, Ordering::SeqCst);
+        assert_eq!(clock.now(), 103);
+
+        i2c.done();
+    }
+
+    #[test]
+    #[should_panic(expected = "non-zero frequency")]
+    fn test_zero_tick_rate_is_rejected() {
+        RtcTimestamp::new(0, ticks);
+    }
+}
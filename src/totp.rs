@@ -0,0 +1,310 @@
+//! # Time-Based One-Time Passwords
+//!
+//! An RFC 6238 TOTP generator (enabled with the `totp` feature) driven by the
+//! DS3231, for hardware tokens and door locks that have no other trustworthy
+//! time source. Codes use HMAC-SHA1, the algorithm every authenticator app
+//! supports.
+//!
+//! The RTC must hold UTC. A code is valid for one time step (30 seconds by
+//! default); [`Totp::verify`] accepts a small window of neighbouring steps to
+//! tolerate clock drift between token and verifier.
+//!
+//! ```no_run
+//! # use ds3231_rtc::Ds3231;
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! # let mut rtc = Ds3231::new(embedded_hal_mock::eh1::i2c::Mock::new(&[]));
+//! use ds3231_rtc::totp::Totp;
+//!
+//! let totp = Totp::new(b"12345678901234567890");
+//! let code = rtc.totp(&totp)?;
+//! # Ok(())
+//! # }
+//! ```
+
+use sha1::{Digest, Sha1};
+
+use crate::{Ds3231, error::Error, interface::RegisterInterface};
+
+/// Default time step in seconds
+pub const DEFAULT_STEP: u64 = 30;
+
+/// Default number of code digits
+pub const DEFAULT_DIGITS: u8 = 6;
+
+/// SHA-1 block size, the HMAC key length
+const BLOCK_SIZE: usize = 64;
+
+/// TOTP parameters for one shared secret
+///
+/// `Debug` prints the secret as `<redacted>`.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct Totp<'a> {
+    secret: &'a [u8],
+    digits: u8,
+    step: u64,
+    t0: u64,
+}
+
+impl core::fmt::Debug for Totp<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Totp")
+            .field("secret", &"<redacted>")
+            .field("digits", &self.digits)
+            .field("step", &self.step)
+            .field("t0", &self.t0)
+            .finish()
+    }
+}
+
+impl<'a> Totp<'a> {
+    /// TOTP with the given raw (not Base32-encoded) secret, 6 digits and a
+    /// 30 second step starting at the Unix epoch.
+    pub const fn new(secret: &'a [u8]) -> Self {
+        Self {
+            secret,
+            digits: DEFAULT_DIGITS,
+            step: DEFAULT_STEP,
+            t0: 0,
+        }
+    }
+
+    /// Set the number of code digits (6-9).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `digits` is outside 6-9.
+    pub const fn with_digits(mut self, digits: u8) -> Self {
+        assert!(digits >= 6 && digits <= 9, "TOTP digits must be 6-9");
+        self.digits = digits;
+        self
+    }
+
+    /// Set the time step in seconds.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `step` is zero.
+    pub const fn with_step(mut self, step: u64) -> Self {
+        assert!(step > 0, "TOTP step must be non-zero");
+        self.step = step;
+        self
+    }
+
+    /// Set the Unix time at which counting starts (`T0`, usually 0).
+    pub const fn with_t0(mut self, t0: u64) -> Self {
+        self.t0 = t0;
+        self
+    }
+
+    /// Number of code digits
+    pub const fn digits(&self) -> u8 {
+        self.digits
+    }
+
+    /// Time step counter for the given Unix time.
+    pub const fn counter(&self, unix_time: u64) -> u64 {
+        unix_time.saturating_sub(self.t0) / self.step()
+    }
+
+    /// Seconds until the code for `unix_time` expires.
+    pub const fn seconds_remaining(&self, unix_time: u64) -> u64 {
+        self.step() - unix_time.saturating_sub(self.t0) % self.step()
+    }
+
+    /// The time step; `with_step` keeps it non-zero, the check lets the
+    /// compiler see it.
+    const fn step(&self) -> u64 {
+        if self.step > 0 { self.step } else { 1 }
+    }
+
+    /// HOTP value (RFC 4226) for a counter.
+    pub fn code_for_counter(&self, counter: u64) -> u32 {
+        let hash = self.hmac_sha1(&counter.to_be_bytes());
+
+        // Dynamic truncation
+        let offset = (hash[19] & 0x0F) as usize;
+        let binary = u32::from_be_bytes([
+            hash[offset] & 0x7F,
+            hash[offset + 1],
+            hash[offset + 2],
+            hash[offset + 3],
+        ]);
+        binary % self.modulus()
+    }
+
+    /// `10^digits`, spelled out so the compiler sees it is non-zero.
+    const fn modulus(&self) -> u32 {
+        match self.digits {
+            6 => 1_000_000,
+            7 => 10_000_000,
+            8 => 100_000_000,
+            _ => 1_000_000_000,
+        }
+    }
+
+    /// HMAC-SHA1 (RFC 2104) of `message` keyed with the secret.
+    fn hmac_sha1(&self, message: &[u8]) -> [u8; 20] {
+        // Longer keys are hashed, shorter ones padded with zeros
+        let mut key = [0u8; BLOCK_SIZE];
+        if self.secret.len() > BLOCK_SIZE {
+            key[..20].copy_from_slice(&Sha1::digest(self.secret));
+        } else {
+            key[..self.secret.len()].copy_from_slice(self.secret);
+        }
+
+        let inner = Sha1::new()
+            .chain_update(key.map(|b| b ^ 0x36))
+            .chain_update(message)
+            .finalize();
+        Sha1::new()
+            .chain_update(key.map(|b| b ^ 0x5C))
+            .chain_update(inner)
+            .finalize()
+            .into()
+    }
+
+    /// TOTP code for the given Unix time.
+    pub fn generate(&self, unix_time: u64) -> u32 {
+        self.code_for_counter(self.counter(unix_time))
+    }
+
+    /// Check a code against the given Unix time, accepting codes from up to
+    /// `window` steps before or after the current one.
+    ///
+    /// Every code in the window is compared, without branching on the
+    /// result, so the time taken doesn't reveal which step matched.
+    pub fn verify(&self, code: u32, unix_time: u64, window: u8) -> bool {
+        let counter = self.counter(unix_time);
+        let first = counter.saturating_sub(window as u64);
+        let last = counter.saturating_add(window as u64);
+        let matched = (first..=last).fold(0u32, |matched, c| {
+            matched | ct_eq(self.code_for_counter(c), code)
+        });
+        matched == 1
+    }
+}
+
+/// 1 if `a == b`, else 0, computed without branches.
+fn ct_eq(a: u32, b: u32) -> u32 {
+    let diff = a ^ b;
+    // The top bit of `diff | -diff` is set exactly when `diff` is non-zero
+    ((diff | diff.wrapping_neg()) >> 31) ^ 1
+}
+
+impl<I2C, V> Ds3231<I2C, V>
+where
+    I2C: RegisterInterface,
+{
+    /// Generate the current TOTP code from the RTC time (assumed UTC).
+    pub fn totp(&mut self, totp: &Totp<'_>) -> Result<u32, Error<I2C::Error>> {
+        Ok(totp.generate(self.get_unix_timestamp()?))
+    }
+
+    /// Verify a TOTP code against the RTC time (assumed UTC).
+    ///
+    /// See [`Totp::verify`] for the meaning of `window`.
+    pub fn verify_totp(
+        &mut self,
+        totp: &Totp<'_>,
+        code: u32,
+        window: u8,
+    ) -> Result<bool, Error<I2C::Error>> {
+        Ok(totp.verify(code, self.get_unix_timestamp()?, window))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registers::Register;
+    use crate::variant::Ds3232Variant;
+    use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTrans};
+
+    const SECRET: &[u8] = b"12345678901234567890";
+
+    #[test]
+    fn test_rfc6238_sha1_vectors() {
+        let totp = Totp::new(SECRET).with_digits(8);
+        assert_eq!(totp.generate(59), 94_287_082);
+        assert_eq!(totp.generate(1_111_111_109), 7_081_804);
+        assert_eq!(totp.generate(1_111_111_111), 14_050_471);
+        assert_eq!(totp.generate(1_234_567_890), 89_005_924);
+        assert_eq!(totp.generate(2_000_000_000), 69_279_037);
+        assert_eq!(totp.generate(20_000_000_000), 65_353_130);
+    }
+
+    #[test]
+    fn test_six_digits_and_window() {
+        let totp = Totp::new(SECRET);
+        assert_eq!(totp.generate(59), 287_082);
+
+        // Code from the previous step is accepted only with a window
+        let previous = totp.generate(1_111_111_109 - 30);
+        assert!(!totp.verify(previous, 1_111_111_109, 0));
+        assert!(totp.verify(previous, 1_111_111_109, 1));
+        assert!(totp.verify(totp.generate(1_111_111_109), 1_111_111_109, 0));
+    }
+
+    #[test]
+    fn test_long_secret_and_debug() {
+        // RFC 2202 test case 6: an 80-byte key is hashed first
+        let key = [0xAA; 80];
+        let totp = Totp::new(&key);
+        assert_eq!(
+            totp.hmac_sha1(b"Test Using Larger Than Block-Size Key - Hash Key First"),
+            [
+                0xAA, 0x4A, 0xE5, 0xE1, 0x52, 0x72, 0xD0, 0x0E, 0x95, 0x70, 0x56, 0x37, 0xCE, 0x8A,
+                0x3B, 0x55, 0xED, 0x40, 0x21, 0x12
+            ]
+        );
+
+        let debug = format!("{:?}", Totp::new(SECRET));
+        assert!(debug.contains("<redacted>"));
+        assert!(!debug.contains("49"));
+        assert_eq!(ct_eq(7, 7), 1);
+        assert_eq!(ct_eq(7, 0x8000_0007), 0);
+    }
+
+    #[test]
+    fn test_counter_and_remaining() {
+        let totp = Totp::new(SECRET);
+        assert_eq!(totp.counter(59), 1);
+        assert_eq!(totp.seconds_remaining(59), 1);
+        assert_eq!(totp.seconds_remaining(60), 30);
+
+        let totp = totp.with_step(60).with_t0(30);
+        assert_eq!(totp.counter(89), 0);
+        assert_eq!(totp.counter(90), 1);
+    }
+
+    #[test]
+    fn test_totp_from_rtc() {
+        // 2009-02-13 23:31:30 UTC = 1234567890
+        let expectations = [I2cTrans::write_read(
+            0x68,
+            vec![Register::Seconds.addr()],
+            vec![0x30, 0x31, 0x23, 0x06, 0x13, 0x02, 0x09],
+        )];
+        let mut ds3231 = Ds3231::new(I2cMock::new(&expectations));
+
+        let totp = Totp::new(SECRET).with_digits(8);
+        assert_eq!(ds3231.totp(&totp).unwrap(), 89_005_924);
+
+        ds3231.release_i2c().done();
+    }
+
+    #[test]
+    fn test_verify_totp_on_typed_variant() {
+        let expectations = [I2cTrans::write_read(
+            0x68,
+            vec![Register::Seconds.addr()],
+            vec![0x30, 0x31, 0x23, 0x06, 0x13, 0x02, 0x09],
+        )];
+        let mut ds3232: Ds3231<_, Ds3232Variant> = Ds3231::new_typed(I2cMock::new(&expectations));
+
+        let totp = Totp::new(SECRET).with_digits(8);
+        assert!(ds3232.verify_totp(&totp, 89_005_924, 0).unwrap());
+
+        ds3232.release_i2c().done();
+    }
+}
@@ -0,0 +1,118 @@
+//! # Tracing
+//!
+//! With the `log` feature the driver emits [`log`](https://crates.io/crates/log)
+//! records, so the traffic can be followed on a host or Linux board without
+//! a logic analyzer:
+//!
+//! - `trace`: every register access with its values, e.g.
+//!   `read 0x00: [00, 30, 14, 05, 21, 08, 25]` or
+//!   `modify 0x0e: 0x1c -> 0x04`
+//! - `debug`: each high-level operation with its result, e.g.
+//!   `get_datetime: 2025-08-21T14:30:00`, failed transfers and retries
+//!
+//! Records use the module path as target (`ds3231_rtc::datetime`, ...), so
+//! `RUST_LOG=ds3231_rtc=trace` with `env_logger` selects the whole driver.
+//! Without the feature the macros below expand to nothing, formatting
+//! arguments included.
+
+/// Register-level `log::trace!` record, if the `log` feature is enabled
+macro_rules! trace {
+    ($($arg:tt)+) => {
+        #[cfg(feature = "log")]
+        ::log::trace!($($arg)+);
+    };
+}
+
+/// Operation-level `log::debug!` record, if the `log` feature is enabled
+macro_rules! debug {
+    ($($arg:tt)+) => {
+        #[cfg(feature = "log")]
+        ::log::debug!($($arg)+);
+    };
+}
+
+pub(crate) use {debug, trace};
+
+#[cfg(all(test, feature = "log"))]
+mod tests {
+    use std::sync::Mutex;
+    use std::thread::{self, ThreadId};
+
+    use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
+    use log::{Level, LevelFilter, Log, Metadata, Record};
+    use rtc_hal::datetime::DateTime;
+
+    use crate::Ds3231;
+    use crate::registers::Register;
+
+    /// Logger keeping the records of each test thread apart
+    struct Recorder(Mutex<Vec<(ThreadId, Level, String)>>);
+
+    impl Log for Recorder {
+        fn enabled(&self, _: &Metadata) -> bool {
+            true
+        }
+
+        fn log(&self, record: &Record) {
+            self.0.lock().unwrap().push((
+                thread::current().id(),
+                record.level(),
+                record.args().to_string(),
+            ));
+        }
+
+        fn flush(&self) {}
+    }
+
+    static RECORDER: Recorder = Recorder(Mutex::new(Vec::new()));
+
+    fn records() -> Vec<(Level, String)> {
+        let id = thread::current().id();
+        RECORDER
+            .0
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(thread, ..)| *thread == id)
+            .map(|(_, level, message)| (*level, message.clone()))
+            .collect()
+    }
+
+    #[test]
+    fn test_records_accesses_and_operations() {
+        log::set_logger(&RECORDER).unwrap();
+        log::set_max_level(LevelFilter::Trace);
+
+        let expectations = [
+            I2cTransaction::write_read(
+                0x68,
+                vec![Register::Seconds.addr()],
+                vec![0x00, 0x30, 0x14, 0x05, 0x21, 0x08, 0x25],
+            ),
+            I2cTransaction::write_read(0x68, vec![Register::Control.addr()], vec![0x1C]),
+            I2cTransaction::write(0x68, vec![Register::Control.addr(), 0x9C]),
+        ];
+        let mut ds3231 = Ds3231::new(I2cMock::new(&expectations));
+
+        assert_eq!(
+            ds3231.get_datetime(),
+            Ok(DateTime::new(2025, 8, 21, 14, 30, 0).unwrap())
+        );
+        ds3231.halt_clock().unwrap();
+
+        assert_eq!(
+            records(),
+            [
+                (
+                    Level::Trace,
+                    "read 0x00: [00, 30, 14, 05, 21, 08, 25]".into()
+                ),
+                (Level::Debug, "get_datetime: 2025-08-21T14:30:00".into()),
+                (Level::Trace, "modify 0x0e: 0x1c -> 0x9c".into()),
+                (Level::Debug, "halt_clock".into()),
+            ]
+        );
+
+        ds3231.release_i2c().done();
+    }
+}
@@ -0,0 +1,288 @@
+//! # Uptime Tracking
+//!
+//! [`UptimeTracker`] stamps the boot time from the RTC and keeps the time of
+//! the very first boot in persistent storage, so field diagnostics can ask
+//! how long the firmware has been running and how long the unit has been in
+//! service, across resets and firmware updates. Both are measured on the
+//! RTC, not on the MCU's own timers that restart with every reset.
+//!
+//! The tracker stores [`UPTIME_RECORD_SIZE`] bytes: the first boot and the
+//! latest boot as big-endian Unix timestamps, followed by a CRC-16. They can
+//! live in the battery-backed SRAM of a DS3232 or DS3234 (or any other
+//! [`RtcNvram`]) with [`start_nvram`](UptimeTracker::start_nvram), or in
+//! storage of the application's choosing behind [`UptimeStore`], such as an
+//! EEPROM next to a plain DS3231:
+//!
+//! ```no_run
+//! # use ds3231_rtc::ds3232::Ds3232;
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! # let mut rtc = Ds3232::new(embedded_hal_mock::eh1::i2c::Mock::new(&[]));
+//! use ds3231_rtc::uptime::UptimeTracker;
+//!
+//! // Once per startup
+//! let uptime = UptimeTracker::start_nvram(&mut rtc, 0xE0)?;
+//!
+//! let running = uptime.uptime(&mut rtc)?;
+//! let in_service = uptime.time_since_first_boot(&mut rtc)?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! A missing or corrupt record, e.g. after the backup battery ran flat, makes
+//! the current boot the first one.
+
+use core::time::Duration;
+
+use rtc_hal::nvram::RtcNvram;
+use rtc_hal::rtc::Rtc;
+
+use crate::blob::{CRC16_INIT, crc16};
+use crate::calendar;
+
+/// Bytes used by the boot record of an [`UptimeTracker`]
+pub const UPTIME_RECORD_SIZE: usize = 10;
+
+/// Persistent storage for the boot record of an [`UptimeTracker`]
+pub trait UptimeStore {
+    /// Error type for storage access
+    type Error;
+
+    /// Read the stored record into `record`.
+    ///
+    /// Storage that was never written may return any contents; the record's
+    /// CRC rejects them.
+    fn load(&mut self, record: &mut [u8; UPTIME_RECORD_SIZE]) -> Result<(), Self::Error>;
+
+    /// Replace the stored record.
+    fn store(&mut self, record: &[u8; UPTIME_RECORD_SIZE]) -> Result<(), Self::Error>;
+}
+
+/// Error from [`UptimeTracker::start`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum UptimeError<R, S> {
+    /// Reading the clock failed
+    Rtc(R),
+    /// Accessing the storage failed
+    Store(S),
+}
+
+impl<R, S> core::fmt::Display for UptimeError<R, S>
+where
+    R: core::fmt::Display,
+    S: core::fmt::Display,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            UptimeError::Rtc(e) => write!(f, "RTC access failed: {e}"),
+            UptimeError::Store(e) => write!(f, "Uptime storage access failed: {e}"),
+        }
+    }
+}
+
+impl<R, S> core::error::Error for UptimeError<R, S>
+where
+    R: core::fmt::Debug + core::fmt::Display,
+    S: core::fmt::Debug + core::fmt::Display,
+{
+}
+
+/// Boot times of the current and earlier boots, as Unix timestamps
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct UptimeTracker {
+    boot: u32,
+    first_boot: u32,
+    previous_boot: Option<u32>,
+}
+
+impl UptimeTracker {
+    /// Stamp the current boot, keeping the record in `store`.
+    ///
+    /// Call once per startup.
+    pub fn start<R: Rtc, S: UptimeStore>(
+        rtc: &mut R,
+        store: &mut S,
+    ) -> Result<Self, UptimeError<R::Error, S::Error>> {
+        let now = now(rtc).map_err(UptimeError::Rtc)?;
+        let mut record = [0u8; UPTIME_RECORD_SIZE];
+        store.load(&mut record).map_err(UptimeError::Store)?;
+
+        let tracker = Self::boot(now, decode(&record));
+        store.store(&tracker.encode()).map_err(UptimeError::Store)?;
+        Ok(tracker)
+    }
+
+    /// Stamp the current boot, keeping the record at NVRAM `offset`.
+    ///
+    /// Call once per startup.
+    pub fn start_nvram<R: RtcNvram>(rtc: &mut R, offset: u8) -> Result<Self, R::Error> {
+        let now = now(rtc)?;
+        let mut record = [0u8; UPTIME_RECORD_SIZE];
+        rtc.read_nvram(offset, &mut record)?;
+
+        let tracker = Self::boot(now, decode(&record));
+        rtc.write_nvram(offset, &tracker.encode())?;
+        Ok(tracker)
+    }
+
+    /// Unix timestamp of the current boot.
+    pub const fn boot_time(&self) -> u32 {
+        self.boot
+    }
+
+    /// Unix timestamp of the first boot on record.
+    pub const fn first_boot_time(&self) -> u32 {
+        self.first_boot
+    }
+
+    /// Unix timestamp of the boot before the current one, if recorded.
+    pub const fn previous_boot_time(&self) -> Option<u32> {
+        self.previous_boot
+    }
+
+    /// Time since the current boot.
+    ///
+    /// Zero if the clock has since been set to before the boot.
+    pub fn uptime<R: Rtc>(&self, rtc: &mut R) -> Result<Duration, R::Error> {
+        Ok(since(self.boot, now(rtc)?))
+    }
+
+    /// Time since the first boot on record.
+    ///
+    /// Zero if the clock has since been set to before that boot.
+    pub fn time_since_first_boot<R: Rtc>(&self, rtc: &mut R) -> Result<Duration, R::Error> {
+        Ok(since(self.first_boot, now(rtc)?))
+    }
+
+    fn boot(now: u32, stored: Option<(u32, u32)>) -> Self {
+        Self {
+            boot: now,
+            first_boot: stored.map_or(now, |(first, _)| first),
+            previous_boot: stored.map(|(_, latest)| latest),
+        }
+    }
+
+    /// Stored form: first boot, latest boot and the CRC over both.
+    fn encode(&self) -> [u8; UPTIME_RECORD_SIZE] {
+        let mut record = [0u8; UPTIME_RECORD_SIZE];
+        record[..4].copy_from_slice(&self.first_boot.to_be_bytes());
+        record[4..8].copy_from_slice(&self.boot.to_be_bytes());
+        let crc = crc16(CRC16_INIT, &record[..8]).to_be_bytes();
+        record[8..].copy_from_slice(&crc);
+        record
+    }
+}
+
+/// First and latest boot from a stored record, if it is intact.
+fn decode(record: &[u8; UPTIME_RECORD_SIZE]) -> Option<(u32, u32)> {
+    let crc = u16::from_be_bytes([record[8], record[9]]);
+    if crc16(CRC16_INIT, &record[..8]) != crc {
+        return None;
+    }
+    let first = u32::from_be_bytes([record[0], record[1], record[2], record[3]]);
+    let latest = u32::from_be_bytes([record[4], record[5], record[6], record[7]]);
+    Some((first, latest))
+}
+
+fn now<R: Rtc>(rtc: &mut R) -> Result<u32, R::Error> {
+    let now = rtc.get_datetime()?;
+    Ok(calendar::to_unix_timestamp(&now).min(u32::MAX as u64) as u32)
+}
+
+fn since(then: u32, now: u32) -> Duration {
+    Duration::from_secs(now.saturating_sub(then) as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blob::tests::MemNvram;
+    use crate::error::Error;
+    use rtc_hal::datetime::DateTime;
+
+    /// 2025-08-21 14:30:00 UTC, the time of a fresh `MemNvram`
+    const BOOT: u32 = 1_755_786_600;
+
+    /// Storage that can fail, standing in for an EEPROM
+    #[derive(Default)]
+    struct MemStore {
+        record: [u8; UPTIME_RECORD_SIZE],
+        fail: bool,
+    }
+
+    impl UptimeStore for MemStore {
+        type Error = ();
+
+        fn load(&mut self, record: &mut [u8; UPTIME_RECORD_SIZE]) -> Result<(), ()> {
+            *record = self.record;
+            Ok(())
+        }
+
+        fn store(&mut self, record: &[u8; UPTIME_RECORD_SIZE]) -> Result<(), ()> {
+            if self.fail {
+                return Err(());
+            }
+            self.record = *record;
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_first_boot_survives_restarts() {
+        let mut rtc = MemNvram::new(236);
+
+        let first = UptimeTracker::start_nvram(&mut rtc, 0x20).unwrap();
+        assert_eq!(first.first_boot_time(), BOOT);
+        assert_eq!(first.previous_boot_time(), None);
+
+        rtc.now = DateTime::new(2025, 8, 22, 14, 30, 0).unwrap();
+        let second = UptimeTracker::start_nvram(&mut rtc, 0x20).unwrap();
+        assert_eq!(second.boot_time(), BOOT + 86_400);
+        assert_eq!(second.first_boot_time(), BOOT);
+        assert_eq!(second.previous_boot_time(), Some(BOOT));
+
+        rtc.now = DateTime::new(2025, 8, 22, 15, 30, 0).unwrap();
+        assert_eq!(second.uptime(&mut rtc), Ok(Duration::from_secs(3600)));
+        assert_eq!(
+            second.time_since_first_boot(&mut rtc),
+            Ok(Duration::from_secs(90_000))
+        );
+
+        // Setting the clock back doesn't underflow
+        rtc.now = DateTime::new(2025, 1, 1, 0, 0, 0).unwrap();
+        assert_eq!(second.uptime(&mut rtc), Ok(Duration::ZERO));
+    }
+
+    #[test]
+    fn test_corrupt_record_restarts() {
+        let mut rtc = MemNvram::new(236);
+        UptimeTracker::start_nvram(&mut rtc, 0).unwrap();
+        rtc.ram[2] ^= 0x01;
+
+        let tracker = UptimeTracker::start_nvram(&mut rtc, 0).unwrap();
+        assert_eq!(tracker.first_boot_time(), BOOT);
+        assert_eq!(tracker.previous_boot_time(), None);
+
+        assert_eq!(
+            UptimeTracker::start_nvram(&mut rtc, 230),
+            Err(Error::NvramOutOfBounds)
+        );
+    }
+
+    #[test]
+    fn test_external_store() {
+        let mut rtc = MemNvram::new(0);
+        let mut store = MemStore::default();
+
+        UptimeTracker::start(&mut rtc, &mut store).unwrap();
+        let tracker = UptimeTracker::start(&mut rtc, &mut store).unwrap();
+        assert_eq!(tracker.previous_boot_time(), Some(BOOT));
+
+        store.fail = true;
+        assert_eq!(
+            UptimeTracker::start(&mut rtc, &mut store),
+            Err(UptimeError::Store(()))
+        );
+    }
+}
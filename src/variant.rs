@@ -0,0 +1,251 @@
+//! # Chip Variants
+//!
+//! Products whose BOM alternates between members of the family can select
+//! the fitted part at runtime with [`Ds3231::new_with_variant`]. The driver
+//! then adapts to the differences between them:
+//!
+//! | Variant   | INT/SQW frequencies          | Temperature update | SRAM      |
+//! |-----------|------------------------------|--------------------|-----------|
+//! | DS3231    | 1 Hz, 1.024/4.096/8.192 kHz  | every 64 s         | none      |
+//! | DS3231M   | 1 Hz only                    | every 10 s         | none      |
+//! | DS3232    | 1 Hz, 1.024/4.096/8.192 kHz  | every 64 s         | 236 bytes |
+//!
+//! Requesting a square wave frequency the fitted part lacks fails with
+//! [`Error::UnsupportedSqwFrequency`] instead of silently producing 1 Hz.
+//! The SRAM of a DS3232 is reached through [`Ds3231::into_ds3232`], which
+//! refuses variants without SRAM.
+//!
+//! ```no_run
+//! use ds3231_rtc::{Ds3231, variant::Variant};
+//! # let i2c = embedded_hal_mock::eh1::i2c::Mock::new(&[]);
+//! # let board_rev = 3;
+//!
+//! let variant = if board_rev >= 3 { Variant::Ds3231M } else { Variant::Ds3231 };
+//! let mut rtc = Ds3231::new_with_variant(i2c, variant);
+//! ```
+//!
+//! When the part is known at build time, the variant can instead be fixed by
+//! the driver's type parameter. APIs the part lacks then don't exist on the
+//! type at all, e.g. the SRAM methods are only implemented for
+//! `Ds3231<I2C, Ds3232Variant>`:
+//!
+//! ```no_run
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! # let i2c = embedded_hal_mock::eh1::i2c::Mock::new(&[]);
+//! use ds3231_rtc::{Ds3231, variant::Ds3232Variant};
+//!
+//! let mut rtc: Ds3231<_, Ds3232Variant> = Ds3231::new_typed(i2c);
+//! rtc.write_sram(0, &[0xCA, 0xFE])?;
+//! # Ok(())
+//! # }
+//! ```
+
+use rtc_hal::square_wave::SquareWaveFreq;
+
+#[cfg(feature = "async")]
+use crate::interface::AsyncRegisterInterface;
+use crate::interface::RegisterInterface;
+
+use crate::Ds3231;
+#[cfg(feature = "async")]
+use crate::ds3231::Ds3231Async;
+use crate::error::Error;
+use crate::square_wave::freq_to_bits;
+
+/// Member of the DS3231 family fitted to the board
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Variant {
+    /// DS3231: TCXO-based, ±2 ppm
+    #[default]
+    Ds3231,
+    /// DS3231M: MEMS resonator, ±5 ppm, INT/SQW fixed at 1 Hz
+    Ds3231M,
+    /// DS3232: DS3231 with 236 bytes of battery-backed SRAM
+    Ds3232,
+}
+
+impl Variant {
+    /// Returns `true` if the INT/SQW pin can output `freq`.
+    pub const fn supports_square_wave(self, freq: SquareWaveFreq) -> bool {
+        match self {
+            Variant::Ds3231M => matches!(freq, SquareWaveFreq::Hz1),
+            Variant::Ds3231 | Variant::Ds3232 => matches!(
+                freq,
+                SquareWaveFreq::Hz1
+                    | SquareWaveFreq::Hz1024
+                    | SquareWaveFreq::Hz4096
+                    | SquareWaveFreq::Hz8192
+            ),
+        }
+    }
+
+    /// Seconds between automatic temperature conversions, i.e. how often
+    /// the temperature registers change.
+    pub const fn temperature_interval_secs(self) -> u8 {
+        match self {
+            Variant::Ds3231M => 10,
+            Variant::Ds3231 | Variant::Ds3232 => 64,
+        }
+    }
+
+    /// Battery-backed SRAM in bytes, 0 if the part has none.
+    pub const fn sram_size(self) -> u16 {
+        match self {
+            Variant::Ds3232 => crate::ds3232::SRAM_SIZE as u16,
+            Variant::Ds3231 | Variant::Ds3231M => 0,
+        }
+    }
+
+    /// RS bits for `freq`, rejecting frequencies this part can't output.
+    pub(crate) fn square_wave_bits<E>(self, freq: SquareWaveFreq) -> Result<u8, Error<E>>
+    where
+        E: core::fmt::Debug,
+    {
+        if !self.supports_square_wave(freq) {
+            return Err(Error::UnsupportedSqwFrequency);
+        }
+        freq_to_bits(freq)
+    }
+}
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// Type-level chip variant, the `V` parameter of [`Ds3231`]
+///
+/// Sealed; implemented by [`AnyVariant`] and the marker types below.
+pub trait ChipVariant: sealed::Sealed {
+    /// Variant the driver starts with
+    const VARIANT: Variant;
+}
+
+/// Variant chosen at runtime with [`Ds3231::new_with_variant`] (the default)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct AnyVariant;
+
+/// Type-level [`Variant::Ds3231`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Ds3231Variant;
+
+/// Type-level [`Variant::Ds3231M`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Ds3231MVariant;
+
+/// Type-level [`Variant::Ds3232`]; enables the SRAM methods
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Ds3232Variant;
+
+impl sealed::Sealed for AnyVariant {}
+impl sealed::Sealed for Ds3231Variant {}
+impl sealed::Sealed for Ds3231MVariant {}
+impl sealed::Sealed for Ds3232Variant {}
+
+impl ChipVariant for AnyVariant {
+    const VARIANT: Variant = Variant::Ds3231;
+}
+
+impl ChipVariant for Ds3231Variant {
+    const VARIANT: Variant = Variant::Ds3231;
+}
+
+impl ChipVariant for Ds3231MVariant {
+    const VARIANT: Variant = Variant::Ds3231M;
+}
+
+impl ChipVariant for Ds3232Variant {
+    const VARIANT: Variant = Variant::Ds3232;
+}
+
+#[maybe_async_cfg::maybe(
+    idents(
+        AsyncRegisterInterface(sync = "RegisterInterface", async = "AsyncRegisterInterface"),
+        Ds3231(sync = "Ds3231", async = "Ds3231Async")
+    ),
+    sync(keep_self),
+    async(feature = "async")
+)]
+impl<I2C> Ds3231<I2C>
+where
+    I2C: AsyncRegisterInterface,
+{
+    /// Create a driver for the given family member.
+    ///
+    /// [`new`](Self::new) assumes a plain [`Variant::Ds3231`].
+    pub fn new_with_variant(i2c: I2C, variant: Variant) -> Self {
+        Self::from_parts(i2c, variant)
+    }
+}
+
+#[maybe_async_cfg::maybe(
+    idents(
+        AsyncRegisterInterface(sync = "RegisterInterface", async = "AsyncRegisterInterface"),
+        Ds3231(sync = "Ds3231", async = "Ds3231Async")
+    ),
+    sync(keep_self),
+    async(feature = "async")
+)]
+impl<I2C, V> Ds3231<I2C, V>
+where
+    I2C: AsyncRegisterInterface,
+    V: ChipVariant,
+{
+    /// Create a driver for the variant fixed by the type parameter `V`.
+    ///
+    /// ```no_run
+    /// # use ds3231_rtc::{Ds3231, variant::Ds3232Variant};
+    /// # let i2c = embedded_hal_mock::eh1::i2c::Mock::new(&[]);
+    /// let rtc: Ds3231<_, Ds3232Variant> = Ds3231::new_typed(i2c);
+    /// ```
+    pub fn new_typed(i2c: I2C) -> Self {
+        Self::from_parts(i2c, V::VARIANT)
+    }
+
+    /// Returns the configured chip variant.
+    pub fn variant(&self) -> Variant {
+        self.variant
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registers::Register;
+    use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
+
+    #[test]
+    fn test_variant_capabilities() {
+        assert!(Variant::Ds3231.supports_square_wave(SquareWaveFreq::Hz8192));
+        assert!(!Variant::Ds3231.supports_square_wave(SquareWaveFreq::Hz32768));
+        assert!(Variant::Ds3231M.supports_square_wave(SquareWaveFreq::Hz1));
+        assert!(!Variant::Ds3231M.supports_square_wave(SquareWaveFreq::Hz1024));
+
+        assert_eq!(Variant::Ds3231M.temperature_interval_secs(), 10);
+        assert_eq!(Variant::Ds3232.temperature_interval_secs(), 64);
+        assert_eq!(Variant::Ds3231.sram_size(), 0);
+        assert_eq!(Variant::Ds3232.sram_size(), 236);
+    }
+
+    #[test]
+    fn test_ds3231m_rejects_fast_square_wave() {
+        let expectations = [
+            I2cTransaction::write_read(0x68, vec![Register::Control.addr()], vec![0x1C]),
+            I2cTransaction::write(0x68, vec![Register::Control.addr(), 0x00]),
+        ];
+        let mut ds3231 = Ds3231::new_with_variant(I2cMock::new(&expectations), Variant::Ds3231M);
+        assert_eq!(ds3231.variant(), Variant::Ds3231M);
+
+        assert_eq!(
+            ds3231.start_square_wave(SquareWaveFreq::Hz4096),
+            Err(Error::UnsupportedSqwFrequency)
+        );
+        ds3231.start_square_wave(SquareWaveFreq::Hz1).unwrap();
+
+        ds3231.release_i2c().done();
+    }
+}
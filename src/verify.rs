@@ -0,0 +1,151 @@
+//! # Write Verification
+//!
+//! With [`set_write_verification`](crate::Ds3231::set_write_verification)
+//! enabled, every register write is read back and compared, and a mismatch
+//! fails with [`Error::VerificationFailed`]. This catches corruption on a
+//! noisy bus and a device that acknowledges but doesn't store, at the cost
+//! of one read per write.
+//!
+//! Bits the chip changes on its own (the oscillator stop, busy and alarm
+//! flags in the status register) are left out of the comparison. Writes to
+//! the DS3234 SRAM data port are not verified, since reading the port
+//! returns the next byte rather than the one just written.
+
+#[cfg(feature = "async")]
+use crate::interface::AsyncRegisterInterface;
+use crate::interface::RegisterInterface;
+
+use crate::Ds3231;
+#[cfg(not(feature = "async"))]
+use crate::ds3231::MAX_BURST;
+#[cfg(feature = "async")]
+use crate::ds3231::{Ds3231Async, MAX_BURST};
+use crate::error::Error;
+use crate::registers::{A1F_BIT, A2F_BIT, BSY_BIT, OSF_BIT, Register};
+
+/// Bits of the register at `address` the chip may change by itself
+const fn volatile_bits(address: u8) -> u8 {
+    if address == Register::Status.addr() {
+        OSF_BIT | BSY_BIT | A2F_BIT | A1F_BIT
+    } else {
+        0
+    }
+}
+
+#[maybe_async_cfg::maybe(
+    idents(
+        AsyncRegisterInterface(sync = "RegisterInterface", async = "AsyncRegisterInterface"),
+        Ds3231(sync = "Ds3231", async = "Ds3231Async")
+    ),
+    sync(keep_self),
+    async(feature = "async")
+)]
+impl<I2C, V> Ds3231<I2C, V>
+where
+    I2C: AsyncRegisterInterface,
+{
+    /// Enable or disable reading back every register write.
+    ///
+    /// Disabled by default. See the [module documentation](crate::verify).
+    pub fn set_write_verification(&mut self, enabled: bool) {
+        self.verify_writes = enabled;
+    }
+
+    /// Returns `true` if register writes are read back.
+    pub fn write_verification(&self) -> bool {
+        self.verify_writes
+    }
+
+    /// Read back `values` just written from `register` on, if verification
+    /// is enabled.
+    pub(crate) async fn verify_written(
+        &mut self,
+        register: u8,
+        values: &[u8],
+    ) -> Result<(), Error<I2C::Error>> {
+        if !self.verify_writes {
+            return Ok(());
+        }
+
+        let mut buffer = [0u8; MAX_BURST];
+        let mut address = register;
+        for chunk in values.chunks(MAX_BURST) {
+            let read_back = &mut buffer[..chunk.len()];
+            self.read_bytes_at_address(address, read_back).await?;
+            for (&written, &read) in chunk.iter().zip(read_back.iter()) {
+                if (written ^ read) & !volatile_bits(address) != 0 {
+                    return Err(Error::VerificationFailed);
+                }
+                address = address.wrapping_add(1);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
+    use rtc_hal::datetime::DateTime;
+    use rtc_hal::square_wave::SquareWaveFreq;
+
+    const DS3231_ADDR: u8 = 0x68;
+
+    #[test]
+    fn test_verified_datetime_write() {
+        let registers = vec![0x00, 0x30, 0x14, 0x05, 0x21, 0x08, 0x25];
+        let mut frame = vec![Register::Seconds.addr()];
+        frame.extend(&registers);
+        let expectations = [
+            I2cTransaction::write(DS3231_ADDR, frame),
+            I2cTransaction::write_read(DS3231_ADDR, vec![0x00], registers),
+        ];
+        let mut ds3231 = Ds3231::new(I2cMock::new(&expectations));
+        assert!(!ds3231.write_verification());
+        ds3231.set_write_verification(true);
+
+        ds3231
+            .set_datetime(&DateTime::new(2025, 8, 21, 14, 30, 0).unwrap())
+            .unwrap();
+
+        ds3231.release_i2c().done();
+    }
+
+    #[test]
+    fn test_mismatch_fails_verification() {
+        let expectations = [
+            I2cTransaction::write_read(DS3231_ADDR, vec![Register::Control.addr()], vec![0x1C]),
+            I2cTransaction::write(DS3231_ADDR, vec![Register::Control.addr(), 0x04]),
+            // Device kept the old value
+            I2cTransaction::write_read(DS3231_ADDR, vec![Register::Control.addr()], vec![0x1C]),
+        ];
+        let mut ds3231 = Ds3231::new(I2cMock::new(&expectations));
+        ds3231.set_write_verification(true);
+
+        assert_eq!(
+            ds3231.set_square_wave_frequency(SquareWaveFreq::Hz1),
+            Err(Error::VerificationFailed)
+        );
+
+        ds3231.release_i2c().done();
+    }
+
+    #[test]
+    #[cfg(feature = "alarms")]
+    fn test_status_flags_raised_meanwhile_are_ignored() {
+        let expectations = [
+            I2cTransaction::write_read(DS3231_ADDR, vec![Register::Status.addr()], vec![0x01]),
+            I2cTransaction::write(DS3231_ADDR, vec![Register::Status.addr(), 0x82]),
+            // Alarm 2 fired right after the write
+            I2cTransaction::write_read(DS3231_ADDR, vec![Register::Status.addr()], vec![0x02]),
+        ];
+        let mut ds3231 = Ds3231::new(I2cMock::new(&expectations));
+        ds3231.set_write_verification(true);
+
+        ds3231.clear_alarm_flag(crate::alarm::Alarm::One).unwrap();
+
+        ds3231.release_i2c().done();
+    }
+}
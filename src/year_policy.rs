@@ -0,0 +1,143 @@
+//! # Year Range Policy
+//!
+//! The DS3231 can only represent a 200-year window: two digits of year plus
+//! the century bit, interpreted relative to the configured base century.
+//! [`YearPolicy`] controls what [`set_datetime`](rtc_hal::rtc::Rtc::set_datetime)
+//! does when the caller asks for a year outside that window.
+
+use rtc_hal::datetime::{DateTime, DateTimeError};
+
+#[cfg(feature = "async")]
+use crate::interface::AsyncRegisterInterface;
+use crate::interface::RegisterInterface;
+
+use crate::Ds3231;
+#[cfg(feature = "async")]
+use crate::ds3231::Ds3231Async;
+
+/// What to do when setting a year the DS3231 cannot represent
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum YearPolicy {
+    /// Only accept years 2000-2099 and never use the century bit.
+    ///
+    /// This matches how most other DS3231 libraries use the chip. The century
+    /// bit stays clear only with the default base century of 20, so every
+    /// year is rejected under any other base century.
+    Strict2000to2099,
+    /// Accept any year in the base century or the one after it, setting the
+    /// century bit for the latter. Other years are rejected.
+    #[default]
+    BaseCenturyWindow,
+    /// Like [`BaseCenturyWindow`](Self::BaseCenturyWindow), but instead of
+    /// rejecting out-of-range datetimes, clamp them to the first or last
+    /// representable second of the window.
+    Clamp,
+}
+
+impl YearPolicy {
+    /// Apply the policy to `datetime` for the window starting at `base_century`.
+    ///
+    /// Returns the datetime to write, or `DateTimeError::InvalidYear` if the
+    /// policy rejects it.
+    pub(crate) fn apply(
+        self,
+        datetime: &DateTime,
+        base_century: u8,
+    ) -> Result<DateTime, DateTimeError> {
+        let first_year = base_century as u16 * 100;
+        let last_year = first_year + 199;
+        let year = datetime.year();
+
+        match self {
+            YearPolicy::Strict2000to2099 => {
+                if base_century == 20 && (2000..=2099).contains(&year) {
+                    Ok(*datetime)
+                } else {
+                    Err(DateTimeError::InvalidYear)
+                }
+            }
+            YearPolicy::BaseCenturyWindow => {
+                if (first_year..=last_year).contains(&year) {
+                    Ok(*datetime)
+                } else {
+                    Err(DateTimeError::InvalidYear)
+                }
+            }
+            YearPolicy::Clamp => {
+                if year < first_year {
+                    DateTime::new(first_year, 1, 1, 0, 0, 0)
+                } else if year > last_year {
+                    DateTime::new(last_year, 12, 31, 23, 59, 59)
+                } else {
+                    Ok(*datetime)
+                }
+            }
+        }
+    }
+}
+
+#[maybe_async_cfg::maybe(
+    idents(
+        AsyncRegisterInterface(sync = "RegisterInterface", async = "AsyncRegisterInterface"),
+        Ds3231(sync = "Ds3231", async = "Ds3231Async")
+    ),
+    sync(keep_self),
+    async(feature = "async")
+)]
+impl<I2C, V> Ds3231<I2C, V>
+where
+    I2C: AsyncRegisterInterface,
+{
+    /// Select how out-of-range years are handled when setting the time.
+    ///
+    /// Defaults to [`YearPolicy::BaseCenturyWindow`].
+    pub fn set_year_policy(&mut self, policy: YearPolicy) {
+        self.year_policy = policy;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dt(year: u16) -> DateTime {
+        DateTime::new(year, 6, 15, 12, 30, 0).unwrap()
+    }
+
+    #[test]
+    fn test_strict_policy() {
+        let policy = YearPolicy::Strict2000to2099;
+        assert_eq!(policy.apply(&dt(2000), 20), Ok(dt(2000)));
+        assert_eq!(policy.apply(&dt(2099), 20), Ok(dt(2099)));
+        assert_eq!(policy.apply(&dt(2100), 20), Err(DateTimeError::InvalidYear));
+        assert_eq!(policy.apply(&dt(1999), 19), Err(DateTimeError::InvalidYear));
+        // 2050 is outside the window of base century 21
+        assert_eq!(policy.apply(&dt(2050), 21), Err(DateTimeError::InvalidYear));
+        // With base century 19, 2050 would need the century bit
+        assert_eq!(policy.apply(&dt(2050), 19), Err(DateTimeError::InvalidYear));
+    }
+
+    #[test]
+    fn test_base_century_window_policy() {
+        let policy = YearPolicy::BaseCenturyWindow;
+        assert_eq!(policy.apply(&dt(2199), 20), Ok(dt(2199)));
+        assert_eq!(policy.apply(&dt(2200), 20), Err(DateTimeError::InvalidYear));
+        assert_eq!(policy.apply(&dt(1999), 20), Err(DateTimeError::InvalidYear));
+        assert_eq!(policy.apply(&dt(1999), 19), Ok(dt(1999)));
+    }
+
+    #[test]
+    fn test_clamp_policy() {
+        let policy = YearPolicy::Clamp;
+        assert_eq!(policy.apply(&dt(2150), 20), Ok(dt(2150)));
+        assert_eq!(
+            policy.apply(&dt(1980), 20),
+            DateTime::new(2000, 1, 1, 0, 0, 0)
+        );
+        assert_eq!(
+            policy.apply(&dt(2300), 20),
+            DateTime::new(2199, 12, 31, 23, 59, 59)
+        );
+    }
+}
@@ -0,0 +1,286 @@
+//! # Time Zone Offset Wrapper
+//!
+//! Storing local time in the RTC causes trouble as soon as a product ships
+//! to more than one region: every unit holds a different notion of "now",
+//! and changing the offset means rewriting the clock. [`ZonedRtc`] keeps the
+//! DS3231 in UTC and applies a fixed offset in software, so reads return
+//! local time and writes accept local time.
+//!
+//! ```no_run
+//! # use ds3231_rtc::Ds3231;
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! # let i2c = embedded_hal_mock::eh1::i2c::Mock::new(&[]);
+//! use ds3231_rtc::zoned::ZonedRtc;
+//! use rtc_hal::rtc::Rtc;
+//!
+//! // UTC+05:30
+//! let mut rtc = ZonedRtc::new(Ds3231::new(i2c), 330);
+//!
+//! let local = rtc.get_datetime()?;      // local time
+//! let utc = rtc.get_utc_datetime()?;    // what the chip holds
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! With the `dst` feature, a [`DstRule`](crate::dst::DstRule) can be attached
+//! so that the offset follows daylight saving time automatically; the fixed
+//! offset is then the zone's standard offset.
+
+use rtc_hal::datetime::DateTime;
+use rtc_hal::error::ErrorType;
+use rtc_hal::rtc::Rtc;
+
+use crate::calendar;
+#[cfg(feature = "dst")]
+use crate::dst::DstRule;
+use crate::error::Error;
+
+/// RTC wrapper that stores UTC in the hardware and exposes local time.
+#[derive(Debug)]
+pub struct ZonedRtc<RTC> {
+    rtc: RTC,
+    offset_minutes: i16,
+    #[cfg(feature = "dst")]
+    dst_rule: Option<DstRule>,
+}
+
+impl<RTC> ZonedRtc<RTC> {
+    /// Wrap an RTC that holds UTC.
+    ///
+    /// # Parameters
+    /// * `rtc` - RTC driver, e.g. a [`Ds3231`](crate::Ds3231) or `&mut Ds3231`
+    /// * `offset_minutes` - Local time offset from UTC in minutes (e.g. `-300` for UTC-05:00)
+    pub fn new(rtc: RTC, offset_minutes: i16) -> Self {
+        Self {
+            rtc,
+            offset_minutes,
+            #[cfg(feature = "dst")]
+            dst_rule: None,
+        }
+    }
+
+    /// Current local time offset from UTC in minutes.
+    ///
+    /// With a DST rule attached this is the standard offset; see
+    /// [`offset_at`](Self::offset_at) for the offset in effect at a given time.
+    pub fn offset_minutes(&self) -> i16 {
+        self.offset_minutes
+    }
+
+    /// Change the local time offset. The RTC itself is not touched.
+    pub fn set_offset_minutes(&mut self, offset_minutes: i16) {
+        self.offset_minutes = offset_minutes;
+    }
+
+    /// DST rule applied on top of the standard offset, if any.
+    #[cfg(feature = "dst")]
+    pub fn dst_rule(&self) -> Option<&DstRule> {
+        self.dst_rule.as_ref()
+    }
+
+    /// Attach or remove a DST rule. The RTC itself is not touched.
+    #[cfg(feature = "dst")]
+    pub fn set_dst_rule(&mut self, rule: Option<DstRule>) {
+        self.dst_rule = rule;
+    }
+
+    /// Offset from UTC in minutes in effect at the given UTC time.
+    pub fn offset_at(&self, utc: &DateTime) -> i32 {
+        #[cfg(feature = "dst")]
+        if let Some(rule) = &self.dst_rule {
+            return rule.offset_at(utc, self.offset_minutes);
+        }
+        #[cfg(not(feature = "dst"))]
+        let _ = utc;
+        i32::from(self.offset_minutes)
+    }
+
+    /// Borrow the wrapped RTC.
+    pub fn inner(&mut self) -> &mut RTC {
+        &mut self.rtc
+    }
+
+    /// Returns the wrapped RTC, consuming the wrapper.
+    pub fn into_inner(self) -> RTC {
+        self.rtc
+    }
+}
+
+impl<RTC, E> ZonedRtc<RTC>
+where
+    RTC: Rtc<Error = Error<E>>,
+    E: core::fmt::Debug,
+{
+    /// Read the UTC time stored in the RTC.
+    pub fn get_utc_datetime(&mut self) -> Result<DateTime, Error<E>> {
+        self.rtc.get_datetime()
+    }
+
+    /// Write a UTC time to the RTC.
+    pub fn set_utc_datetime(&mut self, datetime: &DateTime) -> Result<(), Error<E>> {
+        self.rtc.set_datetime(datetime)
+    }
+
+    /// Convert a UTC time to local time using the offset in effect at that time.
+    pub fn to_local(&self, utc: &DateTime) -> Result<DateTime, Error<E>> {
+        calendar::add_seconds(utc, self.offset_at(utc) as i64 * 60).map_err(Error::DateTime)
+    }
+
+    /// Convert a local time to UTC using the offset in effect at that time.
+    ///
+    /// With a DST rule attached, a local time that occurs twice when clocks go
+    /// back resolves to the first (daylight) occurrence, and a local time
+    /// skipped when clocks go forward is interpreted as standard time.
+    pub fn to_utc(&self, local: &DateTime) -> Result<DateTime, Error<E>> {
+        #[cfg(feature = "dst")]
+        if let Some(rule) = &self.dst_rule {
+            let dst_offset = (i64::from(self.offset_minutes) + i64::from(rule.save_minutes)) * 60;
+            if let Ok(utc) = calendar::add_seconds(local, -dst_offset)
+                && rule.is_dst(&utc, self.offset_minutes)
+            {
+                return Ok(utc);
+            }
+        }
+        calendar::add_seconds(local, -(self.offset_minutes as i64 * 60)).map_err(Error::DateTime)
+    }
+}
+
+impl<RTC, E> ErrorType for ZonedRtc<RTC>
+where
+    RTC: Rtc<Error = Error<E>>,
+    E: core::fmt::Debug,
+{
+    type Error = Error<E>;
+}
+
+impl<RTC, E> Rtc for ZonedRtc<RTC>
+where
+    RTC: Rtc<Error = Error<E>>,
+    E: core::fmt::Debug,
+{
+    /// Read the current local time.
+    fn get_datetime(&mut self) -> Result<DateTime, Self::Error> {
+        let utc = self.get_utc_datetime()?;
+        self.to_local(&utc)
+    }
+
+    /// Set the clock from a local time; the RTC receives the equivalent UTC.
+    fn set_datetime(&mut self, datetime: &DateTime) -> Result<(), Self::Error> {
+        let utc = self.to_utc(datetime)?;
+        self.set_utc_datetime(&utc)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Ds3231;
+    use crate::registers::Register;
+    use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTrans};
+    use rtc_hal::datetime::DateTimeError;
+
+    #[test]
+    fn test_get_datetime_returns_local_time() {
+        // Chip holds 2025-08-21 22:00:00 UTC
+        let data = [0x00, 0x00, 0x22, 0x05, 0x21, 0x08, 0x25];
+        let expectations = [I2cTrans::write_read(
+            0x68,
+            vec![Register::Seconds.addr()],
+            data.to_vec(),
+        )];
+        let mut i2c = I2cMock::new(&expectations);
+        let mut rtc = ZonedRtc::new(Ds3231::new(&mut i2c), 330);
+
+        // UTC+05:30 rolls over into the next day
+        let local = rtc.get_datetime().unwrap();
+        assert_eq!(local, DateTime::new(2025, 8, 22, 3, 30, 0).unwrap());
+
+        i2c.done();
+    }
+
+    #[test]
+    fn test_set_datetime_writes_utc() {
+        // Local 2025-01-01 00:30:00 at UTC-05:00 is 2025-01-01 05:30:00 UTC (Wednesday)
+        let expectations = [I2cTrans::write(
+            0x68,
+            vec![
+                Register::Seconds.addr(),
+                0x00,
+                0x30,
+                0x05,
+                0x04,
+                0x01,
+                0x01,
+                0x25,
+            ],
+        )];
+        let mut i2c = I2cMock::new(&expectations);
+        let mut rtc = ZonedRtc::new(Ds3231::new(&mut i2c), -300);
+
+        let local = DateTime::new(2025, 1, 1, 0, 30, 0).unwrap();
+        rtc.set_datetime(&local).unwrap();
+
+        i2c.done();
+    }
+
+    #[test]
+    fn test_offset_conversion_roundtrip() {
+        let mut rtc = ZonedRtc::new(Ds3231::new(I2cMock::new(&[])), -570);
+        assert_eq!(rtc.offset_minutes(), -570);
+
+        let dt = DateTime::new(2024, 3, 1, 2, 0, 0).unwrap();
+        let utc = rtc.to_utc(&dt).unwrap();
+        assert_eq!(rtc.to_local(&utc).unwrap(), dt);
+
+        rtc.set_offset_minutes(60);
+        let out_of_range = DateTime::new(1970, 1, 1, 0, 0, 0).unwrap();
+        assert!(matches!(
+            rtc.to_utc(&out_of_range),
+            Err(Error::DateTime(DateTimeError::InvalidYear))
+        ));
+
+        rtc.into_inner().release_i2c().done();
+    }
+
+    #[cfg(feature = "dst")]
+    #[test]
+    fn test_dst_rule_applied() {
+        let mut rtc = ZonedRtc::new(Ds3231::new(I2cMock::new(&[])), 60);
+        rtc.set_dst_rule(Some(DstRule::EU));
+        assert_eq!(rtc.dst_rule(), Some(&DstRule::EU));
+
+        // Winter: CET (UTC+01:00)
+        let utc = DateTime::new(2025, 1, 15, 12, 0, 0).unwrap();
+        assert_eq!(
+            rtc.to_local(&utc).unwrap(),
+            DateTime::new(2025, 1, 15, 13, 0, 0).unwrap()
+        );
+
+        // Summer: CEST (UTC+02:00)
+        let utc = DateTime::new(2025, 7, 15, 12, 0, 0).unwrap();
+        let local = rtc.to_local(&utc).unwrap();
+        assert_eq!(local, DateTime::new(2025, 7, 15, 14, 0, 0).unwrap());
+        assert_eq!(rtc.to_utc(&local).unwrap(), utc);
+
+        // 02:30 on 2025-10-26 occurs twice; the daylight occurrence wins
+        let local = DateTime::new(2025, 10, 26, 2, 30, 0).unwrap();
+        assert_eq!(
+            rtc.to_utc(&local).unwrap(),
+            DateTime::new(2025, 10, 26, 0, 30, 0).unwrap()
+        );
+
+        rtc.set_dst_rule(None);
+        assert_eq!(rtc.offset_at(&utc), 60);
+
+        // Offsets at the ends of the range don't overflow
+        rtc.set_offset_minutes(i16::MAX);
+        rtc.set_dst_rule(Some(DstRule {
+            save_minutes: i16::MAX,
+            ..DstRule::EU
+        }));
+        assert_eq!(rtc.offset_at(&utc), 2 * i32::from(i16::MAX));
+        assert!(rtc.to_utc(&local).is_ok());
+
+        rtc.into_inner().release_i2c().done();
+    }
+}